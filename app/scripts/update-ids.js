@@ -0,0 +1,29 @@
+const fs = require('fs');
+const path = require('path');
+const zlib = require('zlib');
+
+// The PCI and USB ID databases compiled in as a fallback for systems without
+// hwdata (and Windows). Both are stored gzipped, exactly as published.
+const sources = [
+    { url: 'https://pci-ids.ucw.cz/v2.2/pci.ids', file: 'pci.ids.gz' },
+    { url: 'http://www.linux-usb.org/usb.ids', file: 'usb.ids.gz' },
+];
+
+const dataDir = path.join(__dirname, '..', 'src-tauri', 'data');
+
+(async () => {
+    for (const { url, file } of sources) {
+        console.log(`Fetching ${url}...`);
+        const response = await fetch(url);
+        if (!response.ok) {
+            throw new Error(`${url}: ${response.status} ${response.statusText}`);
+        }
+        const text = Buffer.from(await response.arrayBuffer());
+        const gzipped = zlib.gzipSync(text, { level: 9 });
+        fs.writeFileSync(path.join(dataDir, file), gzipped);
+        console.log(`Wrote ${file} (${text.length} bytes, ${gzipped.length} compressed)`);
+    }
+})().catch((err) => {
+    console.error(err.message);
+    process.exit(1);
+});
@@ -0,0 +1,261 @@
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use tauri::State;
+
+#[cfg(windows)]
+use crate::wmi_query;
+#[cfg(windows)]
+use wmi::Variant;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BoardInfo {
+    pub board_vendor: String,
+    pub board_model: String,
+    pub board_version: String,
+    pub bios_vendor: String,
+    pub bios_version: String,
+    /// As the firmware reports it, usually `MM/DD/YYYY`.
+    pub bios_date: String,
+    pub chassis_type: String,
+    pub system_vendor: String,
+    pub system_product: String,
+    /// `None` when unreadable or redacted.
+    pub system_serial: Option<String>,
+    pub board_serial: Option<String>,
+    /// A serial was read but withheld. Recordings never carry serials.
+    pub serials_redacted: bool,
+    pub provenance: Provenance,
+}
+
+impl BoardInfo {
+    /// The same info with any serials withheld.
+    pub fn without_serials(mut self) -> Self {
+        let withheld = self.system_serial.take().is_some() | self.board_serial.take().is_some();
+        self.serials_redacted |= withheld;
+        self
+    }
+}
+
+// field name to its value and where it came from, or why it's missing
+type Fields = BTreeMap<&'static str, Result<(String, Source)>>;
+
+/// Serials are redacted unless `show_serials` is set, and always in what
+/// gets recorded.
+#[tauri::command]
+#[specta::specta]
+pub fn get_board_info(session: State<'_, Session>, show_serials: Option<bool>) -> Result<BoardInfo> {
+    let mut full = None;
+    let info = session.sample(|| {
+        let info = read_board_info(false)?;
+        full = Some(info.clone());
+        Ok(info.without_serials())
+    })?;
+    // a replay has no serials to show
+    Ok(match full {
+        Some(full) if show_serials.unwrap_or(false) => full,
+        _ => info,
+    })
+}
+
+pub fn read_board_info(redact_serials: bool) -> Result<BoardInfo> {
+    let mut fields = read_fields()?;
+    let mut provenance = Provenance::new();
+
+    let mut take = |field: &'static str| {
+        let value = fields.remove(field).unwrap_or_else(|| Err(OverwatchError::not_found(field.replace('_', " "))));
+        match value {
+            Ok((value, source)) => {
+                provenance.available(field, source);
+                Some(value)
+            }
+            Err(err) => {
+                provenance.missing(field, err);
+                None
+            }
+        }
+    };
+    let system_serial = take("system_serial");
+    let board_serial = take("board_serial");
+    let mut text = |field| take(field).unwrap_or_else(|| "Unknown".to_string());
+    let board_vendor = text("board_vendor");
+    let board_model = text("board_model");
+    let board_version = text("board_version");
+    let bios_vendor = text("bios_vendor");
+    let bios_version = text("bios_version");
+    let bios_date = text("bios_date");
+    let chassis_type = text("chassis_type");
+    let system_vendor = text("system_vendor");
+    let system_product = text("system_product");
+
+    let info = BoardInfo {
+        board_vendor,
+        board_model,
+        board_version,
+        bios_vendor,
+        bios_version,
+        bios_date,
+        chassis_type,
+        system_vendor,
+        system_product,
+        system_serial,
+        board_serial,
+        serials_redacted: false,
+        provenance,
+    };
+    Ok(if redact_serials { info.without_serials() } else { info })
+}
+
+#[cfg(target_os = "linux")]
+fn read_fields() -> Result<Fields> {
+    use crate::smbios::{self, chassis_type_name, meaningful};
+
+    // sysfs names for each field; the serial files are root-only
+    const DMI_FILES: [(&str, &str); 11] = [
+        ("board_vendor", "board_vendor"),
+        ("board_model", "board_name"),
+        ("board_version", "board_version"),
+        ("board_serial", "board_serial"),
+        ("bios_vendor", "bios_vendor"),
+        ("bios_version", "bios_version"),
+        ("bios_date", "bios_date"),
+        ("chassis_type", "chassis_type"),
+        ("system_vendor", "sys_vendor"),
+        ("system_product", "product_name"),
+        ("system_serial", "product_serial"),
+    ];
+
+    let dmi_dir = std::path::Path::new("/sys/class/dmi/id");
+    if !dmi_dir.exists() {
+        return Err(OverwatchError::not_found("DMI data (/sys/class/dmi/id)"));
+    }
+
+    let mut fields = Fields::new();
+    for (field, file) in DMI_FILES {
+        let value = std::fs::read_to_string(dmi_dir.join(file))
+            .map_err(|err| OverwatchError::from_io(format!("DMI {}", file), err))
+            .and_then(|raw| {
+                let value = if field == "chassis_type" {
+                    raw.trim().parse().ok().and_then(chassis_type_name).map(str::to_string)
+                } else {
+                    meaningful(&raw).map(str::to_string)
+                };
+                value.ok_or_else(|| OverwatchError::not_found(field.replace('_', " ")))
+            });
+        fields.insert(field, value.map(|value| (value, Source::Dmi)));
+    }
+
+    // the raw table fills in what sysfs hid or left blank, when it's readable
+    if fields.values().any(|value| value.is_err()) {
+        if let Ok(table) = smbios::read_table() {
+            let bios = table.first(smbios::BIOS);
+            let system = table.first(smbios::SYSTEM);
+            let board = table.first(smbios::BASEBOARD);
+            let chassis = table.first(smbios::CHASSIS);
+
+            let raw = [
+                ("bios_vendor", bios.and_then(|s| s.string(0x04))),
+                ("bios_version", bios.and_then(|s| s.string(0x05))),
+                ("bios_date", bios.and_then(|s| s.string(0x08))),
+                ("system_vendor", system.and_then(|s| s.string(0x04))),
+                ("system_product", system.and_then(|s| s.string(0x05))),
+                ("system_serial", system.and_then(|s| s.string(0x07))),
+                ("board_vendor", board.and_then(|s| s.string(0x04))),
+                ("board_model", board.and_then(|s| s.string(0x05))),
+                ("board_version", board.and_then(|s| s.string(0x06))),
+                ("board_serial", board.and_then(|s| s.string(0x07))),
+                ("chassis_type", chassis.and_then(|s| s.byte(0x05)).and_then(chassis_type_name).map(str::to_string)),
+            ];
+            for (field, value) in raw {
+                if let (Some(value), Some(slot @ Err(_))) = (value, fields.get_mut(field)) {
+                    *slot = Ok((value, Source::Smbios));
+                }
+            }
+        }
+    }
+
+    Ok(fields)
+}
+
+#[cfg(windows)]
+fn read_fields() -> Result<Fields> {
+    let wmi_con = wmi_query::connect()?;
+    let mut fields = Fields::new();
+
+    let mut read = |class: &str, props: &[(&'static str, &str)]| {
+        let row = wmi_query::query(&wmi_con, &format!("SELECT * FROM {}", class)).map(|rows| rows.into_iter().next());
+        for (field, prop) in props {
+            let value = match &row {
+                Ok(Some(row)) => match row.get(*prop) {
+                    Some(Variant::String(value)) => crate::smbios::meaningful(value)
+                        .map(|value| (value.to_string(), Source::Wmi))
+                        .ok_or_else(|| OverwatchError::not_found(field.replace('_', " "))),
+                    _ => Err(OverwatchError::not_found(format!("{}.{}", class, prop))),
+                },
+                Ok(None) => Err(OverwatchError::not_found(class.to_string())),
+                Err(err) => Err(err.clone()),
+            };
+            fields.insert(*field, value);
+        }
+        row.ok().flatten()
+    };
+
+    read(
+        "Win32_BaseBoard",
+        &[
+            ("board_vendor", "Manufacturer"),
+            ("board_model", "Product"),
+            ("board_version", "Version"),
+            ("board_serial", "SerialNumber"),
+        ],
+    );
+    let bios = read(
+        "Win32_BIOS",
+        &[("bios_vendor", "Manufacturer"), ("bios_version", "SMBIOSBIOSVersion")],
+    );
+    read(
+        "Win32_ComputerSystemProduct",
+        &[
+            ("system_vendor", "Vendor"),
+            ("system_product", "Name"),
+            ("system_serial", "IdentifyingNumber"),
+        ],
+    );
+    let enclosure = read("Win32_SystemEnclosure", &[]);
+
+    // CIM datetime, yyyymmddHHMMSS.mmmmmmsUUU; reformatted the way SMBIOS stores it
+    let bios_date = match bios.as_ref().and_then(|row| row.get("ReleaseDate")) {
+        Some(Variant::String(date)) if date.len() >= 8 && date.is_ascii() => {
+            Ok((format!("{}/{}/{}", &date[4..6], &date[6..8], &date[0..4]), Source::Wmi))
+        }
+        _ => Err(OverwatchError::not_found("BIOS release date")),
+    };
+    fields.insert("bios_date", bios_date);
+
+    let chassis = match enclosure.as_ref().and_then(|row| row.get("ChassisTypes")) {
+        Some(Variant::Array(types)) => types.iter().find_map(|code| match code {
+            Variant::UI1(code) => Some(*code as i64),
+            Variant::UI2(code) => Some(*code as i64),
+            Variant::I2(code) => Some(*code as i64),
+            Variant::I4(code) => Some(*code as i64),
+            Variant::UI4(code) => Some(*code as i64),
+            _ => None,
+        }),
+        _ => None,
+    };
+    let chassis = chassis
+        .and_then(|code| u8::try_from(code).ok())
+        .and_then(crate::smbios::chassis_type_name)
+        .map(|name| (name.to_string(), Source::Wmi))
+        .ok_or_else(|| OverwatchError::not_found("chassis type"));
+    fields.insert("chassis_type", chassis);
+
+    Ok(fields)
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn read_fields() -> Result<Fields> {
+    Err(OverwatchError::unsupported("board info"))
+}
@@ -0,0 +1,431 @@
+//! Resource use per cgroup v2: containers, systemd slices and services,
+//! read from `/sys/fs/cgroup`. Container IDs are named through the Docker
+//! or Podman socket when one is reachable.
+
+use crate::error::{OverwatchError, Provenance, Result};
+use crate::session::Session;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use tauri::State;
+
+#[cfg(target_os = "linux")]
+use crate::error::Source;
+#[cfg(target_os = "linux")]
+use crate::snapshot::LastReading;
+#[cfg(target_os = "linux")]
+use std::sync::Mutex;
+#[cfg(target_os = "linux")]
+use std::time::Instant;
+
+/// How often to ask the container runtimes again about IDs they didn't know.
+#[cfg(target_os = "linux")]
+const NAME_RETRY: Duration = Duration::from_secs(10);
+#[cfg(target_os = "linux")]
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(2);
+
+// cgroup name prefixes the systemd cgroup driver of each runtime uses
+const SCOPE_PREFIXES: [(&str, &str); 4] = [
+    ("docker-", "docker"),
+    ("libpod-", "podman"),
+    ("cri-containerd-", "containerd"),
+    ("crio-", "cri-o"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CgroupKind {
+    Slice,
+    Service,
+    Container,
+}
+
+/// The counters a rate is worked out from.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CgroupCounters {
+    pub cpu_usec: u64,
+    pub periods: Option<u64>,
+    pub throttled_periods: Option<u64>,
+    pub throttled_usec: Option<u64>,
+    pub read_bytes: Option<u64>,
+    pub write_bytes: Option<u64>,
+}
+
+/// One cgroup as read from the filesystem.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CgroupReading {
+    /// Relative to the hierarchy root, e.g. "/system.slice/docker-1f2e….scope".
+    pub path: String,
+    pub kind: CgroupKind,
+    pub depth: u32,
+    /// (runtime, full ID) for containers.
+    pub container: Option<(String, String)>,
+    pub counters: CgroupCounters,
+    /// CPUs' worth of quota from `cpu.max`.
+    pub cpu_limit: Option<f32>,
+    pub memory_current: Option<u64>,
+    pub memory_max: Option<u64>,
+    pub pids_current: Option<u64>,
+    pub pids_max: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct Cgroup {
+    pub path: String,
+    /// Container name, unit name or short container ID.
+    pub name: String,
+    pub kind: CgroupKind,
+    /// Nesting below the listed cgroups' common root, for indenting.
+    pub depth: u32,
+    /// "docker", "podman", "containerd" or "cri-o".
+    pub runtime: Option<String>,
+    pub container_id: Option<String>,
+    /// Percent of one CPU, like `top`.
+    pub cpu_percent: f32,
+    pub cpu_limit: Option<f32>,
+    /// Share of scheduler periods in the window where the quota ran out.
+    pub throttled_percent: Option<f32>,
+    /// Milliseconds per second spent throttled.
+    pub throttled_ms: Option<f32>,
+    pub memory_current: Option<u64>,
+    /// None when unlimited.
+    pub memory_max: Option<u64>,
+    /// Bytes per second.
+    pub io_read: Option<f64>,
+    pub io_write: Option<f64>,
+    pub pids_current: Option<u64>,
+    pub pids_max: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CgroupStats {
+    pub cgroups: Vec<Cgroup>,
+    pub provenance: Provenance,
+}
+
+/// `key value` lines, as in `cpu.stat`.
+pub fn parse_keyed(text: &str) -> HashMap<&str, u64> {
+    text.lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            Some((key, value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// A single number or "max".
+pub fn parse_limit(text: &str) -> Option<u64> {
+    text.trim().parse().ok()
+}
+
+/// `cpu.max`: "quota period", quota being "max" when unlimited.
+pub fn parse_cpu_max(text: &str) -> Option<f32> {
+    let mut fields = text.split_whitespace();
+    let quota: f32 = fields.next()?.parse().ok()?;
+    let period: f32 = fields.next()?.parse().ok()?;
+    (period > 0.0).then(|| quota / period)
+}
+
+/// `io.stat`: one "major:minor rbytes=… wbytes=… …" line per device,
+/// summed over devices.
+pub fn parse_io_stat(text: &str) -> (u64, u64) {
+    let mut read = 0;
+    let mut write = 0;
+    for field in text.split_whitespace() {
+        match field.split_once('=') {
+            Some(("rbytes", value)) => read += value.parse::<u64>().unwrap_or(0),
+            Some(("wbytes", value)) => write += value.parse::<u64>().unwrap_or(0),
+            _ => {}
+        }
+    }
+    (read, write)
+}
+
+fn is_container_id(text: &str) -> bool {
+    text.len() == 64 && text.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// What a cgroup directory is, from its name and its parent's. Returns None
+/// for the ones not worth listing on their own: session scopes, sub-cgroups
+/// inside services, runtime monitor processes.
+pub fn classify(name: &str, parent: &str) -> Option<(CgroupKind, Option<(String, String)>)> {
+    if let Some(scope) = name.strip_suffix(".scope") {
+        return SCOPE_PREFIXES.iter().find_map(|(prefix, runtime)| {
+            let id = scope.strip_prefix(prefix)?;
+            is_container_id(id).then(|| (CgroupKind::Container, Some((runtime.to_string(), id.to_string()))))
+        });
+    }
+    // the cgroupfs driver puts containers in a directory per runtime
+    if is_container_id(name) {
+        let runtime = match parent {
+            "docker" => "docker",
+            "libpod_parent" => "podman",
+            _ => return None,
+        };
+        return Some((CgroupKind::Container, Some((runtime.to_string(), name.to_string()))));
+    }
+    if name.ends_with(".slice") {
+        return Some((CgroupKind::Slice, None));
+    }
+    if name.ends_with(".service") {
+        return Some((CgroupKind::Service, None));
+    }
+    None
+}
+
+fn read_cgroup(dir: &Path, path: String, kind: CgroupKind, depth: u32, container: Option<(String, String)>) -> CgroupReading {
+    let read = |name: &str| std::fs::read_to_string(dir.join(name)).ok();
+    let cpu = read("cpu.stat").unwrap_or_default();
+    let cpu = parse_keyed(&cpu);
+    let io = read("io.stat").map(|text| parse_io_stat(&text));
+    CgroupReading {
+        path,
+        kind,
+        depth,
+        container,
+        counters: CgroupCounters {
+            cpu_usec: cpu.get("usage_usec").copied().unwrap_or(0),
+            periods: cpu.get("nr_periods").copied(),
+            throttled_periods: cpu.get("nr_throttled").copied(),
+            throttled_usec: cpu.get("throttled_usec").copied(),
+            read_bytes: io.map(|(read, _)| read),
+            write_bytes: io.map(|(_, write)| write),
+        },
+        cpu_limit: read("cpu.max").and_then(|text| parse_cpu_max(&text)),
+        memory_current: read("memory.current").and_then(|text| parse_limit(&text)),
+        memory_max: read("memory.max").and_then(|text| parse_limit(&text)),
+        pids_current: read("pids.current").and_then(|text| parse_limit(&text)),
+        pids_max: read("pids.max").and_then(|text| parse_limit(&text)),
+    }
+}
+
+fn walk(dir: &Path, path: &str, depth: u32, out: &mut Vec<CgroupReading>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut children: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    children.sort();
+
+    let parent = path.rsplit('/').next().unwrap_or_default();
+    for name in children {
+        let child_dir = dir.join(&name);
+        let child_path = format!("{}/{}", path, name);
+        match classify(&name, parent) {
+            // what runs inside a container is the container's business
+            Some((CgroupKind::Container, container)) => {
+                out.push(read_cgroup(&child_dir, child_path, CgroupKind::Container, depth, container));
+            }
+            Some((kind, _)) => {
+                out.push(read_cgroup(&child_dir, child_path.clone(), kind, depth, None));
+                walk(&child_dir, &child_path, depth + 1, out);
+            }
+            None => walk(&child_dir, &child_path, depth, out),
+        }
+    }
+}
+
+/// Every slice, service and container under a `/sys/fs/cgroup`-style
+/// directory, parents before children.
+pub fn read_tree(root: &Path) -> Result<Vec<CgroupReading>> {
+    if !root.join("cgroup.controllers").exists() {
+        return Err(OverwatchError::not_found("cgroup v2 hierarchy"));
+    }
+    let mut out = Vec::new();
+    walk(root, "", 0, &mut out);
+    Ok(out)
+}
+
+/// Names by container ID out of a `GET /containers/json` body, which
+/// Docker and Podman's compatible API both answer.
+pub fn parse_containers(body: &str) -> Result<HashMap<String, String>> {
+    #[derive(Deserialize)]
+    struct Container {
+        #[serde(rename = "Id")]
+        id: String,
+        #[serde(rename = "Names", default)]
+        names: Vec<String>,
+    }
+
+    let containers: Vec<Container> = serde_json::from_str(body).map_err(|err| OverwatchError::backend("container runtime", err))?;
+    Ok(containers
+        .into_iter()
+        .filter_map(|container| {
+            let name = container.names.first()?.trim_start_matches('/').to_string();
+            Some((container.id, name))
+        })
+        .collect())
+}
+
+fn rate(before: Option<u64>, after: Option<u64>, secs: f64) -> Option<f64> {
+    Some(after?.saturating_sub(before?) as f64 / secs)
+}
+
+/// Usage between two readings. Cgroups that weren't there before report
+/// zero rates until the next call.
+pub fn usage(before: &[CgroupReading], after: &[CgroupReading], elapsed: Duration, names: &HashMap<String, String>) -> Vec<Cgroup> {
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    let previous: HashMap<&str, &CgroupCounters> = before.iter().map(|reading| (reading.path.as_str(), &reading.counters)).collect();
+
+    after
+        .iter()
+        .map(|reading| {
+            let now = &reading.counters;
+            let then = previous.get(reading.path.as_str()).copied().unwrap_or(now);
+            let periods = now.periods.zip(then.periods).map(|(now, then)| now.saturating_sub(then));
+            let throttled = now.throttled_periods.zip(then.throttled_periods).map(|(now, then)| now.saturating_sub(then));
+            let (runtime, container_id) = reading.container.clone().unzip();
+            let unit = reading.path.rsplit('/').next().unwrap_or_default();
+            let name = match &container_id {
+                Some(id) => names.get(id).cloned().unwrap_or_else(|| id[..12].to_string()),
+                None => unit.to_string(),
+            };
+
+            Cgroup {
+                path: reading.path.clone(),
+                name,
+                kind: reading.kind,
+                depth: reading.depth,
+                runtime,
+                container_id,
+                cpu_percent: (now.cpu_usec.saturating_sub(then.cpu_usec) as f64 / secs / 1e4) as f32,
+                cpu_limit: reading.cpu_limit,
+                throttled_percent: periods.zip(throttled).map(|(periods, throttled)| match periods {
+                    0 => 0.0,
+                    periods => throttled as f32 / periods as f32 * 100.0,
+                }),
+                throttled_ms: rate(then.throttled_usec, now.throttled_usec, secs).map(|usec| (usec / 1000.0) as f32),
+                memory_current: reading.memory_current,
+                memory_max: reading.memory_max,
+                io_read: rate(then.read_bytes, now.read_bytes, secs),
+                io_write: rate(then.write_bytes, now.write_bytes, secs),
+                pids_current: reading.pids_current,
+                pids_max: reading.pids_max,
+            }
+        })
+        .collect()
+}
+
+/// Sockets to ask for container names, rootful and rootless.
+#[cfg(target_os = "linux")]
+fn runtime_sockets() -> Vec<std::path::PathBuf> {
+    let mut sockets = vec!["/var/run/docker.sock".into(), "/run/podman/podman.sock".into()];
+    if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+        let runtime_dir = std::path::PathBuf::from(runtime_dir);
+        sockets.push(runtime_dir.join("docker.sock"));
+        sockets.push(runtime_dir.join("podman/podman.sock"));
+    }
+    sockets
+}
+
+/// A plain HTTP/1.0 GET over a Unix socket, so the reply isn't chunked.
+#[cfg(target_os = "linux")]
+fn container_names(socket: &Path) -> Result<HashMap<String, String>> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(socket).map_err(|err| OverwatchError::from_io("container runtime socket", err))?;
+    stream.set_read_timeout(Some(SOCKET_TIMEOUT)).ok();
+    stream.set_write_timeout(Some(SOCKET_TIMEOUT)).ok();
+    stream
+        .write_all(b"GET /containers/json HTTP/1.0\r\nHost: localhost\r\n\r\n")
+        .map_err(|err| OverwatchError::from_io("container runtime socket", err))?;
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply).map_err(|err| OverwatchError::from_io("container runtime socket", err))?;
+
+    let (head, body) = reply.split_once("\r\n\r\n").unwrap_or((&reply, ""));
+    let status = head.lines().next().unwrap_or_default();
+    if status.split_whitespace().nth(1) != Some("200") {
+        return Err(OverwatchError::backend("container runtime", status));
+    }
+    parse_containers(body)
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct ContainerNames {
+    names: HashMap<String, String>,
+    error: Option<OverwatchError>,
+    asked: Option<Instant>,
+}
+
+#[cfg(target_os = "linux")]
+impl ContainerNames {
+    /// Asks every reachable runtime again when a container isn't named yet,
+    /// at most every `NAME_RETRY`.
+    fn refresh(&mut self, readings: &[CgroupReading]) {
+        let unknown = readings.iter().filter_map(|reading| reading.container.as_ref()).any(|(_, id)| !self.names.contains_key(id));
+        let due = self.asked.map_or(true, |asked| asked.elapsed() >= NAME_RETRY);
+        if !unknown || !due {
+            return;
+        }
+        self.asked = Some(Instant::now());
+        self.error = None;
+        let mut reached = false;
+        for socket in runtime_sockets().iter().filter(|socket| socket.exists()) {
+            match container_names(socket) {
+                Ok(names) => {
+                    reached = true;
+                    self.names.extend(names);
+                }
+                Err(err) => self.error = Some(err),
+            }
+        }
+        if reached {
+            self.error = None;
+        } else if self.error.is_none() {
+            self.error = Some(OverwatchError::not_found("container runtime socket"));
+        }
+    }
+}
+
+/// Usage per cgroup since the previous call.
+#[derive(Default)]
+pub struct CgroupMonitor {
+    #[cfg(target_os = "linux")]
+    last: LastReading<Vec<CgroupReading>>,
+    #[cfg(target_os = "linux")]
+    names: Mutex<ContainerNames>,
+}
+
+impl CgroupMonitor {
+    #[cfg(target_os = "linux")]
+    pub fn read(&self) -> Result<CgroupStats> {
+        let root = Path::new("/sys/fs/cgroup");
+        self.last.advance(
+            || read_tree(root),
+            |before, readings, elapsed| {
+                let mut names = self.names.lock().unwrap();
+                names.refresh(readings);
+                let cgroups = usage(before, readings, elapsed, &names.names);
+
+                let mut provenance = Provenance::new();
+                provenance.available("cgroups", Source::Sysfs);
+                let containers = readings.iter().filter_map(|reading| reading.container.as_ref());
+                if containers.clone().any(|(_, id)| names.names.contains_key(id)) {
+                    provenance.available("names", Source::ContainerRuntime);
+                } else if containers.count() > 0 {
+                    let reason = names.error.clone().unwrap_or_else(|| OverwatchError::not_found("container names"));
+                    provenance.missing("names", reason);
+                }
+                CgroupStats { cgroups, provenance }
+            },
+        )
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn read(&self) -> Result<CgroupStats> {
+        Err(OverwatchError::unsupported("cgroups"))
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_cgroups(session: State<'_, Session>, monitor: State<'_, CgroupMonitor>) -> Result<CgroupStats> {
+    session.sample(|| monitor.read())
+}
@@ -0,0 +1,362 @@
+use crate::cpuflags;
+use crate::cpufreq::{self, FrequencyPolicy};
+use crate::cpuidle::{self, CoreIdle};
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::rapl;
+use crate::sched::{self, SchedulerActivity};
+use crate::session::Session;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use sysinfo::{Components, CpuRefreshKind, RefreshKind, System};
+use tauri::State;
+
+#[cfg(windows)]
+use crate::wmi_query;
+#[cfg(windows)]
+use wmi::Variant;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CpuInfo {
+    pub brand: String,
+    pub frequency: u64,
+    pub cores: usize,
+    pub logical_cores: usize,
+    pub usage: f32,
+    pub per_core_usage: Vec<f32>,
+    /// Load, run queue and per-second scheduler counters over the same window.
+    pub scheduler: Option<SchedulerActivity>,
+    /// Idle-state residency per logical CPU over the same window as the usage.
+    pub idle_states: Vec<CoreIdle>,
+    pub temperature: f32,
+    pub temp_available: bool,
+    /// Package power in watts over the usage sampling window, from RAPL.
+    pub package_power: Option<f32>,
+    pub vendor: String,
+    pub architecture: String,
+    pub max_frequency: u64,
+    pub frequency_policy: Option<FrequencyPolicy>,
+    pub cache_l1: String,
+    pub cache_l2: String,
+    pub cache_l3: String,
+    pub socket: String,
+    pub process_node: String,
+    pub provenance: Provenance,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_cpu_info(session: State<'_, Session>) -> Result<CpuInfo> {
+    session.sample(read_cpu_info)
+}
+
+pub fn read_cpu_info() -> Result<CpuInfo> {
+    let mut sys = System::new_with_specifics(
+        RefreshKind::new()
+            .with_cpu(CpuRefreshKind::everything())
+    );
+
+    // refresh twice for sysinfo, reading the energy, idle and scheduler counters over the same window
+    let energy_before = rapl::read_counters();
+    let idle_window = cpuidle::start();
+    let sched_window = sched::start();
+    let window = std::time::Instant::now();
+    sys.refresh_cpu_all();
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    sys.refresh_cpu_all();
+    let energy_after = rapl::read_counters();
+    let idle_states = idle_window.finish();
+    let scheduler = sched_window.finish();
+    let elapsed = window.elapsed();
+
+    let cpus = sys.cpus();
+    if cpus.is_empty() {
+        return Err(OverwatchError::not_found("CPU"));
+    }
+    let usage = sys.global_cpu_usage();
+
+    let brand = cpus[0].brand().to_string();
+    let freq = cpus[0].frequency();
+
+    let logical = cpus.len();
+    let physical = sys.physical_core_count().unwrap_or(cpus.len());
+    let cores: Vec<f32> = cpus.iter().map(|cpu| cpu.cpu_usage()).collect();
+
+    let mut provenance = Provenance::new();
+
+    let (temp, temp_ok) = match get_cpu_temp() {
+        Ok((temp, source)) => {
+            provenance.available("temperature", source);
+            (temp, true)
+        }
+        Err(err) => {
+            provenance.missing("temperature", err);
+            (0.0, false)
+        }
+    };
+
+    let package_power = match (energy_before, energy_after) {
+        (Ok((before, source)), Ok((after, _))) => {
+            let packages: Vec<f32> = rapl::watts(&before, &after, elapsed)
+                .into_iter()
+                .filter(|(counter, _)| counter.is_package())
+                .map(|(_, watts)| watts)
+                .collect();
+            match packages.is_empty() {
+                true => Err(OverwatchError::not_found("package energy counter")),
+                false => Ok((packages.iter().sum(), source)),
+            }
+        }
+        (Err(err), _) | (_, Err(err)) => Err(err),
+    };
+    let package_power = match package_power {
+        Ok((watts, source)) => {
+            provenance.available("package_power", source);
+            Some(watts)
+        }
+        Err(err) => {
+            provenance.missing("package_power", err);
+            None
+        }
+    };
+
+    let idle_states = match idle_states {
+        Ok((states, source)) => {
+            provenance.available("idle_states", source);
+            states
+        }
+        Err(err) => {
+            provenance.missing("idle_states", err);
+            Vec::new()
+        }
+    };
+    let scheduler = match scheduler {
+        Ok((activity, source)) => {
+            provenance.available("scheduler", source);
+            Some(activity)
+        }
+        Err(err) => {
+            provenance.missing("scheduler", err);
+            None
+        }
+    };
+    let frequency_policy = match cpufreq::read_policy() {
+        Ok((policy, source)) => {
+            provenance.available("frequency_policy", source);
+            Some(policy)
+        }
+        Err(err) => {
+            provenance.missing("frequency_policy", err);
+            None
+        }
+    };
+
+    let vendor = if brand.to_lowercase().contains("intel") {
+        "Intel"
+    } else if brand.to_lowercase().contains("amd") {
+        "AMD"
+    } else {
+        "Other"
+    }.to_string();
+
+    let arch = cpuflags::architecture();
+
+    let max_freq = get_max_freq();
+    provenance.record("max_frequency", Source::Wmi, &max_freq);
+    let max_freq = max_freq.unwrap_or(freq);
+
+    let details = get_cpu_details();
+    let cache_l1 = detail_field(&mut provenance, "cache_l1", &details, |d| d.cache_l1.clone());
+    let cache_l2 = detail_field(&mut provenance, "cache_l2", &details, |d| d.cache_l2.clone());
+    let cache_l3 = detail_field(&mut provenance, "cache_l3", &details, |d| d.cache_l3.clone());
+    let socket = detail_field(&mut provenance, "socket", &details, |d| d.socket.clone());
+
+    let process_node = guess_process_node(&brand);
+    match process_node {
+        Some(_) => provenance.estimated("process_node", Source::NameHeuristic),
+        None => provenance.missing("process_node", OverwatchError::not_found(format!("process node for {}", brand))),
+    }
+
+    Ok(CpuInfo {
+        brand,
+        frequency: freq,
+        cores: physical,
+        logical_cores: logical,
+        usage,
+        per_core_usage: cores,
+        scheduler,
+        idle_states,
+        temperature: temp,
+        temp_available: temp_ok,
+        package_power,
+        vendor,
+        architecture: arch,
+        max_frequency: max_freq,
+        frequency_policy,
+        cache_l1,
+        cache_l2,
+        cache_l3,
+        socket,
+        process_node: process_node.unwrap_or("Unknown").to_string(),
+        provenance,
+    })
+}
+
+// pulls one field out of the WMI details and records where it came from
+fn detail_field(
+    provenance: &mut Provenance,
+    field: &str,
+    details: &Result<CpuDetails>,
+    get: impl Fn(&CpuDetails) -> Option<String>,
+) -> String {
+    let value = details.as_ref().map_err(Clone::clone).and_then(|d| {
+        get(d).ok_or_else(|| OverwatchError::not_found(field.replace('_', " ")))
+    });
+    provenance.record(field, Source::Wmi, &value);
+    value.unwrap_or_else(|_| "Unknown".to_string())
+}
+
+fn get_cpu_temp() -> Result<(f32, Source)> {
+    let mut components = Components::new();
+    components.refresh();
+
+    // cpu temp first
+    for component in &components {
+        let label = component.label().to_lowercase();
+        let temp = component.temperature();
+        if (label.contains("package") || label.contains("cpu")) && temp > 0.0 && temp < 150.0 {
+            return Ok((temp, Source::Sysinfo));
+        }
+    }
+
+    // any temp will do
+    for component in &components {
+        let temp = component.temperature();
+        if temp > 20.0 && temp < 100.0 {
+            return Ok((temp, Source::Sysinfo));
+        }
+    }
+
+    get_cpu_temp_wmi().map(|temp| (temp, Source::Wmi))
+}
+
+#[cfg(windows)]
+fn get_cpu_temp_wmi() -> Result<f32> {
+    let wmi_con = wmi_query::connect()?;
+
+    let zones = wmi_query::query(&wmi_con, "SELECT * FROM Win32_PerfRawData_Counters_ThermalZoneInformation");
+    for result in zones.iter().flatten() {
+        if let Some(Variant::UI8(temp)) = result.get("HighPrecisionTemperature") {
+            let celsius = (*temp as f32 - 2732.0) / 10.0;
+            if celsius > 20.0 && celsius < 100.0 {
+                return Ok(celsius);
+            }
+        }
+    }
+
+    // MSAcpi usually needs admin, so its error is the more useful one to report
+    let acpi = wmi_query::query(&wmi_con, "SELECT * FROM MSAcpi_ThermalZoneTemperature")?;
+    for result in acpi {
+        if let Some(Variant::UI4(temp)) = result.get("CurrentTemperature") {
+            let celsius = (*temp as f32 - 2732.0) / 10.0;
+            if celsius > 20.0 && celsius < 100.0 {
+                return Ok(celsius);
+            }
+        }
+    }
+
+    Err(OverwatchError::not_found("CPU temperature sensor"))
+}
+
+#[cfg(not(windows))]
+fn get_cpu_temp_wmi() -> Result<f32> {
+    Err(OverwatchError::not_found("CPU temperature sensor"))
+}
+
+#[cfg(windows)]
+fn get_max_freq() -> Result<u64> {
+    let wmi_con = wmi_query::connect()?;
+    for result in wmi_query::query(&wmi_con, "SELECT MaxClockSpeed FROM Win32_Processor")? {
+        if let Some(Variant::UI4(freq)) = result.get("MaxClockSpeed") {
+            return Ok(*freq as u64);
+        }
+    }
+    Err(OverwatchError::not_found("MaxClockSpeed"))
+}
+
+#[cfg(not(windows))]
+fn get_max_freq() -> Result<u64> {
+    Err(OverwatchError::unsupported("max CPU frequency"))
+}
+
+#[derive(Default)]
+struct CpuDetails {
+    cache_l1: Option<String>,
+    cache_l2: Option<String>,
+    cache_l3: Option<String>,
+    socket: Option<String>,
+}
+
+#[cfg(windows)]
+fn get_cpu_details() -> Result<CpuDetails> {
+    let mut details = CpuDetails::default();
+
+    let wmi_con = wmi_query::connect()?;
+    if let Some(result) = wmi_query::query(&wmi_con, "SELECT * FROM Win32_Processor")?.first() {
+        if let Some(Variant::UI4(l2)) = result.get("L2CacheSize") {
+            if *l2 > 0 {
+                details.cache_l2 = Some(format!("{} KB", l2));
+            }
+        }
+
+        if let Some(Variant::UI4(l3)) = result.get("L3CacheSize") {
+            if *l3 > 0 {
+                details.cache_l3 = Some(format!("{} KB", l3));
+            }
+        }
+
+        if let Some(Variant::String(socket)) = result.get("SocketDesignation") {
+            if !socket.is_empty() {
+                details.socket = Some(socket.clone());
+            }
+        }
+    }
+
+    // l1 cache
+    for result in wmi_query::query(&wmi_con, "SELECT * FROM Win32_CacheMemory WHERE Level = 3")? {
+        if let Some(Variant::UI4(size)) = result.get("MaxCacheSize") {
+            details.cache_l1 = Some(format!("{} KB", size));
+            break;
+        }
+    }
+
+    Ok(details)
+}
+
+#[cfg(not(windows))]
+fn get_cpu_details() -> Result<CpuDetails> {
+    Err(OverwatchError::unsupported("CPU cache and socket details"))
+}
+
+fn guess_process_node(cpu_name: &str) -> Option<&'static str> {
+    let name = cpu_name.to_lowercase();
+
+    // intel
+    if name.contains("13th gen") || name.contains("-13") || name.contains("12th gen") || name.contains("-12") {
+        Some("Intel 7 (10nm)")
+    } else if name.contains("11th gen") || name.contains("-11") {
+        Some("10nm SuperFin")
+    } else if name.contains("10th gen") || name.contains("-10") {
+        Some("14nm")
+    // amd
+    } else if name.contains("7000") {
+        Some("5nm (TSMC)")
+    } else if name.contains("5000") || name.contains("3000") {
+        Some("7nm (TSMC)")
+    } else if name.contains("2000") {
+        Some("12nm (TSMC)")
+    } else if name.contains("1000") {
+        Some("14nm (GloFo)")
+    } else {
+        None
+    }
+}
@@ -0,0 +1,347 @@
+//! What the CPU actually implements: ISA extensions, microcode revision and
+//! the kernel's verdict on each known hardware vulnerability.
+
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CpuFeatures {
+    /// The hardware's architecture, not the one this build targets.
+    pub architecture: String,
+    /// Every flag, in the order the OS lists them.
+    pub flags: Vec<FeatureFlag>,
+    /// The notable flags by family; a flag is in at most one group.
+    pub groups: Vec<FeatureGroup>,
+    /// e.g. "0x2b000603".
+    pub microcode: Option<String>,
+    pub vulnerabilities: Vec<Vulnerability>,
+    pub provenance: Provenance,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct FeatureFlag {
+    /// As Linux names it in /proc/cpuinfo, e.g. "sse4_2".
+    pub name: String,
+    /// e.g. "SSE4.2".
+    pub label: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct FeatureGroup {
+    pub name: String,
+    pub flags: Vec<FeatureFlag>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MitigationStatus {
+    NotAffected,
+    Mitigated,
+    /// Mitigated, but with a part still reported vulnerable.
+    Partial,
+    Vulnerable,
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct Vulnerability {
+    /// "spectre_v2", "mds", ...
+    pub name: String,
+    pub status: MitigationStatus,
+    /// The kernel's own wording.
+    pub detail: String,
+}
+
+// (group, exact names, prefixes)
+const GROUPS: [(&str, &[&str], &[&str]); 9] = [
+    ("SSE", &["sse", "sse2", "pni", "ssse3", "sse4_1", "sse4_2", "sse4a"], &[]),
+    ("AVX", &["avx", "avx2", "fma", "f16c", "avx_vnni", "avx_vnni_int8", "avx_ne_convert", "avx_ifma"], &[]),
+    ("AVX-512", &[], &["avx512"]),
+    ("AMX", &[], &["amx_"]),
+    ("NEON / SVE", &["asimd", "asimdhp", "asimddp", "asimdfhm", "asimdrdm"], &["sve"]),
+    (
+        "Crypto",
+        &["aes", "vaes", "pclmulqdq", "vpclmulqdq", "sha_ni", "gfni", "rdrand", "rdseed", "pmull", "sha1", "sha2", "sha3", "sha512", "sm3", "sm4"],
+        &[],
+    ),
+    ("Virtualization", &["vmx", "svm", "hypervisor", "ept", "npt", "vpid"], &[]),
+    ("Security", &["smep", "smap", "umip", "pku", "ibt", "shstk", "user_shstk", "sme", "sev", "sev_es", "sev_snp", "tdx_guest", "bti", "paca", "pacg", "mte"], &[]),
+    ("Bit manipulation", &["bmi1", "bmi2", "abm", "popcnt", "adx", "movbe", "lzcnt"], &[]),
+];
+
+// names that don't uppercase into what people call them
+const LABELS: [(&str, &str); 14] = [
+    ("pni", "SSE3"),
+    ("sse4_1", "SSE4.1"),
+    ("sse4_2", "SSE4.2"),
+    ("sse4a", "SSE4a"),
+    ("sha_ni", "SHA"),
+    ("aes", "AES-NI"),
+    ("vmx", "VT-x"),
+    ("svm", "AMD-V"),
+    ("ept", "VT-x EPT"),
+    ("npt", "AMD-V NPT"),
+    ("asimd", "NEON"),
+    ("abm", "ABM (LZCNT)"),
+    ("hypervisor", "Running under a hypervisor"),
+    ("avx_vnni", "AVX-VNNI"),
+];
+
+pub fn label(flag: &str) -> String {
+    if let Some((_, label)) = LABELS.iter().find(|(name, _)| *name == flag) {
+        return label.to_string();
+    }
+    // avx512_vbmi2 -> AVX-512 VBMI2, avx512bw -> AVX-512 BW
+    if let Some(rest) = flag.strip_prefix("avx512") {
+        return format!("AVX-512 {}", rest.trim_start_matches('_').to_uppercase().replace('_', " "));
+    }
+    flag.to_uppercase().replace('_', "-")
+}
+
+fn flag(name: &str) -> FeatureFlag {
+    FeatureFlag { name: name.to_string(), label: label(name) }
+}
+
+/// Sorts flags into `GROUPS`, leaving out the groups with nothing in them.
+pub fn group(flags: &[FeatureFlag]) -> Vec<FeatureGroup> {
+    GROUPS
+        .iter()
+        .map(|(name, exact, prefixes)| FeatureGroup {
+            name: name.to_string(),
+            flags: flags
+                .iter()
+                .filter(|flag| exact.contains(&flag.name.as_str()) || prefixes.iter().any(|prefix| flag.name.starts_with(prefix)))
+                .cloned()
+                .collect(),
+        })
+        .filter(|group| !group.flags.is_empty())
+        .collect()
+}
+
+/// Flags and microcode revision from the first processor in `/proc/cpuinfo`;
+/// x86 lists "flags", ARM "Features".
+pub fn parse_cpuinfo(text: &str) -> Result<(Vec<FeatureFlag>, Option<String>)> {
+    let first = text.split("\n\n").next().unwrap_or_default();
+    let field = |key: &str| {
+        first.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == key).then(|| value.trim().to_string())
+        })
+    };
+    let flags = field("flags")
+        .or_else(|| field("Features"))
+        .ok_or_else(|| OverwatchError::not_found("CPU flags in /proc/cpuinfo"))?;
+    Ok((flags.split_whitespace().map(flag).collect(), field("microcode").filter(|microcode| !microcode.is_empty())))
+}
+
+pub fn classify(detail: &str) -> MitigationStatus {
+    // itlb_multihit is about guests, and says so
+    let detail = detail.strip_prefix("KVM: ").unwrap_or(detail);
+    if detail == "Not affected" {
+        MitigationStatus::NotAffected
+    } else if detail.starts_with("Mitigation") {
+        // "Mitigation: Enhanced IBRS; ...; BHI: Vulnerable"
+        match detail.contains("Vulnerable") {
+            true => MitigationStatus::Partial,
+            false => MitigationStatus::Mitigated,
+        }
+    } else if detail.starts_with("Vulnerable") {
+        MitigationStatus::Vulnerable
+    } else {
+        MitigationStatus::Unknown
+    }
+}
+
+/// The files under a `/sys/devices/system/cpu/vulnerabilities`-style directory.
+pub fn read_vulnerabilities(dir: &Path) -> Result<Vec<Vulnerability>> {
+    let entries = std::fs::read_dir(dir).map_err(|err| OverwatchError::from_io("CPU vulnerabilities", err))?;
+    let mut vulnerabilities: Vec<Vulnerability> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let detail = std::fs::read_to_string(entry.path()).ok()?.trim().to_string();
+            Some(Vulnerability {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                status: classify(&detail),
+                detail,
+            })
+        })
+        .collect();
+    vulnerabilities.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(vulnerabilities)
+}
+
+/// Puts an OS's architecture name the way the CPU card shows it.
+pub fn architecture_name(machine: &str) -> String {
+    match machine {
+        "x86_64" | "amd64" | "AMD64" => "x64",
+        "aarch64" | "arm64" | "ARM64" => "ARM64",
+        "x86" | "i386" | "i586" | "i686" | "ia32" => "x86",
+        other => other,
+    }
+    .to_string()
+}
+
+/// The hardware's architecture, falling back to the build's.
+pub fn architecture() -> String {
+    let machine = native_machine().unwrap_or_else(|_| std::env::consts::ARCH.to_string());
+    architecture_name(&machine)
+}
+
+#[cfg(not(windows))]
+fn native_machine() -> Result<String> {
+    sysinfo::System::cpu_arch().ok_or_else(|| OverwatchError::not_found("machine architecture"))
+}
+
+// the system-wide variable, which WOW64 and x64 emulation on ARM don't rewrite
+#[cfg(windows)]
+fn native_machine() -> Result<String> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"SYSTEM\CurrentControlSet\Control\Session Manager\Environment")
+        .and_then(|key| key.get_value::<String, _>("PROCESSOR_ARCHITECTURE"))
+        .map_err(|err| OverwatchError::from_io("processor architecture", err))
+}
+
+#[cfg(target_os = "linux")]
+pub fn read_cpu_features() -> Result<CpuFeatures> {
+    let mut provenance = Provenance::new();
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").map_err(|err| OverwatchError::from_io("/proc/cpuinfo", err))?;
+    let (flags, microcode) = parse_cpuinfo(&cpuinfo)?;
+    provenance.available("flags", Source::Procfs);
+    match microcode {
+        Some(_) => provenance.available("microcode", Source::Procfs),
+        None => provenance.missing("microcode", OverwatchError::not_found("microcode revision")),
+    }
+    // kernels before 4.15 don't have the directory
+    let vulnerabilities = read_vulnerabilities(Path::new("/sys/devices/system/cpu/vulnerabilities"));
+    provenance.record("vulnerabilities", Source::Sysfs, &vulnerabilities);
+
+    Ok(CpuFeatures {
+        architecture: architecture(),
+        groups: group(&flags),
+        flags,
+        microcode,
+        vulnerabilities: vulnerabilities.unwrap_or_default(),
+        provenance,
+    })
+}
+
+#[cfg(windows)]
+pub fn read_cpu_features() -> Result<CpuFeatures> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let mut provenance = Provenance::new();
+    let flags: Vec<FeatureFlag> = cpuid_flags().iter().map(|name| flag(name)).collect();
+    match flags.is_empty() {
+        true => provenance.missing("flags", OverwatchError::unsupported("CPU flags on this architecture")),
+        false => provenance.available("flags", Source::Cpuid),
+    }
+
+    // 8 bytes, the revision in the high half
+    let microcode = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"HARDWARE\DESCRIPTION\System\CentralProcessor\0")
+        .and_then(|key| key.get_raw_value("Update Revision"))
+        .map_err(|err| OverwatchError::from_io("microcode revision", err))
+        .and_then(|value| match value.bytes.get(4..8) {
+            Some(high) => Ok(format!("0x{:x}", u32::from_le_bytes([high[0], high[1], high[2], high[3]]))),
+            None => Err(OverwatchError::backend("microcode revision", "unexpected format")),
+        });
+    provenance.record("microcode", Source::Registry, &microcode);
+    provenance.missing("vulnerabilities", OverwatchError::unsupported("vulnerability status"));
+
+    Ok(CpuFeatures {
+        architecture: architecture(),
+        groups: group(&flags),
+        flags,
+        microcode: microcode.ok(),
+        vulnerabilities: Vec::new(),
+        provenance,
+    })
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn read_cpu_features() -> Result<CpuFeatures> {
+    Err(OverwatchError::unsupported("CPU features"))
+}
+
+// (leaf, subleaf, register: 0 = EBX, 1 = ECX, 2 = EDX, 3 = EAX, bits)
+#[cfg(all(windows, target_arch = "x86_64"))]
+type CpuidBits = (u32, u32, usize, &'static [(u32, &'static str)]);
+
+/// CPUID bits under the names Linux gives them, so both platforms group alike.
+// the intrinsics only became safe to call after our minimum Rust version
+#[cfg(all(windows, target_arch = "x86_64"))]
+#[allow(unused_unsafe)]
+fn cpuid_flags() -> Vec<&'static str> {
+    use std::arch::x86_64::{__cpuid, __cpuid_count};
+
+    const BITS: [CpuidBits; 7] = [
+        (1, 0, 2, &[(23, "mmx"), (25, "sse"), (26, "sse2"), (28, "ht")]),
+        (
+            1,
+            0,
+            1,
+            &[
+                (0, "pni"), (1, "pclmulqdq"), (5, "vmx"), (9, "ssse3"), (12, "fma"), (13, "cx16"), (19, "sse4_1"), (20, "sse4_2"),
+                (22, "movbe"), (23, "popcnt"), (25, "aes"), (26, "xsave"), (28, "avx"), (29, "f16c"), (30, "rdrand"), (31, "hypervisor"),
+            ],
+        ),
+        (
+            7,
+            0,
+            0,
+            &[
+                (3, "bmi1"), (5, "avx2"), (7, "smep"), (8, "bmi2"), (16, "avx512f"), (17, "avx512dq"), (18, "rdseed"), (19, "adx"),
+                (20, "smap"), (21, "avx512ifma"), (28, "avx512cd"), (29, "sha_ni"), (30, "avx512bw"), (31, "avx512vl"),
+            ],
+        ),
+        (
+            7,
+            0,
+            1,
+            &[
+                (1, "avx512vbmi"), (2, "umip"), (3, "pku"), (6, "avx512_vbmi2"), (8, "gfni"), (9, "vaes"), (10, "vpclmulqdq"),
+                (11, "avx512_vnni"), (12, "avx512_bitalg"), (14, "avx512_vpopcntdq"),
+            ],
+        ),
+        (7, 0, 2, &[(20, "ibt"), (22, "amx_bf16"), (23, "avx512_fp16"), (24, "amx_tile"), (25, "amx_int8")]),
+        (7, 1, 3, &[(4, "avx_vnni"), (5, "avx512_bf16")]),
+        (0x8000_0001, 0, 1, &[(0, "lahf_lm"), (2, "svm"), (5, "abm"), (6, "sse4a")]),
+    ];
+
+    // SAFETY: CPUID is always there on x86_64; out-of-range leaves are skipped
+    let max_leaf = unsafe { __cpuid(0) }.eax;
+    let max_extended = unsafe { __cpuid(0x8000_0000) }.eax;
+    let mut flags = Vec::new();
+    for (leaf, subleaf, register, bits) in BITS {
+        let supported = match leaf >= 0x8000_0000 {
+            true => leaf <= max_extended,
+            false => leaf <= max_leaf,
+        };
+        if !supported {
+            continue;
+        }
+        let result = unsafe { __cpuid_count(leaf, subleaf) };
+        let value = [result.ebx, result.ecx, result.edx, result.eax][register];
+        flags.extend(bits.iter().filter(|(bit, _)| value & (1 << bit) != 0).map(|(_, name)| *name));
+    }
+    flags
+}
+
+#[cfg(all(windows, not(target_arch = "x86_64")))]
+fn cpuid_flags() -> Vec<&'static str> {
+    Vec::new()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_cpu_features(session: State<'_, Session>) -> Result<CpuFeatures> {
+    session.sample(read_cpu_features)
+}
@@ -0,0 +1,113 @@
+//! How the OS scales CPU clocks: driver, governor, energy-performance
+//! preference and boost.
+
+use crate::error::{OverwatchError, Result, Source};
+use crate::sysfs::read_attr;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+
+#[cfg(windows)]
+use crate::wmi_query;
+#[cfg(windows)]
+use wmi::Variant;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct FrequencyPolicy {
+    /// "intel_pstate", "amd-pstate-epp", "acpi-cpufreq", ...
+    pub driver: Option<String>,
+    /// The cpufreq governor on Linux, the active power plan on Windows.
+    pub governor: Option<String>,
+    pub available_governors: Vec<String>,
+    /// Energy-performance preference, e.g. "balance_performance"; only
+    /// drivers running hardware P-states have one.
+    pub epp: Option<String>,
+    pub available_epp: Vec<String>,
+    /// Whether turbo / boost clocks are allowed.
+    pub boost: Option<bool>,
+    /// False when some CPUs run a different governor or preference than
+    /// the ones reported, which come from the first policy.
+    pub uniform: bool,
+}
+
+/// The `cpufreq/policyN` directories under a `/sys/devices/system/cpu`-style
+/// directory, in order; each covers the CPUs that share a clock.
+pub fn policy_dirs(root: &Path) -> Result<Vec<PathBuf>> {
+    let cpufreq = root.join("cpufreq");
+    let entries = std::fs::read_dir(&cpufreq).map_err(|err| OverwatchError::from_io("cpufreq", err))?;
+    let mut policies: Vec<u32> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.strip_prefix("policy")?.parse().ok())
+        .collect();
+    policies.sort_unstable();
+    if policies.is_empty() {
+        return Err(OverwatchError::not_found("cpufreq policies"));
+    }
+    Ok(policies.iter().map(|number| cpufreq.join(format!("policy{}", number))).collect())
+}
+
+/// The scaling setup under a `/sys/devices/system/cpu`-style directory.
+pub fn read_sysfs(root: &Path) -> Result<FrequencyPolicy> {
+    let policies = policy_dirs(root)?;
+    let policy = |dir: &Path, name: &str| read_attr(&dir.join(name));
+    let list = |name: &str| {
+        policy(&policies[0], name)
+            .map(|values| values.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default()
+    };
+
+    let governor = policy(&policies[0], "scaling_governor");
+    let epp = policy(&policies[0], "energy_performance_preference");
+    let uniform = policies
+        .iter()
+        .all(|dir| policy(dir, "scaling_governor") == governor && policy(dir, "energy_performance_preference") == epp);
+
+    // acpi-cpufreq and amd-pstate have a global switch, intel_pstate an inverted one
+    let boost = match read_attr(&root.join("cpufreq/boost")) {
+        Some(boost) => Some(boost == "1"),
+        None => read_attr(&root.join("intel_pstate/no_turbo")).map(|no_turbo| no_turbo == "0"),
+    };
+
+    Ok(FrequencyPolicy {
+        driver: policy(&policies[0], "scaling_driver"),
+        governor,
+        available_governors: list("scaling_available_governors"),
+        epp,
+        available_epp: list("energy_performance_available_preferences"),
+        boost,
+        uniform,
+    })
+}
+
+#[cfg(target_os = "linux")]
+pub fn read_policy() -> Result<(FrequencyPolicy, Source)> {
+    read_sysfs(Path::new("/sys/devices/system/cpu")).map(|policy| (policy, Source::Sysfs))
+}
+
+// Windows hides the processor power settings behind the power plan
+#[cfg(windows)]
+pub fn read_policy() -> Result<(FrequencyPolicy, Source)> {
+    let wmi_con = wmi_query::connect_namespace(r"root\cimv2\power")?;
+    let plans = wmi_query::query(&wmi_con, "SELECT ElementName FROM Win32_PowerPlan WHERE IsActive = TRUE")?;
+    let governor = match plans.first().and_then(|plan| plan.get("ElementName")) {
+        Some(Variant::String(name)) => name.clone(),
+        _ => return Err(OverwatchError::not_found("active power plan")),
+    };
+    Ok((
+        FrequencyPolicy {
+            driver: None,
+            governor: Some(governor),
+            available_governors: Vec::new(),
+            epp: None,
+            available_epp: Vec::new(),
+            boost: None,
+            uniform: true,
+        },
+        Source::Wmi,
+    ))
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn read_policy() -> Result<(FrequencyPolicy, Source)> {
+    Err(OverwatchError::unsupported("CPU frequency policy"))
+}
@@ -0,0 +1,195 @@
+//! Idle-state (C-state) residency per logical CPU.
+
+use crate::error::{OverwatchError, Result, Source};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use std::time::Duration;
+
+#[cfg(windows)]
+use crate::wmi_query;
+#[cfg(windows)]
+use wmi::Variant;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct CoreIdle {
+    pub cpu: u32,
+    /// Shallowest first.
+    pub states: Vec<IdleResidency>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct IdleResidency {
+    /// "POLL", "C1", "C6", ...
+    pub name: String,
+    /// Exit latency in microseconds, where the platform gives it.
+    pub latency_us: Option<u64>,
+    /// Share of the sampling window spent in this state, in percent.
+    pub residency: f32,
+    /// Times the state was entered during the window.
+    pub entries: Option<u64>,
+}
+
+/// Running totals of one idle state, as the kernel keeps them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateCounters {
+    pub cpu: u32,
+    /// The sysfs directory, e.g. "state2".
+    pub state: String,
+    pub name: String,
+    pub latency_us: Option<u64>,
+    pub time_us: u64,
+    pub usage: u64,
+}
+
+/// Every enabled idle state under a `/sys/devices/system/cpu`-style directory.
+pub fn read_sysfs(root: &Path) -> Result<Vec<StateCounters>> {
+    let entries = std::fs::read_dir(root).map_err(|err| OverwatchError::from_io("CPU sysfs", err))?;
+    let mut cpus: Vec<u32> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.strip_prefix("cpu")?.parse().ok())
+        .collect();
+    cpus.sort_unstable();
+
+    let mut counters = Vec::new();
+    for cpu in cpus {
+        let dir = root.join(format!("cpu{}", cpu)).join("cpuidle");
+        let Ok(states) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut states: Vec<(u32, String)> = states
+            .flatten()
+            .filter_map(|entry| {
+                let state = entry.file_name().to_string_lossy().into_owned();
+                Some((state.strip_prefix("state")?.parse().ok()?, state))
+            })
+            .collect();
+        states.sort_unstable();
+
+        for (_, state) in states {
+            let attr = |attr: &str| std::fs::read_to_string(dir.join(&state).join(attr)).ok().map(|text| text.trim().to_string());
+            let number = |name: &str| attr(name).and_then(|value| value.parse::<u64>().ok());
+            if attr("disable").as_deref() == Some("1") {
+                continue;
+            }
+            let Some(time_us) = number("time") else {
+                continue;
+            };
+            counters.push(StateCounters {
+                cpu,
+                name: attr("name").unwrap_or_else(|| state.clone()),
+                latency_us: number("latency"),
+                time_us,
+                usage: number("usage").unwrap_or(0),
+                state,
+            });
+        }
+    }
+
+    if counters.is_empty() {
+        return Err(OverwatchError::not_found("cpuidle states"));
+    }
+    Ok(counters)
+}
+
+/// Residency per CPU between two readings. The kernel only adds to `time`
+/// when a CPU wakes, so a CPU asleep across the whole window reads low.
+pub fn residency(before: &[StateCounters], after: &[StateCounters], elapsed: Duration) -> Vec<CoreIdle> {
+    let window_us = elapsed.as_micros() as f64;
+    let mut cores: Vec<CoreIdle> = Vec::new();
+    if window_us <= 0.0 {
+        return cores;
+    }
+
+    for counter in after {
+        let Some(previous) = before.iter().find(|previous| previous.cpu == counter.cpu && previous.state == counter.state) else {
+            continue;
+        };
+        let residency = IdleResidency {
+            name: counter.name.clone(),
+            latency_us: counter.latency_us,
+            residency: (counter.time_us.saturating_sub(previous.time_us) as f64 / window_us * 100.0).min(100.0) as f32,
+            entries: Some(counter.usage.saturating_sub(previous.usage)),
+        };
+        match cores.last_mut() {
+            Some(core) if core.cpu == counter.cpu => core.states.push(residency),
+            _ => cores.push(CoreIdle { cpu: counter.cpu, states: vec![residency] }),
+        }
+    }
+    cores
+}
+
+/// A residency measurement over the window the caller sleeps through.
+pub struct IdleWindow {
+    #[cfg(target_os = "linux")]
+    before: Result<Vec<StateCounters>>,
+    #[cfg(target_os = "linux")]
+    started: std::time::Instant,
+}
+
+#[cfg(target_os = "linux")]
+pub fn start() -> IdleWindow {
+    IdleWindow {
+        before: read_sysfs(Path::new("/sys/devices/system/cpu")),
+        started: std::time::Instant::now(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl IdleWindow {
+    pub fn finish(self) -> Result<(Vec<CoreIdle>, Source)> {
+        let before = self.before?;
+        let after = read_sysfs(Path::new("/sys/devices/system/cpu"))?;
+        Ok((residency(&before, &after, self.started.elapsed()), Source::Sysfs))
+    }
+}
+
+// the formatted performance counters are already averaged over their own interval
+#[cfg(not(target_os = "linux"))]
+pub fn start() -> IdleWindow {
+    IdleWindow {}
+}
+
+#[cfg(windows)]
+impl IdleWindow {
+    pub fn finish(self) -> Result<(Vec<CoreIdle>, Source)> {
+        let wmi_con = wmi_query::connect()?;
+        let rows = wmi_query::query(
+            &wmi_con,
+            "SELECT Name, PercentC1Time, PercentC2Time, PercentC3Time FROM Win32_PerfFormattedData_Counters_ProcessorInformation",
+        )?;
+        let mut cores: Vec<CoreIdle> = rows
+            .iter()
+            .filter_map(|row| {
+                let Some(Variant::String(name)) = row.get("Name") else {
+                    return None;
+                };
+                let cpu = wmi_query::processor_number(name)?;
+                let states = ["C1", "C2", "C3"]
+                    .iter()
+                    .filter_map(|state| {
+                        Some(IdleResidency {
+                            name: state.to_string(),
+                            latency_us: None,
+                            residency: wmi_query::number(row, &format!("Percent{}Time", state))? as f32,
+                            entries: None,
+                        })
+                    })
+                    .collect();
+                Some(CoreIdle { cpu, states })
+            })
+            .collect();
+        if cores.is_empty() {
+            return Err(OverwatchError::not_found("processor idle counters"));
+        }
+        cores.sort_by_key(|core| core.cpu);
+        Ok((cores, Source::Wmi))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+impl IdleWindow {
+    pub fn finish(self) -> Result<(Vec<CoreIdle>, Source)> {
+        Err(OverwatchError::unsupported("idle-state residency"))
+    }
+}
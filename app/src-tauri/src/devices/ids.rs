@@ -0,0 +1,140 @@
+//! Vendor, device and class names from `pci.ids` / `usb.ids`.
+//!
+//! The distro's hwdata copy is preferred since it's usually newer; the one
+//! compiled in covers systems (and Windows) without it. That one is stored
+//! gzipped in `data/` and refreshed with `npm run update-ids`.
+
+use flate2::read::GzDecoder;
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::OnceLock;
+
+const PCI_PATHS: [&str; 2] = ["/usr/share/hwdata/pci.ids", "/usr/share/misc/pci.ids"];
+const USB_PATHS: [&str; 2] = ["/usr/share/hwdata/usb.ids", "/usr/share/misc/usb.ids"];
+
+#[derive(Debug, Default)]
+pub struct IdDatabase {
+    vendors: HashMap<u16, String>,
+    devices: HashMap<(u16, u16), String>,
+    // vendor, device, subsystem vendor, subsystem device
+    subsystems: HashMap<(u16, u16, u16, u16), String>,
+    // class, subclass, programming interface (or protocol, for USB)
+    classes: HashMap<(u8, Option<u8>, Option<u8>), String>,
+}
+
+#[derive(Clone, Copy)]
+enum Section {
+    Vendors,
+    Classes,
+    // usb.ids has more lists after the classes (HID usages, languages, ...)
+    Other,
+}
+
+impl IdDatabase {
+    pub fn parse(text: &str) -> IdDatabase {
+        let mut db = IdDatabase::default();
+        let mut section = Section::Vendors;
+        let (mut vendor, mut device) = (None, None);
+        let (mut class, mut subclass) = (None, None);
+
+        for line in text.lines() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let depth = line.bytes().take_while(|&b| b == b'\t').count();
+            let Some((id, name)) = line[depth..].split_once("  ") else {
+                continue;
+            };
+            let name = name.trim().to_string();
+
+            match (depth, section) {
+                (0, _) => {
+                    if let Some(code) = id.strip_prefix("C ").and_then(|id| u8::from_str_radix(id.trim(), 16).ok()) {
+                        section = Section::Classes;
+                        class = Some(code);
+                        db.classes.insert((code, None, None), name);
+                    } else if let Ok(id) = u16::from_str_radix(id, 16) {
+                        section = Section::Vendors;
+                        vendor = Some(id);
+                        db.vendors.insert(id, name);
+                    } else {
+                        section = Section::Other;
+                    }
+                }
+                (1, Section::Vendors) => {
+                    device = u16::from_str_radix(id, 16).ok();
+                    if let (Some(vendor), Some(device)) = (vendor, device) {
+                        db.devices.insert((vendor, device), name);
+                    }
+                }
+                (2, Section::Vendors) => {
+                    let ids = id.split_once(' ').and_then(|(sv, sd)| {
+                        Some((u16::from_str_radix(sv, 16).ok()?, u16::from_str_radix(sd.trim(), 16).ok()?))
+                    });
+                    if let (Some(vendor), Some(device), Some((sv, sd))) = (vendor, device, ids) {
+                        db.subsystems.insert((vendor, device, sv, sd), name);
+                    }
+                }
+                (1, Section::Classes) => {
+                    subclass = u8::from_str_radix(id, 16).ok();
+                    if let (Some(class), Some(subclass)) = (class, subclass) {
+                        db.classes.insert((class, Some(subclass), None), name);
+                    }
+                }
+                (2, Section::Classes) => {
+                    if let (Some(class), Some(subclass), Ok(prog_if)) = (class, subclass, u8::from_str_radix(id, 16)) {
+                        db.classes.insert((class, Some(subclass), Some(prog_if)), name);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        db
+    }
+
+    pub fn vendor(&self, vendor: u16) -> Option<&str> {
+        self.vendors.get(&vendor).map(String::as_str)
+    }
+
+    pub fn device(&self, vendor: u16, device: u16) -> Option<&str> {
+        self.devices.get(&(vendor, device)).map(String::as_str)
+    }
+
+    pub fn subsystem(&self, vendor: u16, device: u16, subvendor: u16, subdevice: u16) -> Option<&str> {
+        self.subsystems.get(&(vendor, device, subvendor, subdevice)).map(String::as_str)
+    }
+
+    /// Subclass name, or the class name when the subclass isn't listed.
+    pub fn class(&self, class: u8, subclass: u8) -> Option<&str> {
+        self.classes
+            .get(&(class, Some(subclass), None))
+            .or_else(|| self.classes.get(&(class, None, None)))
+            .map(String::as_str)
+    }
+
+    /// Programming interface (PCI) or protocol (USB) name.
+    pub fn interface(&self, class: u8, subclass: u8, prog_if: u8) -> Option<&str> {
+        self.classes.get(&(class, Some(subclass), Some(prog_if))).map(String::as_str)
+    }
+}
+
+pub fn pci() -> &'static IdDatabase {
+    static PCI: OnceLock<IdDatabase> = OnceLock::new();
+    PCI.get_or_init(|| load(&PCI_PATHS, include_bytes!("../../data/pci.ids.gz")))
+}
+
+pub fn usb() -> &'static IdDatabase {
+    static USB: OnceLock<IdDatabase> = OnceLock::new();
+    USB.get_or_init(|| load(&USB_PATHS, include_bytes!("../../data/usb.ids.gz")))
+}
+
+fn load(paths: &[&str], embedded: &[u8]) -> IdDatabase {
+    let bytes = paths.iter().find_map(|path| std::fs::read(path).ok()).or_else(|| {
+        let mut bytes = Vec::new();
+        GzDecoder::new(embedded).read_to_end(&mut bytes).ok()?;
+        Some(bytes)
+    });
+    // the files aren't pure UTF-8, a few vendor names are Latin-1
+    bytes.map(|bytes| IdDatabase::parse(&String::from_utf8_lossy(&bytes))).unwrap_or_default()
+}
@@ -0,0 +1,166 @@
+//! PCI, USB and NVMe device inventory, with an alert when a PCIe link
+//! trains below what the device and its slot support.
+
+pub mod ids;
+pub mod nvme;
+pub mod pci;
+pub mod usb;
+
+use crate::error::{Provenance, Result, Source};
+use crate::session::Session;
+use crate::snapshot::now_millis;
+use nvme::NvmeDevice;
+use pci::PciDevice;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tauri_specta::Event;
+use usb::UsbDevice;
+
+/// Links only retrain on a reset, a hotplug or a failing slot, so the
+/// background watch looks this often.
+const LINK_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DeviceInventory {
+    /// Sorted by address.
+    pub pci: Vec<PciDevice>,
+    /// Hubs come right before the devices plugged into them.
+    pub usb: Vec<UsbDevice>,
+    /// NVMe controllers with their PCIe link, also listed under `pci`.
+    pub nvme: Vec<NvmeDevice>,
+    pub provenance: Provenance,
+}
+
+/// A PCIe link that trained below what the device and its slot support.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct LinkEvent {
+    /// Unix milliseconds when it was seen.
+    pub at: u64,
+    pub address: String,
+    pub vendor: Option<String>,
+    pub device: Option<String>,
+    /// The link's warning, e.g. "running x8 of x16 lanes".
+    pub warning: String,
+}
+
+/// Emitted when a device's link warning appears or changes, including links
+/// already degraded when the app starts.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct PcieLinkAlert(pub LinkEvent);
+
+/// Each device's last link warning, kept in Tauri state.
+#[derive(Default)]
+pub struct LinkMonitor(Mutex<HashMap<String, String>>);
+
+impl LinkMonitor {
+    /// Returns the devices whose warning is new or different from last time.
+    /// A warning that clears is forgotten, so it's reported again if it
+    /// comes back.
+    pub fn update(&self, devices: &[PciDevice], at: u64) -> Vec<LinkEvent> {
+        let mut last = self.0.lock().unwrap();
+        let mut events = Vec::new();
+        for device in devices {
+            let Some(warning) = device.link.as_ref().and_then(|link| link.warning.clone()) else {
+                last.remove(&device.address);
+                continue;
+            };
+            if last.get(&device.address) != Some(&warning) {
+                last.insert(device.address.clone(), warning.clone());
+                events.push(LinkEvent {
+                    at,
+                    address: device.address.clone(),
+                    vendor: device.vendor.clone(),
+                    device: device.device.clone(),
+                    warning,
+                });
+            }
+        }
+        events
+    }
+}
+
+/// Reads the inventory and raises an alert for each newly degraded link.
+pub fn check(app: &AppHandle) -> Result<DeviceInventory> {
+    let inventory = read_device_inventory()?;
+    let session = app.state::<Session>();
+    for event in app.state::<LinkMonitor>().update(&inventory.pci, now_millis()) {
+        session.record_event(PcieLinkAlert::NAME, &event);
+        PcieLinkAlert(event).emit(app).ok();
+    }
+    Ok(inventory)
+}
+
+/// Keeps checking in the background, so a link dropping lanes raises an
+/// alert whether or not anything is looking at the card.
+pub fn watch(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        if !app.state::<Session>().is_replaying() {
+            if let Err(err) = check(&app) {
+                log::warn!("couldn't check PCIe links: {}", err);
+            }
+        }
+        std::thread::sleep(LINK_CHECK_INTERVAL);
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_device_inventory(app: AppHandle, session: State<'_, Session>) -> Result<DeviceInventory> {
+    session.sample(|| check(&app))
+}
+
+pub fn read_device_inventory() -> Result<DeviceInventory> {
+    let mut provenance = Provenance::new();
+    let (pci, usb, nvme, source) = read_platform();
+    provenance.record("pci", source, &pci);
+    provenance.record("usb", source, &usb);
+    provenance.record("nvme", source, &nvme);
+
+    // one bus unreadable is worth showing, both means there's nothing to show
+    if let (Err(err), Err(_)) = (&pci, &usb) {
+        return Err(err.clone());
+    }
+
+    Ok(DeviceInventory {
+        pci: pci.unwrap_or_default(),
+        usb: usb.unwrap_or_default(),
+        nvme: nvme.unwrap_or_default(),
+        provenance,
+    })
+}
+
+// each bus read on its own, so one failing doesn't hide the others
+type Listings = (Result<Vec<PciDevice>>, Result<Vec<UsbDevice>>, Result<Vec<NvmeDevice>>, Source);
+
+#[cfg(target_os = "linux")]
+fn read_platform() -> Listings {
+    (
+        pci::read_sysfs(std::path::Path::new("/sys/bus/pci/devices")),
+        usb::read_sysfs(std::path::Path::new("/sys/bus/usb/devices")),
+        nvme::read_sysfs(std::path::Path::new("/sys/class/nvme")),
+        Source::Sysfs,
+    )
+}
+
+#[cfg(windows)]
+fn read_platform() -> Listings {
+    let pci = pci::read_wmi();
+    let nvme = pci.as_deref().map(nvme::from_pci).map_err(Clone::clone);
+    (pci, usb::read_wmi(), nvme, Source::Wmi)
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn read_platform() -> Listings {
+    use crate::error::OverwatchError;
+
+    (
+        Err(OverwatchError::unsupported("PCI device listing")),
+        Err(OverwatchError::unsupported("USB device listing")),
+        Err(OverwatchError::unsupported("NVMe device listing")),
+        Source::Sysfs,
+    )
+}
@@ -0,0 +1,59 @@
+use super::pci::{self, PciDevice, PcieLink};
+use crate::error::{OverwatchError, Result};
+use crate::sysfs::read_attr;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+
+/// PCI class of an NVM Express controller.
+pub const NVME_CLASS: u32 = 0x010802;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NvmeDevice {
+    /// Controller name, e.g. "nvme0"; the PCI address on Windows.
+    pub name: String,
+    pub model: Option<String>,
+    pub firmware: Option<String>,
+    /// PCI address of the controller.
+    pub address: Option<String>,
+    pub link: Option<PcieLink>,
+}
+
+/// Every controller under a `/sys/class/nvme`-style directory.
+pub fn read_sysfs(root: &Path) -> Result<Vec<NvmeDevice>> {
+    let entries = std::fs::read_dir(root).map_err(|err| OverwatchError::from_io("NVMe controllers", err))?;
+
+    let mut devices: Vec<NvmeDevice> = entries
+        .flatten()
+        .map(|entry| {
+            let dir = entry.path();
+            NvmeDevice {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                model: read_attr(&dir.join("model")),
+                firmware: read_attr(&dir.join("firmware_rev")),
+                address: read_attr(&dir.join("address")),
+                // the controller's PCI function, which has the link attributes
+                link: pci::read_link(&dir.join("device")),
+            }
+        })
+        .collect();
+
+    // by number, a plain sort puts nvme10 before nvme2
+    devices.sort_by_key(|device| device.name.trim_start_matches("nvme").parse::<u32>().unwrap_or(u32::MAX));
+    Ok(devices)
+}
+
+/// The NVMe controllers in a PCI listing, for platforms without an NVMe class.
+pub fn from_pci(devices: &[PciDevice]) -> Vec<NvmeDevice> {
+    devices
+        .iter()
+        .filter(|device| device.class_code == NVME_CLASS)
+        .map(|device| NvmeDevice {
+            name: device.address.clone(),
+            model: device.subsystem.clone().or_else(|| device.device.clone()),
+            firmware: None,
+            address: Some(device.address.clone()),
+            link: device.link.clone(),
+        })
+        .collect()
+}
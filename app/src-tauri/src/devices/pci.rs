@@ -0,0 +1,324 @@
+use super::ids;
+use crate::error::{OverwatchError, Result};
+use crate::sysfs::{read_attr, read_number};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+
+#[cfg(windows)]
+use crate::wmi_query;
+#[cfg(windows)]
+use std::collections::HashMap;
+#[cfg(windows)]
+use wmi::Variant;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PciDevice {
+    /// `domain:bus:device.function`, e.g. "0000:01:00.0".
+    pub address: String,
+    /// Class, subclass and programming interface, e.g. 0x030000.
+    pub class_code: u32,
+    pub class: String,
+    /// Programming interface, e.g. "NVM Express" or "XHCI".
+    pub interface: Option<String>,
+    pub vendor_id: u16,
+    pub device_id: u16,
+    pub vendor: Option<String>,
+    pub device: Option<String>,
+    pub subsystem_vendor_id: Option<u16>,
+    pub subsystem_device_id: Option<u16>,
+    /// The card or board maker's name for it, falling back to their vendor name.
+    pub subsystem: Option<String>,
+    pub driver: Option<String>,
+    pub iommu_group: Option<u32>,
+    pub link: Option<PcieLink>,
+}
+
+/// Negotiated and maximum PCIe link. Speeds are per lane, in GT/s.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct PcieLink {
+    pub speed_gts: Option<f32>,
+    pub width: Option<u8>,
+    pub max_speed_gts: Option<f32>,
+    pub max_width: Option<u8>,
+    /// Fastest the port it's plugged into can go, `None` for devices on the
+    /// root complex or when the port doesn't say.
+    #[serde(default)]
+    pub upstream_max_speed_gts: Option<f32>,
+    /// Widest link that port takes, `None` like the speed.
+    #[serde(default)]
+    pub upstream_max_width: Option<u8>,
+    /// Set when the link has fewer lanes than the device and its slot support.
+    pub warning: Option<String>,
+    /// Set when the link runs below its top speed. Devices drop the speed to
+    /// save power when idle, so this is no reason to alert.
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl PcieLink {
+    pub fn new(
+        speed_gts: Option<f32>,
+        width: Option<u8>,
+        max_speed_gts: Option<f32>,
+        max_width: Option<u8>,
+        upstream_max_speed_gts: Option<f32>,
+        upstream_max_width: Option<u8>,
+    ) -> Self {
+        // an x16 card in an x4 slot, or a Gen4 card in a Gen3 one, is as good as it gets
+        let best_width = max_width.map(|device| upstream_max_width.map_or(device, |port| device.min(port)));
+        let best_speed = max_speed_gts.map(|device| upstream_max_speed_gts.map_or(device, |port| device.min(port)));
+
+        // lanes never drop to save power, so fewer than both ends have is always worth saying
+        let warning = match (width, best_width) {
+            (Some(width), Some(best)) if width < best => Some(format!("running x{} of x{} lanes", width, best)),
+            _ => None,
+        };
+        let note = match (speed_gts, best_speed) {
+            (Some(speed), Some(best)) if speed < best => Some(format!("linked at {} of {} GT/s, normal while idle", speed, best)),
+            _ => None,
+        };
+
+        PcieLink {
+            speed_gts,
+            width,
+            max_speed_gts,
+            max_width,
+            upstream_max_speed_gts,
+            upstream_max_width,
+            warning,
+            note,
+        }
+    }
+}
+
+impl PciDevice {
+    // fills in the names; everything else comes from the platform reader
+    fn named(mut self) -> Self {
+        let db = ids::pci();
+        let [_, class, subclass, prog_if] = self.class_code.to_be_bytes();
+        self.class = db
+            .class(class, subclass)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Class {:06x}", self.class_code));
+        self.interface = db.interface(class, subclass, prog_if).map(str::to_string);
+        self.vendor = db.vendor(self.vendor_id).map(str::to_string);
+        self.device = db.device(self.vendor_id, self.device_id).map(str::to_string);
+        if let (Some(sv), Some(sd)) = (self.subsystem_vendor_id, self.subsystem_device_id) {
+            self.subsystem = db
+                .subsystem(self.vendor_id, self.device_id, sv, sd)
+                .or_else(|| db.vendor(sv))
+                .map(str::to_string);
+        }
+        self
+    }
+}
+
+/// Every device under a `/sys/bus/pci/devices`-style directory.
+pub fn read_sysfs(root: &Path) -> Result<Vec<PciDevice>> {
+    let entries = std::fs::read_dir(root).map_err(|err| OverwatchError::from_io("PCI devices", err))?;
+
+    let mut devices: Vec<PciDevice> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let hex = |name: &str| {
+                let text = std::fs::read_to_string(dir.join(name)).ok()?;
+                u32::from_str_radix(text.trim().trim_start_matches("0x"), 16).ok()
+            };
+            let link_name = |name: &str| {
+                let target = std::fs::read_link(dir.join(name)).ok()?;
+                Some(target.file_name()?.to_string_lossy().into_owned())
+            };
+
+            Some(
+                PciDevice {
+                    address: entry.file_name().to_string_lossy().into_owned(),
+                    class_code: hex("class")?,
+                    class: String::new(),
+                    interface: None,
+                    vendor_id: hex("vendor")? as u16,
+                    device_id: hex("device")? as u16,
+                    vendor: None,
+                    device: None,
+                    subsystem_vendor_id: hex("subsystem_vendor").map(|id| id as u16),
+                    subsystem_device_id: hex("subsystem_device").map(|id| id as u16),
+                    subsystem: None,
+                    driver: link_name("driver"),
+                    iommu_group: link_name("iommu_group").and_then(|group| group.parse().ok()),
+                    link: read_link(&dir),
+                }
+                .named(),
+            )
+        })
+        .collect();
+
+    devices.sort_by(|a, b| a.address.cmp(&b.address));
+    Ok(devices)
+}
+
+/// Link attributes of a PCI device directory, `None` for devices without one.
+pub fn read_link(dir: &Path) -> Option<PcieLink> {
+    let speed = |dir: &Path, name| read_attr(&dir.join(name)).and_then(|speed| parse_link_speed(&speed));
+    let width = |dir: &Path, name| read_number(&dir.join(name)).filter(|&width: &u8| width != 0);
+
+    let (speed_gts, max_speed_gts) = (speed(dir, "current_link_speed"), speed(dir, "max_link_speed"));
+    if speed_gts.is_none() && max_speed_gts.is_none() {
+        return None;
+    }
+    // the real path nests devices under the bridge they hang off
+    let port = dir.canonicalize().ok().and_then(|real| real.parent().map(Path::to_path_buf));
+    let (port_speed, port_width) = match &port {
+        Some(port) => (speed(port, "max_link_speed"), width(port, "max_link_width")),
+        None => (None, None),
+    };
+    Some(PcieLink::new(
+        speed_gts,
+        width(dir, "current_link_width"),
+        max_speed_gts,
+        width(dir, "max_link_width"),
+        port_speed,
+        port_width,
+    ))
+}
+
+/// "16.0 GT/s PCIe" or "8 GT/s"; "Unknown" when the link is down.
+pub fn parse_link_speed(text: &str) -> Option<f32> {
+    text.split_whitespace().next()?.parse().ok().filter(|&speed: &f32| speed > 0.0)
+}
+
+#[cfg(windows)]
+pub fn read_wmi() -> Result<Vec<PciDevice>> {
+    let wmi_con = wmi_query::connect()?;
+
+    // "PCI bus 1, device 0, function 0" lives on the driver record, not the device
+    let mut locations = HashMap::new();
+    for row in wmi_query::query(&wmi_con, r"SELECT DeviceID, Location FROM Win32_PnPSignedDriver WHERE DeviceID LIKE 'PCI\\%'")? {
+        if let (Some(Variant::String(id)), Some(Variant::String(location))) = (row.get("DeviceID"), row.get("Location")) {
+            if let Some(address) = parse_location(location) {
+                locations.insert(id.to_uppercase(), address);
+            }
+        }
+    }
+
+    let rows = wmi_query::query(
+        &wmi_con,
+        r"SELECT DeviceID, Service, CompatibleID FROM Win32_PnPEntity WHERE DeviceID LIKE 'PCI\\%'",
+    )?;
+    let mut devices: Vec<PciDevice> = rows
+        .iter()
+        .filter_map(|row| {
+            let Some(Variant::String(instance)) = row.get("DeviceID") else {
+                return None;
+            };
+            // PCI\VEN_10DE&DEV_2684&SUBSYS_16F110DE&REV_A1\4&2B8E4ED1&0&0019
+            let hardware = instance.split('\\').nth(1)?;
+            let field = |key: &str| hardware.split('&').find_map(|part| part.strip_prefix(key));
+            let subsys = field("SUBSYS_").and_then(|s| u32::from_str_radix(s, 16).ok());
+
+            // the most specific "PCI\CC_xxxxxx" compatible ID carries the class code
+            let class_code = match row.get("CompatibleID") {
+                Some(Variant::Array(ids)) => ids
+                    .iter()
+                    .filter_map(|id| match id {
+                        Variant::String(id) => id.strip_prefix(r"PCI\CC_"),
+                        _ => None,
+                    })
+                    .max_by_key(|code| code.len())
+                    .and_then(|code| u32::from_str_radix(code, 16).ok().map(|value| value << (4 * (6 - code.len().min(6))))),
+                _ => None,
+            };
+
+            Some(
+                PciDevice {
+                    address: locations.get(&instance.to_uppercase()).cloned().unwrap_or_else(|| instance.clone()),
+                    class_code: class_code.unwrap_or(0xff0000),
+                    class: String::new(),
+                    interface: None,
+                    vendor_id: u16::from_str_radix(field("VEN_")?, 16).ok()?,
+                    device_id: u16::from_str_radix(field("DEV_")?, 16).ok()?,
+                    vendor: None,
+                    device: None,
+                    // SUBSYS_ssssvvvv, device then vendor
+                    subsystem_vendor_id: subsys.map(|s| s as u16),
+                    subsystem_device_id: subsys.map(|s| (s >> 16) as u16),
+                    subsystem: None,
+                    driver: match row.get("Service") {
+                        Some(Variant::String(service)) => Some(service.clone()),
+                        _ => None,
+                    },
+                    iommu_group: None,
+                    link: link_for_instance(instance).ok(),
+                }
+                .named(),
+            )
+        })
+        .collect();
+
+    devices.sort_by(|a, b| a.address.cmp(&b.address));
+    Ok(devices)
+}
+
+#[cfg(windows)]
+fn parse_location(location: &str) -> Option<String> {
+    let mut numbers = location
+        .split(',')
+        .filter_map(|part| part.split_whitespace().last()?.parse::<u32>().ok());
+    let (bus, device, function) = (numbers.next()?, numbers.next()?, numbers.next()?);
+    Some(format!("0000:{:02x}:{:02x}.{}", bus, device, function))
+}
+
+/// Link properties Device Manager shows, by device instance ID.
+#[cfg(windows)]
+pub fn link_for_instance(instance_id: &str) -> Result<PcieLink> {
+    use windows_sys::core::GUID;
+    use windows_sys::Win32::Devices::DeviceAndDriverInstallation::{
+        CM_Get_DevNode_PropertyW, CM_Get_Parent, CM_Locate_DevNodeW, CM_LOCATE_DEVNODE_NORMAL, CR_SUCCESS,
+    };
+    use windows_sys::Win32::Devices::Properties::{DEVPROPKEY, DEVPROPTYPE, DEVPROP_TYPE_UINT32};
+
+    // DEVPKEY_PciDevice_{Current,Max}Link{Speed,Width} share this format ID
+    const PCI_DEVICE: GUID = GUID::from_u128(0x3ab22e31_8264_4b4e_9af5_a8d2d8e33e62);
+
+    let wide: Vec<u16> = instance_id.encode_utf16().chain(Some(0)).collect();
+    let mut node = 0u32;
+    // SAFETY: `wide` is NUL-terminated and outlives the call
+    let status = unsafe { CM_Locate_DevNodeW(&mut node, wide.as_ptr(), CM_LOCATE_DEVNODE_NORMAL) };
+    if status != CR_SUCCESS {
+        return Err(OverwatchError::not_found(format!("device node {}", instance_id)));
+    }
+
+    let read = |node: u32, pid: u32| {
+        let key = DEVPROPKEY { fmtid: PCI_DEVICE, pid };
+        let mut kind: DEVPROPTYPE = 0;
+        let mut value = 0u32;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        // SAFETY: the buffer is a u32 and `size` says so
+        let status = unsafe { CM_Get_DevNode_PropertyW(node, &key, &mut kind, &mut value as *mut u32 as *mut u8, &mut size, 0) };
+        (status == CR_SUCCESS && kind == DEVPROP_TYPE_UINT32).then_some(value)
+    };
+    // speeds come as the PCIe generation
+    let speed = |node, pid| match read(node, pid)? {
+        1 => Some(2.5),
+        2 => Some(5.0),
+        3 => Some(8.0),
+        4 => Some(16.0),
+        5 => Some(32.0),
+        6 => Some(64.0),
+        _ => None,
+    };
+    let width = |node, pid| read(node, pid).and_then(|width| u8::try_from(width).ok()).filter(|&width| width != 0);
+
+    let (speed_gts, max_speed_gts) = (speed(node, 9), speed(node, 11));
+    if speed_gts.is_none() && max_speed_gts.is_none() {
+        return Err(OverwatchError::not_found(format!("PCIe link properties of {}", instance_id)));
+    }
+    // the bridge or root port it's plugged into
+    let mut parent = 0u32;
+    // SAFETY: both are plain out/in device node handles
+    let (port_speed, port_width) = match unsafe { CM_Get_Parent(&mut parent, node, 0) } {
+        CR_SUCCESS => (speed(parent, 11), width(parent, 12)),
+        _ => (None, None),
+    };
+    Ok(PcieLink::new(speed_gts, width(node, 10), max_speed_gts, width(node, 12), port_speed, port_width))
+}
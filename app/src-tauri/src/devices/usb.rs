@@ -0,0 +1,182 @@
+use super::ids;
+use crate::error::{OverwatchError, Result};
+use crate::sysfs::read_attr;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+
+#[cfg(windows)]
+use crate::wmi_query;
+#[cfg(windows)]
+use wmi::Variant;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UsbDevice {
+    /// Port path as the kernel names it, e.g. "1-2.3"; root hubs are "usb1".
+    /// On Windows, the hub and port from the driver record.
+    pub path: String,
+    /// Path of the hub this hangs off, `None` for root hubs.
+    pub parent: Option<String>,
+    pub bus: Option<u8>,
+    /// Port on the parent hub.
+    pub port: Option<u8>,
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub vendor: Option<String>,
+    pub product: Option<String>,
+    /// Device class, or the first interface's when the device defers to them.
+    pub class: Option<String>,
+    /// Negotiated speed in Mbit/s: 1.5, 12, 480, 5000, ...
+    pub speed_mbps: Option<f32>,
+    /// What the device asks the port for, in mA.
+    pub max_power_ma: Option<u32>,
+    /// Drivers bound to its interfaces.
+    pub drivers: Vec<String>,
+}
+
+impl UsbDevice {
+    // the database names first, the device's own strings otherwise
+    fn named(mut self, manufacturer: Option<String>, product: Option<String>, class: Option<u8>) -> Self {
+        let db = ids::usb();
+        self.vendor = db.vendor(self.vendor_id).map(str::to_string).or(manufacturer);
+        self.product = db.device(self.vendor_id, self.product_id).map(str::to_string).or(product);
+        self.class = class.and_then(|class| db.class(class, 0)).map(str::to_string);
+        self
+    }
+}
+
+/// Every device under a `/sys/bus/usb/devices`-style directory.
+pub fn read_sysfs(root: &Path) -> Result<Vec<UsbDevice>> {
+    let entries = std::fs::read_dir(root).map_err(|err| OverwatchError::from_io("USB devices", err))?;
+    let names: Vec<String> = entries.flatten().map(|entry| entry.file_name().to_string_lossy().into_owned()).collect();
+
+    let mut devices: Vec<UsbDevice> = names
+        .iter()
+        // "1-2:1.0" is an interface of 1-2, not a device
+        .filter(|name| !name.contains(':'))
+        .filter_map(|name| {
+            let dir = root.join(name);
+            let attr = |attr: &str| read_attr(&dir.join(attr));
+            let hex = |attr: &str| u16::from_str_radix(&read_attr(&dir.join(attr))?, 16).ok();
+
+            let interfaces: Vec<&String> = names.iter().filter(|other| other.starts_with(&format!("{}:", name))).collect();
+            let mut drivers: Vec<String> = interfaces
+                .iter()
+                .filter_map(|interface| {
+                    let target = std::fs::read_link(root.join(interface).join("driver")).ok()?;
+                    Some(target.file_name()?.to_string_lossy().into_owned())
+                })
+                .collect();
+            drivers.sort();
+            drivers.dedup();
+
+            let class = match hex("bDeviceClass") {
+                Some(0) | None => interfaces
+                    .iter()
+                    .filter_map(|interface| u8::from_str_radix(&read_attr(&root.join(interface).join("bInterfaceClass"))?, 16).ok())
+                    .next(),
+                Some(class) => Some(class as u8),
+            };
+
+            let (parent, port) = match name.strip_prefix("usb") {
+                Some(_) => (None, None),
+                None => {
+                    let (hub, port) = name.rsplit_once(['.', '-'])?;
+                    let parent = if name.contains('.') { hub.to_string() } else { format!("usb{}", hub) };
+                    (Some(parent), port.parse().ok())
+                }
+            };
+
+            Some(
+                UsbDevice {
+                    path: name.clone(),
+                    parent,
+                    bus: attr("busnum").and_then(|bus| bus.parse().ok()),
+                    port,
+                    vendor_id: hex("idVendor")?,
+                    product_id: hex("idProduct")?,
+                    vendor: None,
+                    product: None,
+                    class: None,
+                    speed_mbps: attr("speed").and_then(|speed| speed.parse().ok()),
+                    max_power_ma: attr("bMaxPower").and_then(|power| power.trim_end_matches("mA").parse().ok()),
+                    drivers,
+                }
+                .named(attr("manufacturer"), attr("product"), class),
+            )
+        })
+        .collect();
+
+    devices.sort_by_key(|device| tree_key(&device.path));
+    Ok(devices)
+}
+
+// sorts each hub right before what's plugged into it, with ports in numeric order
+fn tree_key(path: &str) -> Vec<u32> {
+    let path = path.strip_prefix("usb").unwrap_or(path);
+    path.split(['-', '.']).map(|part| part.parse().unwrap_or(0)).collect()
+}
+
+#[cfg(windows)]
+pub fn read_wmi() -> Result<Vec<UsbDevice>> {
+    use std::collections::HashMap;
+
+    let wmi_con = wmi_query::connect()?;
+
+    // "Port_#0003.Hub_#0001"
+    let mut locations = HashMap::new();
+    for row in wmi_query::query(&wmi_con, r"SELECT DeviceID, Location FROM Win32_PnPSignedDriver WHERE DeviceID LIKE 'USB\\VID_%'")? {
+        if let (Some(Variant::String(id)), Some(Variant::String(location))) = (row.get("DeviceID"), row.get("Location")) {
+            locations.insert(id.to_uppercase(), location.clone());
+        }
+    }
+
+    let rows = wmi_query::query(
+        &wmi_con,
+        r"SELECT DeviceID, Service, Manufacturer, Name FROM Win32_PnPEntity WHERE DeviceID LIKE 'USB\\VID_%'",
+    )?;
+    let mut devices: Vec<UsbDevice> = rows
+        .iter()
+        .filter_map(|row| {
+            let Some(Variant::String(instance)) = row.get("DeviceID") else {
+                return None;
+            };
+            // USB\VID_046D&PID_C52B\5&2A4F1B7&0&3; "&MI_xx" entries are interfaces of a composite device
+            let hardware = instance.split('\\').nth(1)?;
+            if hardware.contains("&MI_") {
+                return None;
+            }
+            let field = |key: &str| hardware.split('&').find_map(|part| part.strip_prefix(key));
+            let text = |key: &str| match row.get(key) {
+                Some(Variant::String(value)) => crate::smbios::meaningful(value).map(str::to_string),
+                _ => None,
+            };
+            let location = locations.get(&instance.to_uppercase());
+            let port = location.and_then(|location| {
+                let port = location.strip_prefix("Port_#")?.split('.').next()?;
+                port.parse().ok()
+            });
+
+            Some(
+                UsbDevice {
+                    path: location.cloned().unwrap_or_else(|| instance.clone()),
+                    parent: None,
+                    bus: None,
+                    port,
+                    vendor_id: u16::from_str_radix(field("VID_")?, 16).ok()?,
+                    product_id: u16::from_str_radix(field("PID_")?, 16).ok()?,
+                    vendor: None,
+                    product: None,
+                    class: None,
+                    speed_mbps: None,
+                    max_power_ma: None,
+                    drivers: text("Service").into_iter().collect(),
+                }
+                .named(text("Manufacturer"), text("Name"), None),
+            )
+        })
+        .collect();
+
+    devices.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(devices)
+}
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Every command returns this so the UI can tell "not on this OS" apart from
+/// "access denied" apart from "the sensor just isn't there".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OverwatchError {
+    Unsupported { what: String },
+    PermissionDenied { what: String },
+    BackendFailure { backend: String, source: String },
+    Timeout { what: String, after_ms: u64 },
+    NotFound { what: String },
+}
+
+pub type Result<T> = std::result::Result<T, OverwatchError>;
+
+impl OverwatchError {
+    pub fn unsupported(what: impl Into<String>) -> Self {
+        OverwatchError::Unsupported { what: what.into() }
+    }
+
+    pub fn permission_denied(what: impl Into<String>) -> Self {
+        OverwatchError::PermissionDenied { what: what.into() }
+    }
+
+    pub fn backend(backend: impl Into<String>, source: impl ToString) -> Self {
+        OverwatchError::BackendFailure {
+            backend: backend.into(),
+            source: source.to_string(),
+        }
+    }
+
+    pub fn timeout(what: impl Into<String>, after: std::time::Duration) -> Self {
+        OverwatchError::Timeout {
+            what: what.into(),
+            after_ms: after.as_millis() as u64,
+        }
+    }
+
+    pub fn not_found(what: impl Into<String>) -> Self {
+        OverwatchError::NotFound { what: what.into() }
+    }
+
+    /// Maps an io error onto the closest variant, keeping `what` as context.
+    pub fn from_io(what: impl Into<String>, err: std::io::Error) -> Self {
+        use std::io::ErrorKind;
+
+        let what = what.into();
+        match err.kind() {
+            ErrorKind::NotFound => OverwatchError::NotFound { what },
+            ErrorKind::PermissionDenied => OverwatchError::PermissionDenied { what },
+            ErrorKind::TimedOut => OverwatchError::Timeout { what, after_ms: 0 },
+            ErrorKind::Unsupported => OverwatchError::Unsupported { what },
+            _ => OverwatchError::BackendFailure {
+                backend: what,
+                source: err.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for OverwatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverwatchError::Unsupported { what } => write!(f, "{} is not supported on this system", what),
+            OverwatchError::PermissionDenied { what } => write!(f, "permission denied: {}", what),
+            OverwatchError::BackendFailure { backend, source } => write!(f, "{} failed: {}", backend, source),
+            OverwatchError::Timeout { what, after_ms } => write!(f, "{} timed out after {}ms", what, after_ms),
+            OverwatchError::NotFound { what } => write!(f, "{} not found", what),
+        }
+    }
+}
+
+impl std::error::Error for OverwatchError {}
+
+#[cfg(windows)]
+impl From<wmi::WMIError> for OverwatchError {
+    fn from(err: wmi::WMIError) -> Self {
+        // WBEM_E_ACCESS_DENIED and E_ACCESSDENIED
+        const ACCESS_DENIED: [u32; 2] = [0x8004_1003, 0x8007_0005];
+
+        match err {
+            wmi::WMIError::HResultError { hres } if ACCESS_DENIED.contains(&(hres as u32)) => {
+                OverwatchError::permission_denied("WMI")
+            }
+            other => OverwatchError::backend("WMI", other),
+        }
+    }
+}
+
+/// Where a value in a snapshot was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    Sysinfo,
+    Wmi,
+    Registry,
+    /// Device properties from the Windows configuration manager.
+    SetupApi,
+    Journal,
+    Wtmp,
+    /// Kernel attributes under /sys.
+    Sysfs,
+    /// Kernel statistics under /proc.
+    Procfs,
+    /// Firmware tables as the kernel exposes them in /sys/class/dmi/id.
+    Dmi,
+    /// The raw SMBIOS table.
+    Smbios,
+    /// Device ioctls on the /dev node.
+    Ioctl,
+    /// smartmontools' `smartctl --json`.
+    Smartctl,
+    /// The CPUID instruction.
+    Cpuid,
+    /// Model-specific registers through /dev/cpu/*/msr.
+    Msr,
+    /// NVIDIA's `nvidia-smi` tool.
+    NvidiaSmi,
+    /// iproute2's `ss`, for TCP byte counters per connection.
+    Ss,
+    /// The Docker or Podman API socket.
+    ContainerRuntime,
+    /// Guessed from the model name, not measured.
+    NameHeuristic,
+}
+
+/// Availability of a single field, so the UI can explain an "N/A".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum FieldStatus {
+    Available { source: Source },
+    Estimated { source: Source },
+    Missing { reason: OverwatchError },
+}
+
+/// Per-field availability keyed by the field's serialized name. Fields that
+/// are always read directly (name, usage, core counts) aren't listed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(transparent)]
+pub struct Provenance(BTreeMap<String, FieldStatus>);
+
+impl Provenance {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn available(&mut self, field: &str, source: Source) {
+        self.0.insert(field.to_string(), FieldStatus::Available { source });
+    }
+
+    pub fn estimated(&mut self, field: &str, source: Source) {
+        self.0.insert(field.to_string(), FieldStatus::Estimated { source });
+    }
+
+    pub fn missing(&mut self, field: &str, reason: OverwatchError) {
+        self.0.insert(field.to_string(), FieldStatus::Missing { reason });
+    }
+
+    /// False for fields that are missing or only estimated; unlisted fields
+    /// are always read directly, so they count as measured.
+    pub fn is_measured(&self, field: &str) -> bool {
+        matches!(self.0.get(field), None | Some(FieldStatus::Available { .. }))
+    }
+
+    /// Records `Available` on `Ok`, `Missing` with the error otherwise.
+    pub fn record<T>(&mut self, field: &str, source: Source, result: &Result<T>) {
+        match result {
+            Ok(_) => self.available(field, source),
+            Err(err) => self.missing(field, err.clone()),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! InfluxDB line protocol over HTTP (v1 `/write` or v2 `/api/v2/write`) or UDP.
+//!
+//! One line per group and instance, e.g.
+//! `overwatch_cpu,host=desk,core=3 usage=12.5 1700000000000000000`.
+
+use super::push::{http_agent, http_status_error, udp_sender, PushConfig, Pusher, SendError};
+use super::{metrics, Exporter, Metric};
+use crate::error::{OverwatchError, Result};
+use crate::snapshot::HostSnapshot;
+use std::collections::BTreeMap;
+
+// keeps request bodies well under the server's default limits
+const LINES_PER_REQUEST: usize = 5000;
+
+#[derive(Debug, Clone)]
+pub enum InfluxTarget {
+    /// Full write URL including org/bucket or db, e.g.
+    /// `http://influx:8086/api/v2/write?org=home&bucket=pcs`.
+    Http { url: String, token: Option<String> },
+    Udp { addr: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct InfluxConfig {
+    pub target: InfluxTarget,
+    pub push: PushConfig,
+}
+
+impl InfluxConfig {
+    /// `http(s)://...` writes over HTTP, `udp://host:port` over UDP.
+    pub fn from_url(url: &str, token: Option<String>, push: PushConfig) -> Result<Self> {
+        let target = if let Some(addr) = url.strip_prefix("udp://") {
+            InfluxTarget::Udp { addr: addr.trim_end_matches('/').to_string() }
+        } else if url.starts_with("http://") || url.starts_with("https://") {
+            InfluxTarget::Http { url: url.to_string(), token }
+        } else {
+            return Err(OverwatchError::backend("InfluxDB URL", format!("scheme must be http, https or udp: {}", url)));
+        };
+        Ok(InfluxConfig { target, push })
+    }
+}
+
+pub struct InfluxExporter {
+    pusher: Pusher,
+    prefix: String,
+    tags: Vec<(String, String)>,
+}
+
+impl InfluxExporter {
+    pub fn connect(config: InfluxConfig) -> Result<Self> {
+        let pusher = match config.target {
+            InfluxTarget::Http { url, token } => {
+                let agent = http_agent()?;
+                Pusher::spawn(&config.push, Some("influx"), move |lines| {
+                    lines
+                        .chunks(LINES_PER_REQUEST)
+                        .try_for_each(|chunk| post(&agent, &url, token.as_deref(), chunk))
+                })
+            }
+            // points carry their timestamp, so a spooled backlog still lands in the right place
+            InfluxTarget::Udp { addr } => Pusher::spawn(&config.push, Some("influx"), udp_sender(&addr)?),
+        };
+
+        Ok(InfluxExporter {
+            pusher,
+            prefix: config.push.prefix,
+            tags: config.push.tags,
+        })
+    }
+}
+
+impl Exporter for InfluxExporter {
+    fn name(&self) -> &'static str {
+        "influx"
+    }
+
+    fn export(&mut self, snapshot: &HostSnapshot) -> Result<()> {
+        let lines = lines(snapshot, &self.prefix, &self.tags);
+        self.pusher.push(lines)
+    }
+}
+
+fn post(agent: &ureq::Agent, url: &str, token: Option<&str>, lines: &[String]) -> std::result::Result<(), SendError> {
+    let mut request = agent.post(url).set("Content-Type", "text/plain; charset=utf-8");
+    if let Some(token) = token {
+        request = request.set("Authorization", &format!("Token {}", token));
+    }
+
+    match request.send_string(&lines.join("\n")) {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, response)) => Err(http_status_error("InfluxDB", code, &response.into_string().unwrap_or_default())),
+        Err(err) => Err(SendError::Retry(OverwatchError::backend("InfluxDB", err))),
+    }
+}
+
+// group and instance, everything sharing one becomes fields of one point
+type PointKey<'a> = (&'static str, Option<&'a (&'static str, String)>);
+
+/// Line protocol for one snapshot, timestamped in nanoseconds (the default
+/// precision for both HTTP and UDP).
+pub fn lines(snapshot: &HostSnapshot, prefix: &str, tags: &[(String, String)]) -> Vec<String> {
+    let mut points: BTreeMap<PointKey, Vec<&Metric>> = BTreeMap::new();
+    let metrics = metrics(snapshot);
+    for metric in metrics.iter().filter(|m| m.value.is_finite()) {
+        points.entry((metric.group, metric.instance.as_ref())).or_default().push(metric);
+    }
+
+    let mut common = format!(",host={}", escape_tag(&snapshot.host));
+    for (key, value) in tags {
+        common.push_str(&format!(",{}={}", escape_tag(key), escape_tag(value)));
+    }
+    let timestamp = snapshot.timestamp * 1_000_000;
+
+    points
+        .into_iter()
+        .map(|((group, instance), fields)| {
+            let measurement = if prefix.is_empty() {
+                group.to_string()
+            } else {
+                format!("{}_{}", prefix, group)
+            };
+            let mut line = escape_measurement(&measurement);
+            line.push_str(&common);
+            if let Some((key, value)) = instance {
+                line.push_str(&format!(",{}={}", escape_tag(key), escape_tag(value)));
+            }
+            let fields: Vec<String> = fields.iter().map(|m| format!("{}={}", escape_tag(m.name), m.value)).collect();
+            format!("{} {} {}", line, fields.join(","), timestamp)
+        })
+        .collect()
+}
+
+fn escape_measurement(text: &str) -> String {
+    text.replace(',', "\\,").replace(' ', "\\ ")
+}
+
+fn escape_tag(text: &str) -> String {
+    text.replace(',', "\\,").replace('=', "\\=").replace(' ', "\\ ")
+}
@@ -0,0 +1,339 @@
+//! Push exporters that publish agent samples to other monitoring systems.
+//!
+//! Every exporter works off the same flat list of `Metric`s so a new field
+//! only has to be mapped once, in `metrics()`.
+
+pub mod influx;
+pub mod mqtt;
+pub mod otlp;
+pub mod push;
+pub mod statsd;
+
+use crate::error::{OverwatchError, Result};
+use crate::snapshot::{HostSnapshot, Reading};
+use crate::storage::{ata, HealthStatus};
+use influx::{InfluxConfig, InfluxExporter};
+use mqtt::{MqttConfig, MqttExporter};
+use otlp::{OtlpConfig, OtlpExporter, Protocol};
+use push::PushConfig;
+use statsd::{Flavor, StatsdConfig, StatsdExporter};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Something the agent hands each fresh snapshot to.
+pub trait Exporter: Send {
+    fn name(&self) -> &'static str;
+    fn export(&mut self, snapshot: &HostSnapshot) -> Result<()>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Percent,
+    Celsius,
+    Megahertz,
+    Bytes,
+    Watts,
+    Hours,
+    /// Average runnable threads, as in a load average.
+    Load,
+    PerSecond,
+    /// A plain number: error counts, or a status code.
+    Count,
+}
+
+/// A single measured value out of a snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    /// "cpu", "gpu", "memory", "sensor" or "storage".
+    pub group: &'static str,
+    pub name: &'static str,
+    /// Which core or sensor, for metrics that have several instances.
+    pub instance: Option<(&'static str, String)>,
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Metric {
+    fn new(group: &'static str, name: &'static str, value: f64, unit: Unit) -> Self {
+        Metric { group, name, instance: None, value, unit }
+    }
+
+    fn instance(mut self, key: &'static str, value: impl ToString) -> Self {
+        self.instance = Some((key, value.to_string()));
+        self
+    }
+}
+
+/// Flattens a snapshot into the values worth exporting. Fields that are
+/// missing or only estimated are left out rather than sent as zeroes.
+pub fn metrics(snapshot: &HostSnapshot) -> Vec<Metric> {
+    let mut metrics = Vec::new();
+
+    if let Reading::Ok(cpu) = &snapshot.cpu {
+        metrics.push(Metric::new("cpu", "usage", cpu.usage as f64, Unit::Percent));
+        for (core, usage) in cpu.per_core_usage.iter().enumerate() {
+            metrics.push(Metric::new("cpu", "usage", *usage as f64, Unit::Percent).instance("core", core));
+        }
+        metrics.push(Metric::new("cpu", "frequency", cpu.frequency as f64, Unit::Megahertz));
+        if cpu.temp_available {
+            metrics.push(Metric::new("cpu", "temperature", cpu.temperature as f64, Unit::Celsius));
+        }
+        if let Some(power) = cpu.package_power {
+            metrics.push(Metric::new("cpu", "power", power as f64, Unit::Watts));
+        }
+        if let Some(scheduler) = &cpu.scheduler {
+            if let Some(load) = scheduler.load {
+                metrics.push(Metric::new("cpu", "load1", load.one as f64, Unit::Load));
+                metrics.push(Metric::new("cpu", "load5", load.five as f64, Unit::Load));
+                metrics.push(Metric::new("cpu", "load15", load.fifteen as f64, Unit::Load));
+            }
+            metrics.push(Metric::new("cpu", "run_queue", scheduler.run_queue as f64, Unit::Count));
+            metrics.push(Metric::new("cpu", "context_switches", scheduler.context_switches as f64, Unit::PerSecond));
+            metrics.push(Metric::new("cpu", "interrupts", scheduler.interrupts as f64, Unit::PerSecond));
+            if let Some(pressure) = scheduler.cpu_pressure {
+                metrics.push(Metric::new("cpu", "pressure_some", pressure.some.avg10 as f64, Unit::Percent));
+            }
+            if let Some(pressure) = scheduler.io_pressure {
+                metrics.push(Metric::new("cpu", "io_pressure_some", pressure.some.avg10 as f64, Unit::Percent));
+            }
+        }
+    }
+
+    if let Reading::Ok(gpu) = &snapshot.gpu {
+        let measured = |field| gpu.provenance.is_measured(field);
+        if gpu.temp_available {
+            metrics.push(Metric::new("gpu", "temperature", gpu.temperature as f64, Unit::Celsius));
+        }
+        if measured("utilization") {
+            metrics.push(Metric::new("gpu", "utilization", gpu.utilization as f64, Unit::Percent));
+        }
+        if measured("memory_total") {
+            metrics.push(Metric::new("gpu", "memory_total", gpu.memory_total as f64, Unit::Bytes));
+        }
+        if measured("memory_used") {
+            metrics.push(Metric::new("gpu", "memory_used", gpu.memory_used as f64, Unit::Bytes));
+        }
+        if measured("power_usage") {
+            metrics.push(Metric::new("gpu", "power", gpu.power_usage as f64, Unit::Watts));
+        }
+        if measured("fan_speed") {
+            metrics.push(Metric::new("gpu", "fan_speed", gpu.fan_speed as f64, Unit::Percent));
+        }
+    }
+
+    if let Reading::Ok(memory) = &snapshot.memory {
+        metrics.push(Metric::new("memory", "total", memory.total as f64, Unit::Bytes));
+        metrics.push(Metric::new("memory", "used", memory.used as f64, Unit::Bytes));
+        metrics.push(Metric::new("memory", "available", memory.available as f64, Unit::Bytes));
+        metrics.push(Metric::new("memory", "swap_total", memory.swap_total as f64, Unit::Bytes));
+        metrics.push(Metric::new("memory", "swap_used", memory.swap_used as f64, Unit::Bytes));
+    }
+
+    if let Reading::Ok(sensors) = &snapshot.sensors {
+        for sensor in sensors {
+            metrics.push(Metric::new("sensor", "temperature", sensor.temperature as f64, Unit::Celsius).instance("sensor", &sensor.label));
+        }
+    }
+
+    if let Some(Reading::Ok(storage)) = &snapshot.storage {
+        for drive in &storage.drives {
+            let metric = |name, value, unit| Metric::new("storage", name, value, unit).instance("drive", &drive.name);
+            // 0 good, 1 warning, 2 failing; unreadable drives report nothing
+            let health = match drive.status {
+                HealthStatus::Good => Some(0.0),
+                HealthStatus::Warning => Some(1.0),
+                HealthStatus::Failing => Some(2.0),
+                HealthStatus::Unknown => None,
+            };
+            if let Some(health) = health {
+                metrics.push(metric("health", health, Unit::Count));
+            }
+            if let Some(temperature) = drive.temperature {
+                metrics.push(metric("temperature", temperature as f64, Unit::Celsius));
+            }
+            if let Some(hours) = drive.power_on_hours {
+                metrics.push(metric("power_on_hours", hours as f64, Unit::Hours));
+            }
+            if let Some(nvme) = &drive.nvme {
+                metrics.push(metric("percentage_used", nvme.percentage_used as f64, Unit::Percent));
+                metrics.push(metric("available_spare", nvme.available_spare as f64, Unit::Percent));
+                metrics.push(metric("media_errors", nvme.media_errors as f64, Unit::Count));
+                metrics.push(metric("unsafe_shutdowns", nvme.unsafe_shutdowns as f64, Unit::Count));
+            }
+            for attribute in &drive.attributes {
+                let name = match attribute.id {
+                    ata::REALLOCATED_SECTORS => "reallocated_sectors",
+                    ata::PENDING_SECTORS => "pending_sectors",
+                    ata::OFFLINE_UNCORRECTABLE => "offline_uncorrectable",
+                    _ => continue,
+                };
+                metrics.push(metric(name, attribute.raw as f64, Unit::Count));
+            }
+        }
+    }
+
+    metrics
+}
+
+// topic levels, metric names and entity ids: lowercase, no separators
+pub(crate) fn slug(text: &str) -> String {
+    let slug: String = text
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    if slug.is_empty() {
+        "unknown".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Which exporters the agent runs, built from its command line.
+#[derive(Debug, Clone, Default)]
+pub struct ExportConfig {
+    pub mqtt: Option<MqttConfig>,
+    pub influx: Option<InfluxConfig>,
+    pub statsd: Vec<StatsdConfig>,
+    pub otlp: Option<OtlpConfig>,
+}
+
+impl ExportConfig {
+    pub fn is_empty(&self) -> bool {
+        self.mqtt.is_none() && self.influx.is_none() && self.statsd.is_empty() && self.otlp.is_none()
+    }
+
+    pub fn connect(&self) -> Result<Vec<Box<dyn Exporter>>> {
+        let mut exporters: Vec<Box<dyn Exporter>> = Vec::new();
+        if let Some(mqtt) = &self.mqtt {
+            exporters.push(Box::new(MqttExporter::connect(mqtt.clone())?));
+        }
+        if let Some(influx) = &self.influx {
+            exporters.push(Box::new(InfluxExporter::connect(influx.clone())?));
+        }
+        for statsd in &self.statsd {
+            exporters.push(Box::new(StatsdExporter::connect(statsd.clone())?));
+        }
+        if let Some(otlp) = &self.otlp {
+            exporters.push(Box::new(OtlpExporter::connect(otlp.clone())?));
+        }
+        Ok(exporters)
+    }
+}
+
+/// Exporter flags as they come in; options may appear before the flag that
+/// enables their exporter, so nothing is built until `finish`.
+#[derive(Debug, Default)]
+pub struct ExportArgs {
+    mqtt_url: Option<String>,
+    mqtt_user: Option<String>,
+    mqtt_password: Option<String>,
+    mqtt_ca: Option<PathBuf>,
+    mqtt_topic: Option<String>,
+    mqtt_node_id: Option<String>,
+    mqtt_discovery: Option<Option<String>>,
+    influx_url: Option<String>,
+    influx_token: Option<String>,
+    statsd: Vec<(String, Flavor)>,
+    otlp_endpoint: Option<String>,
+    otlp_protocol: Option<Protocol>,
+    otlp_headers: Vec<(String, String)>,
+    push: PushConfig,
+}
+
+impl ExportArgs {
+    /// Consumes `arg` if it's an exporter flag, pulling its value through
+    /// `value`. Returns false for flags it doesn't know.
+    pub fn parse(&mut self, arg: &str, value: &mut impl FnMut(&str) -> Result<String>) -> Result<bool> {
+        match arg {
+            "--mqtt" => self.mqtt_url = Some(value(arg)?),
+            "--mqtt-user" => self.mqtt_user = Some(value(arg)?),
+            "--mqtt-password" => self.mqtt_password = Some(value(arg)?),
+            "--mqtt-ca" => self.mqtt_ca = Some(PathBuf::from(value(arg)?)),
+            "--mqtt-topic" => self.mqtt_topic = Some(value(arg)?),
+            "--mqtt-node-id" => self.mqtt_node_id = Some(value(arg)?),
+            "--mqtt-discovery-prefix" => self.mqtt_discovery = Some(Some(value(arg)?)),
+            "--no-mqtt-discovery" => self.mqtt_discovery = Some(None),
+            "--influx" => self.influx_url = Some(value(arg)?),
+            "--influx-token" => self.influx_token = Some(value(arg)?),
+            "--statsd" => self.statsd.push((value(arg)?, Flavor::Statsd)),
+            "--dogstatsd" => self.statsd.push((value(arg)?, Flavor::Dogstatsd)),
+            "--otlp" => self.otlp_endpoint = Some(value(arg)?),
+            "--otlp-protocol" => {
+                self.otlp_protocol = Some(match value(arg)?.as_str() {
+                    "grpc" => Protocol::Grpc,
+                    "http" | "http/protobuf" => Protocol::HttpProtobuf,
+                    other => return Err(OverwatchError::unsupported(format!("OTLP protocol {}", other))),
+                });
+            }
+            "--otlp-header" => self.otlp_headers.push(key_value(arg, value(arg)?)?),
+            "--push-interval-ms" => {
+                let ms: u64 = value(arg)?.parse().map_err(|err| OverwatchError::backend(arg, err))?;
+                self.push.interval = Duration::from_millis(ms.max(100));
+            }
+            "--metric-prefix" => self.push.prefix = value(arg)?,
+            "--tag" => self.push.tags.push(key_value(arg, value(arg)?)?),
+            "--buffer-dir" => self.push.buffer_dir = Some(PathBuf::from(value(arg)?)),
+            "--no-buffer" => self.push.buffer_dir = None,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Secrets fall back to `OVERWATCH_MQTT_PASSWORD` and
+    /// `OVERWATCH_INFLUX_TOKEN` so they can stay out of `ps`.
+    pub fn finish(self) -> Result<ExportConfig> {
+        let mqtt = match self.mqtt_url {
+            Some(url) => {
+                let mut config = MqttConfig::from_url(&url)?;
+                config.username = self.mqtt_user;
+                config.password = self.mqtt_password.or_else(|| std::env::var("OVERWATCH_MQTT_PASSWORD").ok());
+                config.tls |= self.mqtt_ca.is_some();
+                config.ca_file = self.mqtt_ca;
+                if let Some(topic) = self.mqtt_topic {
+                    config.base_topic = topic;
+                }
+                if let Some(node_id) = self.mqtt_node_id {
+                    config.node_id = node_id;
+                }
+                if let Some(discovery) = self.mqtt_discovery {
+                    config.discovery_prefix = discovery;
+                }
+                Some(config)
+            }
+            None => None,
+        };
+
+        let influx = match self.influx_url {
+            Some(url) => {
+                let token = self.influx_token.or_else(|| std::env::var("OVERWATCH_INFLUX_TOKEN").ok());
+                Some(InfluxConfig::from_url(&url, token, self.push.clone())?)
+            }
+            None => None,
+        };
+
+        let statsd = self
+            .statsd
+            .into_iter()
+            .map(|(addr, flavor)| StatsdConfig { addr, flavor, push: self.push.clone() })
+            .collect();
+
+        let otlp = self.otlp_endpoint.map(|endpoint| OtlpConfig {
+            endpoint,
+            // the spec's default
+            protocol: self.otlp_protocol.unwrap_or(Protocol::HttpProtobuf),
+            headers: self.otlp_headers,
+            push: self.push.clone(),
+        });
+
+        Ok(ExportConfig { mqtt, influx, statsd, otlp })
+    }
+}
+
+fn key_value(flag: &str, text: String) -> Result<(String, String)> {
+    let (key, value) = text
+        .split_once('=')
+        .ok_or_else(|| OverwatchError::backend(flag, format!("expected key=value, got {}", text)))?;
+    Ok((key.to_string(), value.to_string()))
+}
@@ -0,0 +1,309 @@
+//! Publishes samples as retained MQTT topics, with Home Assistant discovery.
+//!
+//! States go to `<base>/<node>/<group>/[<key>/<instance>/]<name>`, e.g.
+//! `overwatch/desk/cpu/usage`, `overwatch/desk/cpu/core/3/usage` or
+//! `overwatch/desk/sensor/package_id_0/temperature`, and
+//! `<base>/<node>/status` carries `online`/`offline` via the last will.
+
+use super::{metrics, slug, Exporter, Metric, Unit};
+use crate::error::{OverwatchError, Result};
+use crate::snapshot::{hostname, HostSnapshot, Reading};
+use rumqttc::{Client, ClientError, Connection, ConnectionError, Event, LastWill, MqttOptions, Packet, QoS, TlsConfiguration, Transport};
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const DEFAULT_PORT: u16 = 1883;
+pub const DEFAULT_TLS_PORT: u16 = 8883;
+
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+// one snapshot on a big machine is a few hundred publishes plus discovery
+const QUEUE_SIZE: usize = 1024;
+
+#[derive(Debug, Clone)]
+pub struct MqttConfig {
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+    /// PEM CA bundle for brokers with a private CA, system roots otherwise.
+    pub ca_file: Option<PathBuf>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub base_topic: String,
+    /// Identifies this machine in topics and entity ids.
+    pub node_id: String,
+    /// `None` turns Home Assistant discovery off.
+    pub discovery_prefix: Option<String>,
+}
+
+impl MqttConfig {
+    /// Parses `mqtt://host[:port]` or `mqtts://host[:port]`, everything
+    /// else starts at its default. IPv6 addresses go in brackets when
+    /// there's a port, `mqtt://[::1]:1883`.
+    pub fn from_url(url: &str) -> Result<Self> {
+        let invalid = |why: &str| OverwatchError::backend("MQTT broker URL", format!("{}: {}", why, url));
+
+        let (scheme, rest) = url.split_once("://").ok_or_else(|| invalid("missing scheme"))?;
+        let tls = match scheme {
+            "mqtt" | "tcp" => false,
+            "mqtts" | "ssl" => true,
+            _ => return Err(invalid("scheme must be mqtt or mqtts")),
+        };
+        let authority = rest.trim_end_matches('/');
+        let (host, port) = match authority.strip_prefix('[') {
+            Some(bracketed) => {
+                let (host, rest) = bracketed.split_once(']').ok_or_else(|| invalid("unclosed '['"))?;
+                match rest {
+                    "" => (host, None),
+                    _ => (host, Some(rest.strip_prefix(':').ok_or_else(|| invalid("bad port"))?)),
+                }
+            }
+            // a bare IPv6 address has more than one colon and no port
+            None if authority.matches(':').count() > 1 => (authority, None),
+            None => match authority.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            },
+        };
+        let port = match port {
+            Some(port) => port.parse().map_err(|_| invalid("bad port"))?,
+            None if tls => DEFAULT_TLS_PORT,
+            None => DEFAULT_PORT,
+        };
+        if host.is_empty() {
+            return Err(invalid("missing host"));
+        }
+
+        Ok(MqttConfig {
+            host: host.to_string(),
+            port,
+            tls,
+            ca_file: None,
+            username: None,
+            password: None,
+            base_topic: "overwatch".to_string(),
+            node_id: slug(&hostname()),
+            discovery_prefix: Some("homeassistant".to_string()),
+        })
+    }
+}
+
+pub struct MqttExporter {
+    client: Client,
+    prefix: String,
+    discovery_prefix: Option<String>,
+    node_id: String,
+    // object ids that already have a discovery config on the broker
+    announced: BTreeSet<String>,
+    connected: Arc<AtomicBool>,
+    // set on every CONNACK so status and discovery get republished
+    reconnected: Arc<AtomicBool>,
+}
+
+impl MqttExporter {
+    /// Starts connecting in the background; `export` reports while the
+    /// broker is unreachable and the connection keeps retrying.
+    pub fn connect(config: MqttConfig) -> Result<Self> {
+        let prefix = format!("{}/{}", config.base_topic.trim_end_matches('/'), config.node_id);
+
+        let mut options = MqttOptions::new(format!("overwatch-{}", config.node_id), &config.host, config.port);
+        options.set_keep_alive(KEEP_ALIVE);
+        options.set_last_will(LastWill::new(format!("{}/status", prefix), "offline", QoS::AtLeastOnce, true));
+        if let Some(username) = &config.username {
+            options.set_credentials(username, config.password.clone().unwrap_or_default());
+        }
+        if config.tls {
+            let tls = match &config.ca_file {
+                Some(path) => TlsConfiguration::SimpleNative {
+                    ca: std::fs::read(path).map_err(|err| OverwatchError::from_io(path.display().to_string(), err))?,
+                    client_auth: None,
+                },
+                None => TlsConfiguration::Native,
+            };
+            options.set_transport(Transport::tls_with_config(tls));
+        }
+
+        let (client, connection) = Client::new(options, QUEUE_SIZE);
+        let connected = Arc::new(AtomicBool::new(false));
+        let reconnected = Arc::new(AtomicBool::new(false));
+        let broker = format!("{}:{}", config.host, config.port);
+        {
+            let connected = connected.clone();
+            let reconnected = reconnected.clone();
+            std::thread::spawn(move || drive(connection, &broker, &connected, &reconnected));
+        }
+
+        Ok(MqttExporter {
+            client,
+            prefix,
+            discovery_prefix: config.discovery_prefix,
+            node_id: config.node_id,
+            announced: BTreeSet::new(),
+            connected,
+            reconnected,
+        })
+    }
+
+    fn publish(&self, topic: String, qos: QoS, payload: impl Into<Vec<u8>>) -> Result<()> {
+        self.client
+            .try_publish(topic, qos, true, payload)
+            .map_err(|err: ClientError| OverwatchError::backend("MQTT", err))
+    }
+
+    fn announce(&self, metric: &Metric, object_id: &str, state_topic: &str, model: Option<&str>) -> Result<()> {
+        let Some(discovery_prefix) = &self.discovery_prefix else {
+            return Ok(());
+        };
+
+        let unique_id = format!("overwatch_{}_{}", self.node_id, object_id);
+        let mut config = json!({
+            "name": display_name(metric),
+            "unique_id": unique_id,
+            "object_id": unique_id,
+            "state_topic": state_topic,
+            "state_class": "measurement",
+            "availability_topic": format!("{}/status", self.prefix),
+            "device": {
+                "identifiers": [format!("overwatch_{}", self.node_id)],
+                "name": self.node_id,
+                "manufacturer": "Overwatch",
+                "model": model,
+            },
+        });
+        if let Some(unit) = unit_symbol(metric.unit) {
+            config["unit_of_measurement"] = json!(unit);
+        }
+        if let Some(class) = device_class(metric.unit) {
+            config["device_class"] = json!(class);
+        }
+
+        let topic = format!("{}/sensor/overwatch_{}/{}/config", discovery_prefix, self.node_id, object_id);
+        self.publish(topic, QoS::AtLeastOnce, config.to_string())
+    }
+}
+
+impl Exporter for MqttExporter {
+    fn name(&self) -> &'static str {
+        "mqtt"
+    }
+
+    fn export(&mut self, snapshot: &HostSnapshot) -> Result<()> {
+        if !self.connected.load(Ordering::SeqCst) {
+            return Err(OverwatchError::backend("MQTT", "not connected to broker"));
+        }
+        if self.reconnected.swap(false, Ordering::SeqCst) {
+            // the broker may have lost retained messages while we were away
+            self.announced.clear();
+            self.publish(format!("{}/status", self.prefix), QoS::AtLeastOnce, "online")?;
+        }
+
+        let model = match &snapshot.cpu {
+            Reading::Ok(cpu) => Some(cpu.brand.as_str()),
+            Reading::Err(_) => None,
+        };
+        for metric in metrics(snapshot) {
+            let object_id = object_id(&metric);
+            let state_topic = state_topic(&self.prefix, &metric);
+            if !self.announced.contains(&object_id) {
+                self.announce(&metric, &object_id, &state_topic, model)?;
+                self.announced.insert(object_id);
+            }
+            self.publish(state_topic, QoS::AtMostOnce, format_value(&metric))?;
+        }
+        Ok(())
+    }
+}
+
+// polls the connection forever, rumqttc reconnects on the next poll after an error
+fn drive(mut connection: Connection, broker: &str, connected: &AtomicBool, reconnected: &AtomicBool) {
+    for event in connection.iter() {
+        match event {
+            Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                log::info!("mqtt connected to {}", broker);
+                connected.store(true, Ordering::SeqCst);
+                reconnected.store(true, Ordering::SeqCst);
+            }
+            Ok(_) => {}
+            Err(ConnectionError::RequestsDone) => break,
+            Err(err) => {
+                if connected.swap(false, Ordering::SeqCst) {
+                    log::warn!("mqtt connection to {} lost: {}", broker, err);
+                }
+                std::thread::sleep(RETRY_DELAY);
+            }
+        }
+    }
+}
+
+fn state_topic(prefix: &str, metric: &Metric) -> String {
+    format!("{}/{}/{}", prefix, instance_path(metric).join("/"), metric.name)
+}
+
+fn object_id(metric: &Metric) -> String {
+    format!("{}_{}", instance_path(metric).join("_"), metric.name)
+}
+
+// `cpu/core/3` but `sensor/<label>` rather than `sensor/sensor/<label>`
+fn instance_path(metric: &Metric) -> Vec<String> {
+    let mut path = vec![metric.group.to_string()];
+    if let Some((key, instance)) = &metric.instance {
+        if *key != metric.group {
+            path.push(key.to_string());
+        }
+        path.push(slug(instance));
+    }
+    path
+}
+
+fn display_name(metric: &Metric) -> String {
+    let name = metric.name.replace('_', " ");
+    match (metric.group, &metric.instance) {
+        ("sensor", Some((_, label))) => format!("{} {}", label, name),
+        (group, Some((key, instance))) => format!("{} {} {} {}", group_label(group), key, instance, name),
+        (group, None) => format!("{} {}", group_label(group), name),
+    }
+}
+
+fn group_label(group: &str) -> String {
+    match group {
+        "cpu" | "gpu" => group.to_uppercase(),
+        _ => group[..1].to_uppercase() + &group[1..],
+    }
+}
+
+fn format_value(metric: &Metric) -> String {
+    match metric.unit {
+        Unit::Percent | Unit::Celsius | Unit::Watts => format!("{:.1}", metric.value),
+        Unit::Load => format!("{:.2}", metric.value),
+        Unit::Megahertz | Unit::Bytes | Unit::Hours | Unit::Count | Unit::PerSecond => format!("{:.0}", metric.value),
+    }
+}
+
+fn unit_symbol(unit: Unit) -> Option<&'static str> {
+    match unit {
+        Unit::Percent => Some("%"),
+        Unit::Celsius => Some("°C"),
+        Unit::Megahertz => Some("MHz"),
+        Unit::Bytes => Some("B"),
+        Unit::Watts => Some("W"),
+        Unit::Hours => Some("h"),
+        Unit::PerSecond => Some("/s"),
+        Unit::Load | Unit::Count => None,
+    }
+}
+
+fn device_class(unit: Unit) -> Option<&'static str> {
+    match unit {
+        Unit::Percent => None,
+        Unit::Celsius => Some("temperature"),
+        Unit::Megahertz => Some("frequency"),
+        Unit::Bytes => Some("data_size"),
+        Unit::Watts => Some("power"),
+        Unit::Hours => Some("duration"),
+        Unit::Load | Unit::PerSecond | Unit::Count => None,
+    }
+}
@@ -0,0 +1,315 @@
+//! OpenTelemetry metrics over OTLP, gRPC or HTTP/protobuf.
+//!
+//! Names follow the semantic conventions where one exists
+//! (`system.cpu.utilization` per core, `hw.temperature`, `hw.gpu.memory.usage`, ...),
+//! anything else goes out as `<prefix>.<group>.<name>`. Snapshots are
+//! batched as JSON so the shared spool can hold them while the collector
+//! is down.
+
+use super::push::{http_agent, http_status_error, PushConfig, Pusher, SendError};
+use super::{metrics, slug, Exporter, Metric, Unit};
+use crate::error::{OverwatchError, Result};
+use crate::snapshot::{HostSnapshot, Reading};
+use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_client::MetricsServiceClient;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::{metric, number_data_point, Gauge, NumberDataPoint, ResourceMetrics, ScopeMetrics};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use prost::Message;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+
+const GRPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Grpc,
+    HttpProtobuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    /// Collector base URL, e.g. `http://collector:4317` for gRPC or
+    /// `https://collector:4318` for HTTP (`/v1/metrics` is appended).
+    pub endpoint: String,
+    pub protocol: Protocol,
+    /// Sent with every export, typically for auth.
+    pub headers: Vec<(String, String)>,
+    pub push: PushConfig,
+}
+
+pub struct OtlpExporter {
+    pusher: Pusher,
+}
+
+impl OtlpExporter {
+    pub fn connect(config: OtlpConfig) -> Result<Self> {
+        let resource = resource(&config.push.tags);
+        let prefix = config.push.prefix.clone();
+        let headers = config.headers.clone();
+
+        let mut send = match config.protocol {
+            Protocol::HttpProtobuf => {
+                let url = match config.endpoint.trim_end_matches('/') {
+                    url if url.ends_with("/v1/metrics") => url.to_string(),
+                    url => format!("{}/v1/metrics", url),
+                };
+                let agent = http_agent()?;
+                Box::new(move |request: ExportMetricsServiceRequest| post(&agent, &url, &headers, request))
+                    as Box<dyn FnMut(ExportMetricsServiceRequest) -> std::result::Result<(), SendError> + Send>
+            }
+            Protocol::Grpc => {
+                let mut endpoint = Endpoint::from_shared(config.endpoint.clone())
+                    .map_err(|err| OverwatchError::backend("OTLP endpoint", err))?
+                    .timeout(GRPC_TIMEOUT)
+                    .connect_timeout(GRPC_TIMEOUT);
+                if config.endpoint.starts_with("https://") {
+                    endpoint = endpoint
+                        .tls_config(ClientTlsConfig::new().with_native_roots())
+                        .map_err(|err| OverwatchError::backend("OTLP TLS", err))?;
+                }
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|err| OverwatchError::from_io("OTLP runtime", err))?;
+                // the channel reconnects on its own, it just has to be made inside the runtime
+                let channel = runtime.block_on(async { endpoint.connect_lazy() });
+                let mut client = MetricsServiceClient::new(channel);
+                Box::new(move |request| export_grpc(&runtime, &mut client, &headers, request))
+            }
+        };
+
+        let pusher = Pusher::spawn(&config.push, Some("otlp"), move |lines| {
+            let snapshots: Vec<HostSnapshot> = lines.iter().filter_map(|line| serde_json::from_str(line).ok()).collect();
+            send(request(&snapshots, &prefix, resource.clone()))
+        });
+        Ok(OtlpExporter { pusher })
+    }
+}
+
+impl Exporter for OtlpExporter {
+    fn name(&self) -> &'static str {
+        "otlp"
+    }
+
+    fn export(&mut self, snapshot: &HostSnapshot) -> Result<()> {
+        let line = serde_json::to_string(snapshot).map_err(|err| OverwatchError::backend("OTLP", err))?;
+        self.pusher.push([line])
+    }
+}
+
+fn post(agent: &ureq::Agent, url: &str, headers: &[(String, String)], request: ExportMetricsServiceRequest) -> std::result::Result<(), SendError> {
+    let mut call = agent.post(url).set("Content-Type", "application/x-protobuf");
+    for (key, value) in headers {
+        call = call.set(key, value);
+    }
+
+    match call.send_bytes(&request.encode_to_vec()) {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, response)) => Err(http_status_error("OTLP collector", code, &response.into_string().unwrap_or_default())),
+        Err(err) => Err(SendError::Retry(OverwatchError::backend("OTLP collector", err))),
+    }
+}
+
+fn export_grpc(
+    runtime: &tokio::runtime::Runtime,
+    client: &mut MetricsServiceClient<Channel>,
+    headers: &[(String, String)],
+    request: ExportMetricsServiceRequest,
+) -> std::result::Result<(), SendError> {
+    let mut request = tonic::Request::new(request);
+    for (key, value) in headers {
+        let key = tonic::metadata::MetadataKey::from_bytes(key.to_lowercase().as_bytes());
+        let value = value.parse();
+        if let (Ok(key), Ok(value)) = (key, value) {
+            request.metadata_mut().insert(key, value);
+        }
+    }
+
+    runtime.block_on(client.export(request)).map(|_| ()).map_err(|status| {
+        use tonic::Code;
+        let err = OverwatchError::backend("OTLP collector", format!("{:?}: {}", status.code(), status.message()));
+        match status.code() {
+            Code::Unauthenticated | Code::PermissionDenied => SendError::Fatal(OverwatchError::permission_denied("OTLP export")),
+            Code::InvalidArgument | Code::Unimplemented => SendError::Fatal(err),
+            _ => SendError::Retry(err),
+        }
+    })
+}
+
+/// One request with a gauge point per metric per snapshot.
+pub fn request(snapshots: &[HostSnapshot], prefix: &str, resource: Resource) -> ExportMetricsServiceRequest {
+    let mut by_name: BTreeMap<String, (&'static str, Vec<NumberDataPoint>)> = BTreeMap::new();
+    for snapshot in snapshots {
+        let time_unix_nano = snapshot.timestamp * 1_000_000;
+        for metric in metrics(snapshot).iter().filter(|m| m.value.is_finite()) {
+            let (name, unit, value, attributes) = convention(metric, prefix);
+            let (_, points) = by_name.entry(name).or_insert((unit, Vec::new()));
+            points.push(NumberDataPoint {
+                attributes,
+                time_unix_nano,
+                value: Some(number_data_point::Value::AsDouble(value)),
+                ..Default::default()
+            });
+        }
+    }
+
+    let metrics = by_name
+        .into_iter()
+        .map(|(name, (unit, data_points))| opentelemetry_proto::tonic::metrics::v1::Metric {
+            name,
+            unit: unit.to_string(),
+            data: Some(metric::Data::Gauge(Gauge { data_points })),
+            ..Default::default()
+        })
+        .collect();
+
+    // host attributes come from the snapshots, the rest was read at startup
+    let mut resource = resource;
+    if let Some(snapshot) = snapshots.last() {
+        resource.attributes.push(attribute("host.name", &snapshot.host));
+        if let Reading::Ok(cpu) = &snapshot.cpu {
+            resource.attributes.push(attribute("host.cpu.model.name", &cpu.brand));
+        }
+    }
+
+    ExportMetricsServiceRequest {
+        resource_metrics: vec![ResourceMetrics {
+            resource: Some(resource),
+            scope_metrics: vec![ScopeMetrics {
+                scope: Some(InstrumentationScope {
+                    name: "overwatch".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    ..Default::default()
+                }),
+                metrics,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    }
+}
+
+// semantic convention name, unit, value and attributes for a metric
+fn convention(metric: &Metric, prefix: &str) -> (String, &'static str, f64, Vec<KeyValue>) {
+    let instance = metric.instance.as_ref().map(|(_, value)| value.as_str());
+    let hw = |id: &str| vec![attribute("hw.id", id)];
+    let ratio = metric.value / 100.0;
+
+    let (name, unit, value, attributes) = match (metric.group, metric.name) {
+        // the convention has no whole-CPU series; summing cores would be wrong,
+        // so the average goes out under the prefix with everything else
+        ("cpu", "usage") if instance.is_some() => {
+            let core = instance.and_then(|core| core.parse().ok()).unwrap_or(0);
+            ("system.cpu.utilization", "1", ratio, vec![int_attribute("cpu.logical_number", core)])
+        }
+        ("cpu", "frequency") => ("system.cpu.frequency", "Hz", metric.value * 1e6, Vec::new()),
+        ("cpu", "temperature") => ("hw.temperature", "Cel", metric.value, hw("cpu")),
+        ("gpu", "temperature") => ("hw.temperature", "Cel", metric.value, hw("gpu")),
+        ("gpu", "utilization") => ("hw.gpu.utilization", "1", ratio, hw("gpu")),
+        ("gpu", "memory_total") => ("hw.gpu.memory.limit", "By", metric.value, hw("gpu")),
+        ("gpu", "memory_used") => ("hw.gpu.memory.usage", "By", metric.value, hw("gpu")),
+        ("gpu", "power") => ("hw.power", "W", metric.value, hw("gpu")),
+        ("gpu", "fan_speed") => ("hw.fan.speed_ratio", "1", ratio, hw("gpu_fan")),
+        ("memory", "total") => ("system.memory.limit", "By", metric.value, Vec::new()),
+        ("memory", "used") => ("system.memory.usage", "By", metric.value, vec![attribute("system.memory.state", "used")]),
+        // available counts reclaimable cache too, so it isn't the "free" state
+        ("memory", "available") => ("system.memory.available", "By", metric.value, Vec::new()),
+        ("memory", "swap_total") => ("system.paging.limit", "By", metric.value, Vec::new()),
+        ("memory", "swap_used") => ("system.paging.usage", "By", metric.value, vec![attribute("system.paging.state", "used")]),
+        ("sensor", "temperature") => {
+            let label = instance.unwrap_or("unknown");
+            ("hw.temperature", "Cel", metric.value, vec![attribute("hw.id", &slug(label)), attribute("hw.name", label)])
+        }
+        (group, name) => {
+            let name = format!("{}.{}.{}", prefix, group, name);
+            let mut attributes = Vec::new();
+            if let Some((key, value)) = &metric.instance {
+                attributes.push(attribute(key, value));
+            }
+            return (name, unit_code(metric.unit), metric.value, attributes);
+        }
+    };
+    (name.to_string(), unit, value, attributes)
+}
+
+fn unit_code(unit: Unit) -> &'static str {
+    match unit {
+        Unit::Percent => "%",
+        Unit::Celsius => "Cel",
+        Unit::Megahertz => "MHz",
+        Unit::Bytes => "By",
+        Unit::Watts => "W",
+        Unit::Hours => "h",
+        Unit::Load | Unit::Count => "1",
+        Unit::PerSecond => "1/s",
+    }
+}
+
+/// Resource attributes that don't change while the agent runs.
+pub fn resource(tags: &[(String, String)]) -> Resource {
+    use sysinfo::System;
+
+    let mut attributes = vec![
+        attribute("service.name", "overwatch"),
+        attribute("service.version", env!("CARGO_PKG_VERSION")),
+        attribute("os.type", std::env::consts::OS),
+        attribute("host.arch", std::env::consts::ARCH),
+    ];
+    if let Some(description) = System::long_os_version() {
+        attributes.push(attribute("os.description", &description));
+    }
+    if let Some(version) = System::os_version() {
+        attributes.push(attribute("os.version", &version));
+    }
+    if let Some((vendor, model)) = hardware_model() {
+        attributes.push(attribute("hw.vendor", &vendor));
+        attributes.push(attribute("hw.model", &model));
+    }
+    attributes.extend(tags.iter().map(|(key, value)| attribute(key, value)));
+
+    Resource { attributes, dropped_attributes_count: 0 }
+}
+
+// vendor and product as the firmware reports them
+#[cfg(target_os = "linux")]
+fn hardware_model() -> Option<(String, String)> {
+    use crate::sysfs::read_attr;
+    use std::path::Path;
+
+    let read = |name: &str| read_attr(&Path::new("/sys/class/dmi/id").join(name));
+    Some((read("sys_vendor")?, read("product_name")?))
+}
+
+#[cfg(windows)]
+fn hardware_model() -> Option<(String, String)> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let bios = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"HARDWARE\DESCRIPTION\System\BIOS")
+        .ok()?;
+    let vendor: String = bios.get_value("SystemManufacturer").ok()?;
+    let product: String = bios.get_value("SystemProductName").ok()?;
+    Some((vendor, product))
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn hardware_model() -> Option<(String, String)> {
+    None
+}
+
+fn attribute(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.to_string(),
+        value: Some(AnyValue { value: Some(any_value::Value::StringValue(value.to_string())) }),
+    }
+}
+
+fn int_attribute(key: &str, value: i64) -> KeyValue {
+    KeyValue {
+        key: key.to_string(),
+        value: Some(AnyValue { value: Some(any_value::Value::IntValue(value)) }),
+    }
+}
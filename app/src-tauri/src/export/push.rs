@@ -0,0 +1,273 @@
+//! Batching, retries and the on-disk spool shared by the push exporters.
+//!
+//! Lines are collected on the sampler thread and handed to a worker once
+//! per interval, so a slow or dead sink never holds up sampling.
+
+use crate::error::{OverwatchError, Result};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::net::UdpSocket;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const MAX_ATTEMPTS: u32 = 3;
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+const RETRY_BASE: Duration = Duration::from_millis(500);
+// fits a typical ethernet MTU after IP and UDP headers
+const MAX_DATAGRAM: usize = 1432;
+// days of samples for a typical desktop at a 10s interval
+const MAX_SPOOL_BYTES: u64 = 16 << 20;
+
+/// Settings every push exporter shares.
+#[derive(Debug, Clone)]
+pub struct PushConfig {
+    /// How often batched samples are sent.
+    pub interval: Duration,
+    /// Prepended to every measurement or metric name.
+    pub prefix: String,
+    /// Extra tags on every point, on top of `host`.
+    pub tags: Vec<(String, String)>,
+    /// Where undelivered batches wait for the sink to come back. `None`
+    /// drops them after the last retry.
+    pub buffer_dir: Option<PathBuf>,
+}
+
+impl Default for PushConfig {
+    fn default() -> Self {
+        PushConfig {
+            interval: Duration::from_secs(10),
+            prefix: "overwatch".to_string(),
+            tags: Vec::new(),
+            buffer_dir: default_buffer_dir(),
+        }
+    }
+}
+
+/// `/var/lib/overwatch/spool` for root, otherwise the user's state directory
+/// (local data on Windows and macOS). Never the shared temp dir, where
+/// another user could plant the spool file first.
+pub fn default_buffer_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: geteuid can't fail
+        if unsafe { libc::geteuid() } == 0 {
+            return Some(PathBuf::from("/var/lib/overwatch/spool"));
+        }
+    }
+    let base = dirs::state_dir().or_else(dirs::data_local_dir)?;
+    Some(base.join("overwatch").join("spool"))
+}
+
+/// Why a send failed, and whether trying again could help.
+pub enum SendError {
+    Retry(OverwatchError),
+    Fatal(OverwatchError),
+}
+
+/// Sampler-side half: batches lines and passes them to the delivery worker.
+pub struct Pusher {
+    lines: Vec<String>,
+    interval: Duration,
+    last_flush: Instant,
+    batches: SyncSender<Vec<String>>,
+    last_error: Arc<Mutex<Option<OverwatchError>>>,
+}
+
+impl Pusher {
+    /// Starts the delivery worker. `spool_name` names the file under the
+    /// buffer dir; pass `None` for lines that can't be replayed later.
+    pub fn spawn<F>(config: &PushConfig, spool_name: Option<&str>, send: F) -> Self
+    where
+        F: FnMut(&[String]) -> std::result::Result<(), SendError> + Send + 'static,
+    {
+        // one batch in flight and one waiting, anything more merges on this side
+        let (batches, rx) = mpsc::sync_channel::<Vec<String>>(1);
+        let last_error = Arc::new(Mutex::new(None));
+        let spool = spool_name.and_then(|name| Some(config.buffer_dir.as_ref()?.join(format!("{}.spool", name))));
+
+        let worker_error = last_error.clone();
+        std::thread::spawn(move || {
+            let mut send = send;
+            for batch in rx {
+                let result = deliver(batch, spool.as_deref(), &mut send);
+                *worker_error.lock().unwrap() = result.err();
+            }
+        });
+
+        Pusher {
+            lines: Vec::new(),
+            interval: config.interval,
+            last_flush: Instant::now(),
+            batches,
+            last_error,
+        }
+    }
+
+    /// Queues lines and hands the batch over once the interval is up.
+    /// Returns the error from the most recent delivery, if it failed.
+    pub fn push(&mut self, lines: impl IntoIterator<Item = String>) -> Result<()> {
+        self.lines.extend(lines);
+
+        if self.last_flush.elapsed() >= self.interval {
+            match self.batches.try_send(std::mem::take(&mut self.lines)) {
+                Ok(()) => self.last_flush = Instant::now(),
+                // worker still busy, keep collecting
+                Err(TrySendError::Full(lines)) => self.lines = lines,
+                Err(TrySendError::Disconnected(_)) => {
+                    return Err(OverwatchError::backend("push exporter", "delivery thread stopped"));
+                }
+            }
+        }
+
+        match self.last_error.lock().unwrap().clone() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+/// HTTP client with the platform's TLS stack.
+pub fn http_agent() -> Result<ureq::Agent> {
+    let tls = native_tls::TlsConnector::new().map_err(|err| OverwatchError::backend("TLS", err))?;
+    Ok(ureq::AgentBuilder::new()
+        .timeout(HTTP_TIMEOUT)
+        .tls_connector(Arc::new(tls))
+        .build())
+}
+
+/// Sorts an HTTP error status into retryable or not.
+pub fn http_status_error(service: &str, code: u16, body: &str) -> SendError {
+    match code {
+        401 | 403 => SendError::Fatal(OverwatchError::permission_denied(format!("{} write (HTTP {})", service, code))),
+        // the server rejected the data itself, resending it won't help
+        400..=499 if code != 429 => SendError::Fatal(OverwatchError::backend(service, format!("HTTP {}: {}", code, body.trim()))),
+        _ => SendError::Retry(OverwatchError::backend(service, format!("HTTP {}: {}", code, body.trim()))),
+    }
+}
+
+/// Sends lines over UDP, packing as many as fit into each datagram.
+pub fn udp_sender(addr: &str) -> Result<impl FnMut(&[String]) -> std::result::Result<(), SendError>> {
+    let target = std::net::ToSocketAddrs::to_socket_addrs(addr)
+        .map_err(|err| OverwatchError::from_io(addr.to_string(), err))?
+        .next()
+        .ok_or_else(|| OverwatchError::not_found(format!("address for {}", addr)))?;
+    let local = if target.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+    let socket = UdpSocket::bind(local).map_err(|err| OverwatchError::from_io("UDP socket", err))?;
+    // connected so a closed port shows up as an error on the next send
+    socket.connect(target).map_err(|err| OverwatchError::from_io(addr.to_string(), err))?;
+
+    let addr = addr.to_string();
+    Ok(move |lines: &[String]| {
+        let mut datagram = String::new();
+        for line in lines {
+            if !datagram.is_empty() && datagram.len() + 1 + line.len() > MAX_DATAGRAM {
+                send_datagram(&socket, &addr, &datagram)?;
+                datagram.clear();
+            }
+            if !datagram.is_empty() {
+                datagram.push('\n');
+            }
+            datagram.push_str(line);
+        }
+        if datagram.is_empty() {
+            return Ok(());
+        }
+        send_datagram(&socket, &addr, &datagram)
+    })
+}
+
+fn send_datagram(socket: &UdpSocket, addr: &str, datagram: &str) -> std::result::Result<(), SendError> {
+    socket
+        .send(datagram.as_bytes())
+        .map(|_| ())
+        .map_err(|err| SendError::Retry(OverwatchError::from_io(addr.to_string(), err)))
+}
+
+// backlog first, then the new batch; the spool only goes once they're through
+fn deliver(
+    batch: Vec<String>,
+    spool: Option<&Path>,
+    send: &mut impl FnMut(&[String]) -> std::result::Result<(), SendError>,
+) -> Result<()> {
+    let mut lines = spool.map(read_spool).unwrap_or_default();
+    lines.extend(batch);
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    let mut attempt = 0;
+    let err = loop {
+        match send(&lines) {
+            Ok(()) => {
+                if let Some(path) = spool {
+                    std::fs::remove_file(path).ok();
+                }
+                return Ok(());
+            }
+            // kept too: a bad token or missing bucket is fixed on the server, not here
+            Err(SendError::Fatal(err)) => break err,
+            Err(SendError::Retry(err)) => {
+                attempt += 1;
+                if attempt == MAX_ATTEMPTS {
+                    break err;
+                }
+                std::thread::sleep(RETRY_BASE * 2u32.pow(attempt - 1));
+            }
+        }
+    };
+
+    if let Some(path) = spool {
+        write_spool(path, &lines)?;
+    }
+    Err(err)
+}
+
+fn read_spool(path: &Path) -> Vec<String> {
+    let mut options = OpenOptions::new();
+    options.read(true);
+    #[cfg(target_os = "linux")]
+    std::os::unix::fs::OpenOptionsExt::custom_flags(&mut options, libc::O_NOFOLLOW);
+    let Ok(file) = options.open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file).lines().map_while(|line| line.ok()).collect()
+}
+
+fn write_spool(path: &Path, lines: &[String]) -> Result<()> {
+    let io_err = |err| OverwatchError::from_io(path.display().to_string(), err);
+
+    if let Some(dir) = path.parent() {
+        let mut builder = std::fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder.create(dir).map_err(io_err)?;
+    }
+
+    // keep the newest lines once the spool is full
+    let mut size = 0u64;
+    let start = lines
+        .iter()
+        .rposition(|line| {
+            size += line.len() as u64 + 1;
+            size > MAX_SPOOL_BYTES
+        })
+        .map_or(0, |i| i + 1);
+
+    // a fresh file each time, so a symlink left in its place is never followed
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(io_err(err)),
+        _ => {}
+    }
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path).map_err(io_err)?;
+    for line in &lines[start..] {
+        writeln!(file, "{}", line).map_err(io_err)?;
+    }
+    Ok(())
+}
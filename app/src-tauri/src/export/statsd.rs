@@ -0,0 +1,114 @@
+//! StatsD and DogStatsD gauges over UDP.
+//!
+//! Plain StatsD has no tags, so the host and instance go into the name
+//! (`overwatch.desk.cpu.core.3.usage:12.5|g`) and `--tag` is refused;
+//! DogStatsD keeps the name flat and tags the point instead
+//! (`overwatch.cpu.usage:12.5|g|#host:desk,core:3`).
+
+use super::push::{udp_sender, PushConfig, Pusher};
+use super::{metrics, slug, Exporter, Metric};
+use crate::error::{OverwatchError, Result};
+use crate::snapshot::HostSnapshot;
+
+pub const DEFAULT_PORT: u16 = 8125;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flavor {
+    Statsd,
+    Dogstatsd,
+}
+
+#[derive(Debug, Clone)]
+pub struct StatsdConfig {
+    /// `host[:port]`.
+    pub addr: String,
+    pub flavor: Flavor,
+    pub push: PushConfig,
+}
+
+pub struct StatsdExporter {
+    pusher: Pusher,
+    flavor: Flavor,
+    prefix: String,
+    tags: Vec<(String, String)>,
+}
+
+impl StatsdExporter {
+    pub fn connect(config: StatsdConfig) -> Result<Self> {
+        if config.flavor == Flavor::Statsd && !config.push.tags.is_empty() {
+            return Err(OverwatchError::unsupported("tags with plain StatsD (use --dogstatsd)"));
+        }
+        let addr = if config.addr.contains(':') {
+            config.addr.clone()
+        } else {
+            format!("{}:{}", config.addr, DEFAULT_PORT)
+        };
+        // gauges have no timestamp, replaying old ones later would report them as current
+        let pusher = Pusher::spawn(&config.push, None, udp_sender(&addr)?);
+
+        Ok(StatsdExporter {
+            pusher,
+            flavor: config.flavor,
+            prefix: config.push.prefix,
+            tags: config.push.tags,
+        })
+    }
+}
+
+impl Exporter for StatsdExporter {
+    fn name(&self) -> &'static str {
+        match self.flavor {
+            Flavor::Statsd => "statsd",
+            Flavor::Dogstatsd => "dogstatsd",
+        }
+    }
+
+    fn export(&mut self, snapshot: &HostSnapshot) -> Result<()> {
+        let lines = lines(snapshot, self.flavor, &self.prefix, &self.tags);
+        self.pusher.push(lines)
+    }
+}
+
+pub fn lines(snapshot: &HostSnapshot, flavor: Flavor, prefix: &str, tags: &[(String, String)]) -> Vec<String> {
+    // without tags the host has to be part of the name to tell machines apart
+    let host_prefix = match prefix {
+        "" => slug(&snapshot.host),
+        prefix => format!("{}.{}", prefix, slug(&snapshot.host)),
+    };
+    metrics(snapshot)
+        .iter()
+        .filter(|m| m.value.is_finite())
+        .map(|metric| match flavor {
+            Flavor::Statsd => format!("{}:{}|g", name(&host_prefix, metric, true), metric.value),
+            Flavor::Dogstatsd => {
+                let mut point_tags = vec![format!("host:{}", dog_tag(&snapshot.host))];
+                point_tags.extend(tags.iter().map(|(k, v)| format!("{}:{}", dog_tag(k), dog_tag(v))));
+                if let Some((key, value)) = &metric.instance {
+                    point_tags.push(format!("{}:{}", key, dog_tag(value)));
+                }
+                format!("{}:{}|g|#{}", name(prefix, metric, false), metric.value, point_tags.join(","))
+            }
+        })
+        .collect()
+}
+
+fn name(prefix: &str, metric: &Metric, with_instance: bool) -> String {
+    let mut parts = Vec::new();
+    if !prefix.is_empty() {
+        parts.push(prefix.to_string());
+    }
+    parts.push(metric.group.to_string());
+    if let (true, Some((key, value))) = (with_instance, &metric.instance) {
+        if *key != metric.group {
+            parts.push(key.to_string());
+        }
+        parts.push(slug(value));
+    }
+    parts.push(metric.name.to_string());
+    parts.join(".")
+}
+
+// commas and pipes end a tag, keep everything else readable
+fn dog_tag(text: &str) -> String {
+    text.replace([',', '|', '#'], "_")
+}
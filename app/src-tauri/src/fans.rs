@@ -0,0 +1,601 @@
+//! Fan speeds and user fan curves through hwmon PWM.
+//!
+//! Curves run in a separate `--fan-daemon` process, started through pkexec
+//! when the app can't write the PWM files itself. The app sends it the full
+//! set of curves as a JSON line on stdin; when stdin closes, because the app
+//! quit or died, the daemon hands every fan back to the mode it was in
+//! before, which is automatic on any sane board. It does the same on SIGTERM
+//! or SIGQUIT, and when a panic unwinds past the controller. SIGKILL can't be
+//! caught: fans then stay in manual mode at their last duty until the driver
+//! is reloaded or something else sets them.
+
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use crate::sysfs::{read_attr, read_number};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+pub const HWMON_ROOT: &str = "/sys/class/hwmon";
+/// How often the daemon re-reads temperatures and adjusts the fans.
+pub const TICK: Duration = Duration::from_secs(1);
+// pwmN_enable: 1 is manual; 0 (full speed) and 2+ (automatic variants) are the driver's
+const MANUAL: &str = "1";
+const AUTOMATIC: &str = "2";
+
+// set by SIGTERM and SIGQUIT; the daemon checks it at least every tick
+static STOP: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum FanMode {
+    FullSpeed,
+    Manual,
+    Automatic,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct Fan {
+    /// "hwmon2/pwm1"; what curves refer to.
+    pub id: String,
+    /// The hwmon driver, e.g. "nct6798" or "amdgpu".
+    pub chip: String,
+    pub label: Option<String>,
+    pub rpm: Option<u32>,
+    /// Current PWM output, in percent.
+    pub duty: Option<f32>,
+    pub mode: Option<FanMode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct TempSensor {
+    /// "hwmon1/temp1"; what curves read from.
+    pub id: String,
+    pub chip: String,
+    pub label: Option<String>,
+    pub celsius: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+pub struct CurvePoint {
+    pub celsius: f32,
+    /// PWM output, in percent.
+    pub duty: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct FanCurve {
+    pub fan: String,
+    pub sensor: String,
+    /// Ordered by temperature; the duty is interpolated between points and
+    /// held flat past either end.
+    pub points: Vec<CurvePoint>,
+    /// How far the temperature has to fall before the fan slows down, in °C.
+    pub hysteresis: f32,
+    /// Largest change in duty per tick, in percent.
+    pub max_step: f32,
+}
+
+/// Where a curve's output stands between ticks.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CurveState {
+    /// The temperature the target was last worked out for.
+    pub temperature: Option<f32>,
+    pub duty: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FanStatus {
+    pub fans: Vec<Fan>,
+    pub sensors: Vec<TempSensor>,
+    /// Curves in force; fans without one are left to the hardware.
+    pub curves: Vec<FanCurve>,
+    pub provenance: Provenance,
+}
+
+// "hwmon<N>/<prefix><M>", so an ID can't point anywhere else under /sys
+fn valid_id(id: &str, prefix: &str) -> bool {
+    let digits = |text: &str| !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit());
+    match id.split_once('/') {
+        Some((hwmon, channel)) => {
+            hwmon.strip_prefix("hwmon").is_some_and(digits) && channel.strip_prefix(prefix).is_some_and(digits)
+        }
+        None => false,
+    }
+}
+
+impl FanCurve {
+    /// Checks the curve is well-formed; what it points at is checked by whoever applies it.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |why: &str| Err(OverwatchError::backend(format!("fan curve for {}", self.fan), why));
+        if !valid_id(&self.fan, "pwm") {
+            return invalid("not a hwmon PWM channel");
+        }
+        if !valid_id(&self.sensor, "temp") {
+            return invalid("not a hwmon temperature sensor");
+        }
+        if self.points.is_empty() {
+            return invalid("no points");
+        }
+        if self.points.windows(2).any(|pair| pair[1].celsius <= pair[0].celsius) {
+            return invalid("points must go up in temperature");
+        }
+        if self.points.iter().any(|point| !(0.0..=100.0).contains(&point.duty) || !point.celsius.is_finite()) {
+            return invalid("duty must be between 0 and 100%");
+        }
+        if !(0.0..=20.0).contains(&self.hysteresis) {
+            return invalid("hysteresis must be between 0 and 20 °C");
+        }
+        if !(self.max_step > 0.0 && self.max_step <= 100.0) {
+            return invalid("ramp limit must be above 0 and at most 100%");
+        }
+        Ok(())
+    }
+
+    /// The duty the curve asks for at `celsius`.
+    pub fn duty_at(&self, celsius: f32) -> f32 {
+        let first = self.points[0];
+        let last = self.points[self.points.len() - 1];
+        if celsius <= first.celsius {
+            return first.duty;
+        }
+        if celsius >= last.celsius {
+            return last.duty;
+        }
+        let pair = self.points.windows(2).find(|pair| celsius <= pair[1].celsius).unwrap();
+        let (low, high) = (pair[0], pair[1]);
+        low.duty + (high.duty - low.duty) * (celsius - low.celsius) / (high.celsius - low.celsius)
+    }
+}
+
+impl CurveState {
+    /// Moves one tick towards the curve's target for `celsius` and returns
+    /// the duty to write. Rising temperatures are followed straight away,
+    /// falling ones only once they've dropped by the hysteresis.
+    pub fn step(&mut self, curve: &FanCurve, celsius: f32) -> f32 {
+        let follow = match self.temperature {
+            Some(previous) => celsius > previous || celsius <= previous - curve.hysteresis,
+            None => true,
+        };
+        if follow {
+            self.temperature = Some(celsius);
+        }
+        let target = curve.duty_at(self.temperature.unwrap_or(celsius));
+
+        // the first tick starts from the target, there's nothing to ramp from
+        let duty = match self.duty {
+            Some(current) => current + (target - current).clamp(-curve.max_step, curve.max_step),
+            None => target,
+        };
+        self.duty = Some(duty);
+        duty
+    }
+}
+
+/// Fans and temperature sensors under a `/sys/class/hwmon`-style directory.
+pub fn read_hwmon(root: &Path) -> Result<(Vec<Fan>, Vec<TempSensor>)> {
+    let entries = std::fs::read_dir(root).map_err(|err| OverwatchError::from_io("hwmon", err))?;
+    let mut chips: Vec<(u32, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            Some((name.strip_prefix("hwmon")?.parse().ok()?, name))
+        })
+        .collect();
+    chips.sort_unstable();
+
+    let mut fans = Vec::new();
+    let mut sensors = Vec::new();
+    for (_, hwmon) in chips {
+        let dir = root.join(&hwmon);
+        let chip = read_attr(&dir.join("name")).unwrap_or_else(|| hwmon.clone());
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut attrs: Vec<String> = entries.flatten().map(|entry| entry.file_name().to_string_lossy().into_owned()).collect();
+        attrs.sort_by_key(|attr| (attr.len(), attr.clone()));
+
+        for attr in &attrs {
+            let number = |name: &str| read_number::<i64>(&dir.join(name));
+            if let Some(channel) = attr.strip_prefix("pwm").filter(|channel| channel.parse::<u32>().is_ok()) {
+                fans.push(Fan {
+                    id: format!("{}/{}", hwmon, attr),
+                    chip: chip.clone(),
+                    label: read_attr(&dir.join(format!("fan{}_label", channel))),
+                    // fanN_input usually pairs with pwmN, not always
+                    rpm: number(&format!("fan{}_input", channel)).map(|rpm| rpm as u32),
+                    duty: number(attr).map(|pwm| pwm as f32 / 255.0 * 100.0),
+                    mode: number(&format!("{}_enable", attr)).map(|mode| match mode {
+                        0 => FanMode::FullSpeed,
+                        1 => FanMode::Manual,
+                        _ => FanMode::Automatic,
+                    }),
+                });
+            }
+            if let Some(channel) = attr.strip_prefix("temp").and_then(|rest| rest.strip_suffix("_input")) {
+                // millidegrees
+                let Some(millis) = number(attr) else {
+                    continue;
+                };
+                sensors.push(TempSensor {
+                    id: format!("{}/temp{}", hwmon, channel),
+                    chip: chip.clone(),
+                    label: read_attr(&dir.join(format!("temp{}_label", channel))),
+                    celsius: millis as f32 / 1000.0,
+                });
+            }
+        }
+    }
+    Ok((fans, sensors))
+}
+
+/// Runs curves against a hwmon tree, remembering what each fan was set to
+/// before it took over. Dropping it hands every fan back.
+pub struct Controller {
+    root: PathBuf,
+    curves: Vec<(FanCurve, CurveState)>,
+    originals: BTreeMap<String, String>,
+}
+
+impl Controller {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Controller {
+            root: root.into(),
+            curves: Vec::new(),
+            originals: BTreeMap::new(),
+        }
+    }
+
+    /// Replaces the curves. Fans left without one go back to their old
+    /// mode; a curve that didn't change keeps its ramp position.
+    pub fn set_curves(&mut self, curves: Vec<FanCurve>) -> Result<()> {
+        for curve in &curves {
+            curve.validate()?;
+            if !self.root.join(&curve.fan).exists() {
+                return Err(OverwatchError::not_found(format!("fan {}", curve.fan)));
+            }
+        }
+
+        let mut previous = std::mem::take(&mut self.curves);
+        self.curves = curves
+            .into_iter()
+            .map(|curve| match previous.iter().position(|(old, _)| *old == curve) {
+                Some(index) => previous.swap_remove(index),
+                None => (curve, CurveState::default()),
+            })
+            .collect();
+        let released: Vec<String> = self
+            .originals
+            .keys()
+            .filter(|fan| !self.curves.iter().any(|(curve, _)| curve.fan == **fan))
+            .cloned()
+            .collect();
+        for fan in released {
+            self.release(&fan);
+        }
+        Ok(())
+    }
+
+    /// One control step: read each curve's sensor and set its fan. A fan
+    /// whose sensor can't be read goes back to automatic until it can.
+    pub fn tick(&mut self) -> Vec<(String, Result<f32>)> {
+        let mut results = Vec::new();
+        for index in 0..self.curves.len() {
+            let fan = self.curves[index].0.fan.clone();
+            let sensor = self.root.join(format!("{}_input", self.curves[index].0.sensor));
+            let result = read_attr(&sensor)
+                .and_then(|millis| millis.parse::<f32>().ok())
+                .ok_or_else(|| OverwatchError::not_found(format!("temperature {}", self.curves[index].0.sensor)))
+                .and_then(|millis| {
+                    let (curve, state) = &mut self.curves[index];
+                    let duty = state.step(curve, millis / 1000.0);
+                    self.drive(&fan, duty).map(|_| duty)
+                });
+            if result.is_err() {
+                self.curves[index].1 = CurveState::default();
+                self.release(&fan);
+            }
+            results.push((fan, result));
+        }
+        results
+    }
+
+    /// Hands every fan back.
+    pub fn release_all(&mut self) {
+        let fans: Vec<String> = self.originals.keys().cloned().collect();
+        for fan in fans {
+            self.release(&fan);
+        }
+    }
+
+    fn drive(&mut self, fan: &str, duty: f32) -> Result<()> {
+        let pwm = self.root.join(fan);
+        let enable = self.root.join(format!("{}_enable", fan));
+        let write = |path: &Path, text: &str| {
+            std::fs::write(path, text).map_err(|err| OverwatchError::from_io(path.display().to_string(), err))
+        };
+        if !self.originals.contains_key(fan) {
+            let original = read_attr(&enable).unwrap_or_else(|| AUTOMATIC.to_string());
+            write(&enable, MANUAL)?;
+            self.originals.insert(fan.to_string(), original);
+        }
+        write(&pwm, &((duty / 100.0 * 255.0).round() as u8).to_string())
+    }
+
+    fn release(&mut self, fan: &str) {
+        let Some(original) = self.originals.remove(fan) else {
+            return;
+        };
+        // manual with nobody driving it is the one state not worth going back to
+        let mode = if original == MANUAL { AUTOMATIC } else { original.as_str() };
+        let enable = self.root.join(format!("{}_enable", fan));
+        if let Err(err) = std::fs::write(&enable, mode) {
+            eprintln!("overwatch fan daemon: couldn't hand {} back: {}", fan, err);
+        }
+    }
+}
+
+impl Drop for Controller {
+    fn drop(&mut self) {
+        self.release_all();
+    }
+}
+
+#[cfg(target_os = "linux")]
+extern "C" fn request_stop(_signal: libc::c_int) {
+    STOP.store(true, Ordering::SeqCst);
+}
+
+/// Entry point for `--fan-daemon`: runs curves read from stdin until it
+/// closes or the daemon is told to stop.
+pub fn daemon_from_args(args: &[String]) -> Result<()> {
+    if let Some(arg) = args.first() {
+        return Err(OverwatchError::unsupported(format!("fan daemon option {}", arg)));
+    }
+    // a terminal's Ctrl-C or hangup reaches the whole process group; the
+    // app going away closes stdin, which is when to let go. Shutdown sends
+    // SIGTERM, which has to let go too rather than kill the process outright.
+    #[cfg(target_os = "linux")]
+    unsafe {
+        // SAFETY: SIG_IGN installs no handler code, and request_stop only
+        // stores to an atomic, which is async-signal-safe
+        libc::signal(libc::SIGINT, libc::SIG_IGN);
+        libc::signal(libc::SIGHUP, libc::SIG_IGN);
+        let handler = request_stop as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        libc::signal(libc::SIGTERM, handler);
+        libc::signal(libc::SIGQUIT, handler);
+    }
+
+    let (sender, receiver) = std::sync::mpsc::channel::<String>();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    // a panic unwinds through the controller's Drop, which hands the fans back
+    let mut controller = Controller::new(HWMON_ROOT);
+    println!("ready");
+    let mut next_tick = Instant::now();
+    while !STOP.load(Ordering::SeqCst) {
+        match receiver.recv_timeout(next_tick.saturating_duration_since(Instant::now())) {
+            Ok(line) => {
+                let curves = serde_json::from_str(&line).map_err(|err| OverwatchError::backend("fan curves", err));
+                match curves.and_then(|curves| controller.set_curves(curves)) {
+                    Ok(()) => println!("ok"),
+                    Err(err) => println!("{}", serde_json::to_string(&err).unwrap_or_default()),
+                }
+                continue;
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+        for (fan, result) in controller.tick() {
+            if let Err(err) = result {
+                eprintln!("overwatch fan daemon: {}: {}", fan, err);
+            }
+        }
+        next_tick = Instant::now() + TICK;
+    }
+    drop(controller);
+    Ok(())
+}
+
+struct Daemon {
+    privileged: bool,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<std::process::ChildStdout>,
+}
+
+impl Daemon {
+    // straight away if the PWM files are ours to write, otherwise through pkexec
+    fn spawn(privileged: bool) -> Result<Daemon> {
+        let exe = std::env::current_exe().map_err(|err| OverwatchError::from_io("current executable", err))?;
+        let mut command = match privileged {
+            true => {
+                let mut command = Command::new("pkexec");
+                command.arg(exe);
+                command
+            }
+            false => Command::new(exe),
+        };
+        let mut child = command
+            .arg("--fan-daemon")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|err| OverwatchError::from_io("fan daemon", err))?;
+        let stdin = child.stdin.take().unwrap();
+        let mut daemon = Daemon {
+            privileged,
+            stdout: BufReader::new(child.stdout.take().unwrap()),
+            child,
+            stdin,
+        };
+
+        // blocks through the password dialog
+        match daemon.read_line()?.as_str() {
+            "ready" => Ok(daemon),
+            _ => {
+                let status = daemon.child.wait().ok().and_then(|status| status.code());
+                Err(match status {
+                    Some(126) | Some(127) => OverwatchError::permission_denied("fan control"),
+                    _ => OverwatchError::backend("fan daemon", "exited before starting"),
+                })
+            }
+        }
+    }
+
+    fn read_line(&mut self) -> Result<String> {
+        let mut line = String::new();
+        self.stdout.read_line(&mut line).map_err(|err| OverwatchError::from_io("fan daemon", err))?;
+        Ok(line.trim().to_string())
+    }
+
+    fn send(&mut self, curves: &[FanCurve]) -> Result<()> {
+        let line = serde_json::to_string(curves).map_err(|err| OverwatchError::backend("fan curves", err))?;
+        writeln!(self.stdin, "{}", line).map_err(|err| OverwatchError::from_io("fan daemon", err))?;
+        match self.read_line()?.as_str() {
+            "ok" => Ok(()),
+            "" => Err(OverwatchError::backend("fan daemon", "exited")),
+            error => Err(serde_json::from_str(error).unwrap_or_else(|_| OverwatchError::backend("fan daemon", error))),
+        }
+    }
+
+    // closing stdin is the signal to let go of the fans
+    fn stop(self) {
+        let Daemon { mut child, stdin, stdout, .. } = self;
+        drop(stdin);
+        drop(stdout);
+        child.wait().ok();
+    }
+}
+
+/// The curves in force and the daemon running them, kept in Tauri state.
+/// Separate locks, so status reads don't wait on a password dialog.
+#[derive(Default)]
+pub struct FanControl {
+    curves: Mutex<Vec<FanCurve>>,
+    daemon: Mutex<Option<Daemon>>,
+}
+
+impl FanControl {
+    pub fn curves(&self) -> Vec<FanCurve> {
+        self.curves.lock().unwrap().clone()
+    }
+
+    /// Hands the curves to the daemon, starting it if needed; no curves
+    /// stops it, which puts every fan back.
+    pub fn set_curves(&self, curves: Vec<FanCurve>) -> Result<()> {
+        let (fans, sensors) = read_hwmon(Path::new(HWMON_ROOT))?;
+        for curve in &curves {
+            curve.validate()?;
+            if !fans.iter().any(|fan| fan.id == curve.fan) {
+                return Err(OverwatchError::not_found(format!("fan {}", curve.fan)));
+            }
+            if !sensors.iter().any(|sensor| sensor.id == curve.sensor) {
+                return Err(OverwatchError::not_found(format!("temperature sensor {}", curve.sensor)));
+            }
+        }
+
+        let mut slot = self.daemon.lock().unwrap();
+        if curves.is_empty() {
+            if let Some(daemon) = slot.take() {
+                daemon.stop();
+            }
+            self.curves.lock().unwrap().clear();
+            return Ok(());
+        }
+
+        // root only if some fan's files aren't ours to write
+        let writable = curves.iter().all(|curve| {
+            let enable = Path::new(HWMON_ROOT).join(format!("{}_enable", curve.fan));
+            std::fs::OpenOptions::new().write(true).open(enable).is_ok()
+        });
+        // a daemon that died (killed, or pkexec timed out) gets replaced, and
+        // so does one without the rights the new curves need
+        let alive = slot.as_mut().is_some_and(|daemon| matches!(daemon.child.try_wait(), Ok(None)));
+        let mut daemon = match slot.take() {
+            Some(daemon) if alive && (daemon.privileged || writable) => daemon,
+            old => {
+                if let Some(old) = old {
+                    old.stop();
+                }
+                Daemon::spawn(!writable)?
+            }
+        };
+        let result = daemon.send(&curves);
+        *slot = Some(daemon);
+        result?;
+        *self.curves.lock().unwrap() = curves;
+        Ok(())
+    }
+
+    /// Stops the daemon, putting every fan back; for when the app exits.
+    pub fn stop(&self) {
+        if let Some(daemon) = self.daemon.lock().unwrap().take() {
+            daemon.stop();
+        }
+        self.curves.lock().unwrap().clear();
+    }
+
+    pub fn read(&self) -> Result<FanStatus> {
+        let (fans, sensors) = read_platform()?;
+        let mut provenance = Provenance::new();
+        provenance.available("fans", Source::Sysfs);
+        provenance.available("sensors", Source::Sysfs);
+        Ok(FanStatus {
+            fans,
+            sensors,
+            curves: self.curves(),
+            provenance,
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_platform() -> Result<(Vec<Fan>, Vec<TempSensor>)> {
+    read_hwmon(Path::new(HWMON_ROOT))
+}
+
+// Windows has no general fan interface; vendor tools talk to the EC directly
+#[cfg(not(target_os = "linux"))]
+fn read_platform() -> Result<(Vec<Fan>, Vec<TempSensor>)> {
+    Err(OverwatchError::unsupported("fan control"))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_fan_status(session: State<'_, Session>, control: State<'_, FanControl>) -> Result<FanStatus> {
+    session.sample(|| control.read())
+}
+
+// async so the password dialog doesn't hold up the main thread
+#[tauri::command]
+#[specta::specta]
+pub async fn set_fan_curves(session: State<'_, Session>, control: State<'_, FanControl>, curves: Vec<FanCurve>) -> Result<FanStatus> {
+    read_platform()?;
+    if session.is_replaying() {
+        return Err(OverwatchError::unsupported("changing fan curves during a replay"));
+    }
+    control.set_curves(curves)?;
+    session.record_event("fan_curves", &control.curves());
+    control.read()
+}
+
+/// Puts the fans back when the app exits normally; the daemon does the
+/// same on its own if the app dies.
+pub fn on_exit(app: &AppHandle) {
+    app.state::<FanControl>().stop();
+}
@@ -0,0 +1,567 @@
+use crate::devices::pci::{self, PcieLink};
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use sysinfo::{Components, System};
+use tauri::State;
+
+#[cfg(windows)]
+use crate::wmi_query;
+#[cfg(windows)]
+use wmi::Variant;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GpuInfo {
+    pub name: String,
+    pub driver_version: String,
+    pub memory_total: u64,
+    pub memory_used: u64,
+    pub memory_free: u64,
+    pub temperature: f32,
+    pub temp_available: bool,
+    pub power_usage: u32,
+    pub utilization: u32,
+    pub fan_speed: u32,
+    pub gpu_type: String,
+    pub vendor: String,
+    pub architecture: String,
+    pub device_id: String,
+    pub pci_slot: String,
+    /// Negotiated vs maximum link; `warning` is set when it trained low.
+    pub pcie_link: Option<PcieLink>,
+    pub memory_type: String,
+    pub memory_bus_width: u32,
+    pub base_clock: u32,
+    pub boost_clock: u32,
+    pub memory_clock: u32,
+    pub shader_units: u32,
+    pub tmu_count: u32,
+    pub rop_count: u32,
+    pub directx_version: String,
+    pub opengl_version: String,
+    pub vulkan_support: bool,
+    pub provenance: Provenance,
+}
+
+struct GpuAdapter {
+    name: String,
+    driver_version: String,
+    memory_total: u64,
+    device_id: String,
+    /// `domain:bus:device.function`, where the platform gives it directly.
+    pci_address: Option<String>,
+    source: Source,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_gpu_info(session: State<'_, Session>) -> Result<GpuInfo> {
+    session.sample(read_gpu_info)
+}
+
+pub fn read_gpu_info() -> Result<GpuInfo> {
+    let mut provenance = Provenance::new();
+
+    let adapter = match find_adapter()? {
+        Some(adapter) => {
+            provenance.available("name", adapter.source);
+            provenance.available("driver_version", adapter.source);
+            if adapter.memory_total > 0 {
+                provenance.available("memory_total", adapter.source);
+            } else {
+                provenance.missing("memory_total", OverwatchError::not_found("GPU memory size"));
+            }
+            adapter
+        }
+        None => {
+            // no real adapter registered, so assume the CPU's integrated graphics
+            provenance.estimated("name", Source::Sysinfo);
+            provenance.estimated("memory_total", Source::Sysinfo);
+            integrated_adapter()
+        }
+    };
+
+    let gpu_type = get_gpu_type(&adapter.name);
+    let mut gpu_details = get_gpu_details(&adapter.name, &adapter.device_id, &mut provenance);
+    if let Some(address) = &adapter.pci_address {
+        gpu_details.pci_slot = address.clone();
+        provenance.available("pci_slot", adapter.source);
+        provenance.available("device_id", adapter.source);
+    }
+
+    let pcie_link = match gpu_link(&adapter, &gpu_details.device_id) {
+        Ok((link, source)) => {
+            provenance.available("pcie_link", source);
+            Some(link)
+        }
+        Err(err) => {
+            provenance.missing("pcie_link", err);
+            None
+        }
+    };
+
+    let (gpu_temp, temp_found) = match get_gpu_temp() {
+        Ok(temp) => {
+            provenance.available("temperature", Source::Sysinfo);
+            (temp, true)
+        }
+        Err(err) => {
+            provenance.missing("temperature", err);
+            (0.0, false)
+        }
+    };
+
+    // the registry only knows static adapter info, live counters need a vendor API
+    for field in ["memory_used", "memory_free", "power_usage", "utilization", "fan_speed"] {
+        provenance.missing(field, OverwatchError::unsupported(format!("GPU {}", field.replace('_', " "))));
+    }
+
+    Ok(GpuInfo {
+        name: adapter.name,
+        driver_version: adapter.driver_version,
+        memory_total: adapter.memory_total,
+        memory_used: 0,
+        memory_free: 0,
+        temperature: gpu_temp,
+        temp_available: temp_found,
+        power_usage: 0,
+        utilization: 0,
+        fan_speed: 0,
+        gpu_type,
+        vendor: gpu_details.vendor,
+        architecture: gpu_details.architecture,
+        device_id: gpu_details.device_id,
+        pci_slot: gpu_details.pci_slot,
+        pcie_link,
+        memory_type: gpu_details.memory_type,
+        memory_bus_width: gpu_details.memory_bus_width,
+        base_clock: gpu_details.base_clock,
+        boost_clock: gpu_details.boost_clock,
+        memory_clock: gpu_details.memory_clock,
+        shader_units: gpu_details.shader_units,
+        tmu_count: gpu_details.tmu_count,
+        rop_count: gpu_details.rop_count,
+        directx_version: gpu_details.directx_version,
+        opengl_version: gpu_details.opengl_version,
+        vulkan_support: gpu_details.vulkan_support,
+        provenance,
+    })
+}
+
+#[cfg(windows)]
+fn find_adapter() -> Result<Option<GpuAdapter>> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let gpu_key = hklm
+        .open_subkey(r"SYSTEM\CurrentControlSet\Control\Class\{4d36e968-e325-11ce-bfc1-08002be10318}")
+        .map_err(|err| OverwatchError::from_io("GPU registry key", err))?;
+
+    // check adapters
+    for i in 0..10 {
+        let key_name = format!("{:04}", i);
+
+        let Ok(adapter) = gpu_key.open_subkey(&key_name) else {
+            continue;
+        };
+        let Ok(desc) = adapter.get_value::<String, _>("DriverDesc") else {
+            continue;
+        };
+        if desc.is_empty() || desc.contains("Microsoft Basic") {
+            continue;
+        }
+
+        // memory size
+        let memory_total = if let Ok(mem) = adapter.get_value::<u32, _>("HardwareInformation.MemorySize") {
+            mem as u64
+        } else {
+            adapter.get_value::<u64, _>("HardwareInformation.MemorySize").unwrap_or(0)
+        };
+
+        return Ok(Some(GpuAdapter {
+            name: desc,
+            driver_version: adapter.get_value::<String, _>("DriverVersion").unwrap_or_else(|_| "Unknown".to_string()),
+            memory_total,
+            device_id: adapter.get_value::<String, _>("MatchingDeviceId").unwrap_or_else(|_| "Unknown".to_string()),
+            pci_address: None,
+            source: Source::Registry,
+        }));
+    }
+
+    Ok(None)
+}
+
+// DRM cards, preferring one behind a PCIe bridge (discrete) over bus 0 (integrated)
+#[cfg(target_os = "linux")]
+fn find_adapter() -> Result<Option<GpuAdapter>> {
+    let drm = std::path::Path::new("/sys/class/drm");
+    let cards = std::fs::read_dir(drm).map_err(|err| OverwatchError::from_io("DRM devices", err))?;
+
+    // card0, card1, ...; card0-DP-1 and friends are connectors
+    let mut cards: Vec<String> = cards
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with("card") && !name.contains('-'))
+        .collect();
+    cards.sort();
+
+    let mut adapters: Vec<GpuAdapter> = cards
+        .iter()
+        .filter_map(|card| {
+            let device = drm.join(card).join("device");
+            let read = |attr: &str| std::fs::read_to_string(device.join(attr)).ok().map(|text| text.trim().to_string());
+            let hex = |attr: &str| u32::from_str_radix(read(attr)?.trim_start_matches("0x"), 16).ok();
+
+            if hex("class")? >> 16 != 0x03 {
+                return None;
+            }
+            let (vendor, device_id) = (hex("vendor")? as u16, hex("device")? as u16);
+            let address = std::fs::canonicalize(&device).ok()?.file_name()?.to_string_lossy().into_owned();
+            let driver = std::fs::read_link(device.join("driver"))
+                .ok()
+                .and_then(|target| Some(target.file_name()?.to_string_lossy().into_owned()));
+            // out-of-tree modules like nvidia carry a version, in-kernel ones go by the kernel's
+            let driver_version = match driver {
+                Some(driver) => match std::fs::read_to_string(format!("/sys/module/{}/version", driver)) {
+                    Ok(version) => format!("{} {}", driver, version.trim()),
+                    Err(_) => driver,
+                },
+                None => "Unknown".to_string(),
+            };
+
+            Some(GpuAdapter {
+                name: linux_gpu_name(vendor, device_id),
+                driver_version,
+                // only amdgpu exports VRAM size
+                memory_total: read("mem_info_vram_total").and_then(|bytes| bytes.parse().ok()).unwrap_or(0),
+                device_id: format!("PCI\\VEN_{:04X}&DEV_{:04X}", vendor, device_id),
+                pci_address: Some(address),
+                source: Source::Sysfs,
+            })
+        })
+        .collect();
+
+    adapters.sort_by_key(|adapter| adapter.pci_address.as_deref().map_or(true, |address| address.starts_with("0000:00:")));
+    Ok(adapters.into_iter().next())
+}
+
+// "AD102 [GeForce RTX 4090]" reads better as "NVIDIA GeForce RTX 4090"
+#[cfg(target_os = "linux")]
+fn linux_gpu_name(vendor: u16, device: u16) -> String {
+    use crate::devices::ids;
+
+    let vendor_name = match vendor {
+        0x10de => "NVIDIA",
+        0x1002 => "AMD",
+        0x8086 => "Intel",
+        _ => ids::pci().vendor(vendor).unwrap_or("Unknown"),
+    };
+    match ids::pci().device(vendor, device) {
+        Some(name) => {
+            let model = name.rsplit_once('[').and_then(|(_, model)| model.strip_suffix(']')).unwrap_or(name);
+            format!("{} {}", vendor_name, model)
+        }
+        None => format!("{} GPU {:04x}", vendor_name, device),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn find_adapter() -> Result<Option<GpuAdapter>> {
+    Err(OverwatchError::unsupported("GPU enumeration"))
+}
+
+#[cfg(target_os = "linux")]
+fn gpu_link(adapter: &GpuAdapter, _device_id: &str) -> Result<(PcieLink, Source)> {
+    let address = adapter.pci_address.as_ref().ok_or_else(|| OverwatchError::not_found("GPU PCI address"))?;
+    let link = pci::read_link(&std::path::Path::new("/sys/bus/pci/devices").join(address))
+        .ok_or_else(|| OverwatchError::not_found("PCIe link (not a PCIe device)"))?;
+    Ok((link, Source::Sysfs))
+}
+
+// needs the full instance ID, which only WMI's PNPDeviceID gives
+#[cfg(windows)]
+fn gpu_link(_adapter: &GpuAdapter, device_id: &str) -> Result<(PcieLink, Source)> {
+    if !device_id.to_uppercase().starts_with("PCI\\") || device_id.matches('\\').count() < 2 {
+        return Err(OverwatchError::not_found("GPU device instance"));
+    }
+    Ok((pci::link_for_instance(device_id)?, Source::SetupApi))
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn gpu_link(_adapter: &GpuAdapter, _device_id: &str) -> Result<(PcieLink, Source)> {
+    Err(OverwatchError::unsupported("PCIe link info"))
+}
+
+// integrated fallback
+fn integrated_adapter() -> GpuAdapter {
+    let mut sys = System::new();
+    sys.refresh_all();
+
+    let name = match sys.cpus().first().map(|cpu| cpu.brand()) {
+        Some(brand) if brand.contains("Intel") => "Intel Integrated Graphics",
+        Some(brand) if brand.contains("AMD") => "AMD Integrated Graphics",
+        _ => "Integrated Graphics",
+    };
+
+    GpuAdapter {
+        name: name.to_string(),
+        driver_version: "Built-in".to_string(),
+        memory_total: sys.total_memory() / 8,
+        device_id: "Unknown".to_string(),
+        pci_address: None,
+        source: Source::Sysinfo,
+    }
+}
+
+fn get_gpu_temp() -> Result<f32> {
+    let mut components = Components::new();
+    components.refresh();
+
+    for component in &components {
+        let label = component.label().to_lowercase();
+        let temp = component.temperature();
+
+        if (label.contains("gpu") ||
+            label.contains("graphics") ||
+            label.contains("video")) &&
+            temp > 0.0 && temp < 150.0 {
+            return Ok(temp);
+        }
+    }
+
+    Err(OverwatchError::not_found("GPU temperature sensor"))
+}
+
+struct GpuDetails {
+    vendor: String,
+    architecture: String,
+    device_id: String,
+    pci_slot: String,
+    memory_type: String,
+    memory_bus_width: u32,
+    base_clock: u32,
+    boost_clock: u32,
+    memory_clock: u32,
+    shader_units: u32,
+    tmu_count: u32,
+    rop_count: u32,
+    directx_version: String,
+    opengl_version: String,
+    vulkan_support: bool,
+}
+
+// fetch gpu info from WMI
+fn get_gpu_details(gpu_name: &str, device_id: &str, provenance: &mut Provenance) -> GpuDetails {
+    let mut details = GpuDetails {
+        vendor: "Unknown".to_string(),
+        architecture: "Unknown".to_string(),
+        device_id: device_id.to_string(),
+        pci_slot: "Unknown".to_string(),
+        memory_type: "Unknown".to_string(),
+        memory_bus_width: 0,
+        base_clock: 0,
+        boost_clock: 0,
+        memory_clock: 0,
+        shader_units: 0,
+        tmu_count: 0,
+        rop_count: 0,
+        directx_version: "Unknown".to_string(),
+        opengl_version: "Unknown".to_string(),
+        vulkan_support: false,
+    };
+
+    // vendor from name
+    let name_lower = gpu_name.to_lowercase();
+    if name_lower.contains("nvidia") || name_lower.contains("geforce") || name_lower.contains("quadro") || name_lower.contains("rtx") || name_lower.contains("gtx") {
+        details.vendor = "NVIDIA".to_string();
+        details.directx_version = "DirectX 12".to_string();
+        details.opengl_version = "OpenGL 4.6".to_string();
+        details.vulkan_support = true;
+
+        // nvidia specs
+        if name_lower.contains("rtx 40") || name_lower.contains("409") || name_lower.contains("408") || name_lower.contains("407") || name_lower.contains("406") {
+            details.architecture = "Ada Lovelace".to_string();
+            details.memory_type = "GDDR6X".to_string();
+            details.memory_bus_width = if name_lower.contains("4090") { 384 }
+                else if name_lower.contains("4080") { 256 } else { 192 };
+        } else if name_lower.contains("rtx 30") || name_lower.contains("309") || name_lower.contains("308") || name_lower.contains("307") || name_lower.contains("306") {
+            details.architecture = "Ampere".to_string();
+            details.memory_type = if name_lower.contains("3090") || name_lower.contains("3080") { "GDDR6X".to_string() } else { "GDDR6".to_string() };
+            details.memory_bus_width = if name_lower.contains("3090") { 384 }
+                else if name_lower.contains("3080") { 320 } else { 256 };
+        } else if name_lower.contains("rtx 20") || name_lower.contains("208") || name_lower.contains("207") || name_lower.contains("206") {
+            details.architecture = "Turing".to_string();
+            details.memory_type = "GDDR6".to_string();
+            details.memory_bus_width = if name_lower.contains("2080 ti") { 352 }
+                else if name_lower.contains("2080") { 256 } else { 192 };
+        } else if name_lower.contains("gtx 16") || name_lower.contains("166") || name_lower.contains("165") {
+            details.architecture = "Turing".to_string();
+            details.memory_type = "GDDR6".to_string();
+            details.memory_bus_width = 192;
+        } else if name_lower.contains("gtx 10") || name_lower.contains("108") || name_lower.contains("107") || name_lower.contains("106") {
+            details.architecture = "Pascal".to_string();
+            details.memory_type = "GDDR5X".to_string();
+            details.memory_bus_width = if name_lower.contains("1080 ti") { 352 }
+                else if name_lower.contains("1080") { 256 } else { 192 };
+        }
+    } else if name_lower.contains("amd") || name_lower.contains("radeon") || name_lower.contains("rx ") {
+        details.vendor = "AMD".to_string();
+        details.directx_version = "DirectX 12".to_string();
+        details.opengl_version = "OpenGL 4.6".to_string();
+        details.vulkan_support = true;
+
+        // amd specs
+        if name_lower.contains("rx 7") || name_lower.contains("790") || name_lower.contains("780") || name_lower.contains("770") || name_lower.contains("760") {
+            details.architecture = "RDNA 3".to_string();
+            details.memory_type = "GDDR6".to_string();
+            details.memory_bus_width = if name_lower.contains("7900") { 384 }
+                else if name_lower.contains("7800") { 256 } else { 128 };
+        } else if name_lower.contains("rx 6") || name_lower.contains("690") || name_lower.contains("680") || name_lower.contains("670") || name_lower.contains("660") {
+            details.architecture = "RDNA 2".to_string();
+            details.memory_type = "GDDR6".to_string();
+            details.memory_bus_width = if name_lower.contains("6900") || name_lower.contains("6800") { 256 } else { 128 };
+        } else if name_lower.contains("rx 5") || name_lower.contains("570") || name_lower.contains("560") || name_lower.contains("550") {
+            details.architecture = "RDNA".to_string();
+            details.memory_type = "GDDR6".to_string();
+            details.memory_bus_width = if name_lower.contains("5700") { 256 } else { 128 };
+        } else if name_lower.contains("rx 580") || name_lower.contains("rx 570") || name_lower.contains("rx 560") {
+            details.architecture = "Polaris".to_string();
+            details.memory_type = "GDDR5".to_string();
+            details.memory_bus_width = if name_lower.contains("580") { 256 } else { 128 };
+        }
+    } else if name_lower.contains("intel") {
+        details.vendor = "Intel".to_string();
+        details.directx_version = "DirectX 12".to_string();
+        details.opengl_version = "OpenGL 4.5".to_string();
+        details.vulkan_support = true;
+
+        // intel gpu
+        if name_lower.contains("arc") {
+            details.architecture = "Xe-HPG (Alchemist)".to_string();
+            details.memory_type = "GDDR6".to_string();
+            details.memory_bus_width = if name_lower.contains("a770") || name_lower.contains("a750") { 256 } else { 128 };
+        } else if name_lower.contains("iris xe") || name_lower.contains("xe graphics") {
+            details.architecture = "Xe-LP".to_string();
+            details.memory_type = "System RAM".to_string();
+            details.memory_bus_width = 128;
+        } else if name_lower.contains("uhd") || name_lower.contains("hd graphics") {
+            details.architecture = "Gen 9.5/Gen 11".to_string();
+            details.memory_type = "System RAM".to_string();
+            details.memory_bus_width = 64;
+        }
+    }
+
+    let unknown_model = || OverwatchError::not_found(format!("specs for {}", gpu_name));
+    for (field, known) in [
+        ("vendor", details.vendor != "Unknown"),
+        ("directx_version", details.directx_version != "Unknown"),
+        ("opengl_version", details.opengl_version != "Unknown"),
+        ("vulkan_support", details.vendor != "Unknown"),
+        ("architecture", details.architecture != "Unknown"),
+        ("memory_type", details.memory_type != "Unknown"),
+        ("memory_bus_width", details.memory_bus_width > 0),
+    ] {
+        if known {
+            provenance.estimated(field, Source::NameHeuristic);
+        } else {
+            provenance.missing(field, unknown_model());
+        }
+    }
+
+    // nothing reads clocks or unit counts yet
+    for field in ["base_clock", "boost_clock", "memory_clock", "shader_units", "tmu_count", "rop_count"] {
+        provenance.missing(field, OverwatchError::unsupported(format!("GPU {}", field.replace('_', " "))));
+    }
+
+    if details.device_id != "Unknown" {
+        provenance.available("device_id", Source::Registry);
+    }
+
+    match get_gpu_wmi_details(gpu_name, &mut details) {
+        Ok(found_slot) => {
+            if details.device_id != device_id {
+                provenance.available("device_id", Source::Wmi);
+            }
+            if found_slot {
+                provenance.available("pci_slot", Source::Wmi);
+            } else {
+                provenance.missing("pci_slot", OverwatchError::not_found("PCI location"));
+            }
+        }
+        Err(err) => {
+            if details.device_id == "Unknown" {
+                provenance.missing("device_id", err.clone());
+            }
+            provenance.missing("pci_slot", err);
+        }
+    }
+
+    details
+}
+
+// wmi details, returns whether a PCI slot was found
+#[cfg(windows)]
+fn get_gpu_wmi_details(gpu_name: &str, details: &mut GpuDetails) -> Result<bool> {
+    let wmi_con = wmi_query::connect()?;
+
+    // display adapter info
+    let name_prefix = gpu_name.to_lowercase().split_whitespace().take(2).collect::<Vec<_>>().join(" ");
+    for result in wmi_query::query(&wmi_con, "SELECT * FROM Win32_VideoController")? {
+        if let Some(Variant::String(name)) = result.get("Name") {
+            if name.to_lowercase().contains(&name_prefix) {
+                // pci device info
+                if let Some(Variant::String(pci_id)) = result.get("PNPDeviceID") {
+                    details.device_id = pci_id.clone();
+                }
+
+                // DirectX version
+                if let Some(Variant::String(dx_ver)) = result.get("VideoModeDescription") {
+                    if !dx_ver.is_empty() {
+                        details.directx_version = format!("DirectX 12 ({})", dx_ver);
+                    }
+                }
+
+                break;
+            }
+        }
+    }
+
+    // PCI device location
+    for result in wmi_query::query(&wmi_con, "SELECT * FROM Win32_PnPEntity WHERE Name LIKE '%Display%'")? {
+        if let Some(Variant::String(location)) = result.get("LocationInformation") {
+            if location.contains("PCI") {
+                details.pci_slot = location.clone();
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+#[cfg(not(windows))]
+fn get_gpu_wmi_details(_gpu_name: &str, _details: &mut GpuDetails) -> Result<bool> {
+    Err(OverwatchError::unsupported("GPU PCI details"))
+}
+
+fn get_gpu_type(name: &str) -> String {
+    let name_lower = name.to_lowercase();
+
+    if name_lower.contains("intel") {
+        "Integrated (Intel)".to_string()
+    } else if name_lower.contains("amd") || name_lower.contains("radeon") {
+        if name_lower.contains("integrated") || name_lower.contains("apu") {
+            "Integrated (AMD)".to_string()
+        } else {
+            "Discrete (AMD)".to_string()
+        }
+    } else if name_lower.contains("nvidia") || name_lower.contains("geforce") {
+        "Discrete (NVIDIA)".to_string()
+    } else if name_lower.contains("integrated") || name_lower.contains("basic") {
+        "Integrated Graphics".to_string()
+    } else {
+        "Unknown Type".to_string()
+    }
+}
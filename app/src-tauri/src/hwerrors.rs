@@ -0,0 +1,504 @@
+//! Corrected and uncorrected hardware errors: machine checks, EDAC memory
+//! errors per DIMM and PCIe AER per device. Corrected errors are the early
+//! warning for failing RAM and marginal links, so every increase is kept
+//! in a history and raised as an alert.
+
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use crate::snapshot::now_millis;
+use crate::sysfs::{read_attr, read_number};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tauri_specta::Event;
+
+#[cfg(target_os = "linux")]
+use crate::devices::ids;
+#[cfg(target_os = "linux")]
+use crate::interrupts;
+#[cfg(windows)]
+use crate::wmi_query;
+#[cfg(windows)]
+use wmi::Variant;
+
+/// How often the background watch looks at the counters.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Increases kept for the UI.
+pub const MAX_EVENTS: usize = 200;
+// what the kernel prints once per logged machine check, or per batch of them
+#[cfg(target_os = "linux")]
+const MCE_MARKERS: [&str; 3] = ["Machine Check:", "Machine check events logged", "Corrected error"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorSource {
+    MachineCheck,
+    Memory,
+    Pcie,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorSeverity {
+    Corrected,
+    /// Uncorrected but survivable: EDAC UE, AER non-fatal.
+    Uncorrected,
+    Fatal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct ErrorCounter {
+    pub source: ErrorSource,
+    /// "mc0/dimm2", "0000:01:00.0", "CPU" ...
+    pub device: String,
+    /// DIMM slot label, PCI device name, ...
+    pub label: Option<String>,
+    pub severity: ErrorSeverity,
+    /// Since boot.
+    pub count: u64,
+    /// Which kinds of error the count is made of, or the last message.
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct ErrorEvent {
+    /// Unix milliseconds when the increase was seen.
+    pub at: u64,
+    pub source: ErrorSource,
+    pub device: String,
+    pub label: Option<String>,
+    pub severity: ErrorSeverity,
+    pub increase: u64,
+    pub total: u64,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HardwareErrors {
+    pub counters: Vec<ErrorCounter>,
+    /// Oldest first.
+    pub history: Vec<ErrorEvent>,
+    pub provenance: Provenance,
+}
+
+/// Emitted for every counter that went up, including errors from before the
+/// app started, which show up on its first look.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct HardwareErrorAlert(pub ErrorEvent);
+
+/// Memory errors under a `/sys/devices/system/edac/mc`-style directory:
+/// per DIMM (or rank) where the driver knows, per controller otherwise.
+pub fn read_edac(root: &Path) -> Result<Vec<ErrorCounter>> {
+    let entries = std::fs::read_dir(root).map_err(|err| OverwatchError::from_io("EDAC", err))?;
+    let mut controllers: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with("mc") && name[2..].parse::<u32>().is_ok())
+        .collect();
+    controllers.sort_by_key(|name| name[2..].parse::<u32>().unwrap_or_default());
+    if controllers.is_empty() {
+        return Err(OverwatchError::not_found("EDAC memory controllers"));
+    }
+
+    let mut counters = Vec::new();
+    for mc in controllers {
+        let dir = root.join(&mc);
+        let mut dimms: Vec<(u32, String)> = std::fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| {
+                        let name = entry.file_name().to_string_lossy().into_owned();
+                        let number = name.strip_prefix("dimm").or_else(|| name.strip_prefix("rank"))?.parse().ok()?;
+                        Some((number, name))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        dimms.sort_unstable();
+
+        let mut push = |device: String, label: Option<String>, severity, count: Option<u64>| {
+            if let Some(count) = count {
+                counters.push(ErrorCounter { source: ErrorSource::Memory, device, label, severity, count, detail: None });
+            }
+        };
+        if dimms.is_empty() {
+            let label = read_attr(&dir.join("mc_name"));
+            push(mc.clone(), label.clone(), ErrorSeverity::Corrected, read_number(&dir.join("ce_count")));
+            push(mc.clone(), label, ErrorSeverity::Uncorrected, read_number(&dir.join("ue_count")));
+            continue;
+        }
+        for (_, dimm) in dimms {
+            let dimm_dir = dir.join(&dimm);
+            let label = read_attr(&dimm_dir.join("dimm_label")).or_else(|| read_attr(&dimm_dir.join("dimm_location")));
+            let device = format!("{}/{}", mc, dimm);
+            push(device.clone(), label.clone(), ErrorSeverity::Corrected, read_number(&dimm_dir.join("dimm_ce_count")));
+            push(device, label, ErrorSeverity::Uncorrected, read_number(&dimm_dir.join("dimm_ue_count")));
+        }
+        // errors the controller couldn't pin on a DIMM
+        let unattributed = Some("not attributed to a DIMM".to_string());
+        push(mc.clone(), unattributed.clone(), ErrorSeverity::Corrected, read_number(&dir.join("ce_noinfo_count")));
+        push(mc, unattributed, ErrorSeverity::Uncorrected, read_number(&dir.join("ue_noinfo_count")));
+    }
+    Ok(counters)
+}
+
+/// An `aer_dev_*` file: one "Name count" line per error kind and a
+/// TOTAL_ERR_* line. Returns the total and the kinds that have happened.
+pub fn parse_aer(text: &str) -> (u64, Option<String>) {
+    let mut total = None;
+    let mut sum = 0;
+    let mut seen = Vec::new();
+    for line in text.lines() {
+        let Some((name, count)) = line.rsplit_once(' ') else {
+            continue;
+        };
+        let Ok(count) = count.trim().parse::<u64>() else {
+            continue;
+        };
+        if name.starts_with("TOTAL_ERR") {
+            total = Some(count);
+        } else if count > 0 {
+            sum += count;
+            seen.push(format!("{} {}", name.trim(), count));
+        }
+    }
+    let detail = (!seen.is_empty()).then(|| seen.join(", "));
+    (total.unwrap_or(sum), detail)
+}
+
+/// AER counters for every device under a `/sys/bus/pci/devices`-style
+/// directory that reports them; `name` turns vendor and device IDs into a label.
+pub fn read_aer(root: &Path, name: impl Fn(u16, u16) -> Option<String>) -> Result<Vec<ErrorCounter>> {
+    const FILES: [(&str, ErrorSeverity); 3] = [
+        ("aer_dev_correctable", ErrorSeverity::Corrected),
+        ("aer_dev_nonfatal", ErrorSeverity::Uncorrected),
+        ("aer_dev_fatal", ErrorSeverity::Fatal),
+    ];
+
+    let entries = std::fs::read_dir(root).map_err(|err| OverwatchError::from_io("PCI devices", err))?;
+    let mut addresses: Vec<String> = entries.flatten().map(|entry| entry.file_name().to_string_lossy().into_owned()).collect();
+    addresses.sort();
+
+    let mut counters = Vec::new();
+    for address in addresses {
+        let dir = root.join(&address);
+        let hex = |attr: &str| u16::from_str_radix(read_attr(&dir.join(attr))?.trim_start_matches("0x"), 16).ok();
+        let label = hex("vendor").zip(hex("device")).and_then(|(vendor, device)| name(vendor, device));
+        for (file, severity) in FILES {
+            let Some(text) = read_attr(&dir.join(file)) else {
+                continue;
+            };
+            let (count, detail) = parse_aer(&text);
+            counters.push(ErrorCounter {
+                source: ErrorSource::Pcie,
+                device: address.clone(),
+                label: label.clone(),
+                severity,
+                count,
+                detail,
+            });
+        }
+    }
+    if counters.is_empty() {
+        return Err(OverwatchError::not_found("PCIe AER counters"));
+    }
+    Ok(counters)
+}
+
+/// Kernel messages about machine checks out of `journalctl -k --output=json`,
+/// as (Unix milliseconds, message).
+pub fn parse_journal(output: &str) -> Vec<(u64, String)> {
+    #[derive(Deserialize)]
+    struct Entry {
+        #[serde(rename = "MESSAGE")]
+        message: Option<serde_json::Value>,
+        #[serde(rename = "__REALTIME_TIMESTAMP")]
+        timestamp: Option<String>,
+    }
+
+    output
+        .lines()
+        .filter_map(|line| {
+            let entry: Entry = serde_json::from_str(line).ok()?;
+            // non-UTF-8 messages come as byte arrays; MCE reports never are
+            let message = entry.message?.as_str()?.to_string();
+            if !message.contains("[Hardware Error]") {
+                return None;
+            }
+            let micros: u64 = entry.timestamp?.parse().ok()?;
+            Some((micros / 1000, message))
+        })
+        .collect()
+}
+
+/// The cursor `journalctl --show-cursor` prints after the last entry, `None`
+/// when there were no entries.
+pub fn journal_cursor(output: &str) -> Option<&str> {
+    output.lines().rev().find_map(|line| line.strip_prefix("-- cursor: ")).map(str::trim)
+}
+
+/// Keeps the previous counts, kept in Tauri state, so increases can be found.
+#[derive(Default)]
+pub struct HardwareErrorMonitor {
+    state: Mutex<MonitorState>,
+    log: Mutex<LogState>,
+}
+
+#[derive(Default)]
+struct MonitorState {
+    last: Option<Vec<ErrorCounter>>,
+    history: Vec<ErrorEvent>,
+}
+
+// the system log is read from where the last check left off, so the counts
+// from it are kept here and only new entries are added
+#[derive(Default)]
+struct LogState {
+    /// Journal cursor on Linux, time of the newest event on Windows.
+    position: Option<String>,
+    counters: Vec<ErrorCounter>,
+}
+
+impl HardwareErrorMonitor {
+    /// Compares `counters` with the last ones and returns what went up. The
+    /// first call compares against zero, so errors from earlier in this boot
+    /// are reported too. A count that went down (a driver reload) is a new
+    /// baseline, not an error.
+    pub fn update(&self, counters: &[ErrorCounter], at: u64) -> Vec<ErrorEvent> {
+        let mut state = self.state.lock().unwrap();
+        let mut events = Vec::new();
+        for counter in counters {
+            let previous = state.last.as_ref().map_or(0, |last| {
+                last.iter()
+                    .find(|old| old.source == counter.source && old.device == counter.device && old.severity == counter.severity)
+                    .map_or(0, |old| old.count)
+            });
+            if counter.count > previous {
+                events.push(ErrorEvent {
+                    at,
+                    source: counter.source,
+                    device: counter.device.clone(),
+                    label: counter.label.clone(),
+                    severity: counter.severity,
+                    increase: counter.count - previous,
+                    total: counter.count,
+                    detail: counter.detail.clone(),
+                });
+            }
+        }
+        state.last = Some(counters.to_vec());
+        state.history.extend(events.iter().cloned());
+        let excess = state.history.len().saturating_sub(MAX_EVENTS);
+        state.history.drain(..excess);
+        events
+    }
+
+    pub fn history(&self) -> Vec<ErrorEvent> {
+        self.state.lock().unwrap().history.clone()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_journal(log: &mut LogState) -> Result<()> {
+    let mut command = std::process::Command::new("journalctl");
+    command.args(["-k", "-b", "--output=json", "--no-pager", "--show-cursor"]);
+    if let Some(cursor) = &log.position {
+        command.arg(format!("--after-cursor={}", cursor));
+    }
+    let output = command.output().map_err(|err| OverwatchError::from_io("journalctl", err))?;
+    if !output.status.success() {
+        return Err(OverwatchError::backend("journalctl", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    match journal_cursor(&text) {
+        Some(cursor) => log.position = Some(cursor.to_string()),
+        // the kernel always logs something during a boot, so nothing at all
+        // means journalctl left out what this user isn't allowed to read
+        None if log.position.is_none() => return Err(OverwatchError::permission_denied("kernel log")),
+        None => {}
+    }
+
+    if log.counters.is_empty() {
+        log.counters.push(ErrorCounter {
+            source: ErrorSource::MachineCheck,
+            device: "kernel log".to_string(),
+            label: Some("Machine checks reported".to_string()),
+            severity: ErrorSeverity::Corrected,
+            count: 0,
+            detail: None,
+        });
+    }
+    let reports = parse_journal(&text);
+    let counter = &mut log.counters[0];
+    counter.count += reports.iter().filter(|(_, message)| MCE_MARKERS.iter().any(|marker| message.contains(marker))).count() as u64;
+    if let Some((_, message)) = reports.last() {
+        counter.detail = Some(message.clone());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn read_counters(log: &mut LogState) -> Result<(Vec<ErrorCounter>, Provenance)> {
+    let mut provenance = Provenance::new();
+    let mut counters = Vec::new();
+
+    let exceptions = std::fs::read_to_string("/proc/interrupts")
+        .map_err(|err| OverwatchError::from_io("/proc/interrupts", err))
+        .and_then(|text| interrupts::parse_interrupts(&text))
+        .and_then(|(_, sources)| {
+            let mce = sources.into_iter().find(|source| source.id == "MCE");
+            mce.ok_or_else(|| OverwatchError::not_found("machine check exception counter"))
+        });
+    match exceptions {
+        Ok(mce) => {
+            provenance.available("machine_check_exceptions", Source::Procfs);
+            counters.push(ErrorCounter {
+                source: ErrorSource::MachineCheck,
+                device: "CPU".to_string(),
+                label: Some("Machine check exceptions".to_string()),
+                severity: ErrorSeverity::Uncorrected,
+                count: mce.counts.iter().sum(),
+                detail: None,
+            });
+        }
+        Err(err) => provenance.missing("machine_check_exceptions", err),
+    }
+
+    match read_journal(log) {
+        Ok(()) => {
+            provenance.available("machine_check_reports", Source::Journal);
+            counters.extend(log.counters.iter().cloned());
+        }
+        Err(err) => provenance.missing("machine_check_reports", err),
+    }
+
+    // no EDAC driver loaded is the common case on desktops without ECC
+    match read_edac(Path::new("/sys/devices/system/edac/mc")) {
+        Ok(memory) => {
+            provenance.available("memory", Source::Sysfs);
+            counters.extend(memory);
+        }
+        Err(err) => provenance.missing("memory", err),
+    }
+
+    let name = |vendor: u16, device: u16| ids::pci().device(vendor, device).map(str::to_string);
+    match read_aer(Path::new("/sys/bus/pci/devices"), name) {
+        Ok(pcie) => {
+            provenance.available("pcie", Source::Sysfs);
+            counters.extend(pcie);
+        }
+        Err(err) => provenance.missing("pcie", err),
+    }
+
+    Ok((counters, provenance))
+}
+
+// WHEA logs every error it handles to the System event log
+#[cfg(windows)]
+fn read_counters(log: &mut LogState) -> Result<(Vec<ErrorCounter>, Provenance)> {
+    let wmi_con = wmi_query::connect()?;
+    let mut query = String::from(
+        "SELECT EventCode, Message, TimeGenerated FROM Win32_NTLogEvent WHERE Logfile = 'System' AND SourceName = 'Microsoft-Windows-WHEA-Logger'",
+    );
+    if let Some(since) = &log.position {
+        query.push_str(&format!(" AND TimeGenerated > '{}'", since));
+    }
+    let rows = wmi_query::query(&wmi_con, &query)?;
+
+    let mut counters: Vec<ErrorCounter> = Vec::new();
+    for row in &rows {
+        // DMTF datetimes from one machine sort as text
+        if let Some(Variant::String(time)) = row.get("TimeGenerated") {
+            if log.position.as_ref().map_or(true, |newest| time > newest) {
+                log.position = Some(time.clone());
+            }
+        }
+        let code = match row.get("EventCode") {
+            Some(Variant::UI2(code)) => *code as u32,
+            Some(Variant::UI4(code)) => *code,
+            _ => continue,
+        };
+        let (source, severity) = match code {
+            17 => (ErrorSource::Pcie, ErrorSeverity::Corrected),
+            19 => (ErrorSource::MachineCheck, ErrorSeverity::Corrected),
+            47 => (ErrorSource::Memory, ErrorSeverity::Corrected),
+            18 | 20 | 46 => (ErrorSource::MachineCheck, ErrorSeverity::Fatal),
+            _ => continue,
+        };
+        let message = match row.get("Message") {
+            Some(Variant::String(message)) => message.lines().next().map(str::to_string),
+            _ => None,
+        };
+        match counters.iter_mut().find(|counter| counter.source == source && counter.severity == severity) {
+            Some(counter) => counter.count += 1,
+            None => counters.push(ErrorCounter {
+                source,
+                device: "WHEA".to_string(),
+                label: None,
+                severity,
+                count: 1,
+                // the log lists newest first
+                detail: message,
+            }),
+        }
+    }
+    for new in counters {
+        match log.counters.iter_mut().find(|counter| counter.source == new.source && counter.severity == new.severity) {
+            Some(counter) => {
+                counter.count += new.count;
+                counter.detail = new.detail;
+            }
+            None => log.counters.push(new),
+        }
+    }
+    log.counters.sort_by_key(|counter| (counter.source, counter.severity));
+
+    let mut provenance = Provenance::new();
+    provenance.available("machine_check_reports", Source::Wmi);
+    provenance.available("memory", Source::Wmi);
+    provenance.available("pcie", Source::Wmi);
+    Ok((log.counters.clone(), provenance))
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn read_counters(_log: &mut LogState) -> Result<(Vec<ErrorCounter>, Provenance)> {
+    Err(OverwatchError::unsupported("hardware error counters"))
+}
+
+/// Reads the counters, raising an alert for each increase.
+pub fn check(app: &AppHandle) -> Result<HardwareErrors> {
+    let monitor = app.state::<HardwareErrorMonitor>();
+    let session = app.state::<Session>();
+    // held throughout, so the watch and the UI don't both count the same entries
+    let mut log = monitor.log.lock().unwrap();
+    let (counters, provenance) = read_counters(&mut log)?;
+    for event in monitor.update(&counters, now_millis()) {
+        session.record_event(HardwareErrorAlert::NAME, &event);
+        HardwareErrorAlert(event).emit(app).ok();
+    }
+    Ok(HardwareErrors { counters, history: monitor.history(), provenance })
+}
+
+/// Keeps checking in the background, so errors raise alerts whether or
+/// not anything is looking at the counters.
+pub fn watch(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        if !app.state::<Session>().is_replaying() {
+            if let Err(err) = check(&app) {
+                log::warn!("couldn't check hardware error counters: {}", err);
+            }
+        }
+        std::thread::sleep(CHECK_INTERVAL);
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_hardware_errors(app: AppHandle, session: State<'_, Session>) -> Result<HardwareErrors> {
+    session.sample(|| check(&app))
+}
@@ -0,0 +1,326 @@
+//! Interrupt and softirq rates per source and CPU, with IRQ affinity, to
+//! explain a core that's busy without any process to show for it.
+
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use std::time::Duration;
+use tauri::State;
+
+#[cfg(target_os = "linux")]
+use crate::snapshot::LastReading;
+
+#[cfg(windows)]
+use crate::wmi_query;
+#[cfg(windows)]
+use wmi::Variant;
+
+/// Share of a source's interrupts one CPU has to take to count as concentrated.
+pub const CONCENTRATED_SHARE: f32 = 0.9;
+/// Below this many per second nobody cares where a source lands.
+pub const CONCENTRATED_MIN_RATE: f32 = 100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum InterruptKind {
+    /// A numbered IRQ line or MSI vector.
+    Device,
+    /// Per-CPU architecture interrupts: timer, IPIs, NMIs, ...
+    System,
+    Softirq,
+}
+
+/// Running counts of one interrupt source, as the kernel keeps them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterruptCounters {
+    /// "24", "LOC" or "NET_RX".
+    pub id: String,
+    pub kind: InterruptKind,
+    /// The interrupt controller, e.g. "IR-PCI-MSI".
+    pub chip: Option<String>,
+    /// Device names for IRQs, the kernel's description for the rest.
+    pub name: Option<String>,
+    /// One count per column of `cpus`.
+    pub counts: Vec<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct InterruptSource {
+    pub id: String,
+    pub kind: InterruptKind,
+    pub chip: Option<String>,
+    pub name: Option<String>,
+    /// CPUs the IRQ may be delivered to, e.g. "0-7".
+    pub affinity: Option<String>,
+    /// CPUs it's actually delivered to, where the controller says.
+    pub effective_affinity: Option<String>,
+    /// Per second, one per entry of `InterruptStats::cpus`.
+    pub rates: Vec<f32>,
+    pub total: f32,
+    /// The CPU taking nearly all of a busy source.
+    pub concentrated_on: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct InterruptStats {
+    /// The online CPUs the columns refer to.
+    pub cpus: Vec<u32>,
+    /// Busiest first; sources that didn't fire are left out.
+    pub sources: Vec<InterruptSource>,
+    /// Device and system interrupts per second, per CPU.
+    pub cpu_interrupts: Vec<f32>,
+    pub cpu_softirqs: Vec<f32>,
+    pub provenance: Provenance,
+}
+
+// "           CPU0       CPU1       CPU3" -> [0, 1, 3]; offline CPUs have no column
+fn parse_header(line: &str) -> Vec<u32> {
+    line.split_whitespace().filter_map(|cpu| cpu.strip_prefix("CPU")?.parse().ok()).collect()
+}
+
+/// `/proc/interrupts`: a CPU header, then one line per source, e.g.
+/// " 24:   1   0  IR-PCI-MSI 327680-edge  nvme0q0" or
+/// "LOC:  812 907  Local timer interrupts".
+pub fn parse_interrupts(text: &str) -> Result<(Vec<u32>, Vec<InterruptCounters>)> {
+    let mut lines = text.lines();
+    let cpus = lines.next().map(parse_header).unwrap_or_default();
+    if cpus.is_empty() {
+        return Err(OverwatchError::backend("interrupts", "no CPU header"));
+    }
+
+    let mut sources = Vec::new();
+    for line in lines {
+        let Some((id, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let id = id.trim().to_string();
+        let mut tokens = rest.split_whitespace().peekable();
+        let mut counts = Vec::with_capacity(cpus.len());
+        // ERR and MIS carry a single system-wide count
+        while counts.len() < cpus.len() {
+            match tokens.peek().and_then(|token| token.parse::<u64>().ok()) {
+                Some(count) => {
+                    counts.push(count);
+                    tokens.next();
+                }
+                None => break,
+            }
+        }
+        if counts.len() != cpus.len() {
+            continue;
+        }
+        let rest: Vec<&str> = tokens.collect();
+
+        let source = match id.parse::<u32>().is_ok() {
+            // chip, hardware IRQ and trigger, then the devices sharing the line
+            true => InterruptCounters {
+                kind: InterruptKind::Device,
+                chip: rest.first().map(|chip| chip.to_string()),
+                name: Some(rest.iter().skip(2).copied().collect::<Vec<_>>().join(" ")).filter(|name| !name.is_empty()),
+                id,
+                counts,
+            },
+            false => InterruptCounters {
+                kind: InterruptKind::System,
+                chip: None,
+                name: Some(rest.join(" ")).filter(|name| !name.is_empty()),
+                id,
+                counts,
+            },
+        };
+        sources.push(source);
+    }
+    Ok((cpus, sources))
+}
+
+/// `/proc/softirqs`: a CPU header, then "      NET_RX:   12   3456" lines.
+pub fn parse_softirqs(text: &str) -> Result<(Vec<u32>, Vec<InterruptCounters>)> {
+    let mut lines = text.lines();
+    let cpus = lines.next().map(parse_header).unwrap_or_default();
+    if cpus.is_empty() {
+        return Err(OverwatchError::backend("softirqs", "no CPU header"));
+    }
+    let sources = lines
+        .filter_map(|line| {
+            let (id, counts) = line.split_once(':')?;
+            let counts: Vec<u64> = counts.split_whitespace().filter_map(|count| count.parse().ok()).collect();
+            (counts.len() == cpus.len()).then_some(InterruptCounters {
+                id: id.trim().to_string(),
+                kind: InterruptKind::Softirq,
+                chip: None,
+                name: None,
+                counts,
+            })
+        })
+        .collect();
+    Ok((cpus, sources))
+}
+
+/// `/proc/interrupts` and `/proc/softirqs` read together, on the same CPU columns.
+pub struct ProcReading {
+    pub cpus: Vec<u32>,
+    pub counters: Vec<InterruptCounters>,
+}
+
+/// Both files under a `/proc`-style directory.
+pub fn read_procfs(root: &Path) -> Result<ProcReading> {
+    let read = |name: &str| std::fs::read_to_string(root.join(name)).map_err(|err| OverwatchError::from_io(name, err));
+    let (cpus, mut counters) = parse_interrupts(&read("interrupts")?)?;
+    // softirqs lists every possible CPU, so line its columns up with the online ones
+    if let Ok((softirq_cpus, softirqs)) = read("softirqs").and_then(|text| parse_softirqs(&text)) {
+        for mut softirq in softirqs {
+            softirq.counts = cpus
+                .iter()
+                .map(|cpu| softirq_cpus.iter().position(|other| other == cpu).map_or(0, |column| softirq.counts[column]))
+                .collect();
+            counters.push(softirq);
+        }
+    }
+    Ok(ProcReading { cpus, counters })
+}
+
+fn affinity(root: &Path, source: &InterruptCounters, file: &str) -> Option<String> {
+    if source.kind != InterruptKind::Device {
+        return None;
+    }
+    let text = std::fs::read_to_string(root.join("irq").join(&source.id).join(file)).ok()?;
+    Some(text.trim().to_string()).filter(|text| !text.is_empty())
+}
+
+/// Rates between two readings, with affinity looked up under `root`.
+pub fn rates(before: &ProcReading, after: &ProcReading, elapsed: Duration, root: &Path) -> InterruptStats {
+    let seconds = elapsed.as_secs_f32().max(f32::EPSILON);
+    let mut cpu_interrupts = vec![0.0; after.cpus.len()];
+    let mut cpu_softirqs = vec![0.0; after.cpus.len()];
+    let mut sources = Vec::new();
+
+    for counter in &after.counters {
+        let Some(previous) = before.counters.iter().find(|previous| previous.id == counter.id && previous.kind == counter.kind) else {
+            continue;
+        };
+        // a CPU coming online between readings shifts the columns
+        if previous.counts.len() != counter.counts.len() {
+            continue;
+        }
+        let rates: Vec<f32> = counter
+            .counts
+            .iter()
+            .zip(&previous.counts)
+            .map(|(now, then)| now.saturating_sub(*then) as f32 / seconds)
+            .collect();
+        let total: f32 = rates.iter().sum();
+        if total <= 0.0 {
+            continue;
+        }
+
+        let totals = match counter.kind {
+            InterruptKind::Softirq => &mut cpu_softirqs,
+            _ => &mut cpu_interrupts,
+        };
+        for (sum, rate) in totals.iter_mut().zip(&rates) {
+            *sum += rate;
+        }
+
+        // the local timer and friends are per-CPU by nature
+        let (busiest, peak) = rates.iter().enumerate().fold((0, 0.0), |best, (column, rate)| match *rate > best.1 {
+            true => (column, *rate),
+            false => best,
+        });
+        let concentrated_on = (counter.kind != InterruptKind::System
+            && after.cpus.len() > 1
+            && total >= CONCENTRATED_MIN_RATE
+            && peak / total >= CONCENTRATED_SHARE)
+            .then(|| after.cpus[busiest]);
+
+        sources.push(InterruptSource {
+            id: counter.id.clone(),
+            kind: counter.kind,
+            chip: counter.chip.clone(),
+            name: counter.name.clone(),
+            affinity: affinity(root, counter, "smp_affinity_list"),
+            effective_affinity: affinity(root, counter, "effective_affinity_list"),
+            rates,
+            total,
+            concentrated_on,
+        });
+    }
+    sources.sort_by(|a, b| b.total.total_cmp(&a.total));
+
+    InterruptStats {
+        cpus: after.cpus.clone(),
+        sources,
+        cpu_interrupts,
+        cpu_softirqs,
+        provenance: Provenance::new(),
+    }
+}
+
+/// Interrupt rates since the previous call.
+#[derive(Default)]
+pub struct InterruptMonitor(#[cfg(target_os = "linux")] LastReading<ProcReading>);
+
+impl InterruptMonitor {
+    #[cfg(target_os = "linux")]
+    pub fn read(&self) -> Result<InterruptStats> {
+        let root = Path::new("/proc");
+        let mut stats = self.0.advance(|| read_procfs(root), |before, after, elapsed| rates(before, after, elapsed, root))?;
+
+        stats.provenance.available("cpus", Source::Procfs);
+        stats.provenance.available("sources", Source::Procfs);
+        match stats.sources.iter().any(|source| source.kind == InterruptKind::Device && source.affinity.is_some()) {
+            true => stats.provenance.available("affinity", Source::Procfs),
+            false => stats.provenance.missing("affinity", OverwatchError::not_found("IRQ affinity")),
+        }
+        Ok(stats)
+    }
+
+    // Windows only counts interrupts and DPCs per processor, not per source
+    #[cfg(windows)]
+    pub fn read(&self) -> Result<InterruptStats> {
+        let wmi_con = wmi_query::connect()?;
+        let rows = wmi_query::query(
+            &wmi_con,
+            "SELECT Name, InterruptsPersec, DPCsQueuedPersec FROM Win32_PerfFormattedData_Counters_ProcessorInformation",
+        )?;
+        let number = |row: &wmi_query::WmiRow, prop: &str| wmi_query::number(row, prop).unwrap_or(0.0) as f32;
+        let mut cpus: Vec<(u32, f32, f32)> = rows
+            .iter()
+            .filter_map(|row| {
+                let Some(Variant::String(name)) = row.get("Name") else {
+                    return None;
+                };
+                let cpu = wmi_query::processor_number(name)?;
+                Some((cpu, number(row, "InterruptsPersec"), number(row, "DPCsQueuedPersec")))
+            })
+            .collect();
+        if cpus.is_empty() {
+            return Err(OverwatchError::not_found("processor interrupt counters"));
+        }
+        cpus.sort_by_key(|(cpu, _, _)| *cpu);
+
+        let mut provenance = Provenance::new();
+        provenance.available("cpus", Source::Wmi);
+        provenance.missing("sources", OverwatchError::unsupported("per-source interrupt counters"));
+        provenance.missing("affinity", OverwatchError::unsupported("IRQ affinity"));
+        Ok(InterruptStats {
+            cpus: cpus.iter().map(|(cpu, _, _)| *cpu).collect(),
+            sources: Vec::new(),
+            cpu_interrupts: cpus.iter().map(|(_, interrupts, _)| *interrupts).collect(),
+            cpu_softirqs: cpus.iter().map(|(_, _, dpcs)| *dpcs).collect(),
+            provenance,
+        })
+    }
+
+    #[cfg(not(any(target_os = "linux", windows)))]
+    pub fn read(&self) -> Result<InterruptStats> {
+        Err(OverwatchError::unsupported("interrupt counters"))
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_interrupts(session: State<'_, Session>, monitor: State<'_, InterruptMonitor>) -> Result<InterruptStats> {
+    session.sample(|| monitor.read())
+}
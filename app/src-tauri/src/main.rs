@@ -2,5 +2,34 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+  let args: Vec<String> = std::env::args().skip(1).collect();
+
+  // headless mode for machines without a monitor
+  if args.first().map(String::as_str) == Some("--agent") {
+    if let Err(err) = app_lib::remote::agent::run_from_args(&args[1..]) {
+      eprintln!("overwatch agent: {}", err);
+      std::process::exit(1);
+    }
+    return;
+  }
+
+  // pkexec runs this as root to change one power setting
+  if args.first().map(String::as_str) == Some("--power-helper") {
+    if let Err(err) = app_lib::profile::helper_from_args(&args[1..]) {
+      eprintln!("overwatch power helper: {}", err);
+      std::process::exit(1);
+    }
+    return;
+  }
+
+  // runs fan curves, as root if need be, until the app closes its stdin
+  if args.first().map(String::as_str) == Some("--fan-daemon") {
+    if let Err(err) = app_lib::fans::daemon_from_args(&args[1..]) {
+      eprintln!("overwatch fan daemon: {}", err);
+      std::process::exit(1);
+    }
+    return;
+  }
+
   app_lib::run();
 }
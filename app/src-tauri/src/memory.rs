@@ -0,0 +1,187 @@
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use crate::smbios::{self, Structure, Table};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use sysinfo::{MemoryRefreshKind, RefreshKind, System};
+use tauri::State;
+
+/// System RAM and swap, in bytes.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MemoryInfo {
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+}
+
+pub fn read_memory_info() -> Result<MemoryInfo> {
+    let sys = System::new_with_specifics(RefreshKind::new().with_memory(MemoryRefreshKind::everything()));
+    if sys.total_memory() == 0 {
+        return Err(OverwatchError::not_found("memory information"));
+    }
+
+    Ok(MemoryInfo {
+        total: sys.total_memory(),
+        used: sys.used_memory(),
+        available: sys.available_memory(),
+        swap_total: sys.total_swap(),
+        swap_used: sys.used_swap(),
+    })
+}
+
+/// What's in the memory slots, from SMBIOS types 16 and 17.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MemoryLayout {
+    /// Every slot the firmware lists, empty ones included.
+    pub modules: Vec<MemoryModule>,
+    pub total_slots: u32,
+    pub free_slots: u32,
+    /// Sum of the installed modules, in bytes.
+    pub installed: u64,
+    /// Most the board takes across all slots, in bytes.
+    pub max_capacity: Option<u64>,
+    /// What the memory array runs with, e.g. "Multi-bit ECC".
+    pub error_correction: Option<String>,
+    pub provenance: Provenance,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MemoryModule {
+    /// Slot label as printed on the board, e.g. "DIMM_A2".
+    pub slot: String,
+    pub bank: Option<String>,
+    /// Bytes; 0 for an empty slot, `None` when the firmware doesn't say.
+    pub size: Option<u64>,
+    /// "DDR4", "DDR5", ...
+    pub memory_type: Option<String>,
+    pub form_factor: Option<String>,
+    /// What the module is rated for, in MT/s.
+    pub rated_speed: Option<u32>,
+    /// What it's actually running at, in MT/s.
+    pub configured_speed: Option<u32>,
+    pub manufacturer: Option<String>,
+    pub part_number: Option<String>,
+    pub rank: Option<u8>,
+    /// Wider bus than data width, i.e. the module carries ECC bits.
+    pub ecc: Option<bool>,
+    pub configured_voltage_mv: Option<u16>,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_memory_layout(session: State<'_, Session>) -> Result<MemoryLayout> {
+    session.sample(read_memory_layout)
+}
+
+pub fn read_memory_layout() -> Result<MemoryLayout> {
+    let table = smbios::read_table()?;
+    if table.first(smbios::MEMORY_DEVICE).is_none() {
+        return Err(OverwatchError::not_found("memory devices in the SMBIOS table"));
+    }
+    Ok(MemoryLayout::from_smbios(&table))
+}
+
+impl MemoryLayout {
+    pub fn from_smbios(table: &Table) -> MemoryLayout {
+        const SYSTEM_MEMORY: u8 = 0x03;
+
+        let mut provenance = Provenance::new();
+        // boards can list flash or cache arrays too, only system memory counts
+        let arrays: Vec<&Structure> = table
+            .all(smbios::MEMORY_ARRAY)
+            .filter(|array| array.byte(0x05) == Some(SYSTEM_MEMORY))
+            .collect();
+        let foreign: Vec<u16> = table
+            .all(smbios::MEMORY_ARRAY)
+            .filter(|array| array.byte(0x05) != Some(SYSTEM_MEMORY))
+            .map(|array| array.handle)
+            .collect();
+
+        let modules: Vec<MemoryModule> = table
+            .all(smbios::MEMORY_DEVICE)
+            .filter(|device| device.word(0x04).map_or(true, |array| !foreign.contains(&array)))
+            .map(memory_module)
+            .collect();
+
+        let populated = modules.iter().filter(|m| m.size != Some(0)).count() as u32;
+        let listed: u32 = arrays.iter().filter_map(|array| array.word(0x0d)).map(u32::from).sum();
+        let total_slots = listed.max(modules.len() as u32);
+
+        let max_capacity = arrays.iter().map(|array| array_capacity(array)).sum::<Option<u64>>().filter(|_| !arrays.is_empty());
+        let error_correction = arrays
+            .first()
+            .and_then(|array| array.byte(0x06))
+            .and_then(smbios::error_correction_name)
+            .map(str::to_string);
+        for (field, found) in [("max_capacity", max_capacity.is_some()), ("error_correction", error_correction.is_some())] {
+            if found {
+                provenance.available(field, Source::Smbios);
+            } else {
+                provenance.missing(field, OverwatchError::not_found(field.replace('_', " ")));
+            }
+        }
+
+        MemoryLayout {
+            installed: modules.iter().filter_map(|m| m.size).sum(),
+            modules,
+            total_slots,
+            free_slots: total_slots.saturating_sub(populated),
+            max_capacity,
+            error_correction,
+            provenance,
+        }
+    }
+}
+
+// type 17; fields past the length the firmware wrote simply read as None
+fn memory_module(device: &Structure) -> MemoryModule {
+    const KIB: u64 = 1 << 10;
+    const MIB: u64 = 1 << 20;
+
+    let size = match device.word(0x0c) {
+        None | Some(0xffff) => None,
+        Some(0x7fff) => device.dword(0x1c).map(|mib| (mib & 0x7fff_ffff) as u64 * MIB),
+        Some(kib) if kib & 0x8000 != 0 => Some((kib & 0x7fff) as u64 * KIB),
+        Some(mib) => Some(mib as u64 * MIB),
+    };
+    let installed = size != Some(0);
+
+    // 0 is unknown, 0xffff defers to the 32-bit field added in 3.3
+    let speed = |offset, extended| match device.word(offset) {
+        None | Some(0) => None,
+        Some(0xffff) => device.dword(extended).filter(|&speed| speed != 0),
+        Some(speed) => Some(speed as u32),
+    };
+    let ecc = match (device.word(0x08), device.word(0x0a)) {
+        (Some(total), Some(data)) if total != 0xffff && data != 0xffff && data != 0 => Some(total > data),
+        _ => None,
+    };
+
+    MemoryModule {
+        slot: device
+            .string(0x10)
+            .or_else(|| device.string(0x11))
+            .unwrap_or_else(|| format!("Handle {:#06x}", device.handle)),
+        bank: device.string(0x11),
+        size,
+        memory_type: device.byte(0x12).and_then(smbios::memory_type_name).map(str::to_string).filter(|_| installed),
+        form_factor: device.byte(0x0e).and_then(smbios::form_factor_name).map(str::to_string),
+        rated_speed: speed(0x15, 0x54).filter(|_| installed),
+        configured_speed: speed(0x20, 0x58).filter(|_| installed),
+        manufacturer: device.string(0x17).filter(|_| installed),
+        part_number: device.string(0x1a).filter(|_| installed),
+        rank: device.byte(0x1b).map(|attributes| attributes & 0x0f).filter(|&rank| rank != 0 && installed),
+        ecc: ecc.filter(|_| installed),
+        configured_voltage_mv: device.word(0x26).filter(|&mv| mv != 0 && installed),
+    }
+}
+
+// type 16 maximum capacity, in bytes
+fn array_capacity(array: &Structure) -> Option<u64> {
+    match array.dword(0x07)? {
+        0x8000_0000 => array.qword(0x0f),
+        kib => Some(kib as u64 * 1024),
+    }
+}
@@ -0,0 +1,281 @@
+//! Batteries and AC adapters.
+
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use crate::sysfs::read_attr;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use tauri::State;
+
+#[cfg(windows)]
+use crate::wmi_query;
+#[cfg(windows)]
+use wmi::Variant;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PowerInfo {
+    /// Empty on desktops.
+    pub batteries: Vec<Battery>,
+    /// Whether the machine runs off mains power, `None` when nothing says.
+    pub ac_online: Option<bool>,
+    pub provenance: Provenance,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BatteryState {
+    Charging,
+    Discharging,
+    Full,
+    /// Plugged in but held below full, e.g. by a charge limit.
+    NotCharging,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Battery {
+    /// "BAT0" on Linux, the WMI instance name on Windows.
+    pub name: String,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub charge_percent: Option<f32>,
+    pub state: BatteryState,
+    /// Charge or discharge rate, always positive; `state` gives the direction.
+    pub rate_watts: Option<f32>,
+    pub time_to_empty_secs: Option<u64>,
+    pub time_to_full_secs: Option<u64>,
+    pub energy_wh: Option<f32>,
+    /// What it holds when full today.
+    pub energy_full_wh: Option<f32>,
+    /// What it held new.
+    pub energy_full_design_wh: Option<f32>,
+    /// Capacity lost against the design, in percent.
+    pub wear_percent: Option<f32>,
+    pub cycle_count: Option<u32>,
+    /// "Li-ion", "Li-poly", ...
+    pub chemistry: Option<String>,
+}
+
+impl Battery {
+    // fills in what follows from the raw readings
+    fn derived(mut self) -> Self {
+        if let (Some(full), Some(design)) = (self.energy_full_wh, self.energy_full_design_wh) {
+            if design > 0.0 {
+                self.wear_percent = Some(((1.0 - full / design) * 100.0).max(0.0));
+            }
+        }
+        if self.charge_percent.is_none() {
+            if let (Some(now), Some(full)) = (self.energy_wh, self.energy_full_wh.filter(|&full| full > 0.0)) {
+                self.charge_percent = Some((now / full * 100.0).min(100.0));
+            }
+        }
+
+        let seconds_for = |energy: f32| match self.rate_watts {
+            Some(rate) if rate > 0.0 => Some((energy.max(0.0) / rate * 3600.0) as u64),
+            _ => None,
+        };
+        match self.state {
+            BatteryState::Discharging => self.time_to_empty_secs = self.energy_wh.and_then(seconds_for),
+            BatteryState::Charging => {
+                self.time_to_full_secs = self.energy_wh.zip(self.energy_full_wh).and_then(|(now, full)| seconds_for(full - now))
+            }
+            _ => {}
+        }
+        self
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_power_info(session: State<'_, Session>) -> Result<PowerInfo> {
+    session.sample(read_power_info)
+}
+
+pub fn read_power_info() -> Result<PowerInfo> {
+    let (batteries, ac_online, source) = read_platform()?;
+    let mut provenance = Provenance::new();
+    provenance.available("batteries", source);
+    match ac_online {
+        Some(_) => provenance.available("ac_online", source),
+        None => provenance.missing("ac_online", OverwatchError::not_found("AC adapter")),
+    }
+    Ok(PowerInfo { batteries, ac_online, provenance })
+}
+
+#[cfg(target_os = "linux")]
+fn read_platform() -> Result<(Vec<Battery>, Option<bool>, Source)> {
+    let (batteries, ac_online) = read_sysfs(Path::new("/sys/class/power_supply"))?;
+    Ok((batteries, ac_online, Source::Sysfs))
+}
+
+/// System batteries and AC state under a `/sys/class/power_supply`-style
+/// directory. Peripheral batteries (mice, headsets) are left out.
+pub fn read_sysfs(root: &Path) -> Result<(Vec<Battery>, Option<bool>)> {
+    let entries = std::fs::read_dir(root).map_err(|err| OverwatchError::from_io("power supplies", err))?;
+    let mut names: Vec<String> = entries.flatten().map(|entry| entry.file_name().to_string_lossy().into_owned()).collect();
+    names.sort();
+
+    let mut batteries = Vec::new();
+    let mut ac_online = None;
+    for name in names {
+        let dir = root.join(&name);
+        let attr = |attr: &str| read_attr(&dir.join(attr));
+        // sysfs reports micro-units
+        let micro = |name: &str| attr(name).and_then(|value| value.parse::<f64>().ok()).map(|value| value / 1e6);
+        if attr("scope").as_deref() == Some("Device") {
+            continue;
+        }
+
+        match attr("type").as_deref() {
+            Some("Mains") | Some("USB") => {
+                let online = attr("online").map(|online| online == "1");
+                // any adapter online means AC, one offline USB port doesn't mean battery
+                ac_online = match (ac_online, online) {
+                    (Some(true), _) | (_, Some(true)) => Some(true),
+                    (previous, None) => previous,
+                    (_, Some(false)) => Some(false),
+                };
+            }
+            Some("Battery") => {
+                if attr("present").as_deref() == Some("0") {
+                    continue;
+                }
+                // older fuel gauges count charge (µAh) rather than energy (µWh)
+                let volts = micro("voltage_min_design").or_else(|| micro("voltage_now"));
+                let energy = |energy: &str, charge: &str| {
+                    micro(energy).or_else(|| Some(micro(charge)? * volts?)).map(|wh| wh as f32)
+                };
+                let rate = micro("power_now")
+                    .or_else(|| Some(micro("current_now")? * micro("voltage_now")?))
+                    .map(|watts| watts.abs() as f32)
+                    .filter(|&watts| watts > 0.0);
+
+                batteries.push(
+                    Battery {
+                        name: name.clone(),
+                        manufacturer: attr("manufacturer"),
+                        model: attr("model_name"),
+                        charge_percent: attr("capacity").and_then(|capacity| capacity.parse().ok()),
+                        state: match attr("status").as_deref() {
+                            Some("Charging") => BatteryState::Charging,
+                            Some("Discharging") => BatteryState::Discharging,
+                            Some("Full") => BatteryState::Full,
+                            Some("Not charging") => BatteryState::NotCharging,
+                            _ => BatteryState::Unknown,
+                        },
+                        rate_watts: rate,
+                        time_to_empty_secs: None,
+                        time_to_full_secs: None,
+                        energy_wh: energy("energy_now", "charge_now"),
+                        energy_full_wh: energy("energy_full", "charge_full"),
+                        energy_full_design_wh: energy("energy_full_design", "charge_full_design"),
+                        wear_percent: None,
+                        // 0 is what drivers report when the gauge doesn't count
+                        cycle_count: attr("cycle_count").and_then(|count| count.parse().ok()).filter(|&count| count > 0),
+                        chemistry: attr("technology").filter(|technology| technology != "Unknown"),
+                    }
+                    .derived(),
+                );
+            }
+            _ => {}
+        }
+    }
+    Ok((batteries, ac_online))
+}
+
+// the root\WMI battery classes, which unlike Win32_Battery carry capacities and rates
+#[cfg(windows)]
+fn read_platform() -> Result<(Vec<Battery>, Option<bool>, Source)> {
+    use std::collections::HashMap;
+
+    let wmi_con = wmi_query::connect_namespace(r"root\WMI")?;
+    let by_instance = |class: &str| -> HashMap<String, wmi_query::WmiRow> {
+        wmi_query::query(&wmi_con, &format!("SELECT * FROM {}", class))
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|row| match row.get("InstanceName") {
+                Some(Variant::String(instance)) => Some((instance.clone(), row)),
+                _ => None,
+            })
+            .collect()
+    };
+    let number = |row: Option<&wmi_query::WmiRow>, prop: &str| wmi_query::number(row?, prop);
+    let flag = |row: Option<&wmi_query::WmiRow>, prop: &str| match row?.get(prop)? {
+        Variant::Bool(value) => Some(*value),
+        _ => None,
+    };
+    let text = |row: Option<&wmi_query::WmiRow>, prop: &str| match row?.get(prop)? {
+        Variant::String(value) => crate::smbios::meaningful(value).map(str::to_string),
+        _ => None,
+    };
+
+    let status = wmi_query::query(&wmi_con, "SELECT * FROM BatteryStatus")?;
+    let static_data = by_instance("BatteryStaticData");
+    let full_charged = by_instance("BatteryFullChargedCapacity");
+    let cycles = by_instance("BatteryCycleCount");
+
+    let mut ac_online = None;
+    let batteries = status
+        .iter()
+        .filter_map(|row| {
+            let Some(Variant::String(instance)) = row.get("InstanceName") else {
+                return None;
+            };
+            let row = Some(row);
+            let info = static_data.get(instance);
+            ac_online = ac_online.or(flag(row, "PowerOnline"));
+            // capacities are in mWh and rates in mW
+            let wh = |row, prop| number(row, prop).map(|mwh| (mwh / 1000.0) as f32);
+
+            let state = match (flag(row, "Charging"), flag(row, "Discharging"), flag(row, "PowerOnline")) {
+                (Some(true), _, _) => BatteryState::Charging,
+                (_, Some(true), _) => BatteryState::Discharging,
+                // on AC and idle: full, or held back by a charge limit, sorted out below
+                (_, _, Some(true)) => BatteryState::NotCharging,
+                _ => BatteryState::Unknown,
+            };
+            let rate = match state {
+                BatteryState::Charging => wh(row, "ChargeRate"),
+                _ => wh(row, "DischargeRate"),
+            };
+
+            Some(
+                Battery {
+                    name: instance.clone(),
+                    manufacturer: text(info, "ManufactureName"),
+                    model: text(info, "DeviceName"),
+                    charge_percent: None,
+                    state,
+                    rate_watts: rate.map(f32::abs).filter(|&watts| watts > 0.0),
+                    time_to_empty_secs: None,
+                    time_to_full_secs: None,
+                    energy_wh: wh(row, "RemainingCapacity"),
+                    energy_full_wh: wh(full_charged.get(instance), "FullChargedCapacity"),
+                    energy_full_design_wh: wh(info, "DesignedCapacity"),
+                    wear_percent: None,
+                    cycle_count: number(cycles.get(instance), "CycleCount").map(|count| count as u32).filter(|&count| count > 0),
+                    // a four-character code stored little-endian, e.g. "LION"
+                    chemistry: number(info, "Chemistry")
+                        .map(|code| (code as u32).to_le_bytes())
+                        .and_then(|bytes| std::str::from_utf8(&bytes).ok().map(|code| code.trim_end_matches('\0').to_string()))
+                        .filter(|code| !code.is_empty()),
+                }
+                .derived(),
+            )
+        })
+        .map(|mut battery| {
+            if battery.state == BatteryState::NotCharging && battery.charge_percent.is_some_and(|charge| charge >= 99.0) {
+                battery.state = BatteryState::Full;
+            }
+            battery
+        })
+        .collect();
+
+    Ok((batteries, ac_online, Source::Wmi))
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn read_platform() -> Result<(Vec<Battery>, Option<bool>, Source)> {
+    Err(OverwatchError::unsupported("battery monitoring"))
+}
@@ -0,0 +1,446 @@
+//! The process list: CPU and memory from sysinfo, plus what it can't tell
+//! on its own — disk throughput, open descriptors against the limit, TCP
+//! throughput — and the open files and sockets of one process.
+
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use crate::snapshot::LastReading;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, ThreadKind};
+use tauri::State;
+
+// in /proc/net/tcp's "st" column order, from 01
+const TCP_STATES: [&str; 11] = [
+    "established",
+    "syn_sent",
+    "syn_recv",
+    "fin_wait1",
+    "fin_wait2",
+    "time_wait",
+    "close",
+    "close_wait",
+    "last_ack",
+    "listen",
+    "closing",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub parent: Option<u32>,
+    pub name: String,
+    /// Percent of one CPU, like `top`.
+    pub cpu_percent: f32,
+    /// Resident bytes.
+    pub memory: u64,
+    /// Bytes per second that reached storage. None where the counters
+    /// aren't readable, e.g. other users' processes. Windows only counts
+    /// all I/O, network and pipes included, so there it's an estimate.
+    pub disk_read: Option<f64>,
+    pub disk_write: Option<f64>,
+    pub open_files: Option<u64>,
+    /// The soft RLIMIT_NOFILE; None when unlimited or unreadable.
+    pub file_limit: Option<u64>,
+    /// Bytes per second over the process's TCP connections. None when its
+    /// sockets can't be told apart from anyone else's, which is other
+    /// users' processes unless running as root.
+    pub net_sent: Option<f64>,
+    pub net_received: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ProcessList {
+    pub processes: Vec<ProcessInfo>,
+    pub provenance: Provenance,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum FileKind {
+    File,
+    Device,
+    Socket,
+    Pipe,
+    /// eventfd, epoll, inotify and the like.
+    AnonInode,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct SocketInfo {
+    /// "tcp", "tcp6", "udp", "udp6" or "unix".
+    pub protocol: String,
+    /// "127.0.0.1:8080", "[::1]:53", or the path of a Unix socket.
+    pub local: Option<String>,
+    pub remote: Option<String>,
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct OpenFile {
+    pub fd: u32,
+    pub kind: FileKind,
+    /// Where the descriptor points, as the kernel puts it.
+    pub target: String,
+    /// For sockets the kernel still lists in the process's namespace.
+    pub socket: Option<SocketInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ProcessFiles {
+    pub pid: u32,
+    pub files: Vec<OpenFile>,
+    pub file_limit: Option<u64>,
+    pub provenance: Provenance,
+}
+
+/// `/proc/<pid>/io`: bytes read from and written to storage.
+pub fn parse_io(text: &str) -> Option<(u64, u64)> {
+    let field = |name: &str| {
+        text.lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    };
+    Some((field("read_bytes")?, field("write_bytes")?))
+}
+
+/// The soft "Max open files" limit out of `/proc/<pid>/limits`.
+pub fn parse_file_limit(text: &str) -> Option<u64> {
+    let line = text.lines().find(|line| line.starts_with("Max open files"))?;
+    line["Max open files".len()..].split_whitespace().next()?.parse().ok()
+}
+
+/// What an `fd` symlink points at, and the socket inode if it's a socket.
+pub fn classify_fd(target: &str) -> (FileKind, Option<u64>) {
+    let inode = |prefix: &str| target.strip_prefix(prefix)?.strip_suffix(']')?.parse().ok();
+    if let Some(inode) = inode("socket:[") {
+        return (FileKind::Socket, Some(inode));
+    }
+    let kind = if target.starts_with("pipe:[") {
+        FileKind::Pipe
+    } else if target.starts_with("anon_inode:") {
+        FileKind::AnonInode
+    } else if target.starts_with("/dev/") {
+        FileKind::Device
+    } else if target.starts_with('/') {
+        FileKind::File
+    } else {
+        FileKind::Other
+    };
+    (kind, None)
+}
+
+/// "0100007F:0016" or its 32-digit IPv6 form from `/proc/net/tcp*`. The
+/// address is in network order, printed a 32-bit word at a time in host order.
+pub fn decode_address(text: &str) -> Option<String> {
+    let (address, port) = text.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let mut bytes = Vec::with_capacity(16);
+    for word in 0..address.len() / 8 {
+        let word = u32::from_str_radix(address.get(word * 8..word * 8 + 8)?, 16).ok()?;
+        bytes.extend(word.to_ne_bytes());
+    }
+    match bytes.len() {
+        4 => Some(format!("{}:{}", Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]), port)),
+        16 => {
+            let bytes: [u8; 16] = bytes.try_into().ok()?;
+            Some(format!("[{}]:{}", Ipv6Addr::from(bytes), port))
+        }
+        _ => None,
+    }
+}
+
+/// `/proc/net/{tcp,udp}{,6}` as (inode, socket).
+pub fn parse_inet_sockets(text: &str, protocol: &str) -> Vec<(u64, SocketInfo)> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let inode = fields.get(9)?.parse().ok()?;
+            let state = u8::from_str_radix(fields.get(3)?, 16).ok()?;
+            let state = match protocol.starts_with("tcp") {
+                true => TCP_STATES.get(usize::from(state).checked_sub(1)?).map(|state| state.to_string()),
+                // UDP reports 01 once connect()ed and 07 otherwise
+                false => (state == 1).then(|| "connected".to_string()),
+            };
+            let remote = decode_address(fields[2]).filter(|remote| !remote.ends_with(":0"));
+            Some((inode, SocketInfo { protocol: protocol.to_string(), local: decode_address(fields[1]), remote, state }))
+        })
+        .collect()
+}
+
+/// `/proc/net/unix` as (inode, socket).
+pub fn parse_unix_sockets(text: &str) -> Vec<(u64, SocketInfo)> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let inode = fields.get(6)?.parse().ok()?;
+            let state = match (*fields.get(3)?, *fields.get(5)?) {
+                ("00010000", _) => "listen",
+                (_, "03") => "connected",
+                _ => "unconnected",
+            };
+            Some((
+                inode,
+                SocketInfo {
+                    protocol: "unix".to_string(),
+                    local: fields.get(7).map(|path| path.to_string()),
+                    remote: None,
+                    state: Some(state.to_string()),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// The descriptors under a `/proc`-style directory for one process, with
+/// socket details from the process's own view of `net/`.
+pub fn read_files(proc_root: &Path, pid: u32) -> Result<Vec<OpenFile>> {
+    let dir = proc_root.join(pid.to_string());
+    let entries = std::fs::read_dir(dir.join("fd")).map_err(|err| OverwatchError::from_io(format!("open files of {}", pid), err))?;
+
+    let mut sockets = HashMap::new();
+    for protocol in ["tcp", "tcp6", "udp", "udp6"] {
+        if let Ok(text) = std::fs::read_to_string(dir.join("net").join(protocol)) {
+            sockets.extend(parse_inet_sockets(&text, protocol));
+        }
+    }
+    if let Ok(text) = std::fs::read_to_string(dir.join("net/unix")) {
+        sockets.extend(parse_unix_sockets(&text));
+    }
+
+    let mut files: Vec<OpenFile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let fd = entry.file_name().to_str()?.parse().ok()?;
+            // the descriptor may have closed since the listing
+            let target = std::fs::read_link(entry.path()).ok()?.to_string_lossy().into_owned();
+            let (kind, inode) = classify_fd(&target);
+            let socket = inode.and_then(|inode| sockets.get(&inode).cloned());
+            Some(OpenFile { fd, kind, target, socket })
+        })
+        .collect();
+    files.sort_by_key(|file| file.fd);
+    Ok(files)
+}
+
+/// One TCP connection's byte counters from `ss -tinpHOe`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TcpSocket {
+    pub inode: u64,
+    /// The first process holding the socket.
+    pub pid: u32,
+    pub sent: u64,
+    pub received: u64,
+}
+
+/// `ss -tinpHOe` output, one connection per line. Connections with no
+/// visible owner are skipped.
+pub fn parse_ss(output: &str) -> Vec<TcpSocket> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let field = |name: &str| line.split_whitespace().find_map(|field| field.strip_prefix(name)?.parse::<u64>().ok());
+            let pid = line.split_once("pid=")?.1.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()?;
+            Some(TcpSocket {
+                inode: field("ino:")?,
+                pid,
+                // bytes_sent counts retransmissions too
+                sent: field("bytes_acked:").or_else(|| field("bytes_sent:")).unwrap_or(0),
+                received: field("bytes_received:").unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
+/// Bytes per second sent and received per process. Connections that
+/// weren't there before count from the next call, so short-lived ones
+/// are missed.
+pub fn network_rates(before: &[TcpSocket], after: &[TcpSocket], elapsed: Duration) -> HashMap<u32, (f64, f64)> {
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    let previous: HashMap<u64, &TcpSocket> = before.iter().map(|socket| (socket.inode, socket)).collect();
+    let mut rates: HashMap<u32, (f64, f64)> = HashMap::new();
+    for socket in after {
+        let rate = rates.entry(socket.pid).or_default();
+        if let Some(then) = previous.get(&socket.inode) {
+            rate.0 += socket.sent.saturating_sub(then.sent) as f64 / secs;
+            rate.1 += socket.received.saturating_sub(then.received) as f64 / secs;
+        }
+    }
+    rates
+}
+
+#[cfg(target_os = "linux")]
+fn tcp_sockets() -> Result<Vec<TcpSocket>> {
+    let output = std::process::Command::new("ss")
+        .args(["-tinpHOe"])
+        .output()
+        .map_err(|err| OverwatchError::from_io("ss", err))?;
+    if !output.status.success() {
+        return Err(OverwatchError::backend("ss", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(parse_ss(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn tcp_sockets() -> Result<Vec<TcpSocket>> {
+    Err(OverwatchError::unsupported("per-process network accounting"))
+}
+
+#[cfg(target_os = "linux")]
+fn io_totals(pid: u32, _process: &sysinfo::Process) -> Option<(u64, u64)> {
+    parse_io(&std::fs::read_to_string(format!("/proc/{}/io", pid)).ok()?)
+}
+
+// GetProcessIoCounters, through sysinfo; that counts every read and write
+// the process makes, not just the ones that hit a disk
+#[cfg(not(target_os = "linux"))]
+fn io_totals(_pid: u32, process: &sysinfo::Process) -> Option<(u64, u64)> {
+    let usage = process.disk_usage();
+    Some((usage.total_read_bytes, usage.total_written_bytes))
+}
+
+#[cfg(target_os = "linux")]
+fn descriptors(pid: u32) -> (Option<u64>, Option<u64>) {
+    let open = std::fs::read_dir(format!("/proc/{}/fd", pid)).ok().map(|entries| entries.count() as u64);
+    let limit = std::fs::read_to_string(format!("/proc/{}/limits", pid)).ok().and_then(|text| parse_file_limit(&text));
+    (open, limit)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn descriptors(_pid: u32) -> (Option<u64>, Option<u64>) {
+    (None, None)
+}
+
+/// Disk totals and TCP sockets at one point, to take rates against the next.
+struct ProcessReading {
+    io: HashMap<u32, (u64, u64)>,
+    sockets: Result<Vec<TcpSocket>>,
+}
+
+/// The process list with rates since the previous call.
+#[derive(Default)]
+pub struct ProcessMonitor {
+    system: Mutex<System>,
+    last: LastReading<ProcessReading>,
+}
+
+impl ProcessMonitor {
+    fn sample(&self) -> Result<ProcessReading> {
+        let mut system = self.system.lock().unwrap();
+        let kind = ProcessRefreshKind::new().with_cpu().with_memory();
+        #[cfg(not(target_os = "linux"))]
+        let kind = kind.with_disk_usage();
+        system.refresh_processes_specifics(ProcessesToUpdate::All, true, kind);
+        let io = system
+            .processes()
+            .iter()
+            .filter(|(_, process)| process.thread_kind() != Some(ThreadKind::Userland))
+            .filter_map(|(pid, process)| Some((pid.as_u32(), io_totals(pid.as_u32(), process)?)))
+            .collect();
+        Ok(ProcessReading { io, sockets: tcp_sockets() })
+    }
+
+    pub fn read(&self) -> Result<ProcessList> {
+        self.last.advance(|| self.sample(), |before, after, elapsed| self.list(before, after, elapsed))
+    }
+
+    fn list(&self, before: &ProcessReading, after: &ProcessReading, elapsed: Duration) -> ProcessList {
+        let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+        let mut provenance = Provenance::new();
+        provenance.available("processes", Source::Sysinfo);
+        let network = match &after.sockets {
+            Ok(sockets) => {
+                provenance.available("network", Source::Ss);
+                Some(network_rates(before.sockets.as_deref().unwrap_or_default(), sockets, elapsed))
+            }
+            Err(err) => {
+                provenance.missing("network", err.clone());
+                None
+            }
+        };
+        if cfg!(target_os = "linux") {
+            provenance.available("disk", Source::Procfs);
+            provenance.available("open_files", Source::Procfs);
+        } else {
+            provenance.estimated("disk", Source::Sysinfo);
+            provenance.missing("open_files", OverwatchError::unsupported("open file counts"));
+        }
+
+        let system = self.system.lock().unwrap();
+        let mut processes: Vec<ProcessInfo> = system
+            .processes()
+            .iter()
+            // sysinfo lists every thread of a process on Linux too
+            .filter(|(_, process)| process.thread_kind() != Some(ThreadKind::Userland))
+            .map(|(pid, process)| {
+                let pid = pid.as_u32();
+                let disk = after.io.get(&pid).map(|now| {
+                    let before = before.io.get(&pid).unwrap_or(now);
+                    (now.0.saturating_sub(before.0) as f64 / secs, now.1.saturating_sub(before.1) as f64 / secs)
+                });
+                let (open_files, file_limit) = descriptors(pid);
+                // ss finds owners through /proc/<pid>/fd, so a process whose
+                // descriptors can't be listed has sockets that can't be either
+                let net = network
+                    .as_ref()
+                    .filter(|_| open_files.is_some())
+                    .map(|rates| rates.get(&pid).copied().unwrap_or_default());
+                ProcessInfo {
+                    pid,
+                    parent: process.parent().map(|parent| parent.as_u32()),
+                    name: process.name().to_string_lossy().into_owned(),
+                    cpu_percent: process.cpu_usage(),
+                    memory: process.memory(),
+                    disk_read: disk.map(|(read, _)| read),
+                    disk_write: disk.map(|(_, write)| write),
+                    open_files,
+                    file_limit,
+                    net_sent: net.map(|(sent, _)| sent),
+                    net_received: net.map(|(_, received)| received),
+                }
+            })
+            .collect();
+        processes.sort_by_key(|process| process.pid);
+        ProcessList { processes, provenance }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn process_files(pid: u32) -> Result<ProcessFiles> {
+    let root = Path::new("/proc");
+    let files = read_files(root, pid)?;
+    let file_limit = std::fs::read_to_string(root.join(pid.to_string()).join("limits")).ok().and_then(|text| parse_file_limit(&text));
+    let mut provenance = Provenance::new();
+    provenance.available("files", Source::Procfs);
+    provenance.available("sockets", Source::Procfs);
+    Ok(ProcessFiles { pid, files, file_limit, provenance })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_files(_pid: u32) -> Result<ProcessFiles> {
+    Err(OverwatchError::unsupported("listing open files"))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_processes(session: State<'_, Session>, monitor: State<'_, ProcessMonitor>) -> Result<ProcessList> {
+    session.sample(|| monitor.read())
+}
+
+/// Not recorded: a replay can't answer for whichever PID gets picked.
+#[tauri::command]
+#[specta::specta]
+pub fn get_process_files(session: State<'_, Session>, pid: u32) -> Result<ProcessFiles> {
+    if session.is_replaying() {
+        return Err(OverwatchError::unsupported("listing open files during a replay"));
+    }
+    process_files(pid)
+}
@@ -0,0 +1,576 @@
+//! Changing the power profile: cpufreq governor, energy-performance
+//! preference, turbo and the ACPI platform profile.
+//!
+//! The app writes the sysfs files itself when it may. Otherwise it runs its
+//! own binary as `--power-helper` through pkexec, so polkit decides who gets
+//! to change them, and the helper checks the value again before writing.
+//! The `com.hackclub.overwatch.power-helper` action in `polkit/` covers the
+//! installed binary. Every attempt goes to an audit log, and the helper also
+//! logs each change with the authenticated uid to syslog, where the user
+//! can't edit it. The values from before the first change can be put back
+//! when the app exits, policy by policy when the CPUs didn't all agree.
+
+use crate::cpufreq::{self, FrequencyPolicy};
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use crate::snapshot::now_millis;
+use crate::sysfs::read_attr;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+/// Audit log file name in the app's data directory.
+pub const AUDIT_LOG: &str = "power-audit.jsonl";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum PowerSetting {
+    Governor(String),
+    /// Energy-performance preference.
+    Epp(String),
+    Boost(bool),
+    /// The firmware's profile, e.g. "quiet", "balanced" or "performance".
+    PlatformProfile(String),
+}
+
+impl PowerSetting {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PowerSetting::Governor(_) => "governor",
+            PowerSetting::Epp(_) => "epp",
+            PowerSetting::Boost(_) => "boost",
+            PowerSetting::PlatformProfile(_) => "platform_profile",
+        }
+    }
+
+    /// The helper's command line for this setting on every policy.
+    pub fn to_args(&self) -> [String; 2] {
+        let value = match self {
+            PowerSetting::Governor(value) | PowerSetting::Epp(value) | PowerSetting::PlatformProfile(value) => value.clone(),
+            PowerSetting::Boost(on) => on.to_string(),
+        };
+        [self.kind().to_string(), value]
+    }
+
+    pub fn from_args(kind: &str, value: &str) -> Result<Self> {
+        Ok(match kind {
+            "governor" => PowerSetting::Governor(value.to_string()),
+            "epp" => PowerSetting::Epp(value.to_string()),
+            "boost" => PowerSetting::Boost(value.parse().map_err(|err| OverwatchError::backend("boost", err))?),
+            "platform_profile" => PowerSetting::PlatformProfile(value.to_string()),
+            other => return Err(OverwatchError::unsupported(format!("power setting {}", other))),
+        })
+    }
+}
+
+/// The helper's argument for a set of policy numbers, e.g. `0,2`.
+pub fn policies_to_arg(policies: &[u32]) -> String {
+    policies.iter().map(u32::to_string).collect::<Vec<_>>().join(",")
+}
+
+pub fn policies_from_arg(arg: &str) -> Result<Vec<u32>> {
+    arg.split(',')
+        .map(|number| number.parse().map_err(|err| OverwatchError::backend("cpufreq policy", err)))
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PowerControls {
+    /// Current governor, preference and boost, with the choices.
+    pub policy: Option<FrequencyPolicy>,
+    pub platform_profile: Option<String>,
+    pub available_platform_profiles: Vec<String>,
+    /// What each setting changed during this run was before the first change;
+    /// a governor or preference the CPUs didn't agree on is listed per value.
+    pub originals: Vec<PowerSetting>,
+    pub revert_on_exit: bool,
+    pub provenance: Provenance,
+}
+
+/// One line of the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct AuditEntry {
+    /// Unix milliseconds.
+    pub at: u64,
+    /// Account name of the real uid running the app.
+    pub user: String,
+    /// `None` in entries that predate it.
+    #[serde(default)]
+    pub uid: Option<u32>,
+    pub setting: PowerSetting,
+    /// The cpufreq policies it was limited to; `None` for all of them.
+    #[serde(default)]
+    pub policies: Option<Vec<u32>>,
+    pub previous: Option<PowerSetting>,
+    /// Why it was refused or failed; `None` when it took.
+    pub error: Option<OverwatchError>,
+}
+
+/// Where the settings live; the roots can point at a fake tree.
+#[derive(Debug, Clone)]
+pub struct Knobs {
+    /// `/sys/devices/system/cpu`
+    pub cpu: PathBuf,
+    /// `/sys/firmware/acpi`
+    pub acpi: PathBuf,
+}
+
+impl Default for Knobs {
+    fn default() -> Self {
+        Knobs {
+            cpu: PathBuf::from("/sys/devices/system/cpu"),
+            acpi: PathBuf::from("/sys/firmware/acpi"),
+        }
+    }
+}
+
+impl Knobs {
+    /// The active platform profile and the ones the firmware offers.
+    pub fn platform_profile(&self) -> Result<(String, Vec<String>)> {
+        let read = |name: &str| {
+            std::fs::read_to_string(self.acpi.join(name))
+                .map(|text| text.trim().to_string())
+                .map_err(|err| OverwatchError::from_io("platform profile", err))
+        };
+        let choices = read("platform_profile_choices")?;
+        Ok((read("platform_profile")?, choices.split_whitespace().map(str::to_string).collect()))
+    }
+
+    /// The current value of the same kind of setting.
+    pub fn current(&self, like: &PowerSetting) -> Result<PowerSetting> {
+        let missing = || OverwatchError::not_found(like.kind().replace('_', " "));
+        Ok(match like {
+            PowerSetting::Governor(_) => PowerSetting::Governor(cpufreq::read_sysfs(&self.cpu)?.governor.ok_or_else(missing)?),
+            PowerSetting::Epp(_) => PowerSetting::Epp(cpufreq::read_sysfs(&self.cpu)?.epp.ok_or_else(missing)?),
+            PowerSetting::Boost(_) => PowerSetting::Boost(cpufreq::read_sysfs(&self.cpu)?.boost.ok_or_else(missing)?),
+            PowerSetting::PlatformProfile(_) => PowerSetting::PlatformProfile(self.platform_profile()?.0),
+        })
+    }
+
+    /// The current values of the same kind of setting, each with the
+    /// policies that have it. Per-policy settings the CPUs agree on, and
+    /// the global ones, come back as a single value for all of them.
+    pub fn current_by_policy(&self, like: &PowerSetting) -> Result<Vec<(PowerSetting, Option<Vec<u32>>)>> {
+        let (attr, wrap): (&str, fn(String) -> PowerSetting) = match like {
+            PowerSetting::Governor(_) => ("scaling_governor", PowerSetting::Governor),
+            PowerSetting::Epp(_) => ("energy_performance_preference", PowerSetting::Epp),
+            _ => return Ok(vec![(self.current(like)?, None)]),
+        };
+        let mut groups: Vec<(String, Vec<u32>)> = Vec::new();
+        for (number, dir) in numbered_policies(&self.cpu)? {
+            let value = read_attr(&dir.join(attr)).ok_or_else(|| OverwatchError::not_found(like.kind()))?;
+            match groups.iter_mut().find(|(seen, _)| *seen == value) {
+                Some((_, numbers)) => numbers.push(number),
+                None => groups.push((value, vec![number])),
+            }
+        }
+        Ok(match groups.len() {
+            1 => groups.into_iter().map(|(value, _)| (wrap(value), None)).collect(),
+            _ => groups.into_iter().map(|(value, numbers)| (wrap(value), Some(numbers))).collect(),
+        })
+    }
+
+    /// Checks `setting` against what the hardware offers and works out which
+    /// files get what text.
+    pub fn plan(&self, setting: &PowerSetting) -> Result<Vec<(PathBuf, String)>> {
+        self.plan_on(setting, None)
+    }
+
+    /// Like [`Knobs::plan`], with a governor or preference limited to the
+    /// given policies. They have to exist, and the global settings take none.
+    pub fn plan_on(&self, setting: &PowerSetting, policies: Option<&[u32]>) -> Result<Vec<(PathBuf, String)>> {
+        let choose = |value: &str, available: &[String], what: &str| match available.iter().any(|choice| choice == value) {
+            true => Ok(()),
+            false => Err(OverwatchError::backend(
+                what,
+                format!("\"{}\" isn't one of: {}", value, available.join(", ")),
+            )),
+        };
+        let every_policy = |attr: &str, value: &str| -> Result<Vec<(PathBuf, String)>> {
+            let dirs = numbered_policies(&self.cpu)?;
+            let exists = |number: &u32| dirs.iter().any(|(have, _)| have == number);
+            if let Some(missing) = policies.and_then(|wanted| wanted.iter().find(|number| !exists(number))) {
+                return Err(OverwatchError::not_found(format!("cpufreq policy{}", missing)));
+            }
+            Ok(dirs
+                .into_iter()
+                .filter(|(number, _)| policies.map_or(true, |wanted| wanted.contains(number)))
+                .map(|(_, dir)| (dir.join(attr), value.to_string()))
+                .collect())
+        };
+        if policies.is_some() && !matches!(setting, PowerSetting::Governor(_) | PowerSetting::Epp(_)) {
+            return Err(OverwatchError::unsupported(format!("{} per cpufreq policy", setting.kind().replace('_', " "))));
+        }
+
+        match setting {
+            PowerSetting::Governor(governor) => {
+                choose(governor, &cpufreq::read_sysfs(&self.cpu)?.available_governors, "governor")?;
+                every_policy("scaling_governor", governor)
+            }
+            PowerSetting::Epp(epp) => {
+                choose(epp, &cpufreq::read_sysfs(&self.cpu)?.available_epp, "energy-performance preference")?;
+                every_policy("energy_performance_preference", epp)
+            }
+            PowerSetting::Boost(on) => {
+                let boost = self.cpu.join("cpufreq/boost");
+                let no_turbo = self.cpu.join("intel_pstate/no_turbo");
+                if boost.exists() {
+                    Ok(vec![(boost, if *on { "1" } else { "0" }.to_string())])
+                } else if no_turbo.exists() {
+                    Ok(vec![(no_turbo, if *on { "0" } else { "1" }.to_string())])
+                } else {
+                    Err(OverwatchError::not_found("turbo switch"))
+                }
+            }
+            PowerSetting::PlatformProfile(profile) => {
+                choose(profile, &self.platform_profile()?.1, "platform profile")?;
+                Ok(vec![(self.acpi.join("platform_profile"), profile.clone())])
+            }
+        }
+    }
+
+    /// Validates and writes a setting with this process's own rights.
+    pub fn apply(&self, setting: &PowerSetting) -> Result<()> {
+        self.apply_on(setting, None)
+    }
+
+    pub fn apply_on(&self, setting: &PowerSetting, policies: Option<&[u32]>) -> Result<()> {
+        for (path, text) in self.plan_on(setting, policies)? {
+            std::fs::write(&path, &text).map_err(|err| OverwatchError::from_io(path.display().to_string(), err))?;
+        }
+        Ok(())
+    }
+}
+
+// pkexec asks for a password, then runs the helper as root
+#[cfg(target_os = "linux")]
+fn apply_privileged(setting: &PowerSetting, policies: Option<&[u32]>) -> Result<()> {
+    let exe = std::env::current_exe().map_err(|err| OverwatchError::from_io("current executable", err))?;
+    let [kind, value] = setting.to_args();
+    let output = std::process::Command::new("pkexec")
+        .arg(exe)
+        .args(["--power-helper", &kind, &value])
+        .args(policies.map(policies_to_arg))
+        .output()
+        .map_err(|err| OverwatchError::from_io("pkexec", err))?;
+    match output.status.code() {
+        Some(0) => Ok(()),
+        // pkexec's own codes for a dismissed dialog and a refusal
+        Some(126) | Some(127) => Err(OverwatchError::permission_denied(format!("changing the {}", kind.replace('_', " ")))),
+        _ => Err(OverwatchError::backend("power helper", String::from_utf8_lossy(&output.stderr).trim())),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn apply_privileged(_setting: &PowerSetting, _policies: Option<&[u32]>) -> Result<()> {
+    Err(OverwatchError::unsupported("changing power settings"))
+}
+
+/// Entry point for `--power-helper <kind> <value> [<policies>]`, run as
+/// root by pkexec. It trusts nothing from the caller but the arguments, and
+/// logs every attempt with the uid pkexec authenticated.
+pub fn helper_from_args(args: &[String]) -> Result<()> {
+    let (kind, value, policies) = match args {
+        [kind, value] => (kind, value, None),
+        [kind, value, policies] => (kind, value, Some(policies)),
+        _ => return Err(OverwatchError::not_found("power setting and value")),
+    };
+    let target = policies.map_or(String::new(), |policies| format!(" on policies {}", policies));
+    // pkexec sets it and clears the rest of the environment
+    let caller = match std::env::var("PKEXEC_UID").ok().and_then(|uid| uid.parse::<u32>().ok()) {
+        Some(uid) => format!("uid {} ({})", uid, user_name(uid).unwrap_or_else(|| "unknown".to_string())),
+        None => "a caller outside pkexec".to_string(),
+    };
+    let result = PowerSetting::from_args(kind, value).and_then(|setting| {
+        let policies = policies.map(|policies| policies_from_arg(policies)).transpose()?;
+        Knobs::default().apply_on(&setting, policies.as_deref())
+    });
+    match &result {
+        Ok(()) => syslog(&format!("{} set {} to {}{}", caller, kind, value, target)),
+        Err(err) => syslog(&format!("{} failed to set {} to {}{}: {}", caller, kind, value, target, err)),
+    }
+    result
+}
+
+/// One line to the system log, under the authpriv facility the journal
+/// keeps root-only.
+#[cfg(target_os = "linux")]
+fn syslog(message: &str) {
+    let Ok(message) = std::ffi::CString::new(message) else {
+        return;
+    };
+    // SAFETY: the identifier is static and both strings are NUL-terminated;
+    // "%s" keeps the message from being read as a format
+    unsafe {
+        libc::openlog(c"overwatch-power-helper".as_ptr(), libc::LOG_PID, libc::LOG_AUTHPRIV);
+        libc::syslog(libc::LOG_NOTICE, c"%s".as_ptr(), message.as_ptr());
+        libc::closelog();
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn syslog(_message: &str) {}
+
+/// Appends one entry to the audit log, creating it if needed.
+pub fn append_audit(path: &Path, entry: &AuditEntry) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| OverwatchError::from_io(dir.display().to_string(), err))?;
+    }
+    let line = serde_json::to_string(entry).map_err(|err| OverwatchError::backend("audit entry", err))?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| OverwatchError::from_io("power audit log", err))?;
+    writeln!(file, "{}", line).map_err(|err| OverwatchError::from_io("power audit log", err))
+}
+
+/// The last `limit` entries of the audit log, oldest first.
+pub fn read_audit(path: &Path, limit: usize) -> Result<Vec<AuditEntry>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(OverwatchError::from_io("power audit log", err)),
+    };
+    // skip lines that don't parse rather than losing the whole log
+    let entries: Vec<AuditEntry> = BufReader::new(file)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect();
+    Ok(entries[entries.len().saturating_sub(limit)..].to_vec())
+}
+
+/// The account name for `uid` from the password database.
+#[cfg(target_os = "linux")]
+fn user_name(uid: u32) -> Option<String> {
+    let mut buffer = vec![0 as libc::c_char; 4096];
+    // SAFETY: passwd is plain data that getpwuid_r fills in
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut found = std::ptr::null_mut();
+    // SAFETY: every pointer is valid for the call and the buffer length is its own
+    let status = unsafe { libc::getpwuid_r(uid, &mut passwd, buffer.as_mut_ptr(), buffer.len(), &mut found) };
+    if status != 0 || found.is_null() {
+        return None;
+    }
+    // SAFETY: pw_name points into the buffer, NUL-terminated
+    Some(unsafe { std::ffi::CStr::from_ptr(passwd.pw_name) }.to_string_lossy().into_owned())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn user_name(_uid: u32) -> Option<String> {
+    None
+}
+
+// the real uid rather than $USER, which anything that starts the app can set
+#[cfg(target_os = "linux")]
+fn current_user() -> (String, Option<u32>) {
+    // SAFETY: getuid can't fail
+    let uid = unsafe { libc::getuid() };
+    (user_name(uid).unwrap_or_else(|| uid.to_string()), Some(uid))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn current_user() -> (String, Option<u32>) {
+    (std::env::var("USERNAME").unwrap_or_else(|_| "unknown".to_string()), None)
+}
+
+/// Settings changed this run and whether to undo them on exit, kept in Tauri state.
+#[derive(Default)]
+pub struct PowerControl(Mutex<ControlState>);
+
+#[derive(Default)]
+struct ControlState {
+    /// With the policies each value was on, `None` for all of them.
+    originals: Vec<(PowerSetting, Option<Vec<u32>>)>,
+    revert_on_exit: bool,
+}
+
+impl PowerControl {
+    /// Validates, applies and audits one change, remembering the value it
+    /// replaced the first time a setting is touched.
+    pub fn set(&self, knobs: &Knobs, setting: PowerSetting, audit: &Path) -> Result<AuditEntry> {
+        self.set_on(knobs, setting, None, audit)
+    }
+
+    fn set_on(&self, knobs: &Knobs, setting: PowerSetting, policies: Option<Vec<u32>>, audit: &Path) -> Result<AuditEntry> {
+        let before = knobs.current_by_policy(&setting).ok();
+        let previous = before.as_ref().and_then(|before| {
+            // what the first of the targeted policies had
+            let first = policies.as_ref().map(|policies| policies[0]);
+            before
+                .iter()
+                .find(|(_, on)| first.map_or(true, |first| on.as_ref().map_or(true, |on| on.contains(&first))))
+                .map(|(value, _)| value.clone())
+        });
+        // check before asking for a password
+        let on = policies.as_deref();
+        let result = knobs.plan_on(&setting, on).and_then(|_| match knobs.apply_on(&setting, on) {
+            Err(OverwatchError::PermissionDenied { .. }) => apply_privileged(&setting, on),
+            other => other,
+        });
+
+        let (user, uid) = current_user();
+        let entry = AuditEntry {
+            at: now_millis(),
+            user,
+            uid,
+            setting: setting.clone(),
+            policies: policies.clone(),
+            previous: previous.clone(),
+            error: result.as_ref().err().cloned(),
+        };
+        match &result {
+            Ok(()) => log::info!("power setting {:?} applied, was {:?}", setting, previous),
+            Err(err) => log::warn!("power setting {:?} failed: {}", setting, err),
+        }
+        if let Err(err) = append_audit(audit, &entry) {
+            log::warn!("dropping power audit entry: {}", err);
+        }
+        result?;
+
+        let mut state = self.0.lock().unwrap();
+        if let Some(before) = before {
+            if !state.originals.iter().any(|(original, _)| original.kind() == setting.kind()) {
+                state.originals.extend(before);
+            }
+        }
+        Ok(entry)
+    }
+
+    /// Puts back everything changed this run, newest first. Settings that
+    /// couldn't be restored stay listed; the first error is returned.
+    pub fn revert(&self, knobs: &Knobs, audit: &Path) -> Result<()> {
+        let originals = std::mem::take(&mut self.0.lock().unwrap().originals);
+        let mut first_error = None;
+        let mut left = Vec::new();
+        for (original, policies) in originals.into_iter().rev() {
+            if let Err(err) = self.set_on(knobs, original.clone(), policies.clone(), audit) {
+                first_error.get_or_insert(err);
+                left.insert(0, (original, policies));
+            }
+        }
+
+        // the reverts themselves registered as changes; what's left is what failed
+        self.0.lock().unwrap().originals = left;
+        first_error.map_or(Ok(()), Err)
+    }
+
+    pub fn set_revert_on_exit(&self, enabled: bool) {
+        self.0.lock().unwrap().revert_on_exit = enabled;
+    }
+
+    pub fn originals(&self) -> Vec<PowerSetting> {
+        self.0.lock().unwrap().originals.iter().map(|(original, _)| original.clone()).collect()
+    }
+
+    pub fn read(&self, knobs: &Knobs) -> PowerControls {
+        let mut provenance = Provenance::new();
+        let policy = cpufreq::read_sysfs(&knobs.cpu);
+        provenance.record("policy", Source::Sysfs, &policy);
+        let profile = knobs.platform_profile();
+        provenance.record("platform_profile", Source::Sysfs, &profile);
+        let (platform_profile, available_platform_profiles) = match profile {
+            Ok((profile, choices)) => (Some(profile), choices),
+            Err(_) => (None, Vec::new()),
+        };
+
+        let state = self.0.lock().unwrap();
+        PowerControls {
+            policy: policy.ok(),
+            platform_profile,
+            available_platform_profiles,
+            originals: state.originals.iter().map(|(original, _)| original.clone()).collect(),
+            revert_on_exit: state.revert_on_exit,
+            provenance,
+        }
+    }
+}
+
+fn audit_path(app: &AppHandle) -> Result<PathBuf> {
+    let dir = app.path().app_data_dir().map_err(|err| OverwatchError::backend("app data directory", err))?;
+    Ok(dir.join(AUDIT_LOG))
+}
+
+// policy numbers with their directories
+fn numbered_policies(cpu: &Path) -> Result<Vec<(u32, PathBuf)>> {
+    Ok(cpufreq::policy_dirs(cpu)?
+        .into_iter()
+        .filter_map(|dir| Some((dir.file_name()?.to_str()?.strip_prefix("policy")?.parse().ok()?, dir)))
+        .collect())
+}
+
+/// Called as the app exits; undoes this run's changes if asked to.
+pub fn on_exit(app: &AppHandle) {
+    let control = app.state::<PowerControl>();
+    let revert = control.0.lock().unwrap().revert_on_exit;
+    if !revert || control.originals().is_empty() {
+        return;
+    }
+    let result = audit_path(app).and_then(|audit| control.revert(&Knobs::default(), &audit));
+    if let Err(err) = result {
+        log::warn!("couldn't revert power settings on exit: {}", err);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn supported() -> Result<()> {
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn supported() -> Result<()> {
+    Err(OverwatchError::unsupported("power profile control"))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_power_controls(control: State<'_, PowerControl>) -> Result<PowerControls> {
+    supported()?;
+    Ok(control.read(&Knobs::default()))
+}
+
+// async so the password dialog doesn't hold up the main thread
+#[tauri::command]
+#[specta::specta]
+pub async fn set_power_setting(
+    app: AppHandle,
+    session: State<'_, Session>,
+    control: State<'_, PowerControl>,
+    setting: PowerSetting,
+) -> Result<PowerControls> {
+    supported()?;
+    if session.is_replaying() {
+        return Err(OverwatchError::unsupported("changing power settings during a replay"));
+    }
+    let knobs = Knobs::default();
+    let entry = control.set(&knobs, setting, &audit_path(&app)?)?;
+    session.record_event("power_setting", &entry);
+    Ok(control.read(&knobs))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn revert_power_settings(app: AppHandle, control: State<'_, PowerControl>) -> Result<PowerControls> {
+    supported()?;
+    let knobs = Knobs::default();
+    control.revert(&knobs, &audit_path(&app)?)?;
+    Ok(control.read(&knobs))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_revert_on_exit(control: State<'_, PowerControl>, enabled: bool) -> Result<PowerControls> {
+    supported()?;
+    control.set_revert_on_exit(enabled);
+    Ok(control.read(&Knobs::default()))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_power_audit(app: AppHandle) -> Result<Vec<AuditEntry>> {
+    read_audit(&audit_path(&app)?, 50)
+}
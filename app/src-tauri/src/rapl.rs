@@ -0,0 +1,315 @@
+//! CPU energy counters (Intel RAPL and AMD's equivalent) turned into power
+//! readings and running energy totals.
+
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use crate::snapshot::{now_millis, FIRST_WINDOW};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+/// One energy counter as read from the hardware.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Counter {
+    /// Stable ID to match readings up, e.g. "intel-rapl:0:1".
+    pub zone: String,
+    /// "package-0", "core", "uncore", "dram" or "psys".
+    pub name: String,
+    pub package: u32,
+    pub energy_uj: u64,
+    /// The counter wraps back to 0 past this.
+    pub max_energy_uj: u64,
+}
+
+impl Counter {
+    pub fn is_package(&self) -> bool {
+        self.name.starts_with("package")
+    }
+}
+
+/// Energy between two readings of a counter that wraps at `max`. A gap
+/// long enough to wrap more than once can't be told apart from one that
+/// wraps once.
+pub fn delta_uj(previous: u64, next: u64, max: u64) -> u64 {
+    if next >= previous {
+        next - previous
+    } else {
+        max.saturating_sub(previous) + next
+    }
+}
+
+/// Average power per counter between two readings; zones missing from
+/// either side are left out.
+pub fn watts(before: &[Counter], after: &[Counter], elapsed: Duration) -> Vec<(Counter, f32)> {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return Vec::new();
+    }
+    after
+        .iter()
+        .filter_map(|counter| {
+            let previous = before.iter().find(|previous| previous.zone == counter.zone)?;
+            let joules = delta_uj(previous.energy_uj, counter.energy_uj, counter.max_energy_uj) as f64 / 1e6;
+            Some((counter.clone(), (joules / secs) as f32))
+        })
+        .collect()
+}
+
+/// Every RAPL zone under a `/sys/class/powercap`-style directory.
+pub fn read_powercap(root: &Path) -> Result<Vec<Counter>> {
+    let entries = std::fs::read_dir(root).map_err(|err| OverwatchError::from_io("powercap zones", err))?;
+
+    let mut counters = Vec::new();
+    let mut first_error = None;
+    for entry in entries.flatten() {
+        let zone = entry.file_name().to_string_lossy().into_owned();
+        // "intel-rapl:0" and "intel-rapl:0:1"; intel-rapl-mmio mirrors the package zone
+        let Some(path) = zone.strip_prefix("intel-rapl:") else {
+            continue;
+        };
+        let Some(package) = path.split(':').next().and_then(|package| package.parse().ok()) else {
+            continue;
+        };
+
+        let dir = entry.path();
+        let read = |attr: &str| {
+            std::fs::read_to_string(dir.join(attr))
+                .map(|text| text.trim().to_string())
+                .map_err(|err| OverwatchError::from_io(format!("{}/{}", zone, attr), err))
+        };
+        // energy_uj is root-only on most kernels since the PLATYPUS fix
+        let energy = read("energy_uj").and_then(|energy| energy.parse().map_err(|err| OverwatchError::backend(&zone, err)));
+        match energy {
+            Ok(energy_uj) => counters.push(Counter {
+                name: read("name").unwrap_or_else(|_| zone.clone()),
+                package,
+                energy_uj,
+                max_energy_uj: read("max_energy_range_uj").ok().and_then(|max| max.parse().ok()).unwrap_or(u64::MAX),
+                zone,
+            }),
+            Err(err) => {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    if counters.is_empty() {
+        return Err(first_error.unwrap_or_else(|| OverwatchError::not_found("RAPL powercap zones")));
+    }
+    counters.sort_by(|a, b| a.zone.cmp(&b.zone));
+    Ok(counters)
+}
+
+/// Package counters straight from the MSRs of CPU 0, for kernels without
+/// the powercap driver. Only covers the first socket.
+#[cfg(target_os = "linux")]
+pub fn read_msr(device: &Path) -> Result<Vec<Counter>> {
+    use std::os::unix::fs::FileExt;
+
+    const INTEL_UNITS: u32 = 0x606;
+    const INTEL_DOMAINS: [(&str, u32); 4] = [("package-0", 0x611), ("core", 0x639), ("uncore", 0x641), ("dram", 0x619)];
+    const AMD_UNITS: u32 = 0xc001_0299;
+    const AMD_DOMAINS: [(&str, u32); 1] = [("package-0", 0xc001_029b)];
+
+    let file = std::fs::File::open(device).map_err(|err| OverwatchError::from_io(device.display().to_string(), err))?;
+    // the msr driver takes the register number as the file offset
+    let read = |msr: u32| {
+        let mut bytes = [0u8; 8];
+        file.read_exact_at(&mut bytes, msr as u64).ok().map(|_| u64::from_le_bytes(bytes))
+    };
+
+    // reading a register the CPU doesn't have fails, which tells the vendors apart
+    let (units, domains): (u64, &[(&str, u32)]) = match read(INTEL_UNITS) {
+        Some(units) => (units, &INTEL_DOMAINS),
+        None => (
+            read(AMD_UNITS).ok_or_else(|| OverwatchError::not_found("RAPL energy unit MSR"))?,
+            &AMD_DOMAINS,
+        ),
+    };
+    // bits 12:8 give the energy unit as 1/2^n J
+    let microjoules = 1e6 / (1u64 << ((units >> 8) & 0x1f)) as f64;
+    let to_uj = |count: u64| (count as f64 * microjoules) as u64;
+
+    Ok(domains
+        .iter()
+        .filter_map(|(name, msr)| {
+            // the status registers are 32-bit counters
+            let count = read(*msr)? & 0xffff_ffff;
+            Some(Counter {
+                zone: format!("msr:{:#x}", msr),
+                name: name.to_string(),
+                package: 0,
+                energy_uj: to_uj(count),
+                max_energy_uj: to_uj(1 << 32),
+            })
+        })
+        .collect())
+}
+
+/// Powercap first, the MSRs if that's unavailable.
+#[cfg(target_os = "linux")]
+pub fn read_counters() -> Result<(Vec<Counter>, Source)> {
+    match read_powercap(Path::new("/sys/class/powercap")) {
+        Ok(counters) => Ok((counters, Source::Sysfs)),
+        // the powercap error is the one worth showing, the MSR device needs root too
+        Err(err) => match read_msr(Path::new("/dev/cpu/0/msr")) {
+            Ok(counters) if !counters.is_empty() => Ok((counters, Source::Msr)),
+            _ => Err(err),
+        },
+    }
+}
+
+// Windows only exposes these through a kernel driver
+#[cfg(not(target_os = "linux"))]
+pub fn read_counters() -> Result<(Vec<Counter>, Source)> {
+    Err(OverwatchError::unsupported("CPU energy counters"))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CpuPower {
+    /// Sum of the package domains, in watts.
+    pub package_watts: Option<f32>,
+    pub domains: Vec<DomainPower>,
+    /// Unix milliseconds the energy totals count from: the baseline taken
+    /// when the current recording started, or at the last reset.
+    pub energy_since: u64,
+    pub provenance: Provenance,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DomainPower {
+    /// "package-0", "core", "uncore", "dram" or "psys".
+    pub name: String,
+    pub package: u32,
+    /// Average since the previous reading.
+    pub watts: Option<f32>,
+    /// Energy used since `energy_since`.
+    pub energy_wh: f64,
+}
+
+/// Running energy totals, kept in Tauri state.
+#[derive(Default)]
+pub struct EnergyMeter(Mutex<MeterState>);
+
+/// How often `watch` reads the counters while recording, well inside the
+/// time they take to wrap even at full load.
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Default)]
+struct MeterState {
+    last: Option<(Instant, Vec<Counter>)>,
+    joules: BTreeMap<String, f64>,
+    since: u64,
+    // the recording the totals belong to, `Some(None)` when live
+    recording: Option<Option<u64>>,
+}
+
+impl EnergyMeter {
+    /// Folds a reading into the totals. Whenever `recording` (the current
+    /// recording's start time) changes, the reading becomes a fresh baseline
+    /// instead, so nothing from before the recording counts towards it.
+    pub fn update(&self, counters: Vec<Counter>, at: Instant, recording: Option<u64>) -> Vec<DomainPower> {
+        let mut state = self.0.lock().unwrap();
+        if state.recording != Some(recording) {
+            state.last = None;
+            state.joules.clear();
+            state.since = now_millis();
+            state.recording = Some(recording);
+        }
+
+        let (previous_at, previous) = state.last.take().unwrap_or((at, Vec::new()));
+        let secs = at.saturating_duration_since(previous_at).as_secs_f64();
+
+        let domains = counters
+            .iter()
+            .map(|counter| {
+                let joules = previous
+                    .iter()
+                    .find(|previous| previous.zone == counter.zone)
+                    .map(|previous| delta_uj(previous.energy_uj, counter.energy_uj, counter.max_energy_uj) as f64 / 1e6);
+                let total = state.joules.entry(counter.zone.clone()).or_default();
+                *total += joules.unwrap_or(0.0);
+                DomainPower {
+                    name: counter.name.clone(),
+                    package: counter.package,
+                    watts: joules.filter(|_| secs > 0.0).map(|joules| (joules / secs) as f32),
+                    energy_wh: *total / 3600.0,
+                }
+            })
+            .collect();
+        state.last = Some((at, counters));
+        domains
+    }
+
+    /// Starts the totals over; the next reading is the new baseline.
+    pub fn reset(&self) {
+        let mut state = self.0.lock().unwrap();
+        state.last = None;
+        state.joules.clear();
+        state.since = now_millis();
+    }
+
+    pub fn read(&self, recording: Option<u64>) -> Result<CpuPower> {
+        let (mut counters, source) = read_counters()?;
+        let fresh = {
+            let state = self.0.lock().unwrap();
+            state.last.is_none() || state.recording != Some(recording)
+        };
+        if fresh {
+            self.update(counters, Instant::now(), recording);
+            std::thread::sleep(FIRST_WINDOW);
+            counters = read_counters()?.0;
+        }
+
+        let domains = self.update(counters, Instant::now(), recording);
+        let packages: Vec<Option<f32>> = domains.iter().filter(|domain| domain.name.starts_with("package")).map(|domain| domain.watts).collect();
+        let package_watts = match packages.is_empty() {
+            true => None,
+            false => packages.into_iter().sum(),
+        };
+
+        let mut provenance = Provenance::new();
+        provenance.available("domains", source);
+        match package_watts {
+            Some(_) => provenance.available("package_watts", source),
+            None => provenance.missing("package_watts", OverwatchError::not_found("package energy counter")),
+        }
+        Ok(CpuPower {
+            package_watts,
+            domains,
+            energy_since: self.0.lock().unwrap().since,
+            provenance,
+        })
+    }
+}
+
+/// Keeps reading the counters in the background while recording, so the
+/// recording's energy doesn't depend on the card polling and no counter
+/// wraps twice between two readings.
+pub fn watch(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        if let Some(started) = app.state::<Session>().recording_started_at() {
+            if let Err(err) = app.state::<EnergyMeter>().read(Some(started)) {
+                log::debug!("couldn't read the energy counters: {}", err);
+            }
+        }
+        std::thread::sleep(SAMPLE_INTERVAL);
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_cpu_power(session: State<'_, Session>, meter: State<'_, EnergyMeter>) -> Result<CpuPower> {
+    session.sample(|| meter.read(session.recording_started_at()))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn reset_energy(meter: State<'_, EnergyMeter>) -> Result<()> {
+    meter.reset();
+    Ok(())
+}
@@ -0,0 +1,272 @@
+use super::{read_message, write_message, ClientMessage, ServerMessage, DEFAULT_PORT, PROTOCOL_VERSION};
+use crate::error::{OverwatchError, Result};
+use crate::export::{ExportArgs, ExportConfig, Exporter};
+use crate::snapshot::{hostname, HostSnapshot};
+use std::io::BufReader;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+// connections that haven't sent a valid token yet; more are closed right away
+const MAX_PENDING: usize = 16;
+
+#[derive(Debug, Clone)]
+pub struct AgentConfig {
+    /// `None` runs the agent for its exporters only.
+    pub listen: Option<SocketAddr>,
+    pub token: Option<String>,
+    pub interval: Duration,
+    pub export: ExportConfig,
+}
+
+impl AgentConfig {
+    /// Parses `--listen ADDR --token TOKEN --interval-ms N` plus the exporter
+    /// flags. The token can also come from `OVERWATCH_AGENT_TOKEN` so it
+    /// stays out of `ps`. Without `--listen` the agent only answers on
+    /// loopback, so reaching it from another machine is always a choice.
+    pub fn from_args(args: &[String]) -> Result<Self> {
+        let mut listen = Some(SocketAddr::from(([127, 0, 0, 1], DEFAULT_PORT)));
+        let mut token = std::env::var("OVERWATCH_AGENT_TOKEN").ok();
+        let mut interval = Duration::from_secs(1);
+
+        let mut export = ExportArgs::default();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .cloned()
+                    .ok_or_else(|| OverwatchError::not_found(format!("value for {}", name)))
+            };
+            match arg.as_str() {
+                "--listen" => {
+                    listen = Some(
+                        value("--listen")?
+                            .parse()
+                            .map_err(|err| OverwatchError::backend("--listen", err))?,
+                    );
+                }
+                "--no-listen" => listen = None,
+                "--token" => token = Some(value("--token")?),
+                "--interval-ms" => {
+                    let ms: u64 = value("--interval-ms")?
+                        .parse()
+                        .map_err(|err| OverwatchError::backend("--interval-ms", err))?;
+                    interval = Duration::from_millis(ms.max(100));
+                }
+                other => {
+                    if !export.parse(other, &mut value)? {
+                        return Err(OverwatchError::unsupported(format!("agent option {}", other)));
+                    }
+                }
+            }
+        }
+
+        let token = token.filter(|t| !t.is_empty());
+        if listen.is_some() && token.is_none() {
+            return Err(OverwatchError::not_found("agent token (--token or OVERWATCH_AGENT_TOKEN)"));
+        }
+
+        let export = export.finish()?;
+        if listen.is_none() && export.is_empty() {
+            return Err(OverwatchError::not_found("exporter to run with --no-listen"));
+        }
+
+        Ok(AgentConfig { listen, token, interval, export })
+    }
+}
+
+// latest snapshot plus a sequence number so connections can wait for a newer one
+#[derive(Default)]
+struct Latest {
+    seq: u64,
+    message: Option<Arc<ServerMessage>>,
+}
+
+pub struct Agent {
+    listener: Option<TcpListener>,
+    config: AgentConfig,
+    exporters: Vec<Box<dyn Exporter>>,
+    latest: Arc<(Mutex<Latest>, Condvar)>,
+}
+
+impl Agent {
+    pub fn bind(config: AgentConfig) -> Result<Self> {
+        let listener = match config.listen {
+            Some(addr) => Some(TcpListener::bind(addr).map_err(|err| OverwatchError::from_io(format!("listen on {}", addr), err))?),
+            None => None,
+        };
+
+        let exporters = config.export.connect()?;
+
+        Ok(Agent {
+            listener,
+            config,
+            exporters,
+            latest: Arc::default(),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.listener
+            .as_ref()
+            .ok_or_else(|| OverwatchError::not_found("agent socket"))?
+            .local_addr()
+            .map_err(|err| OverwatchError::from_io("agent socket", err))
+    }
+
+    /// Samples on one thread, hands the result to every exporter and fans it
+    /// out to every connection.
+    pub fn serve(self) -> Result<()> {
+        let latest = self.latest.clone();
+        let interval = self.config.interval;
+        let mut exporters = self.exporters;
+        let sampler = std::thread::spawn(move || {
+            // only log when an exporter's error changes, not once per sample
+            let mut last_errors = vec![None; exporters.len()];
+            loop {
+                let snapshot = HostSnapshot::collect();
+                for (exporter, last_error) in exporters.iter_mut().zip(&mut last_errors) {
+                    let error = exporter.export(&snapshot).err().map(|err| err.to_string());
+                    if let Some(error) = error.as_ref().filter(|e| Some(*e) != last_error.as_ref()) {
+                        log::warn!("{} export failed: {}", exporter.name(), error);
+                    }
+                    *last_error = error;
+                }
+
+                let message = ServerMessage::Snapshot { snapshot: Box::new(snapshot) };
+                let (lock, cvar) = &*latest;
+                let mut guard = lock.lock().unwrap();
+                guard.seq += 1;
+                guard.message = Some(Arc::new(message));
+                drop(guard);
+                cvar.notify_all();
+                std::thread::sleep(interval);
+            }
+        });
+
+        let Some(listener) = self.listener else {
+            sampler.join().ok();
+            return Ok(());
+        };
+
+        let pending = Arc::new(AtomicUsize::new(0));
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            let peer = stream.peer_addr().ok();
+            let Some(handshake) = Handshake::start(&pending) else {
+                log::debug!("agent client {:?} refused: {} handshakes already waiting", peer, MAX_PENDING);
+                continue;
+            };
+            let token = self.config.token.clone().unwrap_or_default();
+            let interval = self.config.interval;
+            let latest = self.latest.clone();
+            std::thread::spawn(move || {
+                if let Err(err) = handle_client(stream, handshake, &token, interval, &latest) {
+                    log::warn!("agent client {:?} disconnected: {}", peer, err);
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+// one slot of MAX_PENDING, given back when dropped
+struct Handshake(Arc<AtomicUsize>);
+
+impl Handshake {
+    fn start(pending: &Arc<AtomicUsize>) -> Option<Self> {
+        pending
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| (count < MAX_PENDING).then_some(count + 1))
+            .ok()?;
+        Some(Handshake(pending.clone()))
+    }
+}
+
+impl Drop for Handshake {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn handle_client(
+    stream: TcpStream,
+    handshake: Handshake,
+    token: &str,
+    interval: Duration,
+    latest: &(Mutex<Latest>, Condvar),
+) -> Result<()> {
+    let io_err = |err| OverwatchError::from_io("agent connection", err);
+
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT)).map_err(io_err)?;
+    stream.set_nodelay(true).map_err(io_err)?;
+    let mut writer = stream.try_clone().map_err(io_err)?;
+    let mut reader = BufReader::new(stream);
+
+    let hello = read_message::<ClientMessage>(&mut reader).map_err(|err| match err {
+        OverwatchError::Timeout { what, .. } => OverwatchError::timeout(what, HANDSHAKE_TIMEOUT),
+        other => other,
+    });
+    let ClientMessage::Hello { token: offered, version } = match hello {
+        Ok(hello) => hello,
+        Err(err) => {
+            write_message(&mut writer, &ServerMessage::Error { error: err.clone() }).ok();
+            return Err(err);
+        }
+    };
+
+    if !constant_time_eq(offered.as_bytes(), token.as_bytes()) {
+        let err = OverwatchError::permission_denied("invalid agent token");
+        write_message(&mut writer, &ServerMessage::Error { error: err.clone() })?;
+        return Err(err);
+    }
+    if version != PROTOCOL_VERSION {
+        let err = OverwatchError::unsupported(format!("protocol version {}", version));
+        write_message(&mut writer, &ServerMessage::Error { error: err.clone() })?;
+        return Err(err);
+    }
+
+    write_message(&mut writer, &ServerMessage::Welcome {
+        host: hostname(),
+        version: PROTOCOL_VERSION,
+        interval_ms: interval.as_millis() as u64,
+    })?;
+    drop(handshake);
+
+    let (lock, cvar) = latest;
+    let mut seen = 0;
+    loop {
+        let message = {
+            let guard = cvar.wait_while(lock.lock().unwrap(), |l| l.seq == seen).unwrap();
+            seen = guard.seq;
+            guard.message.clone()
+        };
+        if let Some(message) = message {
+            write_message(&mut writer, &*message)?;
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Entry point for `app --agent ...`.
+pub fn run_from_args(args: &[String]) -> Result<()> {
+    // exporters log through `log`, which only the app's plugin prints otherwise
+    fern::Dispatch::new()
+        .level(log::LevelFilter::Info)
+        .format(|out, message, record| out.finish(format_args!("{} {}", record.level(), message)))
+        .chain(std::io::stderr())
+        .apply()
+        .ok();
+
+    let agent = Agent::bind(AgentConfig::from_args(args)?)?;
+    if agent.listener.is_some() {
+        println!("overwatch agent listening on {}", agent.local_addr()?);
+    }
+    agent.serve()
+}
@@ -0,0 +1,64 @@
+use super::{read_message, write_message, ClientMessage, ServerMessage, PROTOCOL_VERSION};
+use crate::error::{OverwatchError, Result};
+use crate::snapshot::HostSnapshot;
+use std::io::BufReader;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// An authenticated connection to a remote agent.
+pub struct RemoteConnection {
+    reader: BufReader<TcpStream>,
+    pub host: String,
+    pub interval: Duration,
+}
+
+impl RemoteConnection {
+    pub fn connect(address: &str, token: &str, timeout: Duration) -> Result<Self> {
+        let addr = address
+            .to_socket_addrs()
+            .map_err(|err| OverwatchError::from_io(format!("resolve {}", address), err))?
+            .next()
+            .ok_or_else(|| OverwatchError::not_found(format!("address for {}", address)))?;
+
+        let stream = TcpStream::connect_timeout(&addr, timeout).map_err(|err| match err.kind() {
+            std::io::ErrorKind::TimedOut => OverwatchError::timeout(format!("connect to {}", address), timeout),
+            _ => OverwatchError::from_io(format!("connect to {}", address), err),
+        })?;
+        stream
+            .set_read_timeout(Some(timeout))
+            .map_err(|err| OverwatchError::from_io("remote connection", err))?;
+
+        let mut writer = stream.try_clone().map_err(|err| OverwatchError::from_io("remote connection", err))?;
+        let mut reader = BufReader::new(stream);
+
+        write_message(&mut writer, &ClientMessage::Hello {
+            token: token.to_string(),
+            version: PROTOCOL_VERSION,
+        })?;
+
+        match read_message::<ServerMessage>(&mut reader)? {
+            ServerMessage::Welcome { host, interval_ms, .. } => {
+                let interval = Duration::from_millis(interval_ms);
+                // allow a few missed samples before calling the agent dead
+                reader
+                    .get_ref()
+                    .set_read_timeout(Some(timeout.max(interval * 5)))
+                    .map_err(|err| OverwatchError::from_io("remote connection", err))?;
+                Ok(RemoteConnection { reader, host, interval })
+            }
+            ServerMessage::Error { error } => Err(error),
+            ServerMessage::Snapshot { .. } => Err(OverwatchError::backend("remote protocol", "snapshot before welcome")),
+        }
+    }
+
+    /// Blocks until the agent pushes its next snapshot.
+    pub fn next_snapshot(&mut self) -> Result<HostSnapshot> {
+        match read_message::<ServerMessage>(&mut self.reader) {
+            Ok(ServerMessage::Snapshot { snapshot }) => Ok(*snapshot),
+            Ok(ServerMessage::Error { error }) => Err(error),
+            Ok(ServerMessage::Welcome { .. }) => Err(OverwatchError::backend("remote protocol", "unexpected welcome")),
+            Err(OverwatchError::Timeout { what, .. }) => Err(OverwatchError::timeout(what, self.interval * 5)),
+            Err(err) => Err(err),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use super::client::RemoteConnection;
+use crate::error::{OverwatchError, Result};
+use crate::session::Session;
+use crate::snapshot::HostSnapshot;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tauri_specta::Event;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    Connecting,
+    Connected,
+    Disconnected,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RemoteHostStatus {
+    pub id: u32,
+    pub address: String,
+    pub state: ConnectionState,
+    pub last_error: Option<OverwatchError>,
+    pub snapshot: Option<HostSnapshot>,
+}
+
+/// Emitted whenever a remote host connects, drops or sends a snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct RemoteHostUpdate(pub RemoteHostStatus);
+
+struct RemoteHost {
+    status: Mutex<RemoteHostStatus>,
+    stop: AtomicBool,
+}
+
+/// Remote agents the desktop app is watching, kept in Tauri state.
+#[derive(Default)]
+pub struct RemoteHosts {
+    next_id: AtomicU32,
+    hosts: Mutex<BTreeMap<u32, Arc<RemoteHost>>>,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn add_remote_host(app: AppHandle, hosts: State<'_, RemoteHosts>, address: String, token: String) -> Result<RemoteHostStatus> {
+    let address = address.trim().to_string();
+    if address.is_empty() {
+        return Err(OverwatchError::not_found("remote host address"));
+    }
+
+    let id = hosts.next_id.fetch_add(1, Ordering::Relaxed);
+    let status = RemoteHostStatus {
+        id,
+        address,
+        state: ConnectionState::Connecting,
+        last_error: None,
+        snapshot: None,
+    };
+    let host = Arc::new(RemoteHost {
+        status: Mutex::new(status.clone()),
+        stop: AtomicBool::new(false),
+    });
+    hosts.hosts.lock().unwrap().insert(id, host.clone());
+
+    std::thread::spawn(move || watch_host(app, host, token));
+
+    Ok(status)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn remove_remote_host(hosts: State<'_, RemoteHosts>, id: u32) -> Result<()> {
+    let host = hosts
+        .hosts
+        .lock()
+        .unwrap()
+        .remove(&id)
+        .ok_or_else(|| OverwatchError::not_found(format!("remote host {}", id)))?;
+    host.stop.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_remote_hosts(hosts: State<'_, RemoteHosts>) -> Vec<RemoteHostStatus> {
+    hosts
+        .hosts
+        .lock()
+        .unwrap()
+        .values()
+        .map(|host| host.status.lock().unwrap().clone())
+        .collect()
+}
+
+// connect, stream snapshots, back off and retry until removed
+fn watch_host(app: AppHandle, host: Arc<RemoteHost>, token: String) {
+    let address = host.status.lock().unwrap().address.clone();
+    let mut delay = Duration::from_secs(1);
+
+    while !host.stop.load(Ordering::Relaxed) {
+        update(&app, &host, |s| s.state = ConnectionState::Connecting);
+
+        let err = match RemoteConnection::connect(&address, &token, CONNECT_TIMEOUT) {
+            Ok(mut conn) => {
+                delay = Duration::from_secs(1);
+                loop {
+                    if host.stop.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    match conn.next_snapshot() {
+                        Ok(snapshot) => update(&app, &host, |s| {
+                            s.state = ConnectionState::Connected;
+                            s.last_error = None;
+                            s.snapshot = Some(snapshot);
+                        }),
+                        Err(err) => break err,
+                    }
+                }
+            }
+            Err(err) => err,
+        };
+
+        // a wrong token won't fix itself, so stop hammering the agent
+        let fatal = matches!(err, OverwatchError::PermissionDenied { .. } | OverwatchError::Unsupported { .. });
+        update(&app, &host, |s| {
+            s.state = ConnectionState::Disconnected;
+            s.last_error = Some(err);
+        });
+        if fatal {
+            return;
+        }
+
+        std::thread::sleep(delay);
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+}
+
+fn update(app: &AppHandle, host: &RemoteHost, change: impl FnOnce(&mut RemoteHostStatus)) {
+    let status = {
+        let mut status = host.status.lock().unwrap();
+        change(&mut status);
+        status.clone()
+    };
+    if host.stop.load(Ordering::Relaxed) {
+        return;
+    }
+
+    // live updates would fight with the recorded ones during a replay
+    let session = app.state::<Session>();
+    if session.is_replaying() {
+        return;
+    }
+    session.record_event(RemoteHostUpdate::NAME, &status);
+    RemoteHostUpdate(status).emit(app).ok();
+}
@@ -0,0 +1,65 @@
+//! Headless agent mode and the desktop side that watches remote agents.
+//!
+//! The wire protocol is newline-delimited JSON over TCP. A client opens with
+//! `Hello` carrying the shared token; the agent answers `Welcome` and then
+//! pushes a `Snapshot` every interval until the connection drops.
+
+pub mod agent;
+pub mod client;
+pub mod hosts;
+
+use crate::error::{OverwatchError, Result};
+use crate::snapshot::HostSnapshot;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::io::{BufRead, Read, Write};
+
+pub const PROTOCOL_VERSION: u32 = 3;
+pub const DEFAULT_PORT: u16 = 7878;
+
+// a snapshot is a few KB, anything near this is garbage or hostile
+const MAX_MESSAGE_BYTES: u64 = 1 << 20;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Hello { token: String, version: u32 },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Welcome { host: String, version: u32, interval_ms: u64 },
+    Snapshot { snapshot: Box<HostSnapshot> },
+    Error { error: OverwatchError },
+}
+
+fn write_message(writer: &mut impl Write, message: &impl Serialize) -> Result<()> {
+    let mut line = serde_json::to_string(message).map_err(|err| OverwatchError::backend("remote protocol", err))?;
+    line.push('\n');
+    writer
+        .write_all(line.as_bytes())
+        .and_then(|_| writer.flush())
+        .map_err(|err| OverwatchError::from_io("remote connection", err))
+}
+
+fn read_message<T: DeserializeOwned>(reader: &mut impl BufRead) -> Result<T> {
+    let mut line = String::new();
+    let read = reader
+        .by_ref()
+        .take(MAX_MESSAGE_BYTES)
+        .read_line(&mut line)
+        .map_err(|err| match err.kind() {
+            // read timeouts surface as WouldBlock on unix and TimedOut on windows
+            std::io::ErrorKind::WouldBlock => OverwatchError::Timeout { what: "remote connection".to_string(), after_ms: 0 },
+            _ => OverwatchError::from_io("remote connection", err),
+        })?;
+
+    if read == 0 {
+        return Err(OverwatchError::backend("remote connection", "connection closed"));
+    }
+    if !line.ends_with('\n') {
+        return Err(OverwatchError::backend("remote protocol", "message too large"));
+    }
+
+    serde_json::from_str(&line).map_err(|err| OverwatchError::backend("remote protocol", err))
+}
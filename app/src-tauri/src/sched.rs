@@ -0,0 +1,222 @@
+//! Load and scheduler activity: load averages, run queue, context switches,
+//! interrupts, softirqs, forks and pressure stall information (PSI).
+
+use crate::error::{OverwatchError, Result, Source};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use std::time::Duration;
+
+#[cfg(windows)]
+use crate::wmi_query;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct SchedulerActivity {
+    /// 1, 5 and 15-minute load averages; Linux only.
+    pub load: Option<LoadAverage>,
+    /// Threads runnable right now. Linux counts the ones running too
+    /// (`procs_running`); Windows' processor queue length leaves them out,
+    /// so the same load reads lower there.
+    pub run_queue: u32,
+    /// Threads waiting on IO, where the platform counts them.
+    pub blocked: Option<u32>,
+    pub context_switches: f32,
+    pub interrupts: f32,
+    /// Softirqs on Linux, deferred procedure calls on Windows.
+    pub softirqs: f32,
+    /// New processes and threads per second; Linux only.
+    pub forks: Option<f32>,
+    pub cpu_pressure: Option<Pressure>,
+    pub io_pressure: Option<Pressure>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+pub struct LoadAverage {
+    pub one: f32,
+    pub five: f32,
+    pub fifteen: f32,
+}
+
+/// One `/proc/pressure` file: the share of time some or all tasks were
+/// stalled on the resource.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+pub struct Pressure {
+    pub some: PressureAverages,
+    /// Not reported for CPU pressure before Linux 5.13.
+    pub full: Option<PressureAverages>,
+}
+
+/// Percentages over the last 10, 60 and 300 seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+pub struct PressureAverages {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+}
+
+/// The running totals and gauges out of `/proc/stat`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatCounters {
+    pub context_switches: u64,
+    pub interrupts: u64,
+    pub softirqs: u64,
+    pub forks: u64,
+    pub running: u32,
+    pub blocked: u32,
+}
+
+pub fn parse_stat(text: &str) -> Result<StatCounters> {
+    let mut counters = StatCounters::default();
+    let mut found = false;
+    for line in text.lines() {
+        let mut fields = line.split_whitespace();
+        let key = fields.next().unwrap_or_default();
+        // intr and softirq lead with the total, then one column per source
+        let Some(value) = fields.next().and_then(|value| value.parse::<u64>().ok()) else {
+            continue;
+        };
+        match key {
+            "ctxt" => counters.context_switches = value,
+            "intr" => counters.interrupts = value,
+            "softirq" => counters.softirqs = value,
+            "processes" => counters.forks = value,
+            "procs_running" => counters.running = value as u32,
+            "procs_blocked" => counters.blocked = value as u32,
+            _ => continue,
+        }
+        found = true;
+    }
+    match found {
+        true => Ok(counters),
+        false => Err(OverwatchError::not_found("scheduler counters in /proc/stat")),
+    }
+}
+
+/// `/proc/loadavg`: "0.52 0.58 0.59 2/1234 5678".
+pub fn parse_loadavg(text: &str) -> Result<LoadAverage> {
+    let averages: Vec<f32> = text.split_whitespace().take(3).filter_map(|field| field.parse().ok()).collect();
+    match averages[..] {
+        [one, five, fifteen] => Ok(LoadAverage { one, five, fifteen }),
+        _ => Err(OverwatchError::backend("loadavg", "unexpected format")),
+    }
+}
+
+/// A `/proc/pressure/*` file:
+/// "some avg10=0.00 avg60=0.00 avg300=0.00 total=0", then a "full" line.
+pub fn parse_pressure(text: &str) -> Result<Pressure> {
+    let line = |kind: &str| {
+        let line = text.lines().find(|line| line.starts_with(kind))?;
+        let field = |name: &str| {
+            line.split_whitespace()
+                .find_map(|field| field.strip_prefix(name)?.strip_prefix('=')?.parse::<f32>().ok())
+        };
+        Some(PressureAverages {
+            avg10: field("avg10")?,
+            avg60: field("avg60")?,
+            avg300: field("avg300")?,
+        })
+    };
+    match line("some") {
+        Some(some) => Ok(Pressure { some, full: line("full") }),
+        None => Err(OverwatchError::backend("pressure", "unexpected format")),
+    }
+}
+
+/// Turns two `/proc/stat` readings and what's current under a
+/// `/proc`-style directory into activity rates.
+pub fn activity(before: &StatCounters, after: &StatCounters, elapsed: Duration, proc_root: &Path) -> SchedulerActivity {
+    let seconds = elapsed.as_secs_f32().max(f32::EPSILON);
+    let rate = |before: u64, after: u64| after.saturating_sub(before) as f32 / seconds;
+    let read = |name: &str| std::fs::read_to_string(proc_root.join(name)).ok();
+
+    SchedulerActivity {
+        load: read("loadavg").and_then(|text| parse_loadavg(&text).ok()),
+        run_queue: after.running,
+        blocked: Some(after.blocked),
+        context_switches: rate(before.context_switches, after.context_switches),
+        interrupts: rate(before.interrupts, after.interrupts),
+        softirqs: rate(before.softirqs, after.softirqs),
+        forks: Some(rate(before.forks, after.forks)),
+        // absent without CONFIG_PSI, or with psi=0 on the command line
+        cpu_pressure: read("pressure/cpu").and_then(|text| parse_pressure(&text).ok()),
+        io_pressure: read("pressure/io").and_then(|text| parse_pressure(&text).ok()),
+    }
+}
+
+/// A rate measurement over the window the caller sleeps through.
+pub struct SchedWindow {
+    #[cfg(target_os = "linux")]
+    before: Result<StatCounters>,
+    #[cfg(target_os = "linux")]
+    started: std::time::Instant,
+}
+
+#[cfg(target_os = "linux")]
+fn read_stat() -> Result<StatCounters> {
+    let text = std::fs::read_to_string("/proc/stat").map_err(|err| OverwatchError::from_io("/proc/stat", err))?;
+    parse_stat(&text)
+}
+
+#[cfg(target_os = "linux")]
+pub fn start() -> SchedWindow {
+    SchedWindow {
+        before: read_stat(),
+        started: std::time::Instant::now(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl SchedWindow {
+    pub fn finish(self) -> Result<(SchedulerActivity, Source)> {
+        let before = self.before?;
+        let after = read_stat()?;
+        Ok((activity(&before, &after, self.started.elapsed(), Path::new("/proc")), Source::Procfs))
+    }
+}
+
+// like the idle counters, the formatted rates are already averaged
+#[cfg(not(target_os = "linux"))]
+pub fn start() -> SchedWindow {
+    SchedWindow {}
+}
+
+#[cfg(windows)]
+impl SchedWindow {
+    pub fn finish(self) -> Result<(SchedulerActivity, Source)> {
+        let wmi_con = wmi_query::connect()?;
+        let number = |row: &wmi_query::WmiRow, prop: &str| wmi_query::number(row, prop).unwrap_or(0.0) as f32;
+        let system = wmi_query::query(
+            &wmi_con,
+            "SELECT ContextSwitchesPersec, ProcessorQueueLength FROM Win32_PerfFormattedData_PerfOS_System",
+        )?;
+        let system = system.first().ok_or_else(|| OverwatchError::not_found("system performance counters"))?;
+        let processors = wmi_query::query(
+            &wmi_con,
+            "SELECT InterruptsPersec, DPCsQueuedPersec FROM Win32_PerfFormattedData_Counters_ProcessorInformation WHERE Name = '_Total'",
+        )?;
+        let processors = processors.first().ok_or_else(|| OverwatchError::not_found("processor performance counters"))?;
+
+        Ok((
+            SchedulerActivity {
+                load: None,
+                // threads waiting for a CPU, not counting the ones running
+                run_queue: number(system, "ProcessorQueueLength") as u32,
+                blocked: None,
+                context_switches: number(system, "ContextSwitchesPersec"),
+                interrupts: number(processors, "InterruptsPersec"),
+                softirqs: number(processors, "DPCsQueuedPersec"),
+                forks: None,
+                cpu_pressure: None,
+                io_pressure: None,
+            },
+            Source::Wmi,
+        ))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+impl SchedWindow {
+    pub fn finish(self) -> Result<(SchedulerActivity, Source)> {
+        Err(OverwatchError::unsupported("scheduler activity"))
+    }
+}
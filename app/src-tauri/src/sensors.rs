@@ -0,0 +1,32 @@
+use crate::error::{OverwatchError, Result};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use sysinfo::Components;
+
+/// One temperature sensor as reported by the OS (hwmon on Linux).
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Sensor {
+    pub label: String,
+    pub temperature: f32,
+    pub max: Option<f32>,
+    pub critical: Option<f32>,
+}
+
+pub fn read_sensors() -> Result<Vec<Sensor>> {
+    let components = Components::new_with_refreshed_list();
+    let sensors: Vec<Sensor> = components
+        .iter()
+        .filter(|c| c.temperature().is_finite() && c.temperature() > 0.0)
+        .map(|c| Sensor {
+            label: c.label().to_string(),
+            temperature: c.temperature(),
+            max: Some(c.max()).filter(|t| t.is_finite() && *t > 0.0),
+            critical: c.critical().filter(|t| t.is_finite() && *t > 0.0),
+        })
+        .collect();
+
+    if sensors.is_empty() {
+        return Err(OverwatchError::not_found("temperature sensors"));
+    }
+    Ok(sensors)
+}
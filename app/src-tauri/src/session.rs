@@ -0,0 +1,551 @@
+//! Session recording and replay.
+//!
+//! A session file is gzipped JSON lines: one `SessionHeader`, then one
+//! `SessionRecord` per sample or event, timestamped relative to the start.
+//! While replaying, commands answer from the file instead of the hardware,
+//! so the UI can't tell the difference.
+
+use crate::board::BoardInfo;
+use crate::cgroups::CgroupStats;
+use crate::cpu::CpuInfo;
+use crate::cpuflags::CpuFeatures;
+use crate::devices::DeviceInventory;
+use crate::error::{OverwatchError, Result};
+use crate::fans::FanStatus;
+use crate::gpu::GpuInfo;
+use crate::hwerrors::HardwareErrors;
+use crate::interrupts::InterruptStats;
+use crate::memory::MemoryLayout;
+use crate::power::PowerInfo;
+use crate::processes::ProcessList;
+use crate::rapl::CpuPower;
+use crate::snapshot::{hostname, now_millis, Reading};
+use crate::storage::StorageHealth;
+use crate::system::SystemInfo;
+use crate::throttle::ThrottleStatus;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+const FORMAT: &str = "overwatch-session";
+const VERSION: u32 = 1;
+const EVENT_TICK: Duration = Duration::from_millis(50);
+/// Flushing the gzip stream ends a deflate block, which costs size on
+/// every line; this often, a crash loses a few seconds of samples at most.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionHeader {
+    pub format: String,
+    pub version: u32,
+    pub host: String,
+    /// Unix timestamp in milliseconds.
+    pub started_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum SessionEntry {
+    Cpu(Reading<CpuInfo>),
+    Gpu(Reading<GpuInfo>),
+    CpuFeatures(Reading<CpuFeatures>),
+    System(Reading<SystemInfo>),
+    Board(Reading<BoardInfo>),
+    MemoryLayout(Reading<MemoryLayout>),
+    Devices(Reading<DeviceInventory>),
+    Storage(Reading<StorageHealth>),
+    Power(Reading<PowerInfo>),
+    CpuPower(Reading<CpuPower>),
+    Throttle(Reading<ThrottleStatus>),
+    Fans(Reading<FanStatus>),
+    Interrupts(Reading<InterruptStats>),
+    HardwareErrors(Reading<HardwareErrors>),
+    Cgroups(Reading<CgroupStats>),
+    Processes(Reading<ProcessList>),
+    Event { name: String, payload: serde_json::Value },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Milliseconds since the session started.
+    pub t: u64,
+    #[serde(flatten)]
+    pub entry: SessionEntry,
+}
+
+/// Types a command can be recorded and replayed as.
+pub trait Sample: Clone + Sized {
+    const NAME: &'static str;
+    fn wrap(reading: Reading<Self>) -> SessionEntry;
+    fn unwrap(entry: &SessionEntry) -> Option<&Reading<Self>>;
+}
+
+macro_rules! sample {
+    ($ty:ty, $variant:ident, $name:literal) => {
+        impl Sample for $ty {
+            const NAME: &'static str = $name;
+
+            fn wrap(reading: Reading<Self>) -> SessionEntry {
+                SessionEntry::$variant(reading)
+            }
+
+            fn unwrap(entry: &SessionEntry) -> Option<&Reading<Self>> {
+                match entry {
+                    SessionEntry::$variant(reading) => Some(reading),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+sample!(CpuInfo, Cpu, "CPU");
+sample!(GpuInfo, Gpu, "GPU");
+sample!(CpuFeatures, CpuFeatures, "CPU features");
+sample!(SystemInfo, System, "system");
+sample!(BoardInfo, Board, "board");
+sample!(MemoryLayout, MemoryLayout, "memory layout");
+sample!(DeviceInventory, Devices, "device inventory");
+sample!(StorageHealth, Storage, "storage health");
+sample!(PowerInfo, Power, "power");
+sample!(CpuPower, CpuPower, "CPU power");
+sample!(ThrottleStatus, Throttle, "throttling");
+sample!(FanStatus, Fans, "fans");
+sample!(InterruptStats, Interrupts, "interrupts");
+sample!(HardwareErrors, HardwareErrors, "hardware errors");
+sample!(CgroupStats, Cgroups, "cgroups");
+sample!(ProcessList, Processes, "processes");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionMode {
+    Live,
+    Recording,
+    Replaying,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionStatus {
+    pub mode: SessionMode,
+    pub path: Option<String>,
+    pub header: Option<SessionHeader>,
+    pub position_ms: u64,
+    pub duration_ms: u64,
+    pub speed: f64,
+    pub paused: bool,
+    pub records: u64,
+}
+
+struct Recorder {
+    path: PathBuf,
+    header: SessionHeader,
+    started: Instant,
+    writer: GzEncoder<BufWriter<File>>,
+    flushed: Instant,
+    records: u64,
+}
+
+impl Recorder {
+    fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path).map_err(|err| OverwatchError::from_io(path.display().to_string(), err))?;
+        let header = SessionHeader {
+            format: FORMAT.to_string(),
+            version: VERSION,
+            host: hostname(),
+            started_at: now_millis(),
+        };
+
+        let mut recorder = Recorder {
+            path: path.to_path_buf(),
+            header,
+            started: Instant::now(),
+            writer: GzEncoder::new(BufWriter::new(file), Compression::default()),
+            flushed: Instant::now(),
+            records: 0,
+        };
+        let header = recorder.header.clone();
+        recorder.write_line(&header)?;
+        // so even a recording cut off before its first flush can be opened
+        recorder.flush()?;
+        Ok(recorder)
+    }
+
+    fn write(&mut self, entry: SessionEntry) -> Result<()> {
+        let record = SessionRecord {
+            t: self.started.elapsed().as_millis() as u64,
+            entry,
+        };
+        self.write_line(&record)?;
+        self.records += 1;
+        if self.flushed.elapsed() >= FLUSH_INTERVAL {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn write_line(&mut self, value: &impl Serialize) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, value).map_err(|err| OverwatchError::backend("session file", err))?;
+        self.writer.write_all(b"\n").map_err(|err| OverwatchError::from_io("session file", err))
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.flushed = Instant::now();
+        self.writer.flush().map_err(|err| OverwatchError::from_io("session file", err))
+    }
+
+    fn finish(self) -> Result<()> {
+        self.writer
+            .finish()
+            .and_then(|mut w| w.flush())
+            .map_err(|err| OverwatchError::from_io("session file", err))
+    }
+}
+
+struct Replay {
+    path: PathBuf,
+    header: SessionHeader,
+    records: Vec<SessionRecord>,
+    duration_ms: u64,
+    // position = anchor_pos + (now - anchor) * speed, unless paused
+    anchor: Instant,
+    anchor_pos: u64,
+    speed: f64,
+    paused: bool,
+}
+
+impl Replay {
+    fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path).map_err(|err| OverwatchError::from_io(path.display().to_string(), err))?;
+        let mut lines = BufReader::new(GzDecoder::new(file)).lines();
+        let parse_err = |err: serde_json::Error| OverwatchError::backend("session file", err);
+        let io_err = |err| OverwatchError::from_io("session file", err);
+
+        let header: SessionHeader = match lines.next() {
+            Some(line) => serde_json::from_str(&line.map_err(io_err)?).map_err(parse_err)?,
+            None => return Err(OverwatchError::backend("session file", "file is empty")),
+        };
+        if header.format != FORMAT || header.version > VERSION {
+            return Err(OverwatchError::unsupported(format!("session format {} v{}", header.format, header.version)));
+        }
+
+        let mut records = Vec::new();
+        for line in lines {
+            // a recording cut off mid-write still replays up to the break,
+            // even one that never got past its header
+            let Ok(line) = line else { break };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<SessionRecord>(&line) {
+                Ok(record) => records.push(record),
+                Err(_) if !records.is_empty() => break,
+                Err(err) => return Err(parse_err(err)),
+            }
+        }
+        records.sort_by_key(|r| r.t);
+        let duration_ms = records.last().map(|r| r.t).unwrap_or(0);
+
+        Ok(Replay {
+            path: path.to_path_buf(),
+            header,
+            records,
+            duration_ms,
+            anchor: Instant::now(),
+            anchor_pos: 0,
+            speed: 1.0,
+            paused: false,
+        })
+    }
+
+    fn position(&self) -> u64 {
+        if self.paused {
+            return self.anchor_pos;
+        }
+        let advanced = self.anchor.elapsed().as_millis() as f64 * self.speed;
+        (self.anchor_pos + advanced as u64).min(self.duration_ms)
+    }
+
+    fn reanchor(&mut self, position: u64) {
+        self.anchor = Instant::now();
+        self.anchor_pos = position.min(self.duration_ms);
+    }
+
+    // latest sample of this type at or before the current position
+    fn latest<T: Sample>(&self) -> Result<T> {
+        let position = self.position();
+        let end = self.records.partition_point(|r| r.t <= position);
+        let reading = self.records[..end]
+            .iter()
+            .rev()
+            .find_map(|r| T::unwrap(&r.entry))
+            .ok_or_else(|| OverwatchError::not_found(format!("{} sample at this point of the recording", T::NAME)))?;
+
+        match reading {
+            Reading::Ok(value) => Ok(value.clone()),
+            Reading::Err(err) => Err(err.clone()),
+        }
+    }
+
+    // both ends inclusive
+    fn events_between(&self, from: u64, upto: u64) -> impl Iterator<Item = (&str, &serde_json::Value)> {
+        let start = self.records.partition_point(|r| r.t < from);
+        let end = self.records.partition_point(|r| r.t <= upto);
+        self.records[start..end.max(start)].iter().filter_map(|r| match &r.entry {
+            SessionEntry::Event { name, payload } => Some((name.as_str(), payload)),
+            _ => None,
+        })
+    }
+}
+
+enum Mode {
+    Live,
+    Recording(Recorder),
+    Replaying(Replay),
+}
+
+/// Decides whether commands read the hardware, record it, or replay a file.
+pub struct Session {
+    mode: Mutex<Mode>,
+    // bumped whenever a replay opens or closes so stale event threads exit
+    generation: AtomicU64,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session {
+            mode: Mutex::new(Mode::Live),
+            generation: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Session {
+    /// Answers from the replay if one is open, otherwise reads live and
+    /// records the result if recording.
+    pub fn sample<T: Sample>(&self, live: impl FnOnce() -> Result<T>) -> Result<T> {
+        if let Mode::Replaying(replay) = &*self.mode.lock().unwrap() {
+            return replay.latest();
+        }
+
+        // don't hold the lock over a live read, some of them sleep
+        let result = live();
+
+        if let Mode::Recording(recorder) = &mut *self.mode.lock().unwrap() {
+            let reading = match &result {
+                Ok(value) => Reading::Ok(value.clone()),
+                Err(err) => Reading::Err(err.clone()),
+            };
+            if let Err(err) = recorder.write(T::wrap(reading)) {
+                log::warn!("dropping session sample: {}", err);
+            }
+        }
+
+        result
+    }
+
+    pub fn record_event(&self, name: &str, payload: &impl Serialize) {
+        if let Mode::Recording(recorder) = &mut *self.mode.lock().unwrap() {
+            let Ok(payload) = serde_json::to_value(payload) else { return };
+            if let Err(err) = recorder.write(SessionEntry::Event { name: name.to_string(), payload }) {
+                log::warn!("dropping session event: {}", err);
+            }
+        }
+    }
+
+    /// When the current recording started, as unix milliseconds.
+    pub fn recording_started_at(&self) -> Option<u64> {
+        match &*self.mode.lock().unwrap() {
+            Mode::Recording(recorder) => Some(recorder.header.started_at),
+            _ => None,
+        }
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        matches!(&*self.mode.lock().unwrap(), Mode::Replaying(_))
+    }
+
+    pub fn start_recording(&self, path: &Path) -> Result<SessionStatus> {
+        let mut mode = self.mode.lock().unwrap();
+        if !matches!(*mode, Mode::Live) {
+            return Err(OverwatchError::unsupported("recording while another session is active"));
+        }
+        *mode = Mode::Recording(Recorder::create(path)?);
+        Ok(status(&mode))
+    }
+
+    pub fn stop_recording(&self) -> Result<SessionStatus> {
+        let mut mode = self.mode.lock().unwrap();
+        let Mode::Recording(_) = &*mode else {
+            return Err(OverwatchError::not_found("active recording"));
+        };
+        let Mode::Recording(recorder) = std::mem::replace(&mut *mode, Mode::Live) else {
+            unreachable!()
+        };
+        // report what was written, then close the gzip stream
+        let mut summary = recording_status(&recorder);
+        summary.mode = SessionMode::Live;
+        recorder.finish()?;
+        Ok(summary)
+    }
+
+    pub fn open_replay(&self, path: &Path) -> Result<SessionStatus> {
+        let replay = Replay::open(path)?;
+        let mut mode = self.mode.lock().unwrap();
+        if let Mode::Recording(_) = &*mode {
+            return Err(OverwatchError::unsupported("replaying while recording"));
+        }
+        *mode = Mode::Replaying(replay);
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        Ok(status(&mode))
+    }
+
+    pub fn close_replay(&self) -> Result<SessionStatus> {
+        let mut mode = self.mode.lock().unwrap();
+        if !matches!(*mode, Mode::Replaying(_)) {
+            return Err(OverwatchError::not_found("open replay"));
+        }
+        *mode = Mode::Live;
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        Ok(status(&mode))
+    }
+
+    /// Changes speed, pause state and/or position of the open replay.
+    pub fn control_replay(&self, speed: Option<f64>, paused: Option<bool>, seek_ms: Option<u64>) -> Result<SessionStatus> {
+        let mut mode = self.mode.lock().unwrap();
+        let Mode::Replaying(replay) = &mut *mode else {
+            return Err(OverwatchError::not_found("open replay"));
+        };
+
+        let position = seek_ms.unwrap_or_else(|| replay.position());
+        if let Some(speed) = speed {
+            if !(speed.is_finite() && speed > 0.0) {
+                return Err(OverwatchError::unsupported(format!("replay speed {}", speed)));
+            }
+            replay.speed = speed;
+        }
+        if let Some(paused) = paused {
+            replay.paused = paused;
+        }
+        replay.reanchor(position);
+        Ok(status(&mode))
+    }
+
+    pub fn status(&self) -> SessionStatus {
+        status(&self.mode.lock().unwrap())
+    }
+}
+
+fn status(mode: &Mode) -> SessionStatus {
+    match mode {
+        Mode::Live => SessionStatus {
+            mode: SessionMode::Live,
+            path: None,
+            header: None,
+            position_ms: 0,
+            duration_ms: 0,
+            speed: 1.0,
+            paused: false,
+            records: 0,
+        },
+        Mode::Recording(recorder) => recording_status(recorder),
+        Mode::Replaying(replay) => SessionStatus {
+            mode: SessionMode::Replaying,
+            path: Some(replay.path.display().to_string()),
+            header: Some(replay.header.clone()),
+            position_ms: replay.position(),
+            duration_ms: replay.duration_ms,
+            speed: replay.speed,
+            paused: replay.paused,
+            records: replay.records.len() as u64,
+        },
+    }
+}
+
+fn recording_status(recorder: &Recorder) -> SessionStatus {
+    let elapsed = recorder.started.elapsed().as_millis() as u64;
+    SessionStatus {
+        mode: SessionMode::Recording,
+        path: Some(recorder.path.display().to_string()),
+        header: Some(recorder.header.clone()),
+        position_ms: elapsed,
+        duration_ms: elapsed,
+        speed: 1.0,
+        paused: false,
+        records: recorder.records,
+    }
+}
+
+// re-emits recorded events as the replay clock passes them
+fn spawn_event_replay(app: AppHandle, generation: u64) {
+    std::thread::spawn(move || {
+        // the first replay position not emitted yet, so events at 0 go out too
+        let mut next = 0;
+        loop {
+            std::thread::sleep(EVENT_TICK);
+            let session = app.state::<Session>();
+            if session.generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+
+            let mode = session.mode.lock().unwrap();
+            let Mode::Replaying(replay) = &*mode else { return };
+            let position = replay.position();
+            // seeking backwards replays from the new position
+            if position + 1 < next {
+                next = position;
+                continue;
+            }
+            for (name, payload) in replay.events_between(next, position) {
+                app.emit(name, payload).ok();
+            }
+            next = position + 1;
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn start_recording(session: State<'_, Session>, path: String) -> Result<SessionStatus> {
+    session.start_recording(Path::new(&path))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn stop_recording(session: State<'_, Session>) -> Result<SessionStatus> {
+    session.stop_recording()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn open_replay(app: AppHandle, session: State<'_, Session>, path: String) -> Result<SessionStatus> {
+    let status = session.open_replay(Path::new(&path))?;
+    spawn_event_replay(app, session.generation.load(Ordering::SeqCst));
+    Ok(status)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn close_replay(session: State<'_, Session>) -> Result<SessionStatus> {
+    session.close_replay()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn control_replay(session: State<'_, Session>, speed: Option<f64>, paused: Option<bool>, seek_ms: Option<u64>) -> Result<SessionStatus> {
+    session.control_replay(speed, paused, seek_ms)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_session_status(session: State<'_, Session>) -> SessionStatus {
+    session.status()
+}
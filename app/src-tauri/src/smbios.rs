@@ -0,0 +1,264 @@
+//! Raw SMBIOS table parsing.
+//!
+//! The table is a run of structures, each a formatted area (type, length,
+//! handle, then type-specific fields) followed by a set of NUL-terminated
+//! strings that fields refer to by 1-based index.
+
+use crate::error::{OverwatchError, Result};
+
+pub const BIOS: u8 = 0;
+pub const SYSTEM: u8 = 1;
+pub const BASEBOARD: u8 = 2;
+pub const CHASSIS: u8 = 3;
+pub const MEMORY_ARRAY: u8 = 16;
+pub const MEMORY_DEVICE: u8 = 17;
+const END_OF_TABLE: u8 = 127;
+
+/// One structure out of the table.
+#[derive(Debug, Clone)]
+pub struct Structure {
+    pub kind: u8,
+    pub handle: u16,
+    /// The formatted area, header included, so offsets match the spec.
+    pub data: Vec<u8>,
+    pub strings: Vec<String>,
+}
+
+impl Structure {
+    pub fn byte(&self, offset: usize) -> Option<u8> {
+        self.data.get(offset).copied()
+    }
+
+    pub fn word(&self, offset: usize) -> Option<u16> {
+        Some(u16::from_le_bytes(self.data.get(offset..offset + 2)?.try_into().ok()?))
+    }
+
+    pub fn dword(&self, offset: usize) -> Option<u32> {
+        Some(u32::from_le_bytes(self.data.get(offset..offset + 4)?.try_into().ok()?))
+    }
+
+    pub fn qword(&self, offset: usize) -> Option<u64> {
+        Some(u64::from_le_bytes(self.data.get(offset..offset + 8)?.try_into().ok()?))
+    }
+
+    /// The string a field points at, skipping blanks and vendor placeholders.
+    pub fn string(&self, offset: usize) -> Option<String> {
+        let index = self.byte(offset)? as usize;
+        let text = self.strings.get(index.checked_sub(1)?)?;
+        meaningful(text).map(str::to_string)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    pub structures: Vec<Structure>,
+}
+
+impl Table {
+    /// Parses as much of the table as is well-formed; a truncated tail is dropped.
+    pub fn parse(bytes: &[u8]) -> Table {
+        let mut structures = Vec::new();
+        let mut offset = 0;
+
+        while offset + 4 <= bytes.len() {
+            let kind = bytes[offset];
+            let length = bytes[offset + 1] as usize;
+            if length < 4 || offset + length > bytes.len() {
+                break;
+            }
+            let handle = u16::from_le_bytes([bytes[offset + 2], bytes[offset + 3]]);
+            let data = bytes[offset..offset + length].to_vec();
+
+            // strings until an empty one; a structure without strings still ends in two NULs
+            let mut strings = Vec::new();
+            let mut pos = offset + length;
+            loop {
+                let Some(len) = bytes.get(pos..).and_then(|rest| rest.iter().position(|&b| b == 0)) else {
+                    return Table { structures };
+                };
+                pos += len + 1;
+                if len == 0 {
+                    if strings.is_empty() && bytes.get(pos) == Some(&0) {
+                        pos += 1;
+                    }
+                    break;
+                }
+                strings.push(String::from_utf8_lossy(&bytes[pos - len - 1..pos - 1]).trim().to_string());
+            }
+
+            structures.push(Structure { kind, handle, data, strings });
+            if kind == END_OF_TABLE {
+                break;
+            }
+            offset = pos;
+        }
+
+        Table { structures }
+    }
+
+    pub fn first(&self, kind: u8) -> Option<&Structure> {
+        self.structures.iter().find(|s| s.kind == kind)
+    }
+
+    pub fn all(&self, kind: u8) -> impl Iterator<Item = &Structure> {
+        self.structures.iter().filter(move |s| s.kind == kind)
+    }
+}
+
+/// The table as the kernel exports it. Only root can read it.
+#[cfg(target_os = "linux")]
+pub fn read_table() -> Result<Table> {
+    let bytes = std::fs::read("/sys/firmware/dmi/tables/DMI").map_err(|err| OverwatchError::from_io("SMBIOS table", err))?;
+    Ok(Table::parse(&bytes))
+}
+
+/// The table as Windows caches it at boot; no elevation needed.
+#[cfg(windows)]
+pub fn read_table() -> Result<Table> {
+    use wmi::Variant;
+
+    let wmi_con = crate::wmi_query::connect_namespace(r"root\WMI")?;
+    for row in crate::wmi_query::query(&wmi_con, "SELECT SMBiosData FROM MSSmBios_RawSMBiosTables")? {
+        if let Some(Variant::Array(data)) = row.get("SMBiosData") {
+            let bytes: Vec<u8> = data
+                .iter()
+                .filter_map(|byte| match byte {
+                    Variant::UI1(byte) => Some(*byte),
+                    _ => None,
+                })
+                .collect();
+            return Ok(Table::parse(&bytes));
+        }
+    }
+    Err(OverwatchError::not_found("raw SMBIOS table"))
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn read_table() -> Result<Table> {
+    Err(OverwatchError::unsupported("raw SMBIOS table"))
+}
+
+/// `None` for blanks and the strings vendors leave in unfilled fields.
+pub fn meaningful(text: &str) -> Option<&str> {
+    const PLACEHOLDERS: [&str; 11] = [
+        "to be filled by o.e.m.",
+        "default string",
+        "not specified",
+        "not applicable",
+        "not available",
+        "system serial number",
+        "system product name",
+        "unknown",
+        "none",
+        "n/a",
+        "no dimm",
+    ];
+
+    let text = text.trim();
+    let lower = text.to_ascii_lowercase();
+    let filler = text.chars().all(|c| c == '0' || c == ' ' || c == '.');
+    if text.is_empty() || filler || PLACEHOLDERS.contains(&lower.as_str()) || lower == "0123456789" {
+        return None;
+    }
+    Some(text)
+}
+
+/// Chassis type (type 3, offset 05h) by code; the high bit is the lock flag.
+pub fn chassis_type_name(code: u8) -> Option<&'static str> {
+    Some(match code & 0x7f {
+        0x01 => "Other",
+        0x03 => "Desktop",
+        0x04 => "Low Profile Desktop",
+        0x05 => "Pizza Box",
+        0x06 => "Mini Tower",
+        0x07 => "Tower",
+        0x08 => "Portable",
+        0x09 => "Laptop",
+        0x0a => "Notebook",
+        0x0b => "Hand Held",
+        0x0c => "Docking Station",
+        0x0d => "All in One",
+        0x0e => "Sub Notebook",
+        0x0f => "Space-saving",
+        0x10 => "Lunch Box",
+        0x11 => "Main Server Chassis",
+        0x12 => "Expansion Chassis",
+        0x13 => "SubChassis",
+        0x14 => "Bus Expansion Chassis",
+        0x15 => "Peripheral Chassis",
+        0x16 => "RAID Chassis",
+        0x17 => "Rack Mount Chassis",
+        0x18 => "Sealed-case PC",
+        0x19 => "Multi-system Chassis",
+        0x1a => "Compact PCI",
+        0x1b => "Advanced TCA",
+        0x1c => "Blade",
+        0x1d => "Blade Enclosure",
+        0x1e => "Tablet",
+        0x1f => "Convertible",
+        0x20 => "Detachable",
+        0x21 => "IoT Gateway",
+        0x22 => "Embedded PC",
+        0x23 => "Mini PC",
+        0x24 => "Stick PC",
+        // 0x02 is "Unknown", which says nothing
+        _ => return None,
+    })
+}
+
+/// Memory type (type 17, offset 12h) by code.
+pub fn memory_type_name(code: u8) -> Option<&'static str> {
+    Some(match code {
+        0x0f => "SDRAM",
+        0x12 => "DDR",
+        0x13 => "DDR2",
+        0x14 => "DDR2 FB-DIMM",
+        0x18 => "DDR3",
+        0x1a => "DDR4",
+        0x1b => "LPDDR",
+        0x1c => "LPDDR2",
+        0x1d => "LPDDR3",
+        0x1e => "LPDDR4",
+        0x1f => "Logical non-volatile device",
+        0x20 => "HBM",
+        0x21 => "HBM2",
+        0x22 => "DDR5",
+        0x23 => "LPDDR5",
+        0x24 => "HBM3",
+        _ => return None,
+    })
+}
+
+/// Form factor (type 17, offset 0Eh) by code.
+pub fn form_factor_name(code: u8) -> Option<&'static str> {
+    Some(match code {
+        0x01 => "Other",
+        0x05 => "Chip",
+        0x06 => "DIP",
+        0x07 => "ZIP",
+        0x08 => "Proprietary Card",
+        0x09 => "DIMM",
+        0x0a => "TSOP",
+        0x0b => "Row of chips",
+        0x0c => "RIMM",
+        0x0d => "SODIMM",
+        0x0e => "SRIMM",
+        0x0f => "FB-DIMM",
+        0x10 => "Die",
+        0x11 => "CAMM",
+        _ => return None,
+    })
+}
+
+/// Memory array error correction (type 16, offset 06h) by code.
+pub fn error_correction_name(code: u8) -> Option<&'static str> {
+    Some(match code {
+        0x01 => "Other",
+        0x03 => "None",
+        0x04 => "Parity",
+        0x05 => "Single-bit ECC",
+        0x06 => "Multi-bit ECC",
+        0x07 => "CRC",
+        _ => return None,
+    })
+}
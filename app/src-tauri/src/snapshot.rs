@@ -0,0 +1,102 @@
+use crate::cpu::{read_cpu_info, CpuInfo};
+use crate::error::{OverwatchError, Result};
+use crate::gpu::{read_gpu_info, GpuInfo};
+use crate::memory::{read_memory_info, MemoryInfo};
+use crate::sensors::{read_sensors, Sensor};
+use crate::storage::{read_storage_health_cached, StorageHealth};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How long a reading with nothing to compare against waits for a second one.
+pub const FIRST_WINDOW: Duration = Duration::from_millis(200);
+
+/// A command result that travels inside a snapshot instead of failing it.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Reading<T> {
+    Ok(T),
+    Err(OverwatchError),
+}
+
+impl<T> From<Result<T>> for Reading<T> {
+    fn from(result: Result<T>) -> Self {
+        match result {
+            Ok(value) => Reading::Ok(value),
+            Err(err) => Reading::Err(err),
+        }
+    }
+}
+
+/// One sample of everything a host reports.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HostSnapshot {
+    pub host: String,
+    /// Unix timestamp in milliseconds.
+    pub timestamp: u64,
+    pub cpu: Reading<CpuInfo>,
+    pub gpu: Reading<GpuInfo>,
+    pub memory: Reading<MemoryInfo>,
+    pub sensors: Reading<Vec<Sensor>>,
+    /// `None` from agents that predate it.
+    #[serde(default)]
+    pub storage: Option<Reading<StorageHealth>>,
+}
+
+impl HostSnapshot {
+    pub fn collect() -> Self {
+        HostSnapshot {
+            host: hostname(),
+            timestamp: now_millis(),
+            cpu: read_cpu_info().into(),
+            gpu: read_gpu_info().into(),
+            memory: read_memory_info().into(),
+            sensors: read_sensors().into(),
+            storage: Some(read_storage_health_cached().into()),
+        }
+    }
+}
+
+pub fn hostname() -> String {
+    sysinfo::System::host_name().unwrap_or_else(|| "unknown".to_string())
+}
+
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// The previous reading of a rate monitor, kept in Tauri state so rates
+/// cover the time between polls.
+pub struct LastReading<T>(Mutex<Option<(T, Instant)>>);
+
+impl<T> Default for LastReading<T> {
+    fn default() -> Self {
+        LastReading(Mutex::new(None))
+    }
+}
+
+impl<T> LastReading<T> {
+    /// Reads again and hands `compare` the previous reading, the new one and
+    /// the time between them. With nothing to compare against yet it reads
+    /// twice, `FIRST_WINDOW` apart, without holding the lock meanwhile.
+    pub fn advance<R>(&self, read: impl Fn() -> Result<T>, compare: impl FnOnce(&T, &T, Duration) -> R) -> Result<R> {
+        let last = self.0.lock().unwrap().take();
+        let (before, then) = match last {
+            Some(last) => last,
+            None => {
+                let first = (read()?, Instant::now());
+                std::thread::sleep(FIRST_WINDOW);
+                first
+            }
+        };
+        let reading = read()?;
+        let now = Instant::now();
+        let result = compare(&before, &reading, now - then);
+        *self.0.lock().unwrap() = Some((reading, now));
+        Ok(result)
+    }
+}
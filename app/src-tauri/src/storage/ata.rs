@@ -0,0 +1,147 @@
+use crate::error::{OverwatchError, Result};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Size of the SMART READ DATA and READ THRESHOLDS sectors.
+pub const SECTOR_SIZE: usize = 512;
+
+pub const REALLOCATED_SECTORS: u8 = 5;
+pub const POWER_ON_HOURS: u8 = 9;
+pub const REPORTED_UNCORRECTABLE: u8 = 187;
+pub const AIRFLOW_TEMPERATURE: u8 = 190;
+pub const TEMPERATURE: u8 = 194;
+pub const PENDING_SECTORS: u8 = 197;
+pub const OFFLINE_UNCORRECTABLE: u8 = 198;
+
+/// One row of the ATA SMART attribute table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct SmartAttribute {
+    pub id: u8,
+    pub name: String,
+    /// Normalized health, usually counting down from 100 or 200.
+    pub value: u8,
+    /// Lowest `value` seen over the drive's life.
+    pub worst: u8,
+    /// `value` at or below this means the drive considers it failed; 0 never fails.
+    pub threshold: Option<u8>,
+    /// Vendor-specific raw counter; only its meaning for well-known IDs is portable.
+    pub raw: u64,
+    /// Pre-failure attribute, as opposed to one that just tracks age or use.
+    pub prefailure: bool,
+}
+
+impl SmartAttribute {
+    pub fn failing(&self) -> bool {
+        matches!(self.threshold, Some(threshold) if threshold > 0 && self.value <= threshold)
+    }
+
+    /// Temperature attributes keep the current reading in the low byte.
+    pub fn temperature(&self) -> Option<f32> {
+        matches!(self.id, TEMPERATURE | AIRFLOW_TEMPERATURE).then(|| (self.raw & 0xff) as f32)
+    }
+}
+
+/// Decodes the attribute table out of the SMART READ DATA sector, pairing it
+/// with the READ THRESHOLDS one when there is one.
+pub fn parse(values: &[u8], thresholds: Option<&[u8]>) -> Result<Vec<SmartAttribute>> {
+    if values.len() < SECTOR_SIZE || thresholds.is_some_and(|t| t.len() < SECTOR_SIZE) {
+        return Err(OverwatchError::backend("SMART data", "short sector"));
+    }
+
+    // 30 entries of 12 bytes after a 2-byte revision number
+    let entries = |sector: &[u8]| sector[2..2 + 30 * 12].chunks_exact(12).map(<[u8]>::to_vec).collect::<Vec<_>>();
+    let limits: Vec<(u8, u8)> = thresholds
+        .map(|sector| entries(sector).iter().map(|entry| (entry[0], entry[1])).collect())
+        .unwrap_or_default();
+
+    Ok(entries(values)
+        .iter()
+        .filter(|entry| entry[0] != 0)
+        .map(|entry| {
+            let id = entry[0];
+            let mut raw = [0u8; 8];
+            raw[..6].copy_from_slice(&entry[5..11]);
+            SmartAttribute {
+                id,
+                name: attribute_name(id).map(str::to_string).unwrap_or_else(|| format!("Attribute {}", id)),
+                value: entry[3],
+                worst: entry[4],
+                threshold: limits.iter().find(|(limit_id, _)| *limit_id == id).map(|(_, threshold)| *threshold),
+                raw: u64::from_le_bytes(raw),
+                prefailure: entry[1] & 1 != 0,
+            }
+        })
+        .collect())
+}
+
+/// The common meaning of an attribute ID; vendors reuse a few differently.
+pub fn attribute_name(id: u8) -> Option<&'static str> {
+    Some(match id {
+        1 => "Read error rate",
+        3 => "Spin-up time",
+        4 => "Start/stop count",
+        5 => "Reallocated sectors",
+        7 => "Seek error rate",
+        9 => "Power-on hours",
+        10 => "Spin retry count",
+        12 => "Power cycle count",
+        170 => "Available reserved space",
+        171 => "Program fail count",
+        172 => "Erase fail count",
+        173 => "Wear leveling count",
+        174 => "Unexpected power loss count",
+        177 => "Wear leveling count",
+        179 => "Used reserved blocks",
+        181 => "Program fail count",
+        182 => "Erase fail count",
+        183 => "Runtime bad blocks",
+        184 => "End-to-end errors",
+        187 => "Reported uncorrectable errors",
+        188 => "Command timeouts",
+        190 => "Airflow temperature",
+        192 => "Power-off retract count",
+        193 => "Load cycle count",
+        194 => "Temperature",
+        195 => "Hardware ECC recovered",
+        196 => "Reallocation events",
+        197 => "Pending sectors",
+        198 => "Offline uncorrectable sectors",
+        199 => "UDMA CRC errors",
+        200 => "Write error rate",
+        231 => "SSD life left",
+        233 => "Media wearout indicator",
+        241 => "Total LBAs written",
+        242 => "Total LBAs read",
+        _ => return None,
+    })
+}
+
+/// SMART values and thresholds through the legacy IDE ioctl, which libata
+/// translates for SATA disks, e.g. /dev/sda.
+#[cfg(target_os = "linux")]
+pub fn read_ioctl(device: &std::path::Path) -> Result<Vec<SmartAttribute>> {
+    use std::os::unix::io::AsRawFd;
+
+    const HDIO_DRIVE_CMD: libc::c_ulong = 0x031f;
+    const ATA_SMART: u8 = 0xb0;
+    const READ_VALUES: u8 = 0xd0;
+    const READ_THRESHOLDS: u8 = 0xd1;
+
+    let what = device.display().to_string();
+    let file = std::fs::File::open(device).map_err(|err| OverwatchError::from_io(what.clone(), err))?;
+    let command = |feature: u8| {
+        // command, sector number, feature, sector count, then the data
+        let mut buffer = [0u8; 4 + SECTOR_SIZE];
+        buffer[..4].copy_from_slice(&[ATA_SMART, 1, feature, 1]);
+        // SAFETY: HDIO_DRIVE_CMD reads a 4-byte header and writes one sector after it
+        match unsafe { libc::ioctl(file.as_raw_fd(), HDIO_DRIVE_CMD as _, buffer.as_mut_ptr()) } {
+            0 => Ok(buffer[4..].to_vec()),
+            _ => Err(OverwatchError::from_io(what.clone(), std::io::Error::last_os_error())),
+        }
+    };
+
+    let values = command(READ_VALUES)?;
+    // older drives without thresholds still have usable values
+    let thresholds = command(READ_THRESHOLDS).ok();
+    parse(&values, thresholds.as_deref())
+}
@@ -0,0 +1,347 @@
+//! Drive health from ATA SMART attributes and the NVMe health log, with an
+//! alert whenever a drive gets worse.
+
+pub mod ata;
+pub mod nvme;
+pub mod smartctl;
+
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use crate::snapshot::now_millis;
+use ata::SmartAttribute;
+use nvme::NvmeHealth;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+use tauri_specta::Event;
+
+/// SMART data barely moves between samples and smartctl isn't free, so
+/// snapshots and the background watch re-read it at most this often.
+const SNAPSHOT_REFRESH: Duration = Duration::from_secs(300);
+/// Status changes kept for the UI.
+pub const MAX_EVENTS: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StorageHealth {
+    /// One entry per physical drive, including ones that couldn't be read.
+    pub drives: Vec<DriveHealth>,
+    /// Drives getting worse, oldest first. Empty from agents, which only
+    /// report the current state.
+    #[serde(default)]
+    pub history: Vec<HealthEvent>,
+    pub provenance: Provenance,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DriveKind {
+    Nvme,
+    Ata,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Good,
+    /// Worth watching: remapped sectors, media errors, worn past 90%.
+    Warning,
+    /// The drive itself says it's failing or about to.
+    Failing,
+    /// Nothing could be read.
+    Unknown,
+}
+
+/// A drive whose status went to `Warning` or `Failing`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct HealthEvent {
+    /// Unix milliseconds when the change was seen.
+    pub at: u64,
+    pub drive: String,
+    pub model: Option<String>,
+    /// `None` on the first look at the drive.
+    pub from: Option<HealthStatus>,
+    pub to: HealthStatus,
+    pub problems: Vec<String>,
+}
+
+/// Emitted for every drive that gets worse, including ones already in a bad
+/// state when the app starts, which show up on its first look.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct StorageHealthAlert(pub HealthEvent);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DriveHealth {
+    /// Kernel name, e.g. "nvme0" or "sda"; smartctl's device name elsewhere.
+    pub name: String,
+    pub model: Option<String>,
+    pub kind: DriveKind,
+    pub status: HealthStatus,
+    /// Why the status isn't `Good`, worst first.
+    pub problems: Vec<String>,
+    /// The drive's own overall verdict, where the source reports one.
+    pub smart_passed: Option<bool>,
+    pub temperature: Option<f32>,
+    pub power_on_hours: Option<u64>,
+    pub nvme: Option<NvmeHealth>,
+    /// ATA drives only.
+    pub attributes: Vec<SmartAttribute>,
+    pub source: Source,
+    /// Why nothing could be read, for drives with `Unknown` status.
+    pub error: Option<OverwatchError>,
+}
+
+impl DriveHealth {
+    fn unreadable(name: String, model: Option<String>, kind: DriveKind, source: Source, error: OverwatchError) -> Self {
+        DriveHealth {
+            name,
+            model,
+            kind,
+            status: HealthStatus::Unknown,
+            problems: Vec::new(),
+            smart_passed: None,
+            temperature: None,
+            power_on_hours: None,
+            nvme: None,
+            attributes: Vec::new(),
+            source,
+            error: Some(error),
+        }
+    }
+
+    pub fn from_nvme(name: String, model: Option<String>, health: NvmeHealth, source: Source) -> Self {
+        DriveHealth {
+            temperature: health.temperature,
+            power_on_hours: Some(health.power_on_hours),
+            nvme: Some(health),
+            error: None,
+            ..DriveHealth::unreadable(name, model, DriveKind::Nvme, source, OverwatchError::not_found("SMART data"))
+        }
+        .assessed()
+    }
+
+    pub fn from_attributes(name: String, model: Option<String>, attributes: Vec<SmartAttribute>, source: Source) -> Self {
+        let raw = |id| attributes.iter().find(|attribute| attribute.id == id).map(|attribute| attribute.raw);
+        DriveHealth {
+            temperature: attributes.iter().find_map(SmartAttribute::temperature),
+            // some vendors pack minutes and seconds into the upper bytes
+            power_on_hours: raw(ata::POWER_ON_HOURS).map(|hours| hours & 0xffff_ffff),
+            attributes,
+            error: None,
+            ..DriveHealth::unreadable(name, model, DriveKind::Ata, source, OverwatchError::not_found("SMART data"))
+        }
+        .assessed()
+    }
+
+    /// Sets `status` and `problems` from whatever was read.
+    pub fn assessed(mut self) -> Self {
+        let mut failing: Vec<String> = Vec::new();
+        let mut warnings: Vec<String> = Vec::new();
+
+        if self.smart_passed == Some(false) {
+            failing.push("SMART overall self-assessment failed".to_string());
+        }
+        if let Some(health) = &self.nvme {
+            failing.extend(health.warnings().into_iter().map(str::to_string));
+            if health.percentage_used >= 90 {
+                warnings.push(format!("{}% of rated endurance used", health.percentage_used));
+            }
+            if health.media_errors > 0 {
+                warnings.push(format!("{} media errors", health.media_errors));
+            }
+        }
+        for attribute in &self.attributes {
+            if attribute.failing() {
+                let problem = format!("{} at or below threshold", attribute.name);
+                match attribute.prefailure {
+                    true => failing.push(problem),
+                    false => warnings.push(problem),
+                }
+            } else if attribute.raw > 0
+                && matches!(
+                    attribute.id,
+                    ata::REALLOCATED_SECTORS | ata::PENDING_SECTORS | ata::OFFLINE_UNCORRECTABLE | ata::REPORTED_UNCORRECTABLE
+                )
+            {
+                warnings.push(format!("{}: {}", attribute.name, attribute.raw));
+            }
+        }
+
+        let read_anything = self.nvme.is_some() || !self.attributes.is_empty() || self.smart_passed.is_some();
+        self.status = match (failing.is_empty(), warnings.is_empty()) {
+            (false, _) => HealthStatus::Failing,
+            (true, false) => HealthStatus::Warning,
+            (true, true) if read_anything => HealthStatus::Good,
+            (true, true) => HealthStatus::Unknown,
+        };
+        failing.extend(warnings);
+        self.problems = failing;
+        self
+    }
+}
+
+/// Each drive's last known status and the changes seen, kept in Tauri state.
+#[derive(Default)]
+pub struct HealthMonitor(Mutex<MonitorState>);
+
+#[derive(Default)]
+struct MonitorState {
+    last: HashMap<String, HealthStatus>,
+    history: Vec<HealthEvent>,
+}
+
+impl HealthMonitor {
+    /// Compares the drives with their last known status and returns the ones
+    /// that went to `Warning` or `Failing`, or got worse within them. The
+    /// first look at a drive compares against nothing, so drives already in
+    /// trouble are reported too. `Unknown` means the read failed, not that
+    /// the drive changed, so it keeps the last status.
+    pub fn update(&self, drives: &[DriveHealth], at: u64) -> Vec<HealthEvent> {
+        let mut state = self.0.lock().unwrap();
+        let mut events = Vec::new();
+        for drive in drives.iter().filter(|drive| drive.status != HealthStatus::Unknown) {
+            let previous = state.last.insert(drive.name.clone(), drive.status);
+            let worse = matches!(drive.status, HealthStatus::Warning | HealthStatus::Failing)
+                && previous.map_or(true, |previous| previous < drive.status);
+            if worse {
+                events.push(HealthEvent {
+                    at,
+                    drive: drive.name.clone(),
+                    model: drive.model.clone(),
+                    from: previous,
+                    to: drive.status,
+                    problems: drive.problems.clone(),
+                });
+            }
+        }
+        state.history.extend(events.iter().cloned());
+        let excess = state.history.len().saturating_sub(MAX_EVENTS);
+        state.history.drain(..excess);
+        events
+    }
+
+    pub fn history(&self) -> Vec<HealthEvent> {
+        self.0.lock().unwrap().history.clone()
+    }
+}
+
+/// Reads every drive and raises an alert for each one that got worse.
+pub fn check(app: &AppHandle) -> Result<StorageHealth> {
+    let monitor = app.state::<HealthMonitor>();
+    let session = app.state::<Session>();
+    let mut health = read_storage_health()?;
+    for event in monitor.update(&health.drives, now_millis()) {
+        session.record_event(StorageHealthAlert::NAME, &event);
+        StorageHealthAlert(event).emit(app).ok();
+    }
+    health.history = monitor.history();
+    Ok(health)
+}
+
+/// Keeps checking in the background, so a drive going bad raises an alert
+/// whether or not anything is looking at the card.
+pub fn watch(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        if !app.state::<Session>().is_replaying() {
+            if let Err(err) = check(&app) {
+                log::warn!("couldn't check drive health: {}", err);
+            }
+        }
+        std::thread::sleep(SNAPSHOT_REFRESH);
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_storage_health(app: AppHandle, session: State<'_, Session>) -> Result<StorageHealth> {
+    session.sample(|| check(&app))
+}
+
+pub fn read_storage_health() -> Result<StorageHealth> {
+    let (drives, source) = read_platform()?;
+    let mut provenance = Provenance::new();
+    provenance.available("drives", source);
+    Ok(StorageHealth { drives, history: Vec::new(), provenance })
+}
+
+/// `read_storage_health`, reused for `SNAPSHOT_REFRESH` after each read.
+pub fn read_storage_health_cached() -> Result<StorageHealth> {
+    static CACHE: Mutex<Option<(Instant, Result<StorageHealth>)>> = Mutex::new(None);
+
+    let mut cache = CACHE.lock().unwrap();
+    if let Some((read_at, health)) = cache.as_ref() {
+        if read_at.elapsed() < SNAPSHOT_REFRESH {
+            return health.clone();
+        }
+    }
+    let health = read_storage_health();
+    *cache = Some((Instant::now(), health.clone()));
+    health
+}
+
+// block devices from sysfs, each tried through its ioctl before smartctl
+#[cfg(target_os = "linux")]
+fn read_platform() -> Result<(Vec<DriveHealth>, Source)> {
+    use std::path::Path;
+
+    let entries = std::fs::read_dir("/sys/block").map_err(|err| OverwatchError::from_io("block devices", err))?;
+    let mut names: Vec<String> = entries.flatten().map(|entry| entry.file_name().to_string_lossy().into_owned()).collect();
+    names.sort();
+
+    let mut drives: Vec<DriveHealth> = Vec::new();
+    for block in names {
+        let (name, kind) = if let Some(rest) = block.strip_prefix("nvme") {
+            // namespaces share their controller's log: nvme0n1 -> nvme0
+            let controller: String = rest.chars().take_while(char::is_ascii_digit).collect();
+            (format!("nvme{}", controller), DriveKind::Nvme)
+        } else if block.starts_with("sd") {
+            (block.clone(), DriveKind::Ata)
+        } else {
+            // loop, dm, zram, mmcblk and friends have no SMART
+            continue;
+        };
+        if drives.iter().any(|drive| drive.name == name) {
+            continue;
+        }
+
+        let node = Path::new("/dev").join(&name);
+        let model = std::fs::read_to_string(Path::new("/sys/block").join(&block).join("device/model"))
+            .ok()
+            .map(|model| model.trim().to_string())
+            .filter(|model| !model.is_empty());
+
+        let native = match kind {
+            DriveKind::Nvme => nvme::read_ioctl(&node).map(|health| DriveHealth::from_nvme(name.clone(), model.clone(), health, Source::Ioctl)),
+            DriveKind::Ata => ata::read_ioctl(&node).map(|attributes| DriveHealth::from_attributes(name.clone(), model.clone(), attributes, Source::Ioctl)),
+        };
+        // the ioctl's error says more than "smartctl not found" does
+        let drive = native.or_else(|err| {
+            smartctl::read(&node.to_string_lossy())
+                .map(|drive| DriveHealth {
+                    name: name.clone(),
+                    model: drive.model.clone().or(model.clone()),
+                    ..drive
+                })
+                .map_err(|_| err)
+        });
+        drives.push(drive.unwrap_or_else(|err| DriveHealth::unreadable(name, model, kind, Source::Ioctl, err)));
+    }
+    Ok((drives, Source::Ioctl))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_platform() -> Result<(Vec<DriveHealth>, Source)> {
+    let drives = smartctl::scan()?
+        .into_iter()
+        .map(|device| {
+            smartctl::read(&device).unwrap_or_else(|err| {
+                let name = device.trim_start_matches("/dev/").to_string();
+                let kind = if name.starts_with("nvme") { DriveKind::Nvme } else { DriveKind::Ata };
+                DriveHealth::unreadable(name, None, kind, Source::Smartctl, err)
+            })
+        })
+        .collect();
+    Ok((drives, Source::Smartctl))
+}
@@ -0,0 +1,138 @@
+use crate::error::{OverwatchError, Result};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Size of the SMART / Health Information log page.
+pub const LOG_SIZE: usize = 512;
+
+/// The NVMe SMART / Health Information log (log page 02h).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct NvmeHealth {
+    /// Bit field; any bit set means the controller thinks something is wrong.
+    pub critical_warning: u8,
+    pub temperature: Option<f32>,
+    /// Spare capacity left, in percent.
+    pub available_spare: u8,
+    /// Spare level below which the drive raises a critical warning.
+    pub available_spare_threshold: u8,
+    /// Share of the rated endurance used up; can go past 100.
+    pub percentage_used: u8,
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+    pub power_cycles: u64,
+    pub power_on_hours: u64,
+    pub unsafe_shutdowns: u64,
+    /// Unrecovered data integrity errors.
+    pub media_errors: u64,
+    pub error_log_entries: u64,
+}
+
+impl NvmeHealth {
+    /// Decodes the raw log page.
+    pub fn parse(log: &[u8]) -> Result<Self> {
+        if log.len() < LOG_SIZE {
+            return Err(OverwatchError::backend("NVMe health log", format!("{} bytes, expected {}", log.len(), LOG_SIZE)));
+        }
+        // the counters are 128-bit; nothing real gets past 64
+        let counter = |offset: usize| {
+            let bytes: [u8; 8] = log[offset..offset + 8].try_into().unwrap();
+            let high: [u8; 8] = log[offset + 8..offset + 16].try_into().unwrap();
+            match u64::from_le_bytes(high) {
+                0 => u64::from_le_bytes(bytes),
+                _ => u64::MAX,
+            }
+        };
+        // data units are thousands of 512-byte blocks
+        let units = |offset| counter(offset).saturating_mul(512_000);
+        let kelvin = u16::from_le_bytes([log[1], log[2]]);
+
+        Ok(NvmeHealth {
+            critical_warning: log[0],
+            temperature: (kelvin != 0).then_some(kelvin as f32 - 273.0),
+            available_spare: log[3],
+            available_spare_threshold: log[4],
+            percentage_used: log[5],
+            bytes_read: units(32),
+            bytes_written: units(48),
+            power_cycles: counter(112),
+            power_on_hours: counter(128),
+            unsafe_shutdowns: counter(144),
+            media_errors: counter(160),
+            error_log_entries: counter(176),
+        })
+    }
+
+    /// What the critical warning bits mean, in words.
+    pub fn warnings(&self) -> Vec<&'static str> {
+        const BITS: [&str; 6] = [
+            "available spare below threshold",
+            "temperature outside its limits",
+            "reliability degraded by media errors",
+            "media placed in read-only mode",
+            "volatile memory backup failed",
+            "persistent memory region is read-only",
+        ];
+        BITS.iter()
+            .enumerate()
+            .filter(|(bit, _)| self.critical_warning & (1 << bit) != 0)
+            .map(|(_, text)| *text)
+            .collect()
+    }
+}
+
+/// Reads the health log through the controller's character device, e.g. /dev/nvme0.
+#[cfg(target_os = "linux")]
+pub fn read_ioctl(device: &std::path::Path) -> Result<NvmeHealth> {
+    use std::os::unix::io::AsRawFd;
+
+    // struct nvme_passthru_cmd from linux/nvme_ioctl.h
+    #[repr(C)]
+    #[derive(Default)]
+    struct PassthruCmd {
+        opcode: u8,
+        flags: u8,
+        rsvd1: u16,
+        nsid: u32,
+        cdw2: u32,
+        cdw3: u32,
+        metadata: u64,
+        addr: u64,
+        metadata_len: u32,
+        data_len: u32,
+        cdw10: u32,
+        cdw11: u32,
+        cdw12: u32,
+        cdw13: u32,
+        cdw14: u32,
+        cdw15: u32,
+        timeout_ms: u32,
+        result: u32,
+    }
+    // _IOWR('N', 0x41, struct nvme_passthru_cmd)
+    const NVME_IOCTL_ADMIN_CMD: libc::c_ulong = 0xc048_4e41;
+    const GET_LOG_PAGE: u8 = 0x02;
+    const SMART_LOG: u32 = 0x02;
+
+    let what = device.display().to_string();
+    let file = std::fs::File::open(device).map_err(|err| OverwatchError::from_io(what.clone(), err))?;
+    let mut log = [0u8; LOG_SIZE];
+    let mut cmd = PassthruCmd {
+        opcode: GET_LOG_PAGE,
+        // the controller-wide log rather than one namespace's
+        nsid: 0xffff_ffff,
+        addr: log.as_mut_ptr() as u64,
+        data_len: LOG_SIZE as u32,
+        // number of dwords minus one, then the log ID
+        cdw10: ((LOG_SIZE as u32 / 4 - 1) << 16) | SMART_LOG,
+        ..Default::default()
+    };
+
+    // SAFETY: `cmd` matches the kernel's layout and `addr` points at `data_len` writable bytes
+    let status = unsafe { libc::ioctl(file.as_raw_fd(), NVME_IOCTL_ADMIN_CMD as _, &mut cmd) };
+    match status {
+        0 => NvmeHealth::parse(&log),
+        -1 => Err(OverwatchError::from_io(what, std::io::Error::last_os_error())),
+        // a positive value is the NVMe status code
+        code => Err(OverwatchError::backend(what, format!("NVMe status {:#x}", code))),
+    }
+}
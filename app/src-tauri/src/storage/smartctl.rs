@@ -0,0 +1,206 @@
+//! Fallback through smartmontools' `smartctl --json`, for drives the native
+//! path can't reach (USB bridges, RAID controllers, Windows).
+
+use super::ata::{self, SmartAttribute};
+use super::nvme::NvmeHealth;
+use super::{DriveHealth, DriveKind, HealthStatus};
+use crate::error::{OverwatchError, Result, Source};
+use serde::Deserialize;
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Report {
+    smartctl: Status,
+    device: Device,
+    model_name: Option<String>,
+    smart_status: Option<SmartStatus>,
+    temperature: Option<Temperature>,
+    power_on_time: Option<PowerOnTime>,
+    nvme_smart_health_information_log: Option<NvmeLog>,
+    ata_smart_attributes: Option<AtaAttributes>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Status {
+    exit_status: u32,
+    messages: Vec<Message>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Message {
+    string: String,
+    severity: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Device {
+    name: String,
+    protocol: String,
+}
+
+#[derive(Deserialize)]
+struct SmartStatus {
+    passed: bool,
+}
+
+#[derive(Deserialize)]
+struct Temperature {
+    current: f32,
+}
+
+#[derive(Deserialize)]
+struct PowerOnTime {
+    hours: u64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct NvmeLog {
+    critical_warning: u8,
+    temperature: Option<f32>,
+    available_spare: u8,
+    available_spare_threshold: u8,
+    percentage_used: u8,
+    data_units_read: u64,
+    data_units_written: u64,
+    power_cycles: u64,
+    power_on_hours: u64,
+    unsafe_shutdowns: u64,
+    media_errors: u64,
+    num_err_log_entries: u64,
+}
+
+#[derive(Deserialize)]
+struct AtaAttributes {
+    table: Vec<AtaAttribute>,
+}
+
+#[derive(Deserialize)]
+struct AtaAttribute {
+    id: u8,
+    value: u8,
+    worst: u8,
+    thresh: Option<u8>,
+    flags: AtaFlags,
+    raw: AtaRaw,
+}
+
+#[derive(Deserialize)]
+struct AtaFlags {
+    prefailure: bool,
+}
+
+#[derive(Deserialize)]
+struct AtaRaw {
+    value: u64,
+}
+
+#[derive(Deserialize)]
+struct Scan {
+    devices: Vec<ScanDevice>,
+}
+
+#[derive(Deserialize)]
+struct ScanDevice {
+    name: String,
+}
+
+/// Device paths smartctl knows how to talk to.
+pub fn scan() -> Result<Vec<String>> {
+    let output = run(&["--scan", "--json"])?;
+    let scan: Scan = serde_json::from_slice(&output).map_err(|err| OverwatchError::backend("smartctl", err))?;
+    Ok(scan.devices.into_iter().map(|device| device.name).collect())
+}
+
+pub fn read(device: &str) -> Result<DriveHealth> {
+    // -n standby leaves sleeping disks alone instead of spinning them up
+    parse(&run(&["--json", "--all", "--nocheck=standby", device])?)
+}
+
+fn run(args: &[&str]) -> Result<Vec<u8>> {
+    let output = std::process::Command::new("smartctl")
+        .args(args)
+        .output()
+        .map_err(|err| OverwatchError::from_io("smartctl", err))?;
+    // the exit status is a bit mask that's nonzero for failing drives too, so
+    // the JSON is what says whether it worked
+    Ok(output.stdout)
+}
+
+/// Builds a drive out of one `smartctl --json --all` report.
+pub fn parse(json: &[u8]) -> Result<DriveHealth> {
+    let report: Report = serde_json::from_slice(json).map_err(|err| OverwatchError::backend("smartctl", err))?;
+
+    // bit 0 is a bad command line, bit 1 a device it couldn't open or that's asleep
+    if report.smartctl.exit_status & 0b11 != 0 {
+        let message = report
+            .smartctl
+            .messages
+            .iter()
+            .find(|message| message.severity == "error")
+            .or(report.smartctl.messages.first())
+            .map(|message| message.string.clone())
+            .unwrap_or_else(|| format!("exit status {}", report.smartctl.exit_status));
+        return Err(match message.contains("Permission denied") || message.contains("Operation not permitted") {
+            true => OverwatchError::permission_denied(format!("smartctl {}", report.device.name)),
+            false => OverwatchError::backend("smartctl", message),
+        });
+    }
+
+    let nvme = report.nvme_smart_health_information_log.map(|log| NvmeHealth {
+        critical_warning: log.critical_warning,
+        temperature: log.temperature,
+        available_spare: log.available_spare,
+        available_spare_threshold: log.available_spare_threshold,
+        percentage_used: log.percentage_used,
+        bytes_read: log.data_units_read.saturating_mul(512_000),
+        bytes_written: log.data_units_written.saturating_mul(512_000),
+        power_cycles: log.power_cycles,
+        power_on_hours: log.power_on_hours,
+        unsafe_shutdowns: log.unsafe_shutdowns,
+        media_errors: log.media_errors,
+        error_log_entries: log.num_err_log_entries,
+    });
+    let attributes: Vec<SmartAttribute> = report
+        .ata_smart_attributes
+        .map(|attributes| attributes.table)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|attribute| SmartAttribute {
+            id: attribute.id,
+            name: ata::attribute_name(attribute.id)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("Attribute {}", attribute.id)),
+            value: attribute.value,
+            worst: attribute.worst,
+            threshold: attribute.thresh,
+            raw: attribute.raw.value,
+            prefailure: attribute.flags.prefailure,
+        })
+        .collect();
+
+    let kind = match (&nvme, report.device.protocol.as_str()) {
+        (Some(_), _) | (None, "NVMe") => DriveKind::Nvme,
+        _ => DriveKind::Ata,
+    };
+    let name = report.device.name.trim_start_matches("/dev/").to_string();
+
+    Ok(DriveHealth {
+        name,
+        model: report.model_name,
+        kind,
+        status: HealthStatus::Unknown,
+        problems: Vec::new(),
+        smart_passed: report.smart_status.map(|status| status.passed),
+        temperature: report.temperature.map(|temperature| temperature.current),
+        power_on_hours: report.power_on_time.map(|time| time.hours),
+        nvme,
+        attributes,
+        source: Source::Smartctl,
+        error: None,
+    }
+    .assessed())
+}
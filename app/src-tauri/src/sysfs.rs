@@ -0,0 +1,16 @@
+//! Reading single-value attribute files from sysfs and procfs.
+
+use std::path::Path;
+use std::str::FromStr;
+
+/// An attribute's contents without the trailing newline, `None` when it
+/// can't be read or is empty.
+pub fn read_attr(path: &Path) -> Option<String> {
+    let text = std::fs::read_to_string(path).ok()?;
+    Some(text.trim().to_string()).filter(|text| !text.is_empty())
+}
+
+/// An attribute holding a single number.
+pub fn read_number<T: FromStr>(path: &Path) -> Option<T> {
+    read_attr(path)?.parse().ok()
+}
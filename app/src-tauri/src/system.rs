@@ -0,0 +1,164 @@
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use sysinfo::System;
+use tauri::State;
+
+const DEFAULT_BOOT_HISTORY: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SystemInfo {
+    pub hostname: String,
+    pub os_name: String,
+    pub os_version: String,
+    pub kernel_version: String,
+    pub architecture: String,
+    /// Unix timestamp in seconds.
+    pub boot_time: u64,
+    pub uptime_secs: u64,
+    /// Most recent boot first.
+    pub boot_history: Vec<BootRecord>,
+    pub provenance: Provenance,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct BootRecord {
+    pub boot_id: Option<String>,
+    /// Unix timestamp in seconds.
+    pub boot_time: u64,
+    /// Last time anything was logged during this boot, if known.
+    pub last_seen: Option<u64>,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_system_info(session: State<'_, Session>, boot_history: Option<u32>) -> Result<SystemInfo> {
+    session.sample(|| read_system_info(boot_history))
+}
+
+pub fn read_system_info(boot_history: Option<u32>) -> Result<SystemInfo> {
+    let mut provenance = Provenance::new();
+
+    let hostname = text_field(&mut provenance, "hostname", System::host_name());
+    let os_name = text_field(&mut provenance, "os_name", System::name());
+    let os_version = text_field(&mut provenance, "os_version", System::os_version());
+    let kernel_version = text_field(&mut provenance, "kernel_version", System::kernel_version());
+    let architecture = text_field(&mut provenance, "architecture", System::cpu_arch());
+
+    let limit = boot_history.map(|n| n as usize).unwrap_or(DEFAULT_BOOT_HISTORY);
+    let boot_history = match get_boot_history(limit) {
+        Ok((history, source)) => {
+            provenance.available("boot_history", source);
+            history
+        }
+        Err(err) => {
+            provenance.missing("boot_history", err);
+            Vec::new()
+        }
+    };
+
+    Ok(SystemInfo {
+        hostname,
+        os_name,
+        os_version,
+        kernel_version,
+        architecture,
+        boot_time: System::boot_time(),
+        uptime_secs: System::uptime(),
+        boot_history,
+        provenance,
+    })
+}
+
+fn text_field(provenance: &mut Provenance, field: &str, value: Option<String>) -> String {
+    let value = value.ok_or_else(|| OverwatchError::not_found(field.replace('_', " ")));
+    provenance.record(field, Source::Sysinfo, &value);
+    value.unwrap_or_else(|_| "Unknown".to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn get_boot_history(limit: usize) -> Result<(Vec<BootRecord>, Source)> {
+    // journald keeps the best record, wtmp covers systems without a persistent journal
+    match journal_boots(limit) {
+        Ok(boots) if !boots.is_empty() => Ok((boots, Source::Journal)),
+        journal => match wtmp_boots(std::path::Path::new("/var/log/wtmp"), limit) {
+            Ok(boots) => Ok((boots, Source::Wtmp)),
+            Err(err) => Err(journal.err().unwrap_or(err)),
+        },
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_boot_history(_limit: usize) -> Result<(Vec<BootRecord>, Source)> {
+    Err(OverwatchError::unsupported("boot history"))
+}
+
+#[cfg(target_os = "linux")]
+fn journal_boots(limit: usize) -> Result<Vec<BootRecord>> {
+    let output = std::process::Command::new("journalctl")
+        .args(["--list-boots", "--output=json", "--no-pager"])
+        .output()
+        .map_err(|err| OverwatchError::from_io("journalctl", err))?;
+    if !output.status.success() {
+        return Err(OverwatchError::backend("journalctl", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    parse_journal_boots(&output.stdout, limit)
+}
+
+/// `journalctl --list-boots --output=json`, most recent boot first.
+pub fn parse_journal_boots(json: &[u8], limit: usize) -> Result<Vec<BootRecord>> {
+    #[derive(Deserialize)]
+    struct JournalBoot {
+        boot_id: String,
+        first_entry: u64,
+        last_entry: u64,
+    }
+
+    let boots: Vec<JournalBoot> = serde_json::from_slice(json).map_err(|err| OverwatchError::backend("journalctl", err))?;
+
+    // journalctl lists oldest first, timestamps are in microseconds
+    Ok(boots
+        .into_iter()
+        .rev()
+        .take(limit)
+        .map(|boot| BootRecord {
+            boot_id: Some(boot.boot_id),
+            boot_time: boot.first_entry / 1_000_000,
+            last_seen: Some(boot.last_entry / 1_000_000),
+        })
+        .collect())
+}
+
+#[cfg(target_os = "linux")]
+fn wtmp_boots(path: &std::path::Path, limit: usize) -> Result<Vec<BootRecord>> {
+    let data = std::fs::read(path).map_err(|err| OverwatchError::from_io("wtmp", err))?;
+    Ok(parse_wtmp(&data, limit))
+}
+
+/// BOOT_TIME records out of a glibc utmp/wtmp file, most recent first.
+/// Records are `struct utmp` as 64-bit Linux lays it out, in host byte
+/// order; a partial record at the end is ignored.
+pub fn parse_wtmp(data: &[u8], limit: usize) -> Vec<BootRecord> {
+    const RECORD_SIZE: usize = 384;
+    const BOOT_TIME: i16 = 2;
+    const TYPE_OFFSET: usize = 0;
+    const TV_SEC_OFFSET: usize = 340;
+
+    let mut boots: Vec<BootRecord> = data
+        .chunks_exact(RECORD_SIZE)
+        .filter(|record| i16::from_ne_bytes([record[TYPE_OFFSET], record[TYPE_OFFSET + 1]]) == BOOT_TIME)
+        .map(|record| {
+            let secs = &record[TV_SEC_OFFSET..TV_SEC_OFFSET + 4];
+            BootRecord {
+                boot_id: None,
+                boot_time: u32::from_ne_bytes([secs[0], secs[1], secs[2], secs[3]]) as u64,
+                last_seen: None,
+            }
+        })
+        .collect();
+
+    boots.reverse();
+    boots.truncate(limit);
+    boots
+}
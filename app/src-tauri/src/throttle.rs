@@ -0,0 +1,470 @@
+//! Thermal and power throttling, kept as timestamped periods.
+//!
+//! Throttling is only seen through sampling: the kernel's throttle counters
+//! going up between two reads, cpufreq limits below the hardware maximum,
+//! clocks held low under load, and the GPU's own throttle reasons. A period
+//! runs from the first sample that saw it to the last, so it's only as
+//! precise as the polling.
+
+use crate::error::{OverwatchError, Provenance, Result, Source};
+use crate::session::Session;
+use crate::snapshot::{now_millis, FIRST_WINDOW};
+use crate::sysfs::read_number;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, VecDeque};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::State;
+
+#[cfg(windows)]
+use crate::wmi_query;
+#[cfg(windows)]
+use wmi::Variant;
+
+/// Finished periods kept for the UI.
+pub const MAX_EVENTS: usize = 200;
+// busy share above which clocks below the floor count as throttling
+const FULL_LOAD: f64 = 0.9;
+// how far back a counter that went up dates its period, whatever the gap
+// since the last sample; the card polls this often
+const MAX_BACKDATE: Duration = Duration::from_secs(2);
+// nvidia-smi takes a moment to start, so its answer is reused for a while
+const GPU_REFRESH: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrottleCause {
+    Thermal,
+    /// Held to a power or current limit.
+    PowerLimit,
+    /// Clocks held low under load without the hardware saying why, or a
+    /// GPU clock limit.
+    FrequencyCap,
+    /// Anything else the hardware reports, e.g. GPU sync boost.
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct ThrottleEvent {
+    /// "CPU" or "GPU 0".
+    pub device: String,
+    pub cause: ThrottleCause,
+    /// Which cores, the limit, or the reasons the hardware gave.
+    pub detail: String,
+    /// Unix milliseconds.
+    pub started_at: u64,
+    /// `None` while it's still going on.
+    pub ended_at: Option<u64>,
+    pub duration_ms: u64,
+}
+
+/// A cpufreq limit below the hardware maximum. It's policy — a power
+/// profile, powersave, a user setting — that stays until someone changes
+/// it, so it's reported as it stands rather than as a throttle period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct PolicyCap {
+    /// The lowest limit among the capped CPUs.
+    pub limit_khz: u64,
+    pub cpus: u32,
+    pub total_cpus: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ThrottleStatus {
+    /// Periods still going on as of this sample.
+    pub active: Vec<ThrottleEvent>,
+    /// Finished periods, oldest first.
+    pub events: Vec<ThrottleEvent>,
+    /// `None` in recordings that predate it.
+    #[serde(default)]
+    pub policy_cap: Option<PolicyCap>,
+    pub provenance: Provenance,
+}
+
+/// Throttling that holds at the moment of a sample.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub device: String,
+    pub cause: ThrottleCause,
+    pub detail: String,
+}
+
+/// A counter the kernel bumps every time throttling kicks in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThrottleCounter {
+    /// Stable ID to match readings up, e.g. "cpu3/core_throttle_count".
+    pub key: String,
+    pub device: String,
+    pub cause: ThrottleCause,
+    /// "CPU 3" or "package 0".
+    pub detail: String,
+    pub count: u64,
+}
+
+/// Average clock of the CPUs against what they should reach, in kHz.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clocks {
+    pub current_khz: u64,
+    /// The base clock where the driver gives it, otherwise the maximum.
+    pub reference_khz: u64,
+    pub reference_is_base: bool,
+}
+
+/// Everything one read turned up.
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleSample {
+    pub counters: Vec<ThrottleCounter>,
+    pub conditions: Vec<Condition>,
+    pub clocks: Option<Clocks>,
+    pub policy_cap: Option<PolicyCap>,
+    /// Busy and total CPU time since boot, in any unit.
+    pub cpu_time: Option<(u64, u64)>,
+}
+
+/// Throttle counters, cpufreq caps and clocks under a
+/// `/sys/devices/system/cpu`-style directory.
+pub fn read_cpu_sysfs(root: &Path) -> Result<ThrottleSample> {
+    let entries = std::fs::read_dir(root).map_err(|err| OverwatchError::from_io("CPU sysfs", err))?;
+    let mut cpus: Vec<u32> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.strip_prefix("cpu")?.parse().ok())
+        .collect();
+    cpus.sort_unstable();
+    if cpus.is_empty() {
+        return Err(OverwatchError::not_found("CPUs in sysfs"));
+    }
+
+    let mut sample = ThrottleSample::default();
+    let mut capped = Vec::new();
+    let (mut current, mut reference, mut with_clocks, mut base) = (0, 0, 0, true);
+    for cpu in &cpus {
+        let dir = root.join(format!("cpu{}", cpu));
+        let number = |attr: &str| read_number::<u64>(&dir.join(attr));
+
+        // Intel's therm_throt driver; package counters repeat on every CPU of the package
+        let package = number("topology/physical_package_id").unwrap_or(0);
+        let counters = [
+            ("core_throttle_count", ThrottleCause::Thermal, false),
+            ("core_power_limit_count", ThrottleCause::PowerLimit, false),
+            ("package_throttle_count", ThrottleCause::Thermal, true),
+            ("package_power_limit_count", ThrottleCause::PowerLimit, true),
+        ];
+        for (attr, cause, package_wide) in counters {
+            let Some(count) = number(&format!("thermal_throttle/{}", attr)) else {
+                continue;
+            };
+            let (key, detail) = match package_wide {
+                true => (format!("package{}/{}", package, attr), format!("package {}", package)),
+                false => (format!("cpu{}/{}", cpu, attr), format!("CPU {}", cpu)),
+            };
+            if !sample.counters.iter().any(|counter| counter.key == key) {
+                sample.counters.push(ThrottleCounter { key, device: "CPU".to_string(), cause, detail, count });
+            }
+        }
+
+        let hardware_max = number("cpufreq/cpuinfo_max_freq");
+        if let (Some(limit), Some(max)) = (number("cpufreq/scaling_max_freq"), hardware_max) {
+            if limit < max {
+                capped.push(limit);
+            }
+        }
+        if let Some(now) = number("cpufreq/scaling_cur_freq") {
+            let base_clock = number("cpufreq/base_frequency");
+            if let Some(against) = base_clock.or(hardware_max) {
+                current += now;
+                reference += against;
+                with_clocks += 1;
+                base &= base_clock.is_some();
+            }
+        }
+    }
+
+    sample.policy_cap = capped.iter().min().map(|&limit_khz| PolicyCap {
+        limit_khz,
+        cpus: capped.len() as u32,
+        total_cpus: cpus.len() as u32,
+    });
+    sample.clocks = current.checked_div(with_clocks).map(|current_khz| Clocks {
+        current_khz,
+        reference_khz: reference / with_clocks,
+        reference_is_base: base,
+    });
+    Ok(sample)
+}
+
+/// Busy and total time from the aggregate line of a `/proc/stat`-style file.
+pub fn read_proc_stat(path: &Path) -> Option<(u64, u64)> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let line = text.lines().find(|line| line.starts_with("cpu "))?;
+    // user nice system idle iowait irq softirq steal; guest time is already in user
+    let fields: Vec<u64> = line.split_whitespace().skip(1).take(8).filter_map(|field| field.parse().ok()).collect();
+    if fields.len() < 5 {
+        return None;
+    }
+    let total: u64 = fields.iter().sum();
+    Some((total - fields[3] - fields[4], total))
+}
+
+/// Conditions out of `nvidia-smi --query-gpu=index,clocks_throttle_reasons.active
+/// --format=csv,noheader` output.
+pub fn parse_nvidia_smi(output: &str) -> Vec<Condition> {
+    // bits of nvmlClocksThrottleReasons; 0x1 is just the GPU idling
+    const REASONS: [(u64, ThrottleCause, &str); 8] = [
+        (0x2, ThrottleCause::FrequencyCap, "application clocks"),
+        (0x4, ThrottleCause::PowerLimit, "software power cap"),
+        (0x8, ThrottleCause::Other, "hardware slowdown"),
+        (0x10, ThrottleCause::Other, "sync boost"),
+        (0x20, ThrottleCause::Thermal, "software thermal slowdown"),
+        (0x40, ThrottleCause::Thermal, "hardware thermal slowdown"),
+        (0x80, ThrottleCause::PowerLimit, "power brake"),
+        (0x100, ThrottleCause::FrequencyCap, "display clock"),
+    ];
+    const HW_SLOWDOWN_CAUSES: u64 = 0x40 | 0x80;
+
+    let mut conditions: Vec<Condition> = Vec::new();
+    for line in output.lines() {
+        let Some((index, reasons)) = line.split_once(',') else {
+            continue;
+        };
+        let Ok(mask) = u64::from_str_radix(reasons.trim().trim_start_matches("0x"), 16) else {
+            continue;
+        };
+        let device = format!("GPU {}", index.trim());
+        for (bit, cause, text) in REASONS {
+            // hardware slowdown comes with the bit that says why, when there is one
+            if mask & bit == 0 || (bit == 0x8 && mask & HW_SLOWDOWN_CAUSES != 0) {
+                continue;
+            }
+            match conditions.iter_mut().find(|condition| condition.device == device && condition.cause == cause) {
+                Some(condition) => condition.detail = format!("{}, {}", condition.detail, text),
+                None => conditions.push(Condition { device: device.clone(), cause, detail: text.to_string() }),
+            }
+        }
+    }
+    conditions
+}
+
+fn read_nvidia_smi() -> Result<Vec<Condition>> {
+    let output = std::process::Command::new("nvidia-smi")
+        .args(["--query-gpu=index,clocks_throttle_reasons.active", "--format=csv,noheader"])
+        .output()
+        .map_err(|err| OverwatchError::from_io("nvidia-smi", err))?;
+    if !output.status.success() {
+        return Err(OverwatchError::backend("nvidia-smi", String::from_utf8_lossy(&output.stdout).trim()));
+    }
+    Ok(parse_nvidia_smi(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(target_os = "linux")]
+fn read_cpu(provenance: &mut Provenance) -> ThrottleSample {
+    let mut sample = match read_cpu_sysfs(Path::new("/sys/devices/system/cpu")) {
+        Ok(sample) => sample,
+        Err(err) => {
+            provenance.missing("cpu_counters", err.clone());
+            provenance.missing("cpu_frequency", err);
+            return ThrottleSample::default();
+        }
+    };
+    match sample.counters.is_empty() {
+        // only Intel's driver exposes them
+        true => provenance.missing("cpu_counters", OverwatchError::not_found("thermal_throttle counters")),
+        false => provenance.available("cpu_counters", Source::Sysfs),
+    }
+    match sample.clocks {
+        Some(_) => provenance.available("cpu_frequency", Source::Sysfs),
+        None => provenance.missing("cpu_frequency", OverwatchError::not_found("cpufreq")),
+    }
+    sample.cpu_time = read_proc_stat(Path::new("/proc/stat"));
+    sample
+}
+
+// the performance counters say how far the firmware holds the CPU back, not why
+#[cfg(windows)]
+fn read_cpu(provenance: &mut Provenance) -> ThrottleSample {
+    provenance.missing("cpu_counters", OverwatchError::unsupported("CPU throttle counters"));
+    let limit = wmi_query::connect().and_then(|con| {
+        let rows = wmi_query::query(
+            &con,
+            "SELECT PercentPerformanceLimit FROM Win32_PerfFormattedData_Counters_ProcessorInformation WHERE Name = '_Total'",
+        )?;
+        match rows.first().and_then(|row| row.get("PercentPerformanceLimit")) {
+            Some(Variant::UI4(limit)) => Ok(*limit as u64),
+            Some(Variant::UI8(limit)) => Ok(*limit),
+            Some(Variant::String(limit)) => limit.parse().map_err(|err| OverwatchError::backend("PercentPerformanceLimit", err)),
+            _ => Err(OverwatchError::not_found("processor performance limit")),
+        }
+    });
+
+    let mut sample = ThrottleSample::default();
+    match limit {
+        Ok(limit) => {
+            provenance.available("cpu_frequency", Source::Wmi);
+            if limit < 100 {
+                sample.conditions.push(Condition {
+                    device: "CPU".to_string(),
+                    cause: ThrottleCause::FrequencyCap,
+                    detail: format!("limited to {}% of maximum performance", limit),
+                });
+            }
+        }
+        Err(err) => provenance.missing("cpu_frequency", err),
+    }
+    sample
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn read_cpu(provenance: &mut Provenance) -> ThrottleSample {
+    provenance.missing("cpu_counters", OverwatchError::unsupported("CPU throttle counters"));
+    provenance.missing("cpu_frequency", OverwatchError::unsupported("CPU frequency limits"));
+    ThrottleSample::default()
+}
+
+/// Open and finished throttle periods, kept in Tauri state.
+#[derive(Default)]
+pub struct ThrottleMonitor(Mutex<MonitorState>);
+
+#[derive(Default)]
+struct MonitorState {
+    last: Option<Baseline>,
+    open: BTreeMap<(String, ThrottleCause), ThrottleEvent>,
+    events: VecDeque<ThrottleEvent>,
+    gpu: Option<(Instant, Result<Vec<Condition>>)>,
+}
+
+struct Baseline {
+    at: u64,
+    counts: BTreeMap<String, u64>,
+    cpu_time: Option<(u64, u64)>,
+}
+
+impl ThrottleMonitor {
+    /// Folds a sample taken at `at` (unix milliseconds) into the periods and
+    /// returns the ones still going on. A counter that went up opens its
+    /// period at the previous sample, since it happened somewhere in between,
+    /// but no more than a poll interval back.
+    pub fn update(&self, sample: ThrottleSample, at: u64) -> Vec<ThrottleEvent> {
+        let mut state = self.0.lock().unwrap();
+        let mut seen: BTreeMap<(String, ThrottleCause), (u64, Vec<String>)> = BTreeMap::new();
+        for condition in sample.conditions {
+            seen.entry((condition.device, condition.cause)).or_insert((at, Vec::new())).1.push(condition.detail);
+        }
+
+        if let Some(previous) = &state.last {
+            let bumped_since = previous.at.max(at.saturating_sub(MAX_BACKDATE.as_millis() as u64));
+            for counter in &sample.counters {
+                if counter.count > *previous.counts.get(&counter.key).unwrap_or(&counter.count) {
+                    let (since, details) = seen.entry((counter.device.clone(), counter.cause)).or_insert((bumped_since, Vec::new()));
+                    *since = (*since).min(bumped_since);
+                    details.push(counter.detail.clone());
+                }
+            }
+
+            // busy, and below the base clock (or well below the maximum, or the
+            // policy cap when that's lower) with nothing else to blame
+            let load = previous.cpu_time.zip(sample.cpu_time).and_then(|((busy0, total0), (busy1, total1))| {
+                (total1 > total0).then(|| busy1.saturating_sub(busy0) as f64 / (total1 - total0) as f64)
+            });
+            if let (Some(load), Some(clocks)) = (load, sample.clocks) {
+                let (reference, floor, against) = match sample.policy_cap.as_ref().filter(|cap| cap.limit_khz < clocks.reference_khz) {
+                    Some(cap) => (cap.limit_khz, 0.9, "cap"),
+                    None if clocks.reference_is_base => (clocks.reference_khz, 0.9, "base"),
+                    None => (clocks.reference_khz, 0.5, "max"),
+                };
+                let explained = seen.keys().any(|(device, _)| device == "CPU");
+                if load >= FULL_LOAD && (clocks.current_khz as f64) < reference as f64 * floor && !explained {
+                    let detail = format!("{} MHz at {:.0}% load, {} {} MHz", clocks.current_khz / 1000, load * 100.0, against, reference / 1000);
+                    seen.insert(("CPU".to_string(), ThrottleCause::FrequencyCap), (at, vec![detail]));
+                }
+            }
+        }
+
+        let ended: Vec<_> = state.open.keys().filter(|key| !seen.contains_key(*key)).cloned().collect();
+        for key in ended {
+            let mut event = state.open.remove(&key).unwrap();
+            event.ended_at = Some(event.started_at + event.duration_ms);
+            state.events.push_back(event);
+        }
+        while state.events.len() > MAX_EVENTS {
+            state.events.pop_front();
+        }
+
+        for ((device, cause), (since, mut details)) in seen {
+            details.dedup();
+            let detail = match details.len() {
+                0..=4 => details.join(", "),
+                more => format!("{}, {} and {} more", details[0], details[1], more - 2),
+            };
+            let event = state.open.entry((device.clone(), cause)).or_insert(ThrottleEvent {
+                device,
+                cause,
+                detail: String::new(),
+                started_at: since,
+                ended_at: None,
+                duration_ms: 0,
+            });
+            event.detail = detail;
+            event.duration_ms = at.saturating_sub(event.started_at);
+        }
+
+        state.last = Some(Baseline {
+            at,
+            counts: sample.counters.iter().map(|counter| (counter.key.clone(), counter.count)).collect(),
+            cpu_time: sample.cpu_time,
+        });
+        state.open.values().cloned().collect()
+    }
+
+    /// Finished periods, oldest first.
+    pub fn events(&self) -> Vec<ThrottleEvent> {
+        self.0.lock().unwrap().events.iter().cloned().collect()
+    }
+
+    fn read_sample(&self) -> (ThrottleSample, Provenance) {
+        let mut provenance = Provenance::new();
+        let mut sample = read_cpu(&mut provenance);
+        match self.read_gpu() {
+            Ok(conditions) => {
+                provenance.available("gpu", Source::NvidiaSmi);
+                sample.conditions.extend(conditions);
+            }
+            Err(err) => provenance.missing("gpu", err),
+        }
+        (sample, provenance)
+    }
+
+    fn read_gpu(&self) -> Result<Vec<Condition>> {
+        if let Some((read_at, result)) = &self.0.lock().unwrap().gpu {
+            // not installed won't change while we run
+            if read_at.elapsed() < GPU_REFRESH || matches!(result, Err(OverwatchError::NotFound { .. })) {
+                return result.clone();
+            }
+        }
+        let result = read_nvidia_smi();
+        self.0.lock().unwrap().gpu = Some((Instant::now(), result.clone()));
+        result
+    }
+
+    pub fn read(&self) -> Result<ThrottleStatus> {
+        let (mut sample, mut provenance) = self.read_sample();
+        if self.0.lock().unwrap().last.is_none() {
+            self.update(sample, now_millis());
+            std::thread::sleep(FIRST_WINDOW);
+            (sample, provenance) = self.read_sample();
+        }
+        if !provenance.is_measured("cpu_counters") && !provenance.is_measured("cpu_frequency") && !provenance.is_measured("gpu") {
+            return Err(OverwatchError::unsupported("throttling detection"));
+        }
+
+        let policy_cap = sample.policy_cap.clone();
+        let active = self.update(sample, now_millis());
+        Ok(ThrottleStatus { active, events: self.events(), policy_cap, provenance })
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_throttle_status(session: State<'_, Session>, monitor: State<'_, ThrottleMonitor>) -> Result<ThrottleStatus> {
+    session.sample(|| monitor.read())
+}
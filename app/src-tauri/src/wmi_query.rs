@@ -0,0 +1,45 @@
+use crate::error::Result;
+use std::collections::HashMap;
+use wmi::{COMLibrary, Variant, WMIConnection};
+
+pub type WmiRow = HashMap<String, Variant>;
+
+pub fn connect() -> Result<WMIConnection> {
+    let com_con = COMLibrary::new()?;
+    Ok(WMIConnection::new(com_con)?)
+}
+
+pub fn connect_namespace(namespace: &str) -> Result<WMIConnection> {
+    let com_con = COMLibrary::new()?;
+    Ok(WMIConnection::with_namespace_path(namespace, com_con)?)
+}
+
+pub fn query(wmi_con: &WMIConnection, sql: &str) -> Result<Vec<WmiRow>> {
+    Ok(wmi_con.raw_query::<WmiRow>(sql)?)
+}
+
+/// A numeric property, whatever integer type it came back as. 64-bit
+/// counters come back as strings.
+pub fn number(row: &WmiRow, prop: &str) -> Option<f64> {
+    match row.get(prop)? {
+        Variant::UI1(value) => Some(*value as f64),
+        Variant::UI2(value) => Some(*value as f64),
+        Variant::UI4(value) => Some(*value as f64),
+        Variant::UI8(value) => Some(*value as f64),
+        Variant::I1(value) => Some(*value as f64),
+        Variant::I2(value) => Some(*value as f64),
+        Variant::I4(value) => Some(*value as f64),
+        Variant::I8(value) => Some(*value as f64),
+        Variant::R4(value) => Some(*value as f64),
+        Variant::R8(value) => Some(*value),
+        Variant::String(value) => value.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// The logical CPU a processor counter instance is for. Instances are named
+/// "group,index" with 64 CPUs per group; "_Total" and "0,_Total" give `None`.
+pub fn processor_number(name: &str) -> Option<u32> {
+    let (group, index) = name.split_once(',')?;
+    Some(group.parse::<u32>().ok()? * 64 + index.parse::<u32>().ok()?)
+}
@@ -0,0 +1,23 @@
+use std::path::Path;
+
+// Fails when src/bindings.ts no longer matches the Rust types. Run the app in
+// debug once, or rerun this with OVERWATCH_UPDATE_BINDINGS=1, to regenerate.
+#[test]
+fn bindings_are_up_to_date() {
+    let generated = app_lib::specta_builder()
+        .export_str(app_lib::bindings_language())
+        .expect("failed to render TypeScript bindings");
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(app_lib::BINDINGS_PATH);
+    if std::env::var_os("OVERWATCH_UPDATE_BINDINGS").is_some() {
+        std::fs::write(&path, &generated).expect("failed to write bindings");
+        return;
+    }
+
+    let checked_in = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        checked_in == generated,
+        "{} is stale, regenerate it with OVERWATCH_UPDATE_BINDINGS=1 cargo test --test bindings",
+        path.display()
+    );
+}
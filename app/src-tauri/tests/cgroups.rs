@@ -0,0 +1,106 @@
+use app_lib::cgroups::{self, CgroupKind};
+use std::collections::HashMap;
+use std::time::Duration;
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+const ID: &str = "4f1c2a9e8b7d6c5b4a3f2e1d0c9b8a7f6e5d4c3b2a1f0e9d8c7b6a5f4e3d2c1b";
+
+fn cpu_stat(usage: u64, periods: u64, throttled: u64, throttled_usec: u64) -> String {
+    format!(
+        "usage_usec {}\nuser_usec 0\nsystem_usec 0\nnr_periods {}\nnr_throttled {}\nthrottled_usec {}",
+        usage, periods, throttled, throttled_usec
+    )
+}
+
+#[test]
+fn parsers() {
+    assert_eq!(cgroups::parse_cpu_max("max 100000"), None);
+    assert_eq!(cgroups::parse_cpu_max("150000 100000"), Some(1.5));
+    assert_eq!(cgroups::parse_limit("max"), None);
+    assert_eq!(cgroups::parse_limit("536870912\n"), Some(536870912));
+    assert_eq!(
+        cgroups::parse_io_stat("8:0 rbytes=4096 wbytes=8192 rios=1 wios=2 dbytes=0 dios=0\n259:0 rbytes=100 wbytes=0 rios=1 wios=0\n"),
+        (4196, 8192)
+    );
+
+    assert_eq!(cgroups::classify("system.slice", ""), Some((CgroupKind::Slice, None)));
+    assert_eq!(cgroups::classify("sshd.service", "system.slice"), Some((CgroupKind::Service, None)));
+    assert_eq!(
+        cgroups::classify(&format!("docker-{}.scope", ID), "system.slice"),
+        Some((CgroupKind::Container, Some(("docker".to_string(), ID.to_string()))))
+    );
+    assert_eq!(
+        cgroups::classify(ID, "docker"),
+        Some((CgroupKind::Container, Some(("docker".to_string(), ID.to_string()))))
+    );
+    assert_eq!(cgroups::classify(&format!("libpod-conmon-{}.scope", ID), "machine.slice"), None);
+    assert_eq!(cgroups::classify("session-3.scope", "user-1000.slice"), None);
+
+    let names = cgroups::parse_containers(&format!(r#"[{{"Id":"{}","Names":["/builder"],"Image":"rust"}}]"#, ID)).unwrap();
+    assert_eq!(names.get(ID).map(String::as_str), Some("builder"));
+    assert!(cgroups::parse_containers("{\"message\":\"page not found\"}").is_err());
+}
+
+#[test]
+fn tree_and_usage() {
+    let root = fake_dir("tree");
+    write_attrs(&root, &[("cgroup.controllers", "cpuset cpu io memory pids")]);
+    write_attrs(&root.join("init.scope"), &[("cpu.stat", &cpu_stat(10, 0, 0, 0))]);
+    write_attrs(&root.join("system.slice"), &[("cpu.stat", &cpu_stat(1_000_000, 0, 0, 0)), ("memory.current", "1073741824"), ("memory.max", "max")]);
+    write_attrs(&root.join("system.slice/sshd.service"), &[("cpu.stat", &cpu_stat(5_000, 0, 0, 0))]);
+    let container = root.join(format!("system.slice/docker-{}.scope", ID));
+    write_attrs(
+        &container,
+        &[
+            ("cpu.stat", &cpu_stat(2_000_000, 100, 10, 50_000)),
+            ("cpu.max", "200000 100000"),
+            ("memory.current", "268435456"),
+            ("memory.max", "536870912"),
+            ("io.stat", "8:0 rbytes=1000 wbytes=2000 rios=1 wios=1"),
+            ("pids.current", "12"),
+            ("pids.max", "1024"),
+        ],
+    );
+    // processes inside the container aren't listed on their own
+    write_attrs(&container.join("inner.slice"), &[("cpu.stat", &cpu_stat(0, 0, 0, 0))]);
+
+    let before = cgroups::read_tree(&root).unwrap();
+    let paths: Vec<&str> = before.iter().map(|reading| reading.path.as_str()).collect();
+    assert_eq!(paths, vec!["/system.slice", format!("/system.slice/docker-{}.scope", ID).as_str(), "/system.slice/sshd.service"]);
+    assert_eq!(before[1].depth, 1);
+
+    write_attrs(
+        &container,
+        &[("cpu.stat", &cpu_stat(2_150_000, 110, 14, 90_000)), ("io.stat", "8:0 rbytes=21000 wbytes=2000 rios=5 wios=1")],
+    );
+    let after = cgroups::read_tree(&root).unwrap();
+    let names = HashMap::from([(ID.to_string(), "builder".to_string())]);
+    let usage = cgroups::usage(&before, &after, Duration::from_millis(200), &names);
+
+    let slice = &usage[0];
+    assert_eq!((slice.name.as_str(), slice.kind), ("system.slice", CgroupKind::Slice));
+    assert_eq!(slice.cpu_percent, 0.0);
+    assert_eq!(slice.memory_max, None);
+    assert_eq!(slice.io_read, None);
+
+    let builder = &usage[1];
+    assert_eq!(builder.name, "builder");
+    assert_eq!(builder.runtime.as_deref(), Some("docker"));
+    assert_eq!(builder.cpu_percent, 75.0);
+    assert_eq!(builder.cpu_limit, Some(2.0));
+    assert_eq!(builder.throttled_percent, Some(40.0));
+    assert_eq!(builder.throttled_ms, Some(200.0));
+    assert_eq!(builder.io_read, Some(100000.0));
+    assert_eq!(builder.io_write, Some(0.0));
+    assert_eq!((builder.memory_current, builder.memory_max), (Some(268435456), Some(536870912)));
+    assert_eq!((builder.pids_current, builder.pids_max), (Some(12), Some(1024)));
+
+    // unnamed containers fall back to the short ID
+    let unnamed = cgroups::usage(&before, &after, Duration::from_millis(200), &HashMap::new());
+    assert_eq!(unnamed[1].name, &ID[..12]);
+
+    assert!(cgroups::read_tree(&fake_dir("v1")).is_err());
+}
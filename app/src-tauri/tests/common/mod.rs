@@ -0,0 +1,28 @@
+//! Helpers shared by the integration tests: scratch directories laid out
+//! like the sysfs and procfs trees the collectors read.
+
+// each test binary compiles its own copy and uses only some of it
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An empty directory under the system temp dir, unique to this test binary
+/// and process.
+pub fn fake_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("overwatch-{}-{}-{}", env!("CARGO_CRATE_NAME"), name, std::process::id()));
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Writes sysfs-style attributes, one value and a newline per file. Names
+/// may contain directories.
+pub fn write_attrs(dir: &Path, attrs: &[(&str, &str)]) {
+    fs::create_dir_all(dir).unwrap();
+    for (name, value) in attrs {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("{}\n", value)).unwrap();
+    }
+}
@@ -0,0 +1,86 @@
+use app_lib::cpuflags::{self, MitigationStatus};
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+const X86: &str = "processor\t: 0\n\
+vendor_id\t: GenuineIntel\n\
+microcode\t: 0x2b000603\n\
+flags\t\t: fpu sse sse2 pni ssse3 sse4_1 sse4_2 aes avx avx2 fma avx512f avx512bw avx512_vbmi2 amx_tile sha_ni vmx hypervisor smep bmi2\n\
+\n\
+processor\t: 1\n\
+microcode\t: 0x2b000604\n\
+flags\t\t: fpu\n";
+
+const ARM: &str = "processor\t: 0\n\
+BogoMIPS\t: 48.00\n\
+Features\t: fp asimd evtstrm aes pmull sha1 sha2 crc32 atomics sve sve2\n\
+CPU implementer\t: 0x41\n";
+
+#[test]
+fn x86_flags_and_groups() {
+    let (flags, microcode) = cpuflags::parse_cpuinfo(X86).unwrap();
+    // only the first processor counts
+    assert_eq!(microcode.as_deref(), Some("0x2b000603"));
+    assert_eq!(flags.len(), 20);
+    assert_eq!(flags[3].name, "pni");
+    assert_eq!(flags[3].label, "SSE3");
+
+    let groups = cpuflags::group(&flags);
+    let names = |group: &str| -> Vec<String> {
+        groups.iter().find(|candidate| candidate.name == group).unwrap().flags.iter().map(|flag| flag.label.clone()).collect()
+    };
+    assert_eq!(names("SSE"), ["SSE", "SSE2", "SSE3", "SSSE3", "SSE4.1", "SSE4.2"]);
+    assert_eq!(names("AVX-512"), ["AVX-512 F", "AVX-512 BW", "AVX-512 VBMI2"]);
+    assert_eq!(names("AMX"), ["AMX-TILE"]);
+    assert_eq!(names("Crypto"), ["AES-NI", "SHA"]);
+    assert_eq!(names("Virtualization"), ["VT-x", "Running under a hypervisor"]);
+    assert!(!groups.iter().any(|group| group.name == "NEON / SVE"));
+}
+
+#[test]
+fn arm_features() {
+    let (flags, microcode) = cpuflags::parse_cpuinfo(ARM).unwrap();
+    assert_eq!(microcode, None);
+    let groups = cpuflags::group(&flags);
+    let simd = groups.iter().find(|group| group.name == "NEON / SVE").unwrap();
+    assert_eq!(simd.flags.iter().map(|flag| flag.name.as_str()).collect::<Vec<_>>(), ["asimd", "sve", "sve2"]);
+
+    assert!(cpuflags::parse_cpuinfo("processor\t: 0\n").is_err());
+}
+
+#[test]
+fn vulnerability_status() {
+    let dir = fake_dir("vulnerabilities");
+    write_attrs(
+        &dir,
+        &[
+            ("meltdown", "Not affected"),
+            ("spectre_v1", "Mitigation: usercopy/swapgs barriers and __user pointer sanitization"),
+            ("spectre_v2", "Mitigation: Enhanced / Automatic IBRS; IBPB: conditional; BHI: Vulnerable"),
+            ("mds", "Vulnerable: Clear CPU buffers attempted, no microcode; SMT vulnerable"),
+            ("itlb_multihit", "KVM: Mitigation: VMX disabled"),
+            ("srbds", "Unknown: Dependent on hypervisor status"),
+        ],
+    );
+
+    let vulnerabilities = cpuflags::read_vulnerabilities(&dir).unwrap();
+    let status = |name: &str| vulnerabilities.iter().find(|entry| entry.name == name).unwrap().status;
+    assert_eq!(vulnerabilities[0].name, "itlb_multihit");
+    assert_eq!(status("meltdown"), MitigationStatus::NotAffected);
+    assert_eq!(status("spectre_v1"), MitigationStatus::Mitigated);
+    assert_eq!(status("spectre_v2"), MitigationStatus::Partial);
+    assert_eq!(status("mds"), MitigationStatus::Vulnerable);
+    assert_eq!(status("itlb_multihit"), MitigationStatus::Mitigated);
+    assert_eq!(status("srbds"), MitigationStatus::Unknown);
+}
+
+#[test]
+fn architecture_names() {
+    assert_eq!(cpuflags::architecture_name("x86_64"), "x64");
+    assert_eq!(cpuflags::architecture_name("AMD64"), "x64");
+    assert_eq!(cpuflags::architecture_name("aarch64"), "ARM64");
+    assert_eq!(cpuflags::architecture_name("i686"), "x86");
+    assert_eq!(cpuflags::architecture_name("riscv64"), "riscv64");
+}
@@ -0,0 +1,55 @@
+use app_lib::cpufreq;
+use std::path::Path;
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+fn policy(root: &Path, number: u32, governor: &str, epp: &str) {
+    write_attrs(
+        &root.join(format!("cpufreq/policy{}", number)),
+        &[
+            ("scaling_driver", "intel_pstate"),
+            ("scaling_governor", governor),
+            ("scaling_available_governors", "performance powersave"),
+            ("energy_performance_preference", epp),
+            ("energy_performance_available_preferences", "default performance balance_performance balance_power power"),
+        ],
+    );
+}
+
+#[test]
+fn intel_pstate_policy() {
+    let root = fake_dir("intel");
+    policy(&root, 0, "powersave", "balance_performance");
+    policy(&root, 1, "powersave", "balance_performance");
+    write_attrs(&root.join("intel_pstate"), &[("status", "active"), ("no_turbo", "0")]);
+
+    let policy = cpufreq::read_sysfs(&root).unwrap();
+    assert_eq!(policy.driver.as_deref(), Some("intel_pstate"));
+    assert_eq!(policy.governor.as_deref(), Some("powersave"));
+    assert_eq!(policy.available_governors, ["performance", "powersave"]);
+    assert_eq!(policy.epp.as_deref(), Some("balance_performance"));
+    assert_eq!(policy.available_epp.len(), 5);
+    assert_eq!(policy.boost, Some(true));
+    assert!(policy.uniform);
+}
+
+#[test]
+fn mixed_policies_and_global_boost() {
+    let root = fake_dir("mixed");
+    policy(&root, 0, "powersave", "balance_performance");
+    policy(&root, 4, "performance", "performance");
+    write_attrs(&root.join("cpufreq"), &[("boost", "0")]);
+
+    let policy = cpufreq::read_sysfs(&root).unwrap();
+    assert_eq!(policy.governor.as_deref(), Some("powersave"));
+    assert_eq!(policy.boost, Some(false));
+    assert!(!policy.uniform);
+}
+
+#[test]
+fn no_cpufreq() {
+    let root = fake_dir("none");
+    assert!(cpufreq::read_sysfs(&root).is_err());
+}
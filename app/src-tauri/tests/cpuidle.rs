@@ -0,0 +1,60 @@
+use app_lib::cpuidle::{self, StateCounters};
+use std::fs;
+use std::time::Duration;
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+fn counter(cpu: u32, state: &str, time_us: u64, usage: u64) -> StateCounters {
+    StateCounters {
+        cpu,
+        state: state.to_string(),
+        name: state.to_string(),
+        latency_us: None,
+        time_us,
+        usage,
+    }
+}
+
+#[test]
+fn sysfs_states() {
+    let root = fake_dir("sysfs");
+    let idle = root.join("cpu0/cpuidle");
+    write_attrs(&idle.join("state0"), &[("name", "POLL"), ("latency", "0"), ("time", "1200"), ("usage", "40"), ("disable", "0")]);
+    write_attrs(&idle.join("state1"), &[("name", "C1"), ("latency", "2"), ("time", "500000"), ("usage", "900"), ("disable", "0")]);
+    // sorted numerically, not as text
+    write_attrs(&idle.join("state10"), &[("name", "C10"), ("latency", "890"), ("time", "7000000"), ("usage", "120"), ("disable", "0")]);
+    write_attrs(&idle.join("state2"), &[("name", "C6"), ("latency", "170"), ("time", "0"), ("usage", "0"), ("disable", "1")]);
+    // a CPU without cpuidle, e.g. offline
+    fs::create_dir_all(root.join("cpu1")).unwrap();
+
+    let counters = cpuidle::read_sysfs(&root).unwrap();
+    let names: Vec<&str> = counters.iter().map(|counter| counter.name.as_str()).collect();
+    assert_eq!(names, ["POLL", "C1", "C10"]);
+    assert_eq!(counters[2].latency_us, Some(890));
+    assert_eq!(counters[2].time_us, 7_000_000);
+    assert_eq!(counters[1].usage, 900);
+}
+
+#[test]
+fn residency_over_window() {
+    let before = vec![counter(0, "state1", 1_000, 10), counter(0, "state2", 50_000, 3), counter(1, "state1", 0, 0)];
+    let after = vec![
+        counter(0, "state1", 21_000, 30),
+        counter(0, "state2", 200_000, 5),
+        counter(1, "state1", 400_000, 1),
+        // appeared since, e.g. a CPU brought online
+        counter(2, "state1", 9_000, 1),
+    ];
+
+    let cores = cpuidle::residency(&before, &after, Duration::from_millis(200));
+    assert_eq!(cores.len(), 2);
+    assert_eq!(cores[0].cpu, 0);
+    assert_eq!(cores[0].states.len(), 2);
+    assert!((cores[0].states[0].residency - 10.0).abs() < 0.01);
+    assert_eq!(cores[0].states[0].entries, Some(20));
+    assert!((cores[0].states[1].residency - 75.0).abs() < 0.01);
+    // a wake that credits time from before the window can't go past the window
+    assert_eq!(cores[1].states[0].residency, 100.0);
+}
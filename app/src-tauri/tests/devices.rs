@@ -0,0 +1,256 @@
+// fake sysfs trees need symlinks
+#![cfg(unix)]
+
+use app_lib::devices::ids::{self, IdDatabase};
+use app_lib::devices::pci::{PciDevice, PcieLink};
+use app_lib::devices::{nvme, pci, usb, LinkMonitor};
+use std::fs;
+use std::os::unix::fs::symlink;
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+#[test]
+fn id_database_lookups() {
+    let db = IdDatabase::parse(
+        "# comment\n\
+         10de  NVIDIA Corporation\n\
+         \t2684  AD102 [GeForce RTX 4090]\n\
+         \t\t1043 889d  TUF Gaming GeForce RTX 4090\n\
+         1043  ASUSTeK Computer Inc.\n\
+         \n\
+         C 03  Display controller\n\
+         \t00  VGA compatible controller\n\
+         \t\t01  8514 controller\n\
+         C 0c  Serial bus controller\n",
+    );
+
+    assert_eq!(db.vendor(0x10de), Some("NVIDIA Corporation"));
+    assert_eq!(db.device(0x10de, 0x2684), Some("AD102 [GeForce RTX 4090]"));
+    assert_eq!(db.subsystem(0x10de, 0x2684, 0x1043, 0x889d), Some("TUF Gaming GeForce RTX 4090"));
+    assert_eq!(db.device(0x1043, 0x2684), None);
+    assert_eq!(db.class(0x03, 0x00), Some("VGA compatible controller"));
+    assert_eq!(db.interface(0x03, 0x00, 0x01), Some("8514 controller"));
+    // unknown subclass falls back to the class
+    assert_eq!(db.class(0x0c, 0x03), Some("Serial bus controller"));
+}
+
+#[test]
+fn compiled_in_databases_load() {
+    // hwdata when installed, the gzipped copies otherwise; either knows these
+    assert_eq!(ids::pci().vendor(0x8086), Some("Intel Corporation"));
+    assert_eq!(ids::usb().vendor(0x1d6b), Some("Linux Foundation"));
+}
+
+#[test]
+fn pci_devices_from_sysfs() {
+    let root = fake_dir("pci");
+    let gpu = root.join("0000:01:00.0");
+    write_attrs(
+        &gpu,
+        &[
+            ("vendor", "0x8086"),
+            ("device", "0x56a0"),
+            ("class", "0x030000"),
+            ("subsystem_vendor", "0x1043"),
+            ("subsystem_device", "0x889d"),
+            ("current_link_speed", "8.0 GT/s PCIe"),
+            ("current_link_width", "8"),
+            ("max_link_speed", "16.0 GT/s PCIe"),
+            ("max_link_width", "16"),
+        ],
+    );
+    symlink("../../../bus/pci/drivers/i915", gpu.join("driver")).unwrap();
+    symlink("../../../kernel/iommu_groups/14", gpu.join("iommu_group")).unwrap();
+    write_attrs(&root.join("0000:00:00.0"), &[("vendor", "0x8086"), ("device", "0x1237"), ("class", "0x060000")]);
+
+    let devices = pci::read_sysfs(&root).unwrap();
+    assert_eq!(devices.len(), 2);
+    assert_eq!(devices[0].address, "0000:00:00.0");
+    assert_eq!(devices[0].class, "Host bridge");
+    assert!(devices[0].link.is_none());
+
+    let gpu = &devices[1];
+    assert_eq!((gpu.vendor_id, gpu.device_id), (0x8086, 0x56a0));
+    assert_eq!(gpu.vendor.as_deref(), Some("Intel Corporation"));
+    assert_eq!(gpu.class, "VGA compatible controller");
+    assert_eq!(gpu.driver.as_deref(), Some("i915"));
+    assert_eq!(gpu.iommu_group, Some(14));
+    assert_eq!(gpu.subsystem_device_id, Some(0x889d));
+    let link = gpu.link.as_ref().unwrap();
+    assert_eq!((link.speed_gts, link.width), (Some(8.0), Some(8)));
+    assert_eq!((link.max_speed_gts, link.max_width), (Some(16.0), Some(16)));
+    assert!(link.warning.as_deref().unwrap().contains("x8 of x16"));
+
+    fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+fn pcie_link_against_the_upstream_port() {
+    let root = fake_dir("pci-upstream");
+    let bridge = root.join("devices").join("0000:00:01.0");
+    write_attrs(&bridge, &[("max_link_speed", "8.0 GT/s PCIe"), ("max_link_width", "4")]);
+    write_attrs(
+        &bridge.join("0000:01:00.0"),
+        &[
+            ("current_link_speed", "8.0 GT/s PCIe"),
+            ("current_link_width", "4"),
+            ("max_link_speed", "16.0 GT/s PCIe"),
+            ("max_link_width", "16"),
+        ],
+    );
+    // the bus listing links to where the device really sits
+    fs::create_dir_all(root.join("bus")).unwrap();
+    symlink("../devices/0000:00:01.0/0000:01:00.0", root.join("bus").join("0000:01:00.0")).unwrap();
+
+    let link = pci::read_link(&root.join("bus").join("0000:01:00.0")).unwrap();
+    assert_eq!((link.upstream_max_speed_gts, link.upstream_max_width), (Some(8.0), Some(4)));
+    assert_eq!((link.warning, link.note), (None, None));
+
+    fs::remove_dir_all(&root).ok();
+}
+
+fn gpu_with_width(width: u8) -> PciDevice {
+    PciDevice {
+        address: "0000:01:00.0".to_string(),
+        class_code: 0x030000,
+        class: "VGA compatible controller".to_string(),
+        interface: None,
+        vendor_id: 0x10de,
+        device_id: 0x2684,
+        vendor: Some("NVIDIA Corporation".to_string()),
+        device: None,
+        subsystem_vendor_id: None,
+        subsystem_device_id: None,
+        subsystem: None,
+        driver: None,
+        iommu_group: None,
+        link: Some(PcieLink::new(Some(16.0), Some(width), Some(16.0), Some(16), None, None)),
+    }
+}
+
+#[test]
+fn degraded_links_become_events() {
+    let monitor = LinkMonitor::default();
+    assert!(monitor.update(&[gpu_with_width(16)], 1000).is_empty());
+
+    let events = monitor.update(&[gpu_with_width(8)], 2000);
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].at, events[0].address.as_str()), (2000, "0000:01:00.0"));
+    assert_eq!(events[0].warning, "running x8 of x16 lanes");
+    // still the same problem, already reported
+    assert!(monitor.update(&[gpu_with_width(8)], 3000).is_empty());
+    // worse is a different problem
+    assert_eq!(monitor.update(&[gpu_with_width(4)], 4000).len(), 1);
+    // cleared and back again is reported again
+    assert!(monitor.update(&[gpu_with_width(16)], 5000).is_empty());
+    assert_eq!(monitor.update(&[gpu_with_width(4)], 6000).len(), 1);
+}
+
+#[test]
+fn usb_tree_from_sysfs() {
+    let root = fake_dir("usb");
+    write_attrs(
+        &root.join("usb1"),
+        &[("idVendor", "1d6b"), ("idProduct", "0002"), ("bDeviceClass", "09"), ("busnum", "1"), ("speed", "480")],
+    );
+    write_attrs(
+        &root.join("1-2"),
+        &[("idVendor", "05e3"), ("idProduct", "0610"), ("bDeviceClass", "09"), ("busnum", "1"), ("speed", "480"), ("bMaxPower", "100mA")],
+    );
+    write_attrs(
+        &root.join("1-2.10"),
+        &[
+            ("idVendor", "feed"),
+            ("idProduct", "1307"),
+            ("bDeviceClass", "00"),
+            ("busnum", "1"),
+            ("speed", "1.5"),
+            ("bMaxPower", "500mA"),
+            ("manufacturer", "Example Keyboards"),
+            ("product", "Clicky 87"),
+        ],
+    );
+    write_attrs(&root.join("1-2.10:1.0"), &[("bInterfaceClass", "03")]);
+    symlink("../../../../bus/usb/drivers/usbhid", root.join("1-2.10:1.0").join("driver")).unwrap();
+    write_attrs(&root.join("1-2.9"), &[("idVendor", "feed"), ("idProduct", "0001"), ("busnum", "1")]);
+
+    let devices = usb::read_sysfs(&root).unwrap();
+    let paths: Vec<&str> = devices.iter().map(|d| d.path.as_str()).collect();
+    assert_eq!(paths, ["usb1", "1-2", "1-2.9", "1-2.10"]);
+
+    assert_eq!(devices[0].parent, None);
+    assert_eq!(devices[0].product.as_deref(), Some("2.0 root hub"));
+    assert_eq!(devices[1].parent.as_deref(), Some("usb1"));
+    assert_eq!(devices[1].port, Some(2));
+
+    let keyboard = &devices[3];
+    assert_eq!(keyboard.parent.as_deref(), Some("1-2"));
+    assert_eq!(keyboard.port, Some(10));
+    // not in the database, so the device's own strings are used
+    assert_eq!(keyboard.vendor.as_deref(), Some("Example Keyboards"));
+    assert_eq!(keyboard.product.as_deref(), Some("Clicky 87"));
+    assert_eq!(keyboard.class.as_deref(), Some("Human Interface Device"));
+    assert_eq!(keyboard.speed_mbps, Some(1.5));
+    assert_eq!(keyboard.max_power_ma, Some(500));
+    assert_eq!(keyboard.drivers, ["usbhid"]);
+
+    fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+fn pcie_link_warnings() {
+    let link = PcieLink::new(Some(16.0), Some(16), Some(16.0), Some(16), None, None);
+    assert_eq!((link.warning, link.note), (None, None));
+    // a Gen4 x16 card that trained at Gen1 x4: the lanes are a problem, the speed may be idling
+    let link = PcieLink::new(Some(2.5), Some(4), Some(16.0), Some(16), None, None);
+    assert_eq!(link.warning.as_deref(), Some("running x4 of x16 lanes"));
+    assert!(link.note.unwrap().contains("2.5 of 16 GT/s"));
+    // the same card in a Gen3 slot is only slow past what the slot does
+    assert_eq!(PcieLink::new(Some(8.0), Some(16), Some(16.0), Some(16), Some(8.0), None).note, None);
+    let link = PcieLink::new(Some(2.5), Some(16), Some(16.0), Some(16), Some(8.0), None);
+    assert_eq!(link.warning, None);
+    assert!(link.note.unwrap().contains("2.5 of 8 GT/s"));
+    // an x16 card in an x4 slot has all the lanes it can get
+    assert_eq!(PcieLink::new(Some(8.0), Some(4), Some(16.0), Some(16), Some(8.0), Some(4)).warning, None);
+    let warning = PcieLink::new(Some(8.0), Some(8), Some(16.0), Some(16), Some(8.0), Some(16)).warning;
+    assert_eq!(warning.as_deref(), Some("running x8 of x16 lanes"));
+    let warning = PcieLink::new(Some(8.0), Some(2), Some(16.0), Some(16), Some(8.0), Some(4)).warning;
+    assert_eq!(warning.as_deref(), Some("running x2 of x4 lanes"));
+    // half the picture is no reason to warn
+    assert_eq!(PcieLink::new(Some(8.0), None, None, Some(4), Some(16.0), None).warning, None);
+    assert_eq!(pci::parse_link_speed("Unknown"), None);
+    assert_eq!(pci::parse_link_speed("2.5 GT/s PCIe"), Some(2.5));
+}
+
+#[test]
+fn nvme_controllers_from_sysfs() {
+    let root = fake_dir("nvme");
+    let pci_dir = root.join("pci").join("0000:02:00.0");
+    write_attrs(
+        &pci_dir,
+        &[
+            ("current_link_speed", "16.0 GT/s PCIe"),
+            ("current_link_width", "4"),
+            ("max_link_speed", "16.0 GT/s PCIe"),
+            ("max_link_width", "4"),
+        ],
+    );
+    let class = root.join("class");
+    for (name, model) in [("nvme10", "Spare Drive"), ("nvme2", "Fast Drive 2TB")] {
+        write_attrs(&class.join(name), &[("model", model), ("firmware_rev", "1B2QGXA7"), ("address", "0000:02:00.0")]);
+    }
+    symlink(&pci_dir, class.join("nvme2").join("device")).unwrap();
+
+    let devices = nvme::read_sysfs(&class).unwrap();
+    let names: Vec<&str> = devices.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, ["nvme2", "nvme10"]);
+    assert_eq!(devices[0].model.as_deref(), Some("Fast Drive 2TB"));
+    assert_eq!(devices[0].firmware.as_deref(), Some("1B2QGXA7"));
+    let link = devices[0].link.as_ref().unwrap();
+    assert_eq!((link.speed_gts, link.width, link.warning.as_deref()), (Some(16.0), Some(4), None));
+    assert!(devices[1].link.is_none());
+
+    fs::remove_dir_all(&root).ok();
+}
@@ -0,0 +1,168 @@
+use app_lib::fans::{self, Controller, CurvePoint, CurveState, FanCurve, FanMode};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+fn attr(path: PathBuf) -> String {
+    fs::read_to_string(path).unwrap().trim().to_string()
+}
+
+// a CPU temperature chip and a Super I/O chip with two fans
+fn board(root: &Path) {
+    write_attrs(&root.join("hwmon0"), &[("name", "k10temp"), ("temp1_input", "45000"), ("temp1_label", "Tctl")]);
+    write_attrs(
+        &root.join("hwmon2"),
+        &[
+            ("name", "nct6798"),
+            ("pwm1", "128"),
+            ("pwm1_enable", "5"),
+            ("fan1_input", "900"),
+            ("pwm2", "255"),
+            ("pwm2_enable", "0"),
+            ("temp1_input", "38000"),
+        ],
+    );
+}
+
+fn curve(hysteresis: f32, max_step: f32) -> FanCurve {
+    FanCurve {
+        fan: "hwmon2/pwm1".to_string(),
+        sensor: "hwmon0/temp1".to_string(),
+        points: vec![
+            CurvePoint { celsius: 40.0, duty: 20.0 },
+            CurvePoint { celsius: 60.0, duty: 40.0 },
+            CurvePoint { celsius: 80.0, duty: 100.0 },
+        ],
+        hysteresis,
+        max_step,
+    }
+}
+
+#[test]
+fn hwmon_fans_and_sensors() {
+    let root = fake_dir("read");
+    board(&root);
+
+    let (fans, sensors) = fans::read_hwmon(&root).unwrap();
+    assert_eq!(fans.len(), 2);
+    assert_eq!(fans[0].id, "hwmon2/pwm1");
+    assert_eq!(fans[0].chip, "nct6798");
+    assert_eq!(fans[0].rpm, Some(900));
+    assert!((fans[0].duty.unwrap() - 50.2).abs() < 0.1);
+    assert_eq!(fans[0].mode, Some(FanMode::Automatic));
+    assert_eq!(fans[1].rpm, None);
+    assert_eq!(fans[1].mode, Some(FanMode::FullSpeed));
+
+    assert_eq!(sensors.len(), 2);
+    assert_eq!(sensors[0].id, "hwmon0/temp1");
+    assert_eq!(sensors[0].label.as_deref(), Some("Tctl"));
+    assert_eq!(sensors[0].celsius, 45.0);
+}
+
+#[test]
+fn curve_interpolates_and_clamps() {
+    let curve = curve(0.0, 100.0);
+    assert_eq!(curve.duty_at(20.0), 20.0);
+    assert_eq!(curve.duty_at(50.0), 30.0);
+    assert_eq!(curve.duty_at(70.0), 70.0);
+    assert_eq!(curve.duty_at(95.0), 100.0);
+}
+
+#[test]
+fn hysteresis_and_ramp_limit() {
+    let curve = curve(5.0, 10.0);
+    let mut state = CurveState::default();
+    assert_eq!(state.step(&curve, 60.0), 40.0);
+    // heating up is followed, but only 10% a tick
+    assert_eq!(state.step(&curve, 80.0), 50.0);
+    assert_eq!(state.step(&curve, 80.0), 60.0);
+    // a small dip keeps the target where it was
+    assert_eq!(state.step(&curve, 77.0), 70.0);
+    // a bigger one brings it down, again 10% a tick
+    assert_eq!(state.step(&curve, 60.0), 60.0);
+    assert_eq!(state.step(&curve, 60.0), 50.0);
+    assert_eq!(state.step(&curve, 60.0), 40.0);
+}
+
+#[test]
+fn invalid_curves_are_refused() {
+    let mut escape = curve(2.0, 5.0);
+    escape.fan = "../../../etc/passwd".to_string();
+    assert!(escape.validate().is_err());
+
+    let mut unordered = curve(2.0, 5.0);
+    unordered.points.reverse();
+    assert!(unordered.validate().is_err());
+
+    let mut overdriven = curve(2.0, 5.0);
+    overdriven.points[2].duty = 120.0;
+    assert!(overdriven.validate().is_err());
+
+    assert!(curve(2.0, 0.0).validate().is_err());
+    assert!(curve(2.0, 5.0).validate().is_ok());
+}
+
+#[test]
+fn controller_takes_over_and_hands_back() {
+    let root = fake_dir("control");
+    board(&root);
+    let mut controller = Controller::new(&root);
+    controller.set_curves(vec![curve(2.0, 100.0)]).unwrap();
+
+    let results = controller.tick();
+    assert_eq!(results[0].1.as_ref().unwrap(), &25.0);
+    assert_eq!(attr(root.join("hwmon2/pwm1_enable")), "1");
+    assert_eq!(attr(root.join("hwmon2/pwm1")), "64");
+
+    // dropping the curve restores the driver's own automatic mode
+    controller.set_curves(Vec::new()).unwrap();
+    assert_eq!(attr(root.join("hwmon2/pwm1_enable")), "5");
+}
+
+#[test]
+fn dropped_controller_hands_back() {
+    let root = fake_dir("drop");
+    board(&root);
+    let mut controller = Controller::new(&root);
+    controller.set_curves(vec![curve(2.0, 100.0)]).unwrap();
+    controller.tick();
+    assert_eq!(attr(root.join("hwmon2/pwm1_enable")), "1");
+
+    // mid-curve, as when the daemon panics or stops on a signal
+    drop(controller);
+    assert_eq!(attr(root.join("hwmon2/pwm1_enable")), "5");
+}
+
+#[test]
+fn lost_sensor_falls_back_to_automatic() {
+    let root = fake_dir("sensor");
+    board(&root);
+    let mut controller = Controller::new(&root);
+    controller.set_curves(vec![curve(2.0, 100.0)]).unwrap();
+    controller.tick();
+
+    fs::remove_file(root.join("hwmon0/temp1_input")).unwrap();
+    let results = controller.tick();
+    assert!(results[0].1.is_err());
+    assert_eq!(attr(root.join("hwmon2/pwm1_enable")), "5");
+
+    // and is taken over again once the sensor is back
+    write_attrs(&root.join("hwmon0"), &[("temp1_input", "70000")]);
+    controller.tick();
+    assert_eq!(attr(root.join("hwmon2/pwm1_enable")), "1");
+    controller.release_all();
+    assert_eq!(attr(root.join("hwmon2/pwm1_enable")), "5");
+}
+
+#[test]
+fn missing_fan_is_refused() {
+    let root = fake_dir("missing");
+    board(&root);
+    let mut controller = Controller::new(&root);
+    let mut missing = curve(2.0, 5.0);
+    missing.fan = "hwmon2/pwm7".to_string();
+    assert!(controller.set_curves(vec![missing]).is_err());
+}
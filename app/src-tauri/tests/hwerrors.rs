@@ -0,0 +1,107 @@
+use app_lib::hwerrors::{self, ErrorCounter, ErrorSeverity, ErrorSource, HardwareErrorMonitor};
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+#[test]
+fn edac_per_dimm() {
+    let root = fake_dir("edac");
+    write_attrs(&root.join("mc0"), &[("mc_name", "Skylake Socket#0 IMC#0"), ("ce_count", "5"), ("ue_count", "0"), ("ce_noinfo_count", "1")]);
+    write_attrs(&root.join("mc0/dimm0"), &[("dimm_label", "CPU_SrcID#0_MC#0_Chan#0_DIMM#0"), ("dimm_ce_count", "4"), ("dimm_ue_count", "0")]);
+    write_attrs(&root.join("mc0/dimm1"), &[("dimm_label", "CPU_SrcID#0_MC#0_Chan#1_DIMM#0"), ("dimm_ce_count", "0"), ("dimm_ue_count", "0")]);
+    // older drivers only count per controller
+    write_attrs(&root.join("mc1"), &[("mc_name", "Ryzen"), ("ce_count", "2"), ("ue_count", "1")]);
+
+    let counters = hwerrors::read_edac(&root).unwrap();
+    let find = |device: &str, severity| counters.iter().find(|counter| counter.device == device && counter.severity == severity).unwrap();
+
+    let dimm = find("mc0/dimm0", ErrorSeverity::Corrected);
+    assert_eq!(dimm.count, 4);
+    assert_eq!(dimm.label.as_deref(), Some("CPU_SrcID#0_MC#0_Chan#0_DIMM#0"));
+    assert_eq!(find("mc0", ErrorSeverity::Corrected).count, 1);
+    assert_eq!(find("mc1", ErrorSeverity::Uncorrected).count, 1);
+    assert!(counters.iter().all(|counter| counter.source == ErrorSource::Memory));
+    assert_eq!(counters.len(), 7);
+
+    assert!(hwerrors::read_edac(&fake_dir("no-edac")).is_err());
+}
+
+#[test]
+fn aer_counters() {
+    let (total, detail) = hwerrors::parse_aer("RxErr 0\nBadTLP 3\nBadDLLP 1\nRollover 0\nTOTAL_ERR_COR 4\n");
+    assert_eq!(total, 4);
+    assert_eq!(detail.as_deref(), Some("BadTLP 3, BadDLLP 1"));
+    assert_eq!(hwerrors::parse_aer("Undefined 0\nTOTAL_ERR_FATAL 0\n"), (0, None));
+
+    let root = fake_dir("aer");
+    write_attrs(
+        &root.join("0000:01:00.0"),
+        &[
+            ("vendor", "0x10de"),
+            ("device", "0x2684"),
+            ("aer_dev_correctable", "RxErr 2\nBadTLP 0\nTOTAL_ERR_COR 2"),
+            ("aer_dev_nonfatal", "Undefined 0\nTOTAL_ERR_NONFATAL 0"),
+            ("aer_dev_fatal", "Undefined 0\nTOTAL_ERR_FATAL 0"),
+        ],
+    );
+    // no AER capability
+    write_attrs(&root.join("0000:00:00.0"), &[("vendor", "0x8086"), ("device", "0x3e30")]);
+
+    let counters = hwerrors::read_aer(&root, |vendor, device| Some(format!("{:04x}:{:04x}", vendor, device))).unwrap();
+    assert_eq!(counters.len(), 3);
+    assert_eq!(counters[0].device, "0000:01:00.0");
+    assert_eq!(counters[0].label.as_deref(), Some("10de:2684"));
+    assert_eq!((counters[0].severity, counters[0].count), (ErrorSeverity::Corrected, 2));
+    assert_eq!(counters[0].detail.as_deref(), Some("RxErr 2"));
+    assert_eq!(counters[2].severity, ErrorSeverity::Fatal);
+}
+
+#[test]
+fn journal_hardware_errors() {
+    let output = concat!(
+        r#"{"MESSAGE":"mce: [Hardware Error]: Machine check events logged","__REALTIME_TIMESTAMP":"1700000000123456"}"#,
+        "\n",
+        r#"{"MESSAGE":"usb 1-1: new high-speed USB device","__REALTIME_TIMESTAMP":"1700000001000000"}"#,
+        "\n",
+        r#"{"MESSAGE":[104,105],"__REALTIME_TIMESTAMP":"1700000002000000"}"#,
+        "\n",
+    );
+    let reports = hwerrors::parse_journal(output);
+    assert_eq!(reports, vec![(1700000000123, "mce: [Hardware Error]: Machine check events logged".to_string())]);
+    assert_eq!(hwerrors::journal_cursor(output), None);
+
+    let output = format!("{}-- cursor: s=0a1b;i=42;b=9f;m=1c;t=5f;x=77\n", output);
+    assert_eq!(hwerrors::journal_cursor(&output), Some("s=0a1b;i=42;b=9f;m=1c;t=5f;x=77"));
+}
+
+#[test]
+fn increases_become_events() {
+    let counter = |device: &str, count| ErrorCounter {
+        source: ErrorSource::Memory,
+        device: device.to_string(),
+        label: None,
+        severity: ErrorSeverity::Corrected,
+        count,
+        detail: None,
+    };
+    let monitor = HardwareErrorMonitor::default();
+
+    // errors from earlier in the boot are reported on the first look
+    let events = monitor.update(&[counter("mc0/dimm0", 3), counter("mc0/dimm1", 0)], 1000);
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].increase, events[0].total, events[0].at), (3, 3, 1000));
+
+    assert!(monitor.update(&[counter("mc0/dimm0", 3), counter("mc0/dimm1", 0)], 2000).is_empty());
+
+    let events = monitor.update(&[counter("mc0/dimm0", 3), counter("mc0/dimm1", 2)], 3000);
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].device.as_str(), events[0].increase), ("mc0/dimm1", 2));
+
+    // a driver reload resets the counts
+    assert!(monitor.update(&[counter("mc0/dimm0", 0), counter("mc0/dimm1", 0)], 4000).is_empty());
+    assert_eq!(monitor.update(&[counter("mc0/dimm0", 1)], 5000)[0].increase, 1);
+
+    let history = monitor.history();
+    assert_eq!(history.iter().map(|event| event.at).collect::<Vec<_>>(), vec![1000, 3000, 5000]);
+}
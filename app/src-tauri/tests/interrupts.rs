@@ -0,0 +1,91 @@
+use app_lib::interrupts::{self, InterruptKind, ProcReading};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+// CPU2 is offline, so it has no column in interrupts but does in softirqs
+fn write_proc(root: &Path, nvme: [u64; 3], eth: [u64; 3], loc: [u64; 3], net_rx: [u64; 4]) {
+    let interrupts = format!(
+        "            CPU0       CPU1       CPU3       \n\
+         \x20  1:          9          0          0  IR-IO-APIC    1-edge      i8042\n\
+         \x20124:  {:>9}  {:>9}  {:>9}  IR-PCI-MSI 327680-edge      nvme0q1\n\
+         \x20130:  {:>9}  {:>9}  {:>9}  IR-PCI-MSI 524288-edge      enp3s0-rx-0, enp3s0-tx-0\n\
+         NMI:          1          1          1   Non-maskable interrupts\n\
+         LOC:  {:>9}  {:>9}  {:>9}   Local timer interrupts\n\
+         ERR:          0\n",
+        nvme[0], nvme[1], nvme[2], eth[0], eth[1], eth[2], loc[0], loc[1], loc[2]
+    );
+    let softirqs = format!(
+        "                    CPU0       CPU1       CPU2       CPU3       \n\
+         \x20         HI:          0          0          0          0\n\
+         \x20     NET_RX:  {:>9}  {:>9}  {:>9}  {:>9}\n",
+        net_rx[0], net_rx[1], net_rx[2], net_rx[3]
+    );
+    fs::write(root.join("interrupts"), interrupts).unwrap();
+    fs::write(root.join("softirqs"), softirqs).unwrap();
+}
+
+fn reading(root: &Path) -> ProcReading {
+    interrupts::read_procfs(root).unwrap()
+}
+
+#[test]
+fn proc_interrupts_lines() {
+    let root = fake_dir("parse");
+    write_proc(&root, [10, 20, 30], [0, 0, 0], [5, 5, 5], [1, 2, 3, 4]);
+    let reading = reading(&root);
+    assert_eq!(reading.cpus, vec![0, 1, 3]);
+
+    let eth = reading.counters.iter().find(|counter| counter.id == "130").unwrap();
+    assert_eq!(eth.kind, InterruptKind::Device);
+    assert_eq!(eth.chip.as_deref(), Some("IR-PCI-MSI"));
+    assert_eq!(eth.name.as_deref(), Some("enp3s0-rx-0, enp3s0-tx-0"));
+
+    let loc = reading.counters.iter().find(|counter| counter.id == "LOC").unwrap();
+    assert_eq!(loc.kind, InterruptKind::System);
+    assert_eq!(loc.name.as_deref(), Some("Local timer interrupts"));
+    // a single system-wide count doesn't fit the columns
+    assert!(!reading.counters.iter().any(|counter| counter.id == "ERR"));
+
+    // the offline CPU2 column is dropped from softirqs
+    let net_rx = reading.counters.iter().find(|counter| counter.id == "NET_RX").unwrap();
+    assert_eq!(net_rx.counts, vec![1, 2, 4]);
+}
+
+#[test]
+fn rates_affinity_and_concentration() {
+    let root = fake_dir("rates");
+    write_attrs(&root.join("irq/130"), &[("smp_affinity_list", "0-3"), ("effective_affinity_list", "1")]);
+    write_proc(&root, [0, 0, 0], [0, 0, 0], [0, 0, 0], [0, 0, 0, 0]);
+    let before = reading(&root);
+    // the NIC lands almost entirely on CPU1, the NVMe queue is spread out
+    write_proc(&root, [100, 100, 100], [10, 1990, 0], [500, 500, 500], [0, 4000, 0, 0]);
+    let after = reading(&root);
+
+    let stats = interrupts::rates(&before, &after, Duration::from_secs(2), &root);
+    assert_eq!(stats.cpus, vec![0, 1, 3]);
+    assert_eq!(stats.sources[0].id, "NET_RX");
+
+    let eth = stats.sources.iter().find(|source| source.id == "130").unwrap();
+    assert_eq!(eth.rates, vec![5.0, 995.0, 0.0]);
+    assert_eq!(eth.total, 1000.0);
+    assert_eq!(eth.affinity.as_deref(), Some("0-3"));
+    assert_eq!(eth.effective_affinity.as_deref(), Some("1"));
+    assert_eq!(eth.concentrated_on, Some(1));
+
+    let nvme = stats.sources.iter().find(|source| source.id == "124").unwrap();
+    assert_eq!(nvme.concentrated_on, None);
+    assert_eq!(nvme.affinity, None);
+
+    let net_rx = stats.sources.iter().find(|source| source.id == "NET_RX").unwrap();
+    assert_eq!(net_rx.concentrated_on, Some(1));
+
+    // sources that didn't fire are left out
+    assert!(!stats.sources.iter().any(|source| source.id == "1" || source.id == "NMI"));
+    assert_eq!(stats.cpu_interrupts, vec![305.0, 1295.0, 300.0]);
+    assert_eq!(stats.cpu_softirqs, vec![0.0, 2000.0, 0.0]);
+}
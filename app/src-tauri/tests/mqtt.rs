@@ -0,0 +1,144 @@
+use app_lib::export::mqtt::{MqttConfig, MqttExporter};
+use app_lib::export::Exporter;
+use app_lib::{HostSnapshot, OverwatchError, Reading, Sensor};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+// (retain, payload) per topic, last write wins like on a real broker
+type Retained = BTreeMap<String, (bool, String)>;
+
+// just enough MQTT 3.1.1 to accept one client and record what it publishes
+fn spawn_broker() -> (u16, Receiver<(String, bool, String)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        while let Some((header, body)) = read_packet(&mut stream) {
+            match header >> 4 {
+                // CONNECT
+                1 => stream.write_all(&[0x20, 0x02, 0x00, 0x00]).unwrap(),
+                // PUBLISH
+                3 => {
+                    let qos = (header >> 1) & 0x03;
+                    let topic_len = u16::from_be_bytes([body[0], body[1]]) as usize;
+                    let topic = String::from_utf8(body[2..2 + topic_len].to_vec()).unwrap();
+                    let mut rest = &body[2 + topic_len..];
+                    if qos > 0 {
+                        stream.write_all(&[0x40, 0x02, rest[0], rest[1]]).unwrap();
+                        rest = &rest[2..];
+                    }
+                    let payload = String::from_utf8(rest.to_vec()).unwrap();
+                    if tx.send((topic, header & 0x01 == 1, payload)).is_err() {
+                        return;
+                    }
+                }
+                // PINGREQ
+                12 => stream.write_all(&[0xd0, 0x00]).unwrap(),
+                _ => {}
+            }
+        }
+    });
+
+    (port, rx)
+}
+
+fn read_packet(stream: &mut TcpStream) -> Option<(u8, Vec<u8>)> {
+    let mut byte = [0u8];
+    stream.read_exact(&mut byte).ok()?;
+    let header = byte[0];
+
+    let (mut len, mut shift) = (0usize, 0);
+    loop {
+        stream.read_exact(&mut byte).ok()?;
+        len |= ((byte[0] & 0x7f) as usize) << shift;
+        shift += 7;
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+    }
+
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body).ok()?;
+    Some((header, body))
+}
+
+fn snapshot() -> HostSnapshot {
+    HostSnapshot {
+        host: "testhost".to_string(),
+        timestamp: 0,
+        cpu: Reading::Err(OverwatchError::unsupported("CPU")),
+        gpu: Reading::Err(OverwatchError::unsupported("GPU")),
+        memory: Reading::Err(OverwatchError::unsupported("memory")),
+        sensors: Reading::Ok(vec![Sensor {
+            label: "Package id 0".to_string(),
+            temperature: 54.5,
+            max: None,
+            critical: Some(100.0),
+        }]),
+        storage: None,
+    }
+}
+
+#[test]
+fn publishes_retained_states_and_discovery() {
+    let (port, rx) = spawn_broker();
+    let mut config = MqttConfig::from_url(&format!("mqtt://127.0.0.1:{}", port)).unwrap();
+    config.node_id = "testhost".to_string();
+    let mut exporter = MqttExporter::connect(config).unwrap();
+
+    // the connection comes up in the background
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while exporter.export(&snapshot()).is_err() {
+        assert!(Instant::now() < deadline, "exporter never connected");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    let state_topic = "overwatch/testhost/sensor/package_id_0/temperature";
+    let config_topic = "homeassistant/sensor/overwatch_testhost/sensor_package_id_0_temperature/config";
+    let mut retained = Retained::new();
+    while !(retained.contains_key(state_topic) && retained.contains_key(config_topic)) {
+        let (topic, retain, payload) = rx.recv_timeout(Duration::from_secs(10)).expect("broker got no publish");
+        retained.insert(topic, (retain, payload));
+    }
+
+    assert_eq!(retained[state_topic], (true, "54.5".to_string()));
+
+    let (retain, payload) = &retained[config_topic];
+    assert!(retain);
+    let discovery: serde_json::Value = serde_json::from_str(payload).unwrap();
+    assert_eq!(discovery["state_topic"], state_topic);
+    assert_eq!(discovery["device_class"], "temperature");
+    assert_eq!(discovery["unit_of_measurement"], "°C");
+    assert_eq!(discovery["availability_topic"], "overwatch/testhost/status");
+    assert_eq!(discovery["device"]["identifiers"][0], "overwatch_testhost");
+
+    // availability for the entities above
+    assert_eq!(retained.get("overwatch/testhost/status"), Some(&(true, "online".to_string())));
+}
+
+#[test]
+fn parses_broker_urls() {
+    let plain = MqttConfig::from_url("mqtt://broker.local").unwrap();
+    assert_eq!((plain.host.as_str(), plain.port, plain.tls), ("broker.local", 1883, false));
+
+    let tls = MqttConfig::from_url("mqtts://broker.local:9883/").unwrap();
+    assert_eq!((tls.host.as_str(), tls.port, tls.tls), ("broker.local", 9883, true));
+
+    assert!(MqttConfig::from_url("http://broker.local").is_err());
+    assert!(MqttConfig::from_url("mqtt://:1883").is_err());
+
+    let v6 = MqttConfig::from_url("mqtt://[fd00::17]:1884").unwrap();
+    assert_eq!((v6.host.as_str(), v6.port), ("fd00::17", 1884));
+    let v6 = MqttConfig::from_url("mqtts://[::1]").unwrap();
+    assert_eq!((v6.host.as_str(), v6.port), ("::1", 8883));
+    let bare = MqttConfig::from_url("mqtt://fd00::17").unwrap();
+    assert_eq!((bare.host.as_str(), bare.port), ("fd00::17", 1883));
+    assert!(MqttConfig::from_url("mqtt://[::1").is_err());
+    assert!(MqttConfig::from_url("mqtt://[::1]1883").is_err());
+    assert!(MqttConfig::from_url("mqtt://[]:1883").is_err());
+}
@@ -0,0 +1,163 @@
+use app_lib::export::otlp::{self, OtlpConfig, OtlpExporter, Protocol};
+use app_lib::export::push::PushConfig;
+use app_lib::export::Exporter;
+use app_lib::{CpuInfo, HostSnapshot, MemoryInfo, OverwatchError, Provenance, Reading, Sensor};
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::{metric, number_data_point};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use prost::Message;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::time::Duration;
+
+fn snapshot() -> HostSnapshot {
+    HostSnapshot {
+        host: "testhost".to_string(),
+        timestamp: 1_700_000_000_000,
+        cpu: Reading::Err(OverwatchError::unsupported("CPU")),
+        gpu: Reading::Err(OverwatchError::unsupported("GPU")),
+        memory: Reading::Ok(MemoryInfo {
+            total: 16 << 30,
+            used: 4 << 30,
+            available: 12 << 30,
+            swap_total: 0,
+            swap_used: 0,
+        }),
+        sensors: Reading::Ok(vec![Sensor {
+            label: "Package id 0".to_string(),
+            temperature: 54.5,
+            max: None,
+            critical: None,
+        }]),
+        storage: None,
+    }
+}
+
+fn string_attr<'a>(attributes: &'a [KeyValue], key: &str) -> Option<&'a str> {
+    attributes.iter().find(|kv| kv.key == key).and_then(|kv| match kv.value.as_ref()?.value.as_ref()? {
+        any_value::Value::StringValue(value) => Some(value.as_str()),
+        _ => None,
+    })
+}
+
+#[test]
+fn exports_semantic_convention_metrics_over_http() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let config = OtlpConfig {
+        endpoint: format!("http://{}", listener.local_addr().unwrap()),
+        protocol: Protocol::HttpProtobuf,
+        headers: vec![("x-api-key".to_string(), "secret".to_string())],
+        push: PushConfig { interval: Duration::ZERO, buffer_dir: None, ..PushConfig::default() },
+    };
+    let mut exporter = OtlpExporter::connect(config).unwrap();
+    exporter.export(&snapshot()).unwrap();
+
+    let (stream, _) = listener.accept().unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    let (mut content_length, mut api_key) = (0, String::new());
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end().to_ascii_lowercase();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(len) = line.strip_prefix("content-length: ") {
+            content_length = len.parse().unwrap();
+        }
+        if let Some(key) = line.strip_prefix("x-api-key: ") {
+            api_key = key.to_string();
+        }
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).unwrap();
+    (&stream).write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+
+    assert!(request_line.starts_with("POST /v1/metrics "), "{}", request_line);
+    assert_eq!(api_key, "secret");
+
+    let request = ExportMetricsServiceRequest::decode(body.as_slice()).unwrap();
+    let resource_metrics = &request.resource_metrics[0];
+    let resource = resource_metrics.resource.as_ref().unwrap();
+    assert_eq!(string_attr(&resource.attributes, "host.name"), Some("testhost"));
+    assert_eq!(string_attr(&resource.attributes, "service.name"), Some("overwatch"));
+    assert!(string_attr(&resource.attributes, "os.type").is_some());
+
+    let metrics = &resource_metrics.scope_metrics[0].metrics;
+    let temperature = metrics.iter().find(|m| m.name == "hw.temperature").expect("no hw.temperature");
+    assert_eq!(temperature.unit, "Cel");
+    let Some(metric::Data::Gauge(gauge)) = &temperature.data else {
+        panic!("hw.temperature is not a gauge");
+    };
+    let point = &gauge.data_points[0];
+    assert_eq!(point.value, Some(number_data_point::Value::AsDouble(54.5)));
+    assert_eq!(point.time_unix_nano, 1_700_000_000_000_000_000);
+    assert_eq!(string_attr(&point.attributes, "hw.id"), Some("package_id_0"));
+
+    let memory = metrics.iter().find(|m| m.name == "system.memory.usage").expect("no system.memory.usage");
+    assert_eq!(memory.unit, "By");
+    let Some(metric::Data::Gauge(gauge)) = &memory.data else {
+        panic!("system.memory.usage is not a gauge");
+    };
+    // available memory includes cache, so it isn't passed off as the free state
+    let states: Vec<_> = gauge.data_points.iter().map(|point| string_attr(&point.attributes, "system.memory.state")).collect();
+    assert_eq!(states, [Some("used")]);
+    assert!(metrics.iter().any(|m| m.name == "system.memory.available"));
+}
+
+#[test]
+fn grpc_takes_https_endpoints() {
+    let config = OtlpConfig {
+        endpoint: "https://collector.example:4317".to_string(),
+        protocol: Protocol::Grpc,
+        headers: Vec::new(),
+        push: PushConfig { buffer_dir: None, ..PushConfig::default() },
+    };
+    // the channel connects lazily, so this only sets TLS up
+    assert!(OtlpExporter::connect(config).is_ok());
+}
+
+#[test]
+fn cpu_utilization_is_per_core_only() {
+    let mut snapshot = snapshot();
+    snapshot.cpu = Reading::Ok(CpuInfo {
+        brand: "Test CPU".to_string(),
+        frequency: 3000,
+        cores: 2,
+        logical_cores: 2,
+        usage: 30.0,
+        per_core_usage: vec![10.0, 50.0],
+        scheduler: None,
+        idle_states: Vec::new(),
+        temperature: 0.0,
+        temp_available: false,
+        package_power: None,
+        vendor: String::new(),
+        architecture: String::new(),
+        max_frequency: 0,
+        frequency_policy: None,
+        cache_l1: String::new(),
+        cache_l2: String::new(),
+        cache_l3: String::new(),
+        socket: String::new(),
+        process_node: String::new(),
+        provenance: Provenance::new(),
+    });
+
+    let request = otlp::request(&[snapshot], "overwatch", Resource::default());
+    let metrics = &request.resource_metrics[0].scope_metrics[0].metrics;
+    let points = |name: &str| match &metrics.iter().find(|m| m.name == name).unwrap().data {
+        Some(metric::Data::Gauge(gauge)) => gauge.data_points.clone(),
+        _ => panic!("{} is not a gauge", name),
+    };
+
+    // every point carries its core, so summing the series means something
+    let cores = points("system.cpu.utilization");
+    assert_eq!(cores.len(), 2);
+    assert!(cores.iter().all(|point| point.attributes.iter().any(|kv| kv.key == "cpu.logical_number")));
+    assert_eq!(cores[1].value, Some(number_data_point::Value::AsDouble(0.5)));
+    assert_eq!(points("overwatch.cpu.usage")[0].value, Some(number_data_point::Value::AsDouble(30.0)));
+}
@@ -0,0 +1,82 @@
+use app_lib::power::{self, BatteryState};
+use std::fs;
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+#[test]
+fn energy_battery_discharging() {
+    let root = fake_dir("energy");
+    write_attrs(&root.join("AC"), &[("type", "Mains"), ("online", "0")]);
+    write_attrs(
+        &root.join("BAT0"),
+        &[
+            ("type", "Battery"),
+            ("present", "1"),
+            ("status", "Discharging"),
+            ("capacity", "50"),
+            ("power_now", "10000000"),
+            ("energy_now", "25000000"),
+            ("energy_full", "50000000"),
+            ("energy_full_design", "57000000"),
+            ("cycle_count", "312"),
+            ("technology", "Li-poly"),
+            ("manufacturer", "SMP"),
+            ("model_name", "5B10W13975"),
+        ],
+    );
+    // a wireless mouse
+    write_attrs(&root.join("hidpp_battery_0"), &[("type", "Battery"), ("scope", "Device"), ("capacity", "80")]);
+
+    let (batteries, ac_online) = power::read_sysfs(&root).unwrap();
+    assert_eq!(ac_online, Some(false));
+    assert_eq!(batteries.len(), 1);
+    let battery = &batteries[0];
+    assert_eq!(battery.name, "BAT0");
+    assert_eq!(battery.state, BatteryState::Discharging);
+    assert_eq!((battery.charge_percent, battery.rate_watts), (Some(50.0), Some(10.0)));
+    assert_eq!(battery.time_to_empty_secs, Some(9000));
+    assert_eq!(battery.time_to_full_secs, None);
+    assert_eq!(battery.energy_full_design_wh, Some(57.0));
+    assert!((battery.wear_percent.unwrap() - 12.28).abs() < 0.01);
+    assert_eq!(battery.cycle_count, Some(312));
+    assert_eq!(battery.chemistry.as_deref(), Some("Li-poly"));
+
+    fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+fn charge_battery_charging() {
+    let root = fake_dir("charge");
+    write_attrs(&root.join("ADP1"), &[("type", "Mains"), ("online", "1")]);
+    write_attrs(&root.join("ucsi-source-psy-USBC000:001"), &[("type", "USB"), ("online", "0")]);
+    // µAh counters with a 10 V design voltage, no capacity or cycle count
+    write_attrs(
+        &root.join("BAT1"),
+        &[
+            ("type", "Battery"),
+            ("status", "Charging"),
+            ("voltage_min_design", "10000000"),
+            ("voltage_now", "12000000"),
+            ("current_now", "2000000"),
+            ("charge_now", "3000000"),
+            ("charge_full", "4000000"),
+            ("charge_full_design", "4000000"),
+            ("cycle_count", "0"),
+        ],
+    );
+
+    let (batteries, ac_online) = power::read_sysfs(&root).unwrap();
+    assert_eq!(ac_online, Some(true));
+    let battery = &batteries[0];
+    assert_eq!(battery.state, BatteryState::Charging);
+    assert_eq!(battery.rate_watts, Some(24.0));
+    assert_eq!((battery.energy_wh, battery.energy_full_wh), (Some(30.0), Some(40.0)));
+    assert_eq!(battery.charge_percent, Some(75.0));
+    assert_eq!(battery.time_to_full_secs, Some(1500));
+    assert_eq!(battery.wear_percent, Some(0.0));
+    assert_eq!(battery.cycle_count, None);
+
+    fs::remove_dir_all(&root).ok();
+}
@@ -0,0 +1,103 @@
+use app_lib::processes::{self, FileKind, TcpSocket};
+#[cfg(unix)]
+use std::fs;
+use std::time::Duration;
+
+mod common;
+
+#[cfg(unix)]
+use common::fake_dir;
+
+#[test]
+fn proc_files() {
+    let io = "rchar: 4096\nwchar: 1024\nsyscr: 10\nsyscw: 5\nread_bytes: 8192\nwrite_bytes: 512\ncancelled_write_bytes: 0\n";
+    assert_eq!(processes::parse_io(io), Some((8192, 512)));
+    assert_eq!(processes::parse_io(""), None);
+
+    let limits = "Limit                     Soft Limit           Hard Limit           Units     \n\
+                  Max processes             24001                24001                processes \n\
+                  Max open files            1024                 524288               files     \n";
+    assert_eq!(processes::parse_file_limit(limits), Some(1024));
+    assert_eq!(processes::parse_file_limit("Max open files            unlimited            unlimited            files\n"), None);
+}
+
+#[test]
+fn descriptors_and_sockets() {
+    assert_eq!(processes::classify_fd("socket:[178546]"), (FileKind::Socket, Some(178546)));
+    assert_eq!(processes::classify_fd("pipe:[1234]"), (FileKind::Pipe, None));
+    assert_eq!(processes::classify_fd("anon_inode:[eventfd]"), (FileKind::AnonInode, None));
+    assert_eq!(processes::classify_fd("/dev/null"), (FileKind::Device, None));
+    assert_eq!(processes::classify_fd("/home/user/build.log (deleted)"), (FileKind::File, None));
+
+    assert_eq!(processes::decode_address("0100007F:0016").as_deref(), Some("127.0.0.1:22"));
+    assert_eq!(processes::decode_address("00000000000000000000000001000000:0035").as_deref(), Some("[::1]:53"));
+    assert_eq!(processes::decode_address("zz:0016"), None);
+
+    let tcp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+               0: 00000000:07E8 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 662 1 0000000061ab6b87 100 0 0 10 0\n\
+               1: 0100007F:B7C6 0100007F:BC8F 01 00000000:00000000 02:000A0000 00000000  1000        0 178546 1 0000000084fba34b 20 4 30 10 -1\n";
+    let sockets = processes::parse_inet_sockets(tcp, "tcp");
+    assert_eq!(sockets.len(), 2);
+    assert_eq!(sockets[0].0, 662);
+    assert_eq!((sockets[0].1.local.as_deref(), sockets[0].1.remote.as_deref()), (Some("0.0.0.0:2024"), None));
+    assert_eq!(sockets[0].1.state.as_deref(), Some("listen"));
+    assert_eq!(sockets[1].1.remote.as_deref(), Some("127.0.0.1:48271"));
+    assert_eq!(sockets[1].1.state.as_deref(), Some("established"));
+
+    let unix = "Num       RefCount Protocol Flags    Type St Inode Path\n\
+                0000000000000000: 00000002 00000000 00010000 0001 01 159204 /run/user/1000/bus\n\
+                0000000000000000: 00000003 00000000 00000000 0001 03 658\n";
+    let sockets = processes::parse_unix_sockets(unix);
+    assert_eq!(sockets[0].1.local.as_deref(), Some("/run/user/1000/bus"));
+    assert_eq!(sockets[0].1.state.as_deref(), Some("listen"));
+    assert_eq!((sockets[1].0, sockets[1].1.state.as_deref()), (658, Some("connected")));
+}
+
+#[cfg(unix)]
+#[test]
+fn open_files_of_a_process() {
+    let root = fake_dir("files");
+    let fd = root.join("42/fd");
+    fs::create_dir_all(&fd).unwrap();
+    fs::create_dir_all(root.join("42/net")).unwrap();
+    std::os::unix::fs::symlink("/dev/null", fd.join("0")).unwrap();
+    std::os::unix::fs::symlink("socket:[662]", fd.join("10")).unwrap();
+    std::os::unix::fs::symlink("pipe:[99]", fd.join("2")).unwrap();
+    fs::write(
+        root.join("42/net/tcp"),
+        "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+         0: 00000000:07E8 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 662 1 0000000061ab6b87 100 0 0 10 0\n",
+    )
+    .unwrap();
+
+    let files = processes::read_files(&root, 42).unwrap();
+    assert_eq!(files.iter().map(|file| file.fd).collect::<Vec<_>>(), vec![0, 2, 10]);
+    assert_eq!(files[0].kind, FileKind::Device);
+    assert_eq!(files[1].kind, FileKind::Pipe);
+    let socket = files[2].socket.as_ref().unwrap();
+    assert_eq!((socket.protocol.as_str(), socket.local.as_deref()), ("tcp", Some("0.0.0.0:2024")));
+
+    assert!(processes::read_files(&root, 43).is_err());
+}
+
+#[test]
+fn tcp_throughput() {
+    let line = |bytes_acked: u64, received: u64| {
+        format!(
+            "ESTAB 0 0 127.0.0.1:47046 127.0.0.1:48271 users:((\"cargo\",pid=1234,fd=23),(\"cargo\",pid=1240,fd=23)) \
+             timer:(keepalive,18sec,0) ino:178546 sk:1 cgroup:/ <-> ts sack cubic rtt:0.081/0.021 \
+             bytes_sent:{} bytes_acked:{} bytes_received:{} segs_out:347",
+            bytes_acked + 100,
+            bytes_acked,
+            received
+        )
+    };
+    let before = processes::parse_ss(&format!("{}\n", line(1000, 500)));
+    assert_eq!(before, vec![TcpSocket { inode: 178546, pid: 1234, sent: 1000, received: 500 }]);
+    // nobody we can see owns this one
+    assert!(processes::parse_ss("ESTAB 0 0 10.0.0.2:22 10.0.0.3:5000 ino:1 bytes_acked:5 bytes_received:5\n").is_empty());
+
+    let after = processes::parse_ss(&format!("{}\n{}\n", line(21000, 10500), line(0, 0).replace("ino:178546", "ino:200000")));
+    let rates = processes::network_rates(&before, &after, Duration::from_secs(2));
+    assert_eq!(rates[&1234], (10000.0, 5000.0));
+}
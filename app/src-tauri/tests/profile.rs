@@ -0,0 +1,139 @@
+use app_lib::profile::{self, Knobs, PowerControl, PowerSetting};
+use std::fs;
+use std::path::PathBuf;
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+fn read(path: PathBuf) -> String {
+    fs::read_to_string(path).unwrap().trim().to_string()
+}
+
+fn fake_knobs(name: &str) -> Knobs {
+    let root = fake_dir(name);
+    let knobs = Knobs { cpu: root.join("cpu"), acpi: root.join("acpi") };
+    for policy in ["policy0", "policy1"] {
+        write_attrs(
+            &knobs.cpu.join("cpufreq").join(policy),
+            &[
+                ("scaling_driver", "amd-pstate-epp"),
+                ("scaling_governor", "powersave"),
+                ("scaling_available_governors", "performance powersave"),
+                ("energy_performance_preference", "balance_performance"),
+                ("energy_performance_available_preferences", "default performance balance_performance balance_power power"),
+            ],
+        );
+    }
+    write_attrs(&knobs.cpu.join("cpufreq"), &[("boost", "1")]);
+    write_attrs(&knobs.acpi, &[("platform_profile", "balanced"), ("platform_profile_choices", "low-power balanced performance")]);
+    knobs
+}
+
+#[test]
+fn applies_to_every_policy() {
+    let knobs = fake_knobs("apply");
+    knobs.apply(&PowerSetting::Governor("performance".to_string())).unwrap();
+    knobs.apply(&PowerSetting::Epp("power".to_string())).unwrap();
+    knobs.apply(&PowerSetting::Boost(false)).unwrap();
+    knobs.apply(&PowerSetting::PlatformProfile("low-power".to_string())).unwrap();
+
+    for policy in ["policy0", "policy1"] {
+        let dir = knobs.cpu.join("cpufreq").join(policy);
+        assert_eq!(read(dir.join("scaling_governor")), "performance");
+        assert_eq!(read(dir.join("energy_performance_preference")), "power");
+    }
+    assert_eq!(read(knobs.cpu.join("cpufreq/boost")), "0");
+    assert_eq!(read(knobs.acpi.join("platform_profile")), "low-power");
+    assert_eq!(knobs.current(&PowerSetting::Boost(true)).unwrap(), PowerSetting::Boost(false));
+}
+
+#[test]
+fn rejects_what_the_hardware_doesnt_offer() {
+    let knobs = fake_knobs("reject");
+    assert!(knobs.plan(&PowerSetting::Governor("ondemand".to_string())).is_err());
+    assert!(knobs.plan(&PowerSetting::Epp("balance_performance\nperformance".to_string())).is_err());
+    assert!(knobs.plan(&PowerSetting::PlatformProfile("quiet".to_string())).is_err());
+    assert_eq!(read(knobs.cpu.join("cpufreq/policy0/scaling_governor")), "powersave");
+}
+
+#[test]
+fn intel_turbo_is_inverted() {
+    let knobs = fake_knobs("intel");
+    fs::remove_file(knobs.cpu.join("cpufreq/boost")).unwrap();
+    write_attrs(&knobs.cpu.join("intel_pstate"), &[("no_turbo", "0")]);
+
+    knobs.apply(&PowerSetting::Boost(false)).unwrap();
+    assert_eq!(read(knobs.cpu.join("intel_pstate/no_turbo")), "1");
+}
+
+#[test]
+fn helper_arguments() {
+    for setting in [PowerSetting::Governor("performance".to_string()), PowerSetting::Boost(true), PowerSetting::PlatformProfile("quiet".to_string())] {
+        let [kind, value] = setting.to_args();
+        assert_eq!(PowerSetting::from_args(&kind, &value).unwrap(), setting);
+    }
+    assert!(PowerSetting::from_args("scaling_max_freq", "800000").is_err());
+    assert!(profile::helper_from_args(&["governor".to_string()]).is_err());
+}
+
+#[test]
+fn audits_and_reverts() {
+    let knobs = fake_knobs("revert");
+    let audit = knobs.cpu.parent().unwrap().join("data").join(profile::AUDIT_LOG);
+    let control = PowerControl::default();
+
+    control.set(&knobs, PowerSetting::Governor("performance".to_string()), &audit).unwrap();
+    control.set(&knobs, PowerSetting::PlatformProfile("performance".to_string()), &audit).unwrap();
+    // the original is the value from before the first change
+    control.set(&knobs, PowerSetting::Governor("powersave".to_string()), &audit).unwrap();
+    control.set(&knobs, PowerSetting::Governor("performance".to_string()), &audit).unwrap();
+    assert!(control.set(&knobs, PowerSetting::Governor("turbo".to_string()), &audit).is_err());
+    assert_eq!(
+        control.originals(),
+        [PowerSetting::Governor("powersave".to_string()), PowerSetting::PlatformProfile("balanced".to_string())]
+    );
+
+    let entries = profile::read_audit(&audit, 50).unwrap();
+    assert_eq!(entries.len(), 5);
+    assert_eq!(entries[0].previous, Some(PowerSetting::Governor("powersave".to_string())));
+    assert!(entries[0].error.is_none());
+    assert!(entries[4].error.is_some());
+    assert_eq!(profile::read_audit(&audit, 2).unwrap()[1], entries[4]);
+
+    control.revert(&knobs, &audit).unwrap();
+    assert!(control.originals().is_empty());
+    assert_eq!(read(knobs.cpu.join("cpufreq/policy1/scaling_governor")), "powersave");
+    assert_eq!(read(knobs.acpi.join("platform_profile")), "balanced");
+    assert_eq!(profile::read_audit(&audit, 50).unwrap().len(), 7);
+
+    let controls = control.read(&knobs);
+    assert_eq!(controls.platform_profile.as_deref(), Some("balanced"));
+    assert_eq!(controls.available_platform_profiles.len(), 3);
+    assert_eq!(controls.policy.unwrap().governor.as_deref(), Some("powersave"));
+}
+
+#[test]
+fn reverts_mixed_governors_per_policy() {
+    let knobs = fake_knobs("mixed");
+    let audit = knobs.cpu.parent().unwrap().join("data").join(profile::AUDIT_LOG);
+    write_attrs(&knobs.cpu.join("cpufreq/policy1"), &[("scaling_governor", "performance")]);
+    let control = PowerControl::default();
+
+    control.set(&knobs, PowerSetting::Governor("performance".to_string()), &audit).unwrap();
+    assert_eq!(
+        control.originals(),
+        [PowerSetting::Governor("powersave".to_string()), PowerSetting::Governor("performance".to_string())]
+    );
+    assert!(knobs.plan_on(&PowerSetting::Governor("powersave".to_string()), Some(&[2])).is_err());
+    assert!(knobs.plan_on(&PowerSetting::Boost(true), Some(&[0])).is_err());
+
+    control.revert(&knobs, &audit).unwrap();
+    assert_eq!(read(knobs.cpu.join("cpufreq/policy0/scaling_governor")), "powersave");
+    assert_eq!(read(knobs.cpu.join("cpufreq/policy1/scaling_governor")), "performance");
+    let entries = profile::read_audit(&audit, 50).unwrap();
+    assert_eq!(entries.len(), 3);
+    assert!(entries[1..].iter().all(|entry| entry.policies.as_ref().is_some_and(|policies| policies.len() == 1)));
+    assert_eq!(profile::policies_from_arg(&profile::policies_to_arg(&[0, 2])).unwrap(), [0, 2]);
+    assert!(profile::policies_from_arg("0,../1").is_err());
+}
@@ -0,0 +1,193 @@
+use app_lib::export::influx::{InfluxConfig, InfluxExporter};
+use app_lib::export::push::PushConfig;
+use app_lib::export::statsd::{Flavor, StatsdConfig, StatsdExporter};
+use app_lib::export::Exporter;
+use app_lib::{HostSnapshot, OverwatchError, Reading, Sensor};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, UdpSocket};
+use std::time::{Duration, Instant};
+
+fn snapshot(timestamp: u64) -> HostSnapshot {
+    HostSnapshot {
+        host: "testhost".to_string(),
+        timestamp,
+        cpu: Reading::Err(OverwatchError::unsupported("CPU")),
+        gpu: Reading::Err(OverwatchError::unsupported("GPU")),
+        memory: Reading::Err(OverwatchError::unsupported("memory")),
+        sensors: Reading::Ok(vec![Sensor {
+            label: "Package id 0".to_string(),
+            temperature: 54.5,
+            max: None,
+            critical: None,
+        }]),
+        storage: None,
+    }
+}
+
+fn push_config(buffer_dir: Option<std::path::PathBuf>) -> PushConfig {
+    PushConfig {
+        interval: Duration::ZERO,
+        prefix: "ow".to_string(),
+        tags: vec![("site".to_string(), "lab".to_string())],
+        buffer_dir,
+    }
+}
+
+// exports until the receiver sees something, the first flush may race the socket
+fn recv_udp(socket: &UdpSocket, exporter: &mut dyn Exporter) -> String {
+    socket.set_read_timeout(Some(Duration::from_millis(200))).unwrap();
+    let mut buf = [0u8; 2048];
+    for _ in 0..25 {
+        exporter.export(&snapshot(1_700_000_000_000)).ok();
+        if let Ok(n) = socket.recv(&mut buf) {
+            return String::from_utf8(buf[..n].to_vec()).unwrap();
+        }
+    }
+    panic!("nothing arrived over UDP");
+}
+
+#[test]
+fn influx_udp_sends_line_protocol() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let url = format!("udp://{}", socket.local_addr().unwrap());
+    let mut exporter = InfluxExporter::connect(InfluxConfig::from_url(&url, None, push_config(None)).unwrap()).unwrap();
+
+    let datagram = recv_udp(&socket, &mut exporter);
+    assert!(
+        datagram.lines().any(|line| line == "ow_sensor,host=testhost,site=lab,sensor=Package\\ id\\ 0 temperature=54.5 1700000000000000000"),
+        "unexpected datagram: {}",
+        datagram
+    );
+}
+
+#[test]
+fn statsd_flavors() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = socket.local_addr().unwrap().to_string();
+
+    // plain StatsD would have to drop tags, so it won't take any
+    let config = StatsdConfig { addr: addr.clone(), flavor: Flavor::Statsd, push: push_config(None) };
+    assert!(StatsdExporter::connect(config).is_err());
+
+    let push = PushConfig { tags: Vec::new(), ..push_config(None) };
+    let config = StatsdConfig { addr: addr.clone(), flavor: Flavor::Statsd, push };
+    let mut plain = StatsdExporter::connect(config).unwrap();
+    assert_eq!(recv_udp(&socket, &mut plain).lines().next(), Some("ow.testhost.sensor.package_id_0.temperature:54.5|g"));
+    drop(plain);
+    // drain anything the plain exporter still had in flight
+    let mut buf = [0u8; 2048];
+    while socket.recv(&mut buf).is_ok() {}
+
+    let config = StatsdConfig { addr, flavor: Flavor::Dogstatsd, push: push_config(None) };
+    let mut dog = StatsdExporter::connect(config).unwrap();
+    assert_eq!(
+        recv_udp(&socket, &mut dog).lines().next(),
+        Some("ow.sensor.temperature:54.5|g|#host:testhost,site:lab,sensor:Package id 0")
+    );
+}
+
+#[test]
+fn influx_http_spools_while_down_and_catches_up() {
+    let buffer_dir = std::env::temp_dir().join(format!("overwatch-push-test-{}", std::process::id()));
+    std::fs::remove_dir_all(&buffer_dir).ok();
+
+    // grab a free port, then leave it closed so writes are refused
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let url = format!("http://127.0.0.1:{}/api/v2/write?org=o&bucket=b", port);
+    let config = InfluxConfig::from_url(&url, Some("secret".to_string()), push_config(Some(buffer_dir.clone()))).unwrap();
+    let mut exporter = InfluxExporter::connect(config).unwrap();
+
+    exporter.export(&snapshot(1_000)).ok();
+    let spool = buffer_dir.join("influx.spool");
+    let deadline = Instant::now() + Duration::from_secs(15);
+    while !spool.exists() {
+        assert!(Instant::now() < deadline, "batch was never spooled");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    // bring the "server" up; the next flush should carry the backlog too
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let stream = loop {
+        exporter.export(&snapshot(2_000)).ok();
+        if let Ok((stream, _)) = listener.accept() {
+            break stream;
+        }
+        assert!(Instant::now() < deadline, "exporter never retried");
+        std::thread::sleep(Duration::from_millis(200));
+    };
+    stream.set_nonblocking(false).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut content_length = 0;
+    let mut authorization = String::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let lower = line.to_ascii_lowercase();
+        if let Some(len) = lower.strip_prefix("content-length: ") {
+            content_length = len.parse().unwrap();
+        }
+        if lower.starts_with("authorization: ") {
+            authorization = line["authorization: ".len()..].to_string();
+        }
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).unwrap();
+    (&stream).write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n").unwrap();
+
+    assert_eq!(authorization, "Token secret");
+    let body = String::from_utf8(body).unwrap();
+    let timestamps: Vec<&str> = body.lines().filter_map(|line| line.rsplit(' ').next()).collect();
+    assert!(timestamps.contains(&"1000000000"), "spooled point missing: {}", body);
+    assert!(timestamps.contains(&"2000000000"), "new point missing: {}", body);
+
+    std::fs::remove_dir_all(&buffer_dir).ok();
+}
+
+#[test]
+fn influx_keeps_the_spool_when_the_server_refuses() {
+    let buffer_dir = std::env::temp_dir().join(format!("overwatch-push-refused-{}", std::process::id()));
+    std::fs::remove_dir_all(&buffer_dir).ok();
+    std::fs::create_dir_all(&buffer_dir).unwrap();
+    let spool = buffer_dir.join("influx.spool");
+    std::fs::write(&spool, "ow_old value=1 1000000000\n").unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/api/v2/write?org=o&bucket=b", listener.local_addr().unwrap());
+    let config = InfluxConfig::from_url(&url, Some("stale".to_string()), push_config(Some(buffer_dir.clone()))).unwrap();
+    let mut exporter = InfluxExporter::connect(config).unwrap();
+    exporter.export(&snapshot(2_000)).ok();
+
+    let (stream, _) = listener.accept().unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length: ") {
+            content_length = len.trim().parse().unwrap();
+        }
+        if line.trim_end().is_empty() {
+            break;
+        }
+    }
+    reader.read_exact(&mut vec![0u8; content_length]).unwrap();
+    (&stream).write_all(b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n").unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(15);
+    loop {
+        let spooled = std::fs::read_to_string(&spool).unwrap_or_default();
+        if spooled.contains(" 2000000000") {
+            assert!(spooled.contains("ow_old value=1 1000000000"), "backlog dropped: {}", spooled);
+            break;
+        }
+        assert!(Instant::now() < deadline, "refused batch was never spooled");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    std::fs::remove_dir_all(&buffer_dir).ok();
+}
@@ -0,0 +1,95 @@
+use app_lib::rapl::{self, Counter, EnergyMeter};
+use std::time::{Duration, Instant};
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+fn counter(zone: &str, name: &str, energy_uj: u64) -> Counter {
+    Counter {
+        zone: zone.to_string(),
+        name: name.to_string(),
+        package: 0,
+        energy_uj,
+        max_energy_uj: 262_143_328_850,
+    }
+}
+
+#[test]
+fn delta_handles_wraparound() {
+    assert_eq!(rapl::delta_uj(1_000, 5_000, 10_000), 4_000);
+    assert_eq!(rapl::delta_uj(9_000, 500, 10_000), 1_500);
+}
+
+#[test]
+fn powercap_zones() {
+    let root = fake_dir("powercap");
+    let max = ("max_energy_range_uj", "262143328850");
+    write_attrs(&root.join("intel-rapl:0"), &[("name", "package-0"), ("energy_uj", "8500000"), max]);
+    write_attrs(&root.join("intel-rapl:0:0"), &[("name", "core"), ("energy_uj", "4200000"), max]);
+    write_attrs(&root.join("intel-rapl-mmio:0"), &[("name", "package-0"), ("energy_uj", "8400000"), max]);
+
+    let counters = rapl::read_powercap(&root).unwrap();
+    assert_eq!(counters.len(), 2);
+    assert_eq!(counters[0].zone, "intel-rapl:0");
+    assert!(counters[0].is_package());
+    assert_eq!(counters[0].energy_uj, 8_500_000);
+    assert_eq!(counters[0].max_energy_uj, 262_143_328_850);
+    assert_eq!(counters[1].name, "core");
+    assert!(!counters[1].is_package());
+
+    let elapsed = Duration::from_millis(500);
+    let after = vec![counter("intel-rapl:0", "package-0", 13_500_000)];
+    let watts = rapl::watts(&counters, &after, elapsed);
+    assert_eq!(watts.len(), 1);
+    assert!((watts[0].1 - 10.0).abs() < 0.01);
+}
+
+#[test]
+fn powercap_without_zones() {
+    let root = fake_dir("empty");
+    assert!(rapl::read_powercap(&root).is_err());
+}
+
+#[test]
+fn meter_accumulates_energy() {
+    let meter = EnergyMeter::default();
+    let start = Instant::now();
+
+    let first = meter.update(vec![counter("intel-rapl:0", "package-0", 1_000_000)], start, None);
+    assert_eq!(first[0].watts, None);
+    assert_eq!(first[0].energy_wh, 0.0);
+
+    // 36 J over a second
+    let second = meter.update(vec![counter("intel-rapl:0", "package-0", 37_000_000)], start + Duration::from_secs(1), None);
+    assert!((second[0].watts.unwrap() - 36.0).abs() < 0.01);
+    assert!((second[0].energy_wh - 0.01).abs() < 1e-9);
+
+    // the counter wrapped: 262143.32885 J max, 3.32885 J to the top and 2 J past it
+    meter.update(vec![counter("intel-rapl:0", "package-0", 262_140_000_000)], start + Duration::from_secs(2), None);
+    let after_wrap = meter.update(vec![counter("intel-rapl:0", "package-0", 2_000_000)], start + Duration::from_secs(3), None);
+    assert!((after_wrap[0].watts.unwrap() - 5.32885).abs() < 0.01);
+}
+
+#[test]
+fn meter_restarts_with_recording() {
+    let meter = EnergyMeter::default();
+    let start = Instant::now();
+    let at = |secs| start + Duration::from_secs(secs);
+    let package = |energy_uj| vec![counter("intel-rapl:0", "package-0", energy_uj)];
+    meter.update(package(0), start, None);
+    let live = meter.update(package(3_600_000), at(1), None);
+    assert!((live[0].energy_wh - 0.001).abs() < 1e-9);
+
+    // the first reading of a recording is its baseline; the interval before it doesn't count
+    let recording = meter.update(package(7_200_000), at(2), Some(1_700_000_000_000));
+    assert_eq!((recording[0].watts, recording[0].energy_wh), (None, 0.0));
+    let recording = meter.update(package(10_800_000), at(3), Some(1_700_000_000_000));
+    assert!((recording[0].energy_wh - 0.001).abs() < 1e-9);
+
+    meter.reset();
+    let reset = meter.update(package(14_400_000), at(4), Some(1_700_000_000_000));
+    assert_eq!((reset[0].watts, reset[0].energy_wh), (None, 0.0));
+    let reset = meter.update(package(18_000_000), at(5), Some(1_700_000_000_000));
+    assert!((reset[0].energy_wh - 0.001).abs() < 1e-9);
+}
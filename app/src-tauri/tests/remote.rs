@@ -0,0 +1,92 @@
+use app_lib::remote::agent::AgentConfig;
+use app_lib::remote::client::RemoteConnection;
+use app_lib::{OverwatchError, Reading};
+use std::io::{BufRead, BufReader};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+const TOKEN: &str = "loopback-test-token";
+
+struct AgentProcess(Child);
+
+impl Drop for AgentProcess {
+    fn drop(&mut self) {
+        self.0.kill().ok();
+        self.0.wait().ok();
+    }
+}
+
+// starts `app --agent` on an ephemeral loopback port and returns its address
+fn spawn_agent() -> (AgentProcess, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_app"))
+        .args(["--agent", "--listen", "127.0.0.1:0", "--token", TOKEN, "--interval-ms", "200"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start agent");
+
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut line)
+        .expect("agent printed nothing");
+    let address = line
+        .trim()
+        .rsplit(' ')
+        .next()
+        .expect("agent did not print its address")
+        .to_string();
+
+    (AgentProcess(child), address)
+}
+
+#[test]
+fn agent_streams_snapshots_over_loopback() {
+    let (_agent, address) = spawn_agent();
+
+    let mut conn = RemoteConnection::connect(&address, TOKEN, Duration::from_secs(5)).expect("handshake failed");
+    let first = conn.next_snapshot().expect("no snapshot");
+    let second = conn.next_snapshot().expect("no second snapshot");
+
+    assert_eq!(first.host, conn.host);
+    assert!(second.timestamp > first.timestamp);
+    match first.cpu {
+        Reading::Ok(cpu) => assert!(cpu.logical_cores > 0),
+        Reading::Err(err) => panic!("agent failed to read the CPU: {}", err),
+    }
+}
+
+#[test]
+fn agent_rejects_wrong_token() {
+    let (_agent, address) = spawn_agent();
+
+    let result = RemoteConnection::connect(&address, "not-the-token", Duration::from_secs(5));
+    assert!(matches!(result, Err(OverwatchError::PermissionDenied { .. })));
+}
+
+#[test]
+fn agent_caps_connections_that_never_say_hello() {
+    let (_agent, address) = spawn_agent();
+
+    // the agent takes 16 waiting handshakes and closes anything past that
+    let silent: Vec<TcpStream> = (0..16).map(|_| TcpStream::connect(&address).unwrap()).collect();
+    assert!(RemoteConnection::connect(&address, TOKEN, Duration::from_secs(5)).is_err());
+
+    // hanging up gives the slots back
+    drop(silent);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while RemoteConnection::connect(&address, TOKEN, Duration::from_secs(5)).is_err() {
+        assert!(Instant::now() < deadline, "slots never came back");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn agent_listens_on_loopback_unless_told_otherwise() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    let config = AgentConfig::from_args(&args(&["--token", TOKEN])).unwrap();
+    assert_eq!(config.listen, Some("127.0.0.1:7878".parse().unwrap()));
+
+    let config = AgentConfig::from_args(&args(&["--listen", "0.0.0.0:7878", "--token", TOKEN])).unwrap();
+    assert_eq!(config.listen, Some("0.0.0.0:7878".parse().unwrap()));
+}
@@ -0,0 +1,77 @@
+use app_lib::sched;
+use std::fs;
+use std::time::Duration;
+
+mod common;
+
+use common::fake_dir;
+
+fn stat(ctxt: u64, intr: u64, softirq: u64, processes: u64, running: u32) -> String {
+    format!(
+        "cpu  4705 356 584 3699 23 23 0 0 0 0\n\
+         cpu0 1393 280 290 3699 23 23 0 0 0 0\n\
+         intr {} 26 0 0 0 0\n\
+         ctxt {}\n\
+         btime 1700000000\n\
+         processes {}\n\
+         procs_running {}\n\
+         procs_blocked 1\n\
+         softirq {} 0 12 0 0\n",
+        intr, ctxt, processes, running, softirq
+    )
+}
+
+#[test]
+fn proc_stat_counters() {
+    let counters = sched::parse_stat(&stat(1000, 500, 300, 42, 3)).unwrap();
+    assert_eq!(counters.context_switches, 1000);
+    assert_eq!(counters.interrupts, 500);
+    assert_eq!(counters.softirqs, 300);
+    assert_eq!(counters.forks, 42);
+    assert_eq!(counters.running, 3);
+    assert_eq!(counters.blocked, 1);
+
+    assert!(sched::parse_stat("cpu 1 2 3 4\n").is_err());
+}
+
+#[test]
+fn loadavg_and_pressure() {
+    let load = sched::parse_loadavg("0.52 1.58 2.59 2/1234 5678\n").unwrap();
+    assert_eq!((load.one, load.five, load.fifteen), (0.52, 1.58, 2.59));
+    assert!(sched::parse_loadavg("").is_err());
+
+    let pressure = sched::parse_pressure(
+        "some avg10=1.50 avg60=0.75 avg300=0.10 total=123456\nfull avg10=0.50 avg60=0.25 avg300=0.00 total=4567\n",
+    )
+    .unwrap();
+    assert_eq!(pressure.some.avg10, 1.5);
+    assert_eq!(pressure.some.avg300, 0.1);
+    assert_eq!(pressure.full.unwrap().avg60, 0.25);
+
+    // CPU pressure before 5.13 has no "full" line
+    let older = sched::parse_pressure("some avg10=3.00 avg60=2.00 avg300=1.00 total=99\n").unwrap();
+    assert_eq!(older.full, None);
+}
+
+#[test]
+fn rates_over_window() {
+    let root = fake_dir("rates");
+    fs::write(root.join("loadavg"), "4.00 3.00 2.00 5/900 1234\n").unwrap();
+    fs::create_dir_all(root.join("pressure")).unwrap();
+    fs::write(root.join("pressure/io"), "some avg10=12.00 avg60=8.00 avg300=4.00 total=1\nfull avg10=6.00 avg60=4.00 avg300=2.00 total=1\n").unwrap();
+
+    let before = sched::parse_stat(&stat(1000, 500, 300, 42, 3)).unwrap();
+    let after = sched::parse_stat(&stat(3000, 900, 400, 52, 5)).unwrap();
+    let activity = sched::activity(&before, &after, Duration::from_millis(200), &root);
+
+    assert_eq!(activity.load.unwrap().one, 4.0);
+    assert_eq!(activity.run_queue, 5);
+    assert_eq!(activity.blocked, Some(1));
+    assert_eq!(activity.context_switches, 10000.0);
+    assert_eq!(activity.interrupts, 2000.0);
+    assert_eq!(activity.softirqs, 500.0);
+    assert_eq!(activity.forks, Some(50.0));
+    // no PSI for CPU here, only IO
+    assert_eq!(activity.cpu_pressure, None);
+    assert_eq!(activity.io_pressure.unwrap().some.avg10, 12.0);
+}
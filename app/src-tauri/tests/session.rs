@@ -0,0 +1,57 @@
+use app_lib::session::{Session, SessionMode};
+use app_lib::{OverwatchError, Provenance, SystemInfo};
+
+fn system_info(hostname: &str) -> SystemInfo {
+    SystemInfo {
+        hostname: hostname.to_string(),
+        os_name: "Linux".to_string(),
+        os_version: "1".to_string(),
+        kernel_version: "6.0".to_string(),
+        architecture: "x86_64".to_string(),
+        boot_time: 1_700_000_000,
+        uptime_secs: 42,
+        boot_history: Vec::new(),
+        provenance: Provenance::new(),
+    }
+}
+
+#[test]
+fn recorded_samples_replay_in_place_of_live_reads() {
+    let path = std::env::temp_dir().join(format!("overwatch-session-{}.jsonl.gz", std::process::id()));
+    let session = Session::default();
+
+    session.start_recording(&path).unwrap();
+    assert_eq!(session.sample(|| Ok(system_info("recorded"))).unwrap().hostname, "recorded");
+    session.record_event("remote-host-update", &serde_json::json!({ "id": 1 }));
+    let stopped = session.stop_recording().unwrap();
+    assert_eq!(stopped.mode, SessionMode::Live);
+    assert_eq!(stopped.records, 2);
+
+    let opened = session.open_replay(&path).unwrap();
+    assert_eq!(opened.mode, SessionMode::Replaying);
+    session.control_replay(None, Some(true), Some(opened.duration_ms)).unwrap();
+
+    // the live closure must not run while replaying
+    let replayed = session.sample::<SystemInfo>(|| Err(OverwatchError::unsupported("live read"))).unwrap();
+    assert_eq!(replayed.hostname, "recorded");
+
+    session.close_replay().unwrap();
+    assert_eq!(session.sample(|| Ok(system_info("live"))).unwrap().hostname, "live");
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn recording_cut_off_before_a_flush_still_opens() {
+    let path = std::env::temp_dir().join(format!("overwatch-session-cut-{}.jsonl.gz", std::process::id()));
+    let session = Session::default();
+    session.start_recording(&path).unwrap();
+    session.sample(|| Ok(system_info("recorded"))).unwrap();
+    // a crash: the writer is never finished or dropped
+    std::mem::forget(session);
+
+    let replay = Session::default();
+    assert_eq!(replay.open_replay(&path).unwrap().mode, SessionMode::Replaying);
+
+    std::fs::remove_file(&path).ok();
+}
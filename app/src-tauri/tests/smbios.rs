@@ -0,0 +1,131 @@
+use app_lib::smbios::{self, chassis_type_name, meaningful, Table};
+use app_lib::MemoryLayout;
+
+// type, handle, fields after the 4-byte header, strings
+fn structure(kind: u8, handle: u16, fields: &[u8], strings: &[&str]) -> Vec<u8> {
+    let mut bytes = vec![kind, 4 + fields.len() as u8];
+    bytes.extend(handle.to_le_bytes());
+    bytes.extend(fields);
+    for string in strings {
+        bytes.extend(string.as_bytes());
+        bytes.push(0);
+    }
+    if strings.is_empty() {
+        bytes.push(0);
+    }
+    bytes.push(0);
+    bytes
+}
+
+fn table() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    // vendor, version, start segment (2), release date
+    bytes.extend(structure(smbios::BIOS, 0, &[1, 2, 0, 0, 3], &["American Megatrends Inc.", "1.40", "05/15/2023"]));
+    // manufacturer, product, version, serial
+    bytes.extend(structure(smbios::SYSTEM, 1, &[1, 2, 0, 3], &["Micro-Star International Co., Ltd.", "MS-7D25", "To Be Filled By O.E.M."]));
+    bytes.extend(structure(smbios::BASEBOARD, 2, &[1, 2, 3, 4], &["Micro-Star International Co., Ltd.", "PRO Z690-A", "1.0", "07D2511_M91E123456"]));
+    // manufacturer, type with the lock bit set
+    bytes.extend(structure(smbios::CHASSIS, 3, &[1, 0x83], &["Micro-Star International Co., Ltd."]));
+    bytes.extend(structure(127, 4, &[], &[]));
+    bytes
+}
+
+#[test]
+fn parses_strings_and_fields() {
+    let table = Table::parse(&table());
+    assert_eq!(table.structures.len(), 5);
+
+    let bios = table.first(smbios::BIOS).unwrap();
+    assert_eq!(bios.string(0x04).as_deref(), Some("American Megatrends Inc."));
+    assert_eq!(bios.string(0x08).as_deref(), Some("05/15/2023"));
+    assert_eq!(bios.word(0x06), Some(0));
+
+    let board = table.first(smbios::BASEBOARD).unwrap();
+    assert_eq!(board.handle, 2);
+    assert_eq!(board.string(0x05).as_deref(), Some("PRO Z690-A"));
+    assert_eq!(board.string(0x07).as_deref(), Some("07D2511_M91E123456"));
+
+    // placeholders and unset (0) string indexes read as nothing
+    let system = table.first(smbios::SYSTEM).unwrap();
+    assert_eq!(system.string(0x07), None);
+    assert_eq!(system.string(0x06), None);
+
+    let chassis = table.first(smbios::CHASSIS).unwrap();
+    assert_eq!(chassis.byte(0x05).and_then(chassis_type_name), Some("Desktop"));
+}
+
+#[test]
+fn truncated_tables_keep_the_complete_structures() {
+    let bytes = table();
+    let table = Table::parse(&bytes[..bytes.len() - 12]);
+    assert_eq!(table.all(smbios::BIOS).count(), 1);
+    assert!(table.first(127).is_none());
+
+    assert!(Table::parse(&[]).structures.is_empty());
+    assert!(Table::parse(&[1, 2, 0, 0]).structures.is_empty());
+}
+
+#[test]
+fn placeholder_strings() {
+    assert_eq!(meaningful("  ASUSTeK COMPUTER INC. \n"), Some("ASUSTeK COMPUTER INC."));
+    assert_eq!(meaningful("Default string"), None);
+    assert_eq!(meaningful("0000000000"), None);
+    assert_eq!(meaningful(""), None);
+    assert_eq!(chassis_type_name(0x02), None);
+    assert_eq!(chassis_type_name(0x0a), Some("Notebook"));
+}
+
+// a 2.8-length type 17 record; strings are locator, bank, manufacturer, part number
+fn memory_device(handle: u16, size_mib: u16, speeds: (u16, u16), strings: &[&str]) -> Vec<u8> {
+    let mut fields = vec![0u8; 0x28 - 4];
+    let mut put = |offset: usize, bytes: &[u8]| fields[offset - 4..offset - 4 + bytes.len()].copy_from_slice(bytes);
+    put(0x04, &0x10u16.to_le_bytes());
+    put(0x08, &72u16.to_le_bytes());
+    put(0x0a, &64u16.to_le_bytes());
+    put(0x0c, &size_mib.to_le_bytes());
+    put(0x0e, &[0x09]);
+    put(0x10, &[1, 2, 0x22]);
+    put(0x15, &speeds.0.to_le_bytes());
+    put(0x17, &[3, 0, 0, 4, 0x02]);
+    put(0x1c, &(64u32 * 1024).to_le_bytes());
+    put(0x20, &speeds.1.to_le_bytes());
+    put(0x26, &1100u16.to_le_bytes());
+    structure(smbios::MEMORY_DEVICE, handle, &fields, strings)
+}
+
+#[test]
+fn memory_layout_from_types_16_and_17() {
+    let mut bytes = Vec::new();
+    // system board, system memory, multi-bit ECC, 128 GiB in KiB, 4 slots
+    let mut array = vec![0x03, 0x03, 0x06];
+    array.extend((128u32 << 20).to_le_bytes());
+    array.extend([0xfe, 0xff, 4, 0]);
+    bytes.extend(structure(smbios::MEMORY_ARRAY, 0x10, &array, &[]));
+    bytes.extend(memory_device(0x11, 16384, (5600, 4800), &["DIMM_A2", "BANK 0", "Kingston", "KF556C40-16"]));
+    // 0x7fff defers to the extended size
+    bytes.extend(memory_device(0x12, 0x7fff, (5600, 5600), &["DIMM_B2", "BANK 1", "Kingston", "KF556C40-16"]));
+    bytes.extend(memory_device(0x13, 0, (0, 0), &["DIMM_A1", "BANK 0", "NO DIMM", "NO DIMM"]));
+
+    let layout = MemoryLayout::from_smbios(&Table::parse(&bytes));
+    assert_eq!(layout.total_slots, 4);
+    assert_eq!(layout.free_slots, 2);
+    assert_eq!(layout.installed, 80 << 30);
+    assert_eq!(layout.max_capacity, Some(128 << 30));
+    assert_eq!(layout.error_correction.as_deref(), Some("Multi-bit ECC"));
+
+    let first = &layout.modules[0];
+    assert_eq!(first.slot, "DIMM_A2");
+    assert_eq!(first.size, Some(16 << 30));
+    assert_eq!(first.memory_type.as_deref(), Some("DDR5"));
+    assert_eq!(first.form_factor.as_deref(), Some("DIMM"));
+    assert_eq!((first.rated_speed, first.configured_speed), (Some(5600), Some(4800)));
+    assert_eq!(first.part_number.as_deref(), Some("KF556C40-16"));
+    assert_eq!(first.rank, Some(2));
+    assert_eq!(first.ecc, Some(true));
+    assert_eq!(first.configured_voltage_mv, Some(1100));
+    assert_eq!(layout.modules[1].size, Some(64 << 30));
+
+    let empty = &layout.modules[2];
+    assert_eq!(empty.size, Some(0));
+    assert_eq!((empty.manufacturer.as_ref(), empty.memory_type.as_ref(), empty.rated_speed), (None, None, None));
+}
@@ -0,0 +1,178 @@
+use app_lib::storage::ata::{self, SmartAttribute};
+use app_lib::storage::nvme::{NvmeHealth, LOG_SIZE};
+use app_lib::storage::smartctl;
+use app_lib::storage::{DriveHealth, DriveKind, HealthMonitor, HealthStatus};
+use app_lib::{OverwatchError, Source};
+
+fn put(log: &mut [u8], offset: usize, value: u64) {
+    log[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+#[test]
+fn nvme_health_log() {
+    let mut log = [0u8; LOG_SIZE];
+    log[0] = 0b0100;
+    // 310 K
+    log[1..3].copy_from_slice(&310u16.to_le_bytes());
+    log[3] = 100;
+    log[4] = 10;
+    log[5] = 3;
+    put(&mut log, 32, 2_000);
+    put(&mut log, 48, 1_000);
+    put(&mut log, 112, 842);
+    put(&mut log, 128, 5_210);
+    put(&mut log, 144, 37);
+    put(&mut log, 160, 2);
+
+    let health = NvmeHealth::parse(&log).unwrap();
+    assert_eq!(health.temperature, Some(37.0));
+    assert_eq!((health.available_spare, health.available_spare_threshold, health.percentage_used), (100, 10, 3));
+    assert_eq!(health.bytes_read, 1_024_000_000);
+    assert_eq!(health.bytes_written, 512_000_000);
+    assert_eq!((health.power_cycles, health.power_on_hours, health.unsafe_shutdowns), (842, 5_210, 37));
+    assert_eq!(health.media_errors, 2);
+    assert_eq!(health.warnings(), ["reliability degraded by media errors"]);
+
+    let drive = DriveHealth::from_nvme("nvme0".to_string(), None, health, Source::Ioctl);
+    assert_eq!(drive.status, HealthStatus::Failing);
+    assert_eq!(drive.problems, ["reliability degraded by media errors", "2 media errors"]);
+    assert_eq!(drive.power_on_hours, Some(5_210));
+
+    assert!(NvmeHealth::parse(&log[..64]).is_err());
+}
+
+// one 12-byte attribute entry: id, flags, value, worst, 6 raw bytes, reserved
+fn entry(sector: &mut [u8], index: usize, id: u8, flags: u16, value: u8, worst: u8, raw: u64) {
+    let at = 2 + index * 12;
+    sector[at] = id;
+    sector[at + 1..at + 3].copy_from_slice(&flags.to_le_bytes());
+    sector[at + 3] = value;
+    sector[at + 4] = worst;
+    sector[at + 5..at + 11].copy_from_slice(&raw.to_le_bytes()[..6]);
+}
+
+#[test]
+fn ata_attributes_and_thresholds() {
+    let mut values = [0u8; ata::SECTOR_SIZE];
+    let mut thresholds = [0u8; ata::SECTOR_SIZE];
+    entry(&mut values, 0, ata::REALLOCATED_SECTORS, 0x33, 100, 100, 8);
+    entry(&mut values, 1, ata::POWER_ON_HOURS, 0x32, 88, 88, 0x0012_0000_2a3c);
+    entry(&mut values, 2, ata::TEMPERATURE, 0x22, 64, 45, 0x0028_0012_0024);
+    entry(&mut values, 3, 250, 0x00, 100, 100, 0);
+    for (index, (id, limit)) in [(ata::REALLOCATED_SECTORS, 10), (ata::POWER_ON_HOURS, 0)].iter().enumerate() {
+        thresholds[2 + index * 12] = *id;
+        thresholds[3 + index * 12] = *limit;
+    }
+
+    let attributes = ata::parse(&values, Some(&thresholds)).unwrap();
+    assert_eq!(attributes.len(), 4);
+    assert_eq!(attributes[0].name, "Reallocated sectors");
+    assert_eq!((attributes[0].threshold, attributes[0].raw, attributes[0].prefailure), (Some(10), 8, true));
+    assert_eq!(attributes[2].temperature(), Some(36.0));
+    assert_eq!(attributes[3].name, "Attribute 250");
+    assert_eq!(attributes[3].threshold, None);
+
+    let drive = DriveHealth::from_attributes("sda".to_string(), Some("Spinny 4TB".to_string()), attributes, Source::Ioctl);
+    assert_eq!(drive.kind, DriveKind::Ata);
+    assert_eq!(drive.status, HealthStatus::Warning);
+    assert_eq!(drive.problems, ["Reallocated sectors: 8"]);
+    assert_eq!(drive.temperature, Some(36.0));
+    assert_eq!(drive.power_on_hours, Some(0x2a3c));
+}
+
+#[test]
+fn failing_prefailure_attribute() {
+    let failing = SmartAttribute {
+        id: 1,
+        name: "Read error rate".to_string(),
+        value: 6,
+        worst: 6,
+        threshold: Some(6),
+        raw: 0,
+        prefailure: true,
+    };
+    // a threshold of 0 means "never fails"
+    let never = SmartAttribute { id: 3, threshold: Some(0), value: 0, ..failing.clone() };
+    assert!(failing.failing());
+    assert!(!never.failing());
+
+    let drive = DriveHealth::from_attributes("sdb".to_string(), None, vec![failing, never], Source::Smartctl);
+    assert_eq!(drive.status, HealthStatus::Failing);
+    assert_eq!(drive.problems, ["Read error rate at or below threshold"]);
+}
+
+#[test]
+fn smartctl_reports() {
+    let nvme = br#"{
+        "smartctl": {"exit_status": 0},
+        "device": {"name": "/dev/nvme0", "type": "nvme", "protocol": "NVMe"},
+        "model_name": "Fast Drive 2TB",
+        "smart_status": {"passed": true},
+        "temperature": {"current": 41},
+        "power_on_time": {"hours": 1200},
+        "nvme_smart_health_information_log": {
+            "critical_warning": 0, "temperature": 41, "available_spare": 100, "available_spare_threshold": 10,
+            "percentage_used": 93, "data_units_read": 10, "data_units_written": 20, "power_cycles": 300,
+            "power_on_hours": 1200, "unsafe_shutdowns": 12, "media_errors": 0, "num_err_log_entries": 4
+        }
+    }"#;
+    let drive = smartctl::parse(nvme).unwrap();
+    assert_eq!((drive.name.as_str(), drive.kind, drive.source), ("nvme0", DriveKind::Nvme, Source::Smartctl));
+    assert_eq!(drive.model.as_deref(), Some("Fast Drive 2TB"));
+    assert_eq!(drive.status, HealthStatus::Warning);
+    assert_eq!(drive.problems, ["93% of rated endurance used"]);
+    let health = drive.nvme.unwrap();
+    assert_eq!((health.bytes_written, health.unsafe_shutdowns), (10_240_000, 12));
+
+    let ata = br#"{
+        "smartctl": {"exit_status": 8},
+        "device": {"name": "/dev/sda", "type": "sat", "protocol": "ATA"},
+        "smart_status": {"passed": false},
+        "ata_smart_attributes": {"table": [
+            {"id": 197, "name": "Current_Pending_Sector", "value": 100, "worst": 100, "thresh": 0,
+             "flags": {"value": 50, "prefailure": false}, "raw": {"value": 16, "string": "16"}}
+        ]}
+    }"#;
+    let drive = smartctl::parse(ata).unwrap();
+    assert_eq!(drive.kind, DriveKind::Ata);
+    assert_eq!(drive.status, HealthStatus::Failing);
+    assert_eq!(drive.problems, ["SMART overall self-assessment failed", "Pending sectors: 16"]);
+
+    let denied = br#"{
+        "smartctl": {"exit_status": 2, "messages": [{"string": "Smartctl open device: /dev/sda failed: Permission denied", "severity": "error"}]},
+        "device": {"name": "/dev/sda"}
+    }"#;
+    assert_eq!(
+        smartctl::parse(denied).unwrap_err(),
+        OverwatchError::permission_denied("smartctl /dev/sda")
+    );
+}
+
+#[test]
+fn getting_worse_becomes_events() {
+    let drive = |name: &str, status| DriveHealth {
+        status,
+        ..DriveHealth::from_attributes(name.to_string(), Some("WDC WD40EFRX".to_string()), Vec::new(), Source::Ioctl)
+    };
+    let monitor = HealthMonitor::default();
+
+    // a drive already in trouble is reported on the first look
+    let events = monitor.update(&[drive("sda", HealthStatus::Good), drive("sdb", HealthStatus::Warning)], 1000);
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].drive.as_str(), events[0].from, events[0].to), ("sdb", None, HealthStatus::Warning));
+
+    assert!(monitor.update(&[drive("sda", HealthStatus::Good), drive("sdb", HealthStatus::Warning)], 2000).is_empty());
+
+    // a failed read says nothing about the drive
+    assert!(monitor.update(&[drive("sda", HealthStatus::Unknown), drive("sdb", HealthStatus::Warning)], 3000).is_empty());
+    let events = monitor.update(&[drive("sda", HealthStatus::Warning), drive("sdb", HealthStatus::Failing)], 4000);
+    assert_eq!(events.len(), 2);
+    assert_eq!((events[0].from, events[1].from), (Some(HealthStatus::Good), Some(HealthStatus::Warning)));
+
+    // getting better isn't an alert, getting worse again is
+    assert!(monitor.update(&[drive("sda", HealthStatus::Good)], 5000).is_empty());
+    assert_eq!(monitor.update(&[drive("sda", HealthStatus::Warning)], 6000).len(), 1);
+
+    let history = monitor.history();
+    assert_eq!(history.iter().map(|event| event.at).collect::<Vec<_>>(), vec![1000, 4000, 4000, 6000]);
+}
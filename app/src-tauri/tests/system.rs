@@ -0,0 +1,48 @@
+use app_lib::system;
+use app_lib::BootRecord;
+
+// struct utmp on 64-bit Linux: ut_type at 0, ut_tv.tv_sec at 340, 384 bytes long
+fn utmp(kind: i16, tv_sec: u32) -> Vec<u8> {
+    let mut record = vec![0u8; 384];
+    record[..2].copy_from_slice(&kind.to_ne_bytes());
+    record[340..344].copy_from_slice(&tv_sec.to_ne_bytes());
+    record
+}
+
+#[test]
+fn wtmp_boot_records() {
+    const BOOT_TIME: i16 = 2;
+    const USER_PROCESS: i16 = 7;
+
+    let mut data = Vec::new();
+    data.extend(utmp(BOOT_TIME, 1_700_000_000));
+    data.extend(utmp(USER_PROCESS, 1_700_000_100));
+    data.extend(utmp(BOOT_TIME, 1_700_086_400));
+    data.extend(utmp(BOOT_TIME, 1_700_172_800));
+    // a record still being written when the file was read
+    data.extend(&utmp(BOOT_TIME, 1_800_000_000)[..100]);
+
+    let boot = |boot_time| BootRecord { boot_id: None, boot_time, last_seen: None };
+    assert_eq!(system::parse_wtmp(&data, 10), [boot(1_700_172_800), boot(1_700_086_400), boot(1_700_000_000)]);
+    assert_eq!(system::parse_wtmp(&data, 1), [boot(1_700_172_800)]);
+    assert!(system::parse_wtmp(&[], 10).is_empty());
+}
+
+#[test]
+fn journal_boot_list() {
+    // journalctl --list-boots --output=json, oldest first
+    let output = br#"[
+        {"index":-2,"boot_id":"2f6b1e0c8a4d4c1e9d1f2a3b4c5d6e7f","first_entry":1700000000123456,"last_entry":1700003600654321},
+        {"index":-1,"boot_id":"8c1d2e3f4a5b4c6d8e9f0a1b2c3d4e5f","first_entry":1700086400000000,"last_entry":1700090000000000},
+        {"index":0,"boot_id":"d4e5f6a7b8c94d0e8f1a2b3c4d5e6f70","first_entry":1700172800000000,"last_entry":1700172900000000}
+    ]"#;
+
+    let boots = system::parse_journal_boots(output, 2).unwrap();
+    assert_eq!(boots.len(), 2);
+    assert_eq!(boots[0].boot_id.as_deref(), Some("d4e5f6a7b8c94d0e8f1a2b3c4d5e6f70"));
+    assert_eq!((boots[0].boot_time, boots[0].last_seen), (1_700_172_800, Some(1_700_172_900)));
+    assert_eq!((boots[1].boot_time, boots[1].last_seen), (1_700_086_400, Some(1_700_090_000)));
+
+    assert_eq!(system::parse_journal_boots(output, 10).unwrap()[2].boot_time, 1_700_000_000);
+    assert!(system::parse_journal_boots(b"No journal files were found.", 10).is_err());
+}
@@ -0,0 +1,147 @@
+use app_lib::throttle::{self, Clocks, PolicyCap, ThrottleCause, ThrottleCounter, ThrottleMonitor, ThrottleSample};
+
+mod common;
+
+use common::{fake_dir, write_attrs};
+
+fn thermal(count: u64) -> ThrottleCounter {
+    ThrottleCounter {
+        key: "cpu2/core_throttle_count".to_string(),
+        device: "CPU".to_string(),
+        cause: ThrottleCause::Thermal,
+        detail: "CPU 2".to_string(),
+        count,
+    }
+}
+
+#[test]
+fn sysfs_counters_and_caps() {
+    let root = fake_dir("sysfs");
+    for cpu in 0..2 {
+        write_attrs(
+            &root.join(format!("cpu{}", cpu)),
+            &[
+                ("topology/physical_package_id", "0"),
+                ("thermal_throttle/core_throttle_count", if cpu == 0 { "3" } else { "0" }),
+                ("thermal_throttle/package_throttle_count", "7"),
+                ("thermal_throttle/package_power_limit_count", "12"),
+                ("cpufreq/cpuinfo_max_freq", "4800000"),
+                ("cpufreq/scaling_max_freq", if cpu == 0 { "2000000" } else { "4800000" }),
+                ("cpufreq/scaling_cur_freq", "1800000"),
+                ("cpufreq/base_frequency", "2400000"),
+            ],
+        );
+    }
+    // not a CPU
+    write_attrs(&root.join("cpufreq"), &[("boost", "1")]);
+
+    let sample = throttle::read_cpu_sysfs(&root).unwrap();
+    let keys: Vec<&str> = sample.counters.iter().map(|counter| counter.key.as_str()).collect();
+    assert_eq!(
+        keys,
+        ["cpu0/core_throttle_count", "package0/package_throttle_count", "package0/package_power_limit_count", "cpu1/core_throttle_count"]
+    );
+    assert_eq!(sample.counters[2].cause, ThrottleCause::PowerLimit);
+    // a lowered scaling_max_freq is policy, not a throttle period
+    assert!(sample.conditions.is_empty());
+    assert_eq!(sample.policy_cap, Some(PolicyCap { limit_khz: 2_000_000, cpus: 1, total_cpus: 2 }));
+    assert_eq!(sample.clocks, Some(Clocks { current_khz: 1_800_000, reference_khz: 2_400_000, reference_is_base: true }));
+}
+
+#[test]
+fn proc_stat_busy_time() {
+    let root = fake_dir("stat");
+    write_attrs(&root, &[("stat", "cpu  100 0 50 800 50 0 0 0 0 0\ncpu0 50 0 25 400 25 0 0 0 0 0")]);
+    assert_eq!(throttle::read_proc_stat(&root.join("stat")), Some((150, 1000)));
+}
+
+#[test]
+fn nvidia_reasons() {
+    let conditions = throttle::parse_nvidia_smi("0, 0x0000000000000001\n1, 0x0000000000000064\n");
+    // GPU 0 is just idle; GPU 1 has a power cap and a thermal slowdown that explains the hardware one
+    assert_eq!(conditions.len(), 2);
+    assert_eq!(conditions[0].device, "GPU 1");
+    assert_eq!(conditions[0].cause, ThrottleCause::PowerLimit);
+    assert_eq!(conditions[0].detail, "software power cap");
+    assert_eq!(conditions[1].cause, ThrottleCause::Thermal);
+    assert_eq!(conditions[1].detail, "software thermal slowdown, hardware thermal slowdown");
+
+    assert_eq!(throttle::parse_nvidia_smi("0, 0x0000000000000008")[0].cause, ThrottleCause::Other);
+    assert!(throttle::parse_nvidia_smi("0, [Not Supported]").is_empty());
+}
+
+#[test]
+fn counter_periods() {
+    let monitor = ThrottleMonitor::default();
+    let sample = |count| ThrottleSample { counters: vec![thermal(count)], ..Default::default() };
+
+    // a baseline, even with a nonzero count, isn't throttling
+    assert!(monitor.update(sample(5), 1_000).is_empty());
+
+    let active = monitor.update(sample(9), 2_000);
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].cause, ThrottleCause::Thermal);
+    assert_eq!(active[0].detail, "CPU 2");
+    // it went up somewhere after the previous sample
+    assert_eq!(active[0].started_at, 1_000);
+    assert_eq!(active[0].ended_at, None);
+
+    assert_eq!(monitor.update(sample(12), 3_000)[0].duration_ms, 2_000);
+    assert!(monitor.update(sample(12), 4_000).is_empty());
+
+    let events = monitor.events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].started_at, 1_000);
+    assert_eq!(events[0].ended_at, Some(3_000));
+    assert_eq!(events[0].duration_ms, 2_000);
+
+    // an hour without a sample doesn't make the next bump an hour long
+    let active = monitor.update(sample(13), 3_604_000);
+    assert_eq!(active[0].started_at, 3_602_000);
+}
+
+#[test]
+fn low_clocks_under_load() {
+    let monitor = ThrottleMonitor::default();
+    let clocks = Clocks { current_khz: 1_200_000, reference_khz: 3_000_000, reference_is_base: true };
+    let sample = |busy, total| ThrottleSample { clocks: Some(clocks), cpu_time: Some((busy, total)), ..Default::default() };
+
+    monitor.update(sample(0, 0), 1_000);
+    // half busy at a low clock is just power saving
+    assert!(monitor.update(sample(500, 1_000), 2_000).is_empty());
+
+    let active = monitor.update(sample(1_480, 2_000), 3_000);
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].cause, ThrottleCause::FrequencyCap);
+    assert_eq!(active[0].detail, "1200 MHz at 98% load, base 3000 MHz");
+    assert_eq!(active[0].started_at, 3_000);
+
+    // with a thermal counter going up over the same load, that gets the blame
+    monitor.update(ThrottleSample { counters: vec![thermal(0)], ..sample(1_480, 2_000) }, 3_500);
+    let active = monitor.update(ThrottleSample { counters: vec![thermal(1)], ..sample(2_480, 3_000) }, 4_000);
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].cause, ThrottleCause::Thermal);
+}
+
+#[test]
+fn load_against_policy_cap() {
+    let monitor = ThrottleMonitor::default();
+    let clocks = Clocks { current_khz: 1_200_000, reference_khz: 3_000_000, reference_is_base: true };
+    let cap = |limit_khz| PolicyCap { limit_khz, cpus: 8, total_cpus: 8 };
+    let sample = |busy, total, limit| ThrottleSample {
+        clocks: Some(clocks),
+        cpu_time: Some((busy, total)),
+        policy_cap: Some(cap(limit)),
+        ..Default::default()
+    };
+
+    // powersave holding the clocks at its cap isn't throttling, however long it lasts
+    monitor.update(sample(0, 0, 1_300_000), 1_000);
+    assert!(monitor.update(sample(980, 1_000, 1_300_000), 2_000).is_empty());
+    assert!(monitor.events().is_empty());
+
+    // well under a higher cap is
+    let active = monitor.update(sample(1_960, 2_000, 2_000_000), 3_000);
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].detail, "1200 MHz at 98% load, cap 2000 MHz");
+}
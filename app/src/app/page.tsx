@@ -1,5 +1,22 @@
+import BoardCard from "@/components/BoardCard";
+import CgroupsCard from "@/components/CgroupsCard";
 import CpuCard from "@/components/CpuCard";
+import CpuFeaturesCard from "@/components/CpuFeaturesCard";
+import CpuPowerCard from "@/components/CpuPowerCard";
+import DevicesCard from "@/components/DevicesCard";
+import FanCard from "@/components/FanCard";
 import GpuCard from "@/components/GpuCard";
+import HardwareErrorsCard from "@/components/HardwareErrorsCard";
+import InterruptsCard from "@/components/InterruptsCard";
+import MemoryCard from "@/components/MemoryCard";
+import PowerCard from "@/components/PowerCard";
+import PowerProfileCard from "@/components/PowerProfileCard";
+import ProcessesCard from "@/components/ProcessesCard";
+import StorageCard from "@/components/StorageCard";
+import SystemCard from "@/components/SystemCard";
+import ThrottleCard from "@/components/ThrottleCard";
+import RemoteHosts from "@/components/RemoteHosts";
+import SessionControls from "@/components/SessionControls";
 
 export default function Home() {
   return (
@@ -13,6 +30,23 @@ export default function Home() {
         <div className="grid grid-cols-1 lg:grid-cols-2 xl:grid-cols-3 gap-6">
           <CpuCard />
           <GpuCard />
+          <SystemCard />
+          <BoardCard />
+          <MemoryCard />
+          <DevicesCard />
+          <StorageCard />
+          <PowerCard />
+          <CpuPowerCard />
+          <ThrottleCard />
+          <PowerProfileCard />
+          <FanCard />
+          <InterruptsCard />
+          <CpuFeaturesCard />
+          <HardwareErrorsCard />
+          <CgroupsCard />
+          <ProcessesCard />
+          <RemoteHosts />
+          <SessionControls />
         </div>
       </div>
     </div>
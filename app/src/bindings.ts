@@ -0,0 +1,1103 @@
+// @ts-nocheck
+/* eslint-disable */
+// This file was generated by [tauri-specta](https://github.com/oscartbeaumont/tauri-specta). Do not edit this file manually.
+
+/** user-defined commands **/
+
+
+export const commands = {
+async getCpuInfo() : Promise<CpuInfo> {
+    return await TAURI_INVOKE("get_cpu_info");
+},
+async getCpuFeatures() : Promise<CpuFeatures> {
+    return await TAURI_INVOKE("get_cpu_features");
+},
+async getGpuInfo() : Promise<GpuInfo> {
+    return await TAURI_INVOKE("get_gpu_info");
+},
+async getSystemInfo(bootHistory: number | null) : Promise<SystemInfo> {
+    return await TAURI_INVOKE("get_system_info", { bootHistory });
+},
+/**
+ * Serials are redacted unless `show_serials` is set, and always in what
+ * gets recorded.
+ */
+async getBoardInfo(showSerials: boolean | null) : Promise<BoardInfo> {
+    return await TAURI_INVOKE("get_board_info", { showSerials });
+},
+async getMemoryLayout() : Promise<MemoryLayout> {
+    return await TAURI_INVOKE("get_memory_layout");
+},
+async getDeviceInventory() : Promise<DeviceInventory> {
+    return await TAURI_INVOKE("get_device_inventory");
+},
+async getStorageHealth() : Promise<StorageHealth> {
+    return await TAURI_INVOKE("get_storage_health");
+},
+async getPowerInfo() : Promise<PowerInfo> {
+    return await TAURI_INVOKE("get_power_info");
+},
+async getCpuPower() : Promise<CpuPower> {
+    return await TAURI_INVOKE("get_cpu_power");
+},
+async resetEnergy() : Promise<null> {
+    return await TAURI_INVOKE("reset_energy");
+},
+async getThrottleStatus() : Promise<ThrottleStatus> {
+    return await TAURI_INVOKE("get_throttle_status");
+},
+async getInterrupts() : Promise<InterruptStats> {
+    return await TAURI_INVOKE("get_interrupts");
+},
+async getHardwareErrors() : Promise<HardwareErrors> {
+    return await TAURI_INVOKE("get_hardware_errors");
+},
+async getCgroups() : Promise<CgroupStats> {
+    return await TAURI_INVOKE("get_cgroups");
+},
+async getProcesses() : Promise<ProcessList> {
+    return await TAURI_INVOKE("get_processes");
+},
+/**
+ * Not recorded: a replay can't answer for whichever PID gets picked.
+ */
+async getProcessFiles(pid: number) : Promise<ProcessFiles> {
+    return await TAURI_INVOKE("get_process_files", { pid });
+},
+async getPowerControls() : Promise<PowerControls> {
+    return await TAURI_INVOKE("get_power_controls");
+},
+async setPowerSetting(setting: PowerSetting) : Promise<PowerControls> {
+    return await TAURI_INVOKE("set_power_setting", { setting });
+},
+async revertPowerSettings() : Promise<PowerControls> {
+    return await TAURI_INVOKE("revert_power_settings");
+},
+async setRevertOnExit(enabled: boolean) : Promise<PowerControls> {
+    return await TAURI_INVOKE("set_revert_on_exit", { enabled });
+},
+async getPowerAudit() : Promise<AuditEntry[]> {
+    return await TAURI_INVOKE("get_power_audit");
+},
+async getFanStatus() : Promise<FanStatus> {
+    return await TAURI_INVOKE("get_fan_status");
+},
+async setFanCurves(curves: FanCurve[]) : Promise<FanStatus> {
+    return await TAURI_INVOKE("set_fan_curves", { curves });
+},
+async addRemoteHost(address: string, token: string) : Promise<RemoteHostStatus> {
+    return await TAURI_INVOKE("add_remote_host", { address, token });
+},
+async removeRemoteHost(id: number) : Promise<null> {
+    return await TAURI_INVOKE("remove_remote_host", { id });
+},
+async listRemoteHosts() : Promise<RemoteHostStatus[]> {
+    return await TAURI_INVOKE("list_remote_hosts");
+},
+async startRecording(path: string) : Promise<SessionStatus> {
+    return await TAURI_INVOKE("start_recording", { path });
+},
+async stopRecording() : Promise<SessionStatus> {
+    return await TAURI_INVOKE("stop_recording");
+},
+async openReplay(path: string) : Promise<SessionStatus> {
+    return await TAURI_INVOKE("open_replay", { path });
+},
+async closeReplay() : Promise<SessionStatus> {
+    return await TAURI_INVOKE("close_replay");
+},
+async controlReplay(speed: number | null, paused: boolean | null, seekMs: number | null) : Promise<SessionStatus> {
+    return await TAURI_INVOKE("control_replay", { speed, paused, seekMs });
+},
+async getSessionStatus() : Promise<SessionStatus> {
+    return await TAURI_INVOKE("get_session_status");
+}
+}
+
+/** user-defined events **/
+
+
+export const events = __makeEvents__<{
+hardwareErrorAlert: HardwareErrorAlert,
+pcieLinkAlert: PcieLinkAlert,
+remoteHostUpdate: RemoteHostUpdate,
+storageHealthAlert: StorageHealthAlert
+}>({
+hardwareErrorAlert: "hardware-error-alert",
+pcieLinkAlert: "pcie-link-alert",
+remoteHostUpdate: "remote-host-update",
+storageHealthAlert: "storage-health-alert"
+})
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * One line of the audit log.
+ */
+export type AuditEntry = { 
+/**
+ * Unix milliseconds.
+ */
+at: number; 
+/**
+ * Account name of the real uid running the app.
+ */
+user: string; 
+/**
+ * `None` in entries that predate it.
+ */
+uid?: number | null; setting: PowerSetting; 
+/**
+ * The cpufreq policies it was limited to; `None` for all of them.
+ */
+policies?: number[] | null; previous: PowerSetting | null; 
+/**
+ * Why it was refused or failed; `None` when it took.
+ */
+error: OverwatchError | null }
+export type Battery = { 
+/**
+ * "BAT0" on Linux, the WMI instance name on Windows.
+ */
+name: string; manufacturer: string | null; model: string | null; charge_percent: number | null; state: BatteryState; 
+/**
+ * Charge or discharge rate, always positive; `state` gives the direction.
+ */
+rate_watts: number | null; time_to_empty_secs: number | null; time_to_full_secs: number | null; energy_wh: number | null; 
+/**
+ * What it holds when full today.
+ */
+energy_full_wh: number | null; 
+/**
+ * What it held new.
+ */
+energy_full_design_wh: number | null; 
+/**
+ * Capacity lost against the design, in percent.
+ */
+wear_percent: number | null; cycle_count: number | null; 
+/**
+ * "Li-ion", "Li-poly", ...
+ */
+chemistry: string | null }
+export type BatteryState = "charging" | "discharging" | "full" | 
+/**
+ * Plugged in but held below full, e.g. by a charge limit.
+ */
+"not_charging" | "unknown"
+export type BoardInfo = { board_vendor: string; board_model: string; board_version: string; bios_vendor: string; bios_version: string; 
+/**
+ * As the firmware reports it, usually `MM/DD/YYYY`.
+ */
+bios_date: string; chassis_type: string; system_vendor: string; system_product: string; 
+/**
+ * `None` when unreadable or redacted.
+ */
+system_serial: string | null; board_serial: string | null; 
+/**
+ * A serial was read but withheld. Recordings never carry serials.
+ */
+serials_redacted: boolean; provenance: Provenance }
+export type BootRecord = { boot_id: string | null; 
+/**
+ * Unix timestamp in seconds.
+ */
+boot_time: number; 
+/**
+ * Last time anything was logged during this boot, if known.
+ */
+last_seen: number | null }
+export type Cgroup = { path: string; 
+/**
+ * Container name, unit name or short container ID.
+ */
+name: string; kind: CgroupKind; 
+/**
+ * Nesting below the listed cgroups' common root, for indenting.
+ */
+depth: number; 
+/**
+ * "docker", "podman", "containerd" or "cri-o".
+ */
+runtime: string | null; container_id: string | null; 
+/**
+ * Percent of one CPU, like `top`.
+ */
+cpu_percent: number; cpu_limit: number | null; 
+/**
+ * Share of scheduler periods in the window where the quota ran out.
+ */
+throttled_percent: number | null; 
+/**
+ * Milliseconds per second spent throttled.
+ */
+throttled_ms: number | null; memory_current: number | null; 
+/**
+ * None when unlimited.
+ */
+memory_max: number | null; 
+/**
+ * Bytes per second.
+ */
+io_read: number | null; io_write: number | null; pids_current: number | null; pids_max: number | null }
+export type CgroupKind = "slice" | "service" | "container"
+export type CgroupStats = { cgroups: Cgroup[]; provenance: Provenance }
+export type ConnectionState = "connecting" | "connected" | "disconnected"
+export type CoreIdle = { cpu: number; 
+/**
+ * Shallowest first.
+ */
+states: IdleResidency[] }
+export type CpuFeatures = { 
+/**
+ * The hardware's architecture, not the one this build targets.
+ */
+architecture: string; 
+/**
+ * Every flag, in the order the OS lists them.
+ */
+flags: FeatureFlag[]; 
+/**
+ * The notable flags by family; a flag is in at most one group.
+ */
+groups: FeatureGroup[]; 
+/**
+ * e.g. "0x2b000603".
+ */
+microcode: string | null; vulnerabilities: Vulnerability[]; provenance: Provenance }
+export type CpuInfo = { brand: string; frequency: number; cores: number; logical_cores: number; usage: number; per_core_usage: number[]; 
+/**
+ * Load, run queue and per-second scheduler counters over the same window.
+ */
+scheduler: SchedulerActivity | null; 
+/**
+ * Idle-state residency per logical CPU over the same window as the usage.
+ */
+idle_states: CoreIdle[]; temperature: number; temp_available: boolean; 
+/**
+ * Package power in watts over the usage sampling window, from RAPL.
+ */
+package_power: number | null; vendor: string; architecture: string; max_frequency: number; frequency_policy: FrequencyPolicy | null; cache_l1: string; cache_l2: string; cache_l3: string; socket: string; process_node: string; provenance: Provenance }
+export type CpuPower = { 
+/**
+ * Sum of the package domains, in watts.
+ */
+package_watts: number | null; domains: DomainPower[]; 
+/**
+ * Unix milliseconds the energy totals count from: the baseline taken
+ * when the current recording started, or at the last reset.
+ */
+energy_since: number; provenance: Provenance }
+export type CurvePoint = { celsius: number; 
+/**
+ * PWM output, in percent.
+ */
+duty: number }
+export type DeviceInventory = { 
+/**
+ * Sorted by address.
+ */
+pci: PciDevice[]; 
+/**
+ * Hubs come right before the devices plugged into them.
+ */
+usb: UsbDevice[]; 
+/**
+ * NVMe controllers with their PCIe link, also listed under `pci`.
+ */
+nvme: NvmeDevice[]; provenance: Provenance }
+export type DomainPower = { 
+/**
+ * "package-0", "core", "uncore", "dram" or "psys".
+ */
+name: string; package: number; 
+/**
+ * Average since the previous reading.
+ */
+watts: number | null; 
+/**
+ * Energy used since `energy_since`.
+ */
+energy_wh: number }
+export type DriveHealth = { 
+/**
+ * Kernel name, e.g. "nvme0" or "sda"; smartctl's device name elsewhere.
+ */
+name: string; model: string | null; kind: DriveKind; status: HealthStatus; 
+/**
+ * Why the status isn't `Good`, worst first.
+ */
+problems: string[]; 
+/**
+ * The drive's own overall verdict, where the source reports one.
+ */
+smart_passed: boolean | null; temperature: number | null; power_on_hours: number | null; nvme: NvmeHealth | null; 
+/**
+ * ATA drives only.
+ */
+attributes: SmartAttribute[]; source: Source; 
+/**
+ * Why nothing could be read, for drives with `Unknown` status.
+ */
+error: OverwatchError | null }
+export type DriveKind = "nvme" | "ata"
+export type ErrorCounter = { source: ErrorSource; 
+/**
+ * "mc0/dimm2", "0000:01:00.0", "CPU" ...
+ */
+device: string; 
+/**
+ * DIMM slot label, PCI device name, ...
+ */
+label: string | null; severity: ErrorSeverity; 
+/**
+ * Since boot.
+ */
+count: number; 
+/**
+ * Which kinds of error the count is made of, or the last message.
+ */
+detail: string | null }
+export type ErrorEvent = { 
+/**
+ * Unix milliseconds when the increase was seen.
+ */
+at: number; source: ErrorSource; device: string; label: string | null; severity: ErrorSeverity; increase: number; total: number; detail: string | null }
+export type ErrorSeverity = "corrected" | 
+/**
+ * Uncorrected but survivable: EDAC UE, AER non-fatal.
+ */
+"uncorrected" | "fatal"
+export type ErrorSource = "machine_check" | "memory" | "pcie"
+export type Fan = { 
+/**
+ * "hwmon2/pwm1"; what curves refer to.
+ */
+id: string; 
+/**
+ * The hwmon driver, e.g. "nct6798" or "amdgpu".
+ */
+chip: string; label: string | null; rpm: number | null; 
+/**
+ * Current PWM output, in percent.
+ */
+duty: number | null; mode: FanMode | null }
+export type FanCurve = { fan: string; sensor: string; 
+/**
+ * Ordered by temperature; the duty is interpolated between points and
+ * held flat past either end.
+ */
+points: CurvePoint[]; 
+/**
+ * How far the temperature has to fall before the fan slows down, in °C.
+ */
+hysteresis: number; 
+/**
+ * Largest change in duty per tick, in percent.
+ */
+max_step: number }
+export type FanMode = "full_speed" | "manual" | "automatic"
+export type FanStatus = { fans: Fan[]; sensors: TempSensor[]; 
+/**
+ * Curves in force; fans without one are left to the hardware.
+ */
+curves: FanCurve[]; provenance: Provenance }
+export type FeatureFlag = { 
+/**
+ * As Linux names it in /proc/cpuinfo, e.g. "sse4_2".
+ */
+name: string; 
+/**
+ * e.g. "SSE4.2".
+ */
+label: string }
+export type FeatureGroup = { name: string; flags: FeatureFlag[] }
+/**
+ * Availability of a single field, so the UI can explain an "N/A".
+ */
+export type FieldStatus = { status: "available"; source: Source } | { status: "estimated"; source: Source } | { status: "missing"; reason: OverwatchError }
+export type FileKind = "file" | "device" | "socket" | "pipe" | 
+/**
+ * eventfd, epoll, inotify and the like.
+ */
+"anon_inode" | "other"
+export type FrequencyPolicy = { 
+/**
+ * "intel_pstate", "amd-pstate-epp", "acpi-cpufreq", ...
+ */
+driver: string | null; 
+/**
+ * The cpufreq governor on Linux, the active power plan on Windows.
+ */
+governor: string | null; available_governors: string[]; 
+/**
+ * Energy-performance preference, e.g. "balance_performance"; only
+ * drivers running hardware P-states have one.
+ */
+epp: string | null; available_epp: string[]; 
+/**
+ * Whether turbo / boost clocks are allowed.
+ */
+boost: boolean | null; 
+/**
+ * False when some CPUs run a different governor or preference than
+ * the ones reported, which come from the first policy.
+ */
+uniform: boolean }
+export type GpuInfo = { name: string; driver_version: string; memory_total: number; memory_used: number; memory_free: number; temperature: number; temp_available: boolean; power_usage: number; utilization: number; fan_speed: number; gpu_type: string; vendor: string; architecture: string; device_id: string; pci_slot: string; 
+/**
+ * Negotiated vs maximum link; `warning` is set when it trained low.
+ */
+pcie_link: PcieLink | null; memory_type: string; memory_bus_width: number; base_clock: number; boost_clock: number; memory_clock: number; shader_units: number; tmu_count: number; rop_count: number; directx_version: string; opengl_version: string; vulkan_support: boolean; provenance: Provenance }
+/**
+ * Emitted for every counter that went up, including errors from before the
+ * app started, which show up on its first look.
+ */
+export type HardwareErrorAlert = ErrorEvent
+export type HardwareErrors = { counters: ErrorCounter[]; 
+/**
+ * Oldest first.
+ */
+history: ErrorEvent[]; provenance: Provenance }
+/**
+ * A drive whose status went to `Warning` or `Failing`.
+ */
+export type HealthEvent = { 
+/**
+ * Unix milliseconds when the change was seen.
+ */
+at: number; drive: string; model: string | null; 
+/**
+ * `None` on the first look at the drive.
+ */
+from: HealthStatus | null; to: HealthStatus; problems: string[] }
+export type HealthStatus = "good" | 
+/**
+ * Worth watching: remapped sectors, media errors, worn past 90%.
+ */
+"warning" | 
+/**
+ * The drive itself says it's failing or about to.
+ */
+"failing" | 
+/**
+ * Nothing could be read.
+ */
+"unknown"
+/**
+ * One sample of everything a host reports.
+ */
+export type HostSnapshot = { host: string; 
+/**
+ * Unix timestamp in milliseconds.
+ */
+timestamp: number; cpu: Reading<CpuInfo>; gpu: Reading<GpuInfo>; memory: Reading<MemoryInfo>; sensors: Reading<Sensor[]>; 
+/**
+ * `None` from agents that predate it.
+ */
+storage?: Reading<StorageHealth> | null }
+export type IdleResidency = { 
+/**
+ * "POLL", "C1", "C6", ...
+ */
+name: string; 
+/**
+ * Exit latency in microseconds, where the platform gives it.
+ */
+latency_us: number | null; 
+/**
+ * Share of the sampling window spent in this state, in percent.
+ */
+residency: number; 
+/**
+ * Times the state was entered during the window.
+ */
+entries: number | null }
+export type InterruptKind = 
+/**
+ * A numbered IRQ line or MSI vector.
+ */
+"device" | 
+/**
+ * Per-CPU architecture interrupts: timer, IPIs, NMIs, ...
+ */
+"system" | "softirq"
+export type InterruptSource = { id: string; kind: InterruptKind; chip: string | null; name: string | null; 
+/**
+ * CPUs the IRQ may be delivered to, e.g. "0-7".
+ */
+affinity: string | null; 
+/**
+ * CPUs it's actually delivered to, where the controller says.
+ */
+effective_affinity: string | null; 
+/**
+ * Per second, one per entry of `InterruptStats::cpus`.
+ */
+rates: number[]; total: number; 
+/**
+ * The CPU taking nearly all of a busy source.
+ */
+concentrated_on: number | null }
+export type InterruptStats = { 
+/**
+ * The online CPUs the columns refer to.
+ */
+cpus: number[]; 
+/**
+ * Busiest first; sources that didn't fire are left out.
+ */
+sources: InterruptSource[]; 
+/**
+ * Device and system interrupts per second, per CPU.
+ */
+cpu_interrupts: number[]; cpu_softirqs: number[]; provenance: Provenance }
+/**
+ * A PCIe link that trained below what the device and its slot support.
+ */
+export type LinkEvent = { 
+/**
+ * Unix milliseconds when it was seen.
+ */
+at: number; address: string; vendor: string | null; device: string | null; 
+/**
+ * The link's warning, e.g. "running x8 of x16 lanes".
+ */
+warning: string }
+export type LoadAverage = { one: number; five: number; fifteen: number }
+/**
+ * System RAM and swap, in bytes.
+ */
+export type MemoryInfo = { total: number; used: number; available: number; swap_total: number; swap_used: number }
+/**
+ * What's in the memory slots, from SMBIOS types 16 and 17.
+ */
+export type MemoryLayout = { 
+/**
+ * Every slot the firmware lists, empty ones included.
+ */
+modules: MemoryModule[]; total_slots: number; free_slots: number; 
+/**
+ * Sum of the installed modules, in bytes.
+ */
+installed: number; 
+/**
+ * Most the board takes across all slots, in bytes.
+ */
+max_capacity: number | null; 
+/**
+ * What the memory array runs with, e.g. "Multi-bit ECC".
+ */
+error_correction: string | null; provenance: Provenance }
+export type MemoryModule = { 
+/**
+ * Slot label as printed on the board, e.g. "DIMM_A2".
+ */
+slot: string; bank: string | null; 
+/**
+ * Bytes; 0 for an empty slot, `None` when the firmware doesn't say.
+ */
+size: number | null; 
+/**
+ * "DDR4", "DDR5", ...
+ */
+memory_type: string | null; form_factor: string | null; 
+/**
+ * What the module is rated for, in MT/s.
+ */
+rated_speed: number | null; 
+/**
+ * What it's actually running at, in MT/s.
+ */
+configured_speed: number | null; manufacturer: string | null; part_number: string | null; rank: number | null; 
+/**
+ * Wider bus than data width, i.e. the module carries ECC bits.
+ */
+ecc: boolean | null; configured_voltage_mv: number | null }
+export type MitigationStatus = "not_affected" | "mitigated" | 
+/**
+ * Mitigated, but with a part still reported vulnerable.
+ */
+"partial" | "vulnerable" | "unknown"
+export type NvmeDevice = { 
+/**
+ * Controller name, e.g. "nvme0"; the PCI address on Windows.
+ */
+name: string; model: string | null; firmware: string | null; 
+/**
+ * PCI address of the controller.
+ */
+address: string | null; link: PcieLink | null }
+/**
+ * The NVMe SMART / Health Information log (log page 02h).
+ */
+export type NvmeHealth = { 
+/**
+ * Bit field; any bit set means the controller thinks something is wrong.
+ */
+critical_warning: number; temperature: number | null; 
+/**
+ * Spare capacity left, in percent.
+ */
+available_spare: number; 
+/**
+ * Spare level below which the drive raises a critical warning.
+ */
+available_spare_threshold: number; 
+/**
+ * Share of the rated endurance used up; can go past 100.
+ */
+percentage_used: number; bytes_read: number; bytes_written: number; power_cycles: number; power_on_hours: number; unsafe_shutdowns: number; 
+/**
+ * Unrecovered data integrity errors.
+ */
+media_errors: number; error_log_entries: number }
+export type OpenFile = { fd: number; kind: FileKind; 
+/**
+ * Where the descriptor points, as the kernel puts it.
+ */
+target: string; 
+/**
+ * For sockets the kernel still lists in the process's namespace.
+ */
+socket: SocketInfo | null }
+/**
+ * Every command returns this so the UI can tell "not on this OS" apart from
+ * "access denied" apart from "the sensor just isn't there".
+ */
+export type OverwatchError = { kind: "unsupported"; what: string } | { kind: "permission_denied"; what: string } | { kind: "backend_failure"; backend: string; source: string } | { kind: "timeout"; what: string; after_ms: number } | { kind: "not_found"; what: string }
+export type PciDevice = { 
+/**
+ * `domain:bus:device.function`, e.g. "0000:01:00.0".
+ */
+address: string; 
+/**
+ * Class, subclass and programming interface, e.g. 0x030000.
+ */
+class_code: number; class: string; 
+/**
+ * Programming interface, e.g. "NVM Express" or "XHCI".
+ */
+interface: string | null; vendor_id: number; device_id: number; vendor: string | null; device: string | null; subsystem_vendor_id: number | null; subsystem_device_id: number | null; 
+/**
+ * The card or board maker's name for it, falling back to their vendor name.
+ */
+subsystem: string | null; driver: string | null; iommu_group: number | null; link: PcieLink | null }
+/**
+ * Negotiated and maximum PCIe link. Speeds are per lane, in GT/s.
+ */
+export type PcieLink = { speed_gts: number | null; width: number | null; max_speed_gts: number | null; max_width: number | null; 
+/**
+ * Fastest the port it's plugged into can go, `None` for devices on the
+ * root complex or when the port doesn't say.
+ */
+upstream_max_speed_gts?: number | null; 
+/**
+ * Widest link that port takes, `None` like the speed.
+ */
+upstream_max_width?: number | null; 
+/**
+ * Set when the link has fewer lanes than the device and its slot support.
+ */
+warning: string | null; 
+/**
+ * Set when the link runs below its top speed. Devices drop the speed to
+ * save power when idle, so this is no reason to alert.
+ */
+note?: string | null }
+/**
+ * Emitted when a device's link warning appears or changes, including links
+ * already degraded when the app starts.
+ */
+export type PcieLinkAlert = LinkEvent
+/**
+ * A cpufreq limit below the hardware maximum. It's policy — a power
+ * profile, powersave, a user setting — that stays until someone changes
+ * it, so it's reported as it stands rather than as a throttle period.
+ */
+export type PolicyCap = { 
+/**
+ * The lowest limit among the capped CPUs.
+ */
+limit_khz: number; cpus: number; total_cpus: number }
+export type PowerControls = { 
+/**
+ * Current governor, preference and boost, with the choices.
+ */
+policy: FrequencyPolicy | null; platform_profile: string | null; available_platform_profiles: string[]; 
+/**
+ * What each setting changed during this run was before the first change;
+ * a governor or preference the CPUs didn't agree on is listed per value.
+ */
+originals: PowerSetting[]; revert_on_exit: boolean; provenance: Provenance }
+export type PowerInfo = { 
+/**
+ * Empty on desktops.
+ */
+batteries: Battery[]; 
+/**
+ * Whether the machine runs off mains power, `None` when nothing says.
+ */
+ac_online: boolean | null; provenance: Provenance }
+export type PowerSetting = { kind: "governor"; value: string } | 
+/**
+ * Energy-performance preference.
+ */
+{ kind: "epp"; value: string } | { kind: "boost"; value: boolean } | 
+/**
+ * The firmware's profile, e.g. "quiet", "balanced" or "performance".
+ */
+{ kind: "platform_profile"; value: string }
+/**
+ * One `/proc/pressure` file: the share of time some or all tasks were
+ * stalled on the resource.
+ */
+export type Pressure = { some: PressureAverages; 
+/**
+ * Not reported for CPU pressure before Linux 5.13.
+ */
+full: PressureAverages | null }
+/**
+ * Percentages over the last 10, 60 and 300 seconds.
+ */
+export type PressureAverages = { avg10: number; avg60: number; avg300: number }
+export type ProcessFiles = { pid: number; files: OpenFile[]; file_limit: number | null; provenance: Provenance }
+export type ProcessInfo = { pid: number; parent: number | null; name: string; 
+/**
+ * Percent of one CPU, like `top`.
+ */
+cpu_percent: number; 
+/**
+ * Resident bytes.
+ */
+memory: number; 
+/**
+ * Bytes per second that reached storage. None where the counters
+ * aren't readable, e.g. other users' processes. Windows only counts
+ * all I/O, network and pipes included, so there it's an estimate.
+ */
+disk_read: number | null; disk_write: number | null; open_files: number | null; 
+/**
+ * The soft RLIMIT_NOFILE; None when unlimited or unreadable.
+ */
+file_limit: number | null; 
+/**
+ * Bytes per second over the process's TCP connections. None when its
+ * sockets can't be told apart from anyone else's, which is other
+ * users' processes unless running as root.
+ */
+net_sent: number | null; net_received: number | null }
+export type ProcessList = { processes: ProcessInfo[]; provenance: Provenance }
+/**
+ * Per-field availability keyed by the field's serialized name. Fields that
+ * are always read directly (name, usage, core counts) aren't listed.
+ */
+export type Provenance = Partial<{ [key in string]: FieldStatus }>
+/**
+ * A command result that travels inside a snapshot instead of failing it.
+ */
+export type Reading<T> = { ok: T } | { err: OverwatchError }
+export type RemoteHostStatus = { id: number; address: string; state: ConnectionState; last_error: OverwatchError | null; snapshot: HostSnapshot | null }
+/**
+ * Emitted whenever a remote host connects, drops or sends a snapshot.
+ */
+export type RemoteHostUpdate = RemoteHostStatus
+export type SchedulerActivity = { 
+/**
+ * 1, 5 and 15-minute load averages; Linux only.
+ */
+load: LoadAverage | null; 
+/**
+ * Threads runnable right now. Linux counts the ones running too
+ * (`procs_running`); Windows' processor queue length leaves them out,
+ * so the same load reads lower there.
+ */
+run_queue: number; 
+/**
+ * Threads waiting on IO, where the platform counts them.
+ */
+blocked: number | null; context_switches: number; interrupts: number; 
+/**
+ * Softirqs on Linux, deferred procedure calls on Windows.
+ */
+softirqs: number; 
+/**
+ * New processes and threads per second; Linux only.
+ */
+forks: number | null; cpu_pressure: Pressure | null; io_pressure: Pressure | null }
+/**
+ * One temperature sensor as reported by the OS (hwmon on Linux).
+ */
+export type Sensor = { label: string; temperature: number; max: number | null; critical: number | null }
+export type SessionHeader = { format: string; version: number; host: string; 
+/**
+ * Unix timestamp in milliseconds.
+ */
+started_at: number }
+export type SessionMode = "live" | "recording" | "replaying"
+export type SessionStatus = { mode: SessionMode; path: string | null; header: SessionHeader | null; position_ms: number; duration_ms: number; speed: number; paused: boolean; records: number }
+/**
+ * One row of the ATA SMART attribute table.
+ */
+export type SmartAttribute = { id: number; name: string; 
+/**
+ * Normalized health, usually counting down from 100 or 200.
+ */
+value: number; 
+/**
+ * Lowest `value` seen over the drive's life.
+ */
+worst: number; 
+/**
+ * `value` at or below this means the drive considers it failed; 0 never fails.
+ */
+threshold: number | null; 
+/**
+ * Vendor-specific raw counter; only its meaning for well-known IDs is portable.
+ */
+raw: number; 
+/**
+ * Pre-failure attribute, as opposed to one that just tracks age or use.
+ */
+prefailure: boolean }
+export type SocketInfo = { 
+/**
+ * "tcp", "tcp6", "udp", "udp6" or "unix".
+ */
+protocol: string; 
+/**
+ * "127.0.0.1:8080", "[::1]:53", or the path of a Unix socket.
+ */
+local: string | null; remote: string | null; state: string | null }
+/**
+ * Where a value in a snapshot was read from.
+ */
+export type Source = "sysinfo" | "wmi" | "registry" | 
+/**
+ * Device properties from the Windows configuration manager.
+ */
+"setup_api" | "journal" | "wtmp" | 
+/**
+ * Kernel attributes under /sys.
+ */
+"sysfs" | 
+/**
+ * Kernel statistics under /proc.
+ */
+"procfs" | 
+/**
+ * Firmware tables as the kernel exposes them in /sys/class/dmi/id.
+ */
+"dmi" | 
+/**
+ * The raw SMBIOS table.
+ */
+"smbios" | 
+/**
+ * Device ioctls on the /dev node.
+ */
+"ioctl" | 
+/**
+ * smartmontools' `smartctl --json`.
+ */
+"smartctl" | 
+/**
+ * The CPUID instruction.
+ */
+"cpuid" | 
+/**
+ * Model-specific registers through /dev/cpu/*/msr.
+ */
+"msr" | 
+/**
+ * NVIDIA's `nvidia-smi` tool.
+ */
+"nvidia_smi" | 
+/**
+ * iproute2's `ss`, for TCP byte counters per connection.
+ */
+"ss" | 
+/**
+ * The Docker or Podman API socket.
+ */
+"container_runtime" | 
+/**
+ * Guessed from the model name, not measured.
+ */
+"name_heuristic"
+export type StorageHealth = { 
+/**
+ * One entry per physical drive, including ones that couldn't be read.
+ */
+drives: DriveHealth[]; 
+/**
+ * Drives getting worse, oldest first. Empty from agents, which only
+ * report the current state.
+ */
+history?: HealthEvent[]; provenance: Provenance }
+/**
+ * Emitted for every drive that gets worse, including ones already in a bad
+ * state when the app starts, which show up on its first look.
+ */
+export type StorageHealthAlert = HealthEvent
+export type SystemInfo = { hostname: string; os_name: string; os_version: string; kernel_version: string; architecture: string; 
+/**
+ * Unix timestamp in seconds.
+ */
+boot_time: number; uptime_secs: number; 
+/**
+ * Most recent boot first.
+ */
+boot_history: BootRecord[]; provenance: Provenance }
+export type TempSensor = { 
+/**
+ * "hwmon1/temp1"; what curves read from.
+ */
+id: string; chip: string; label: string | null; celsius: number }
+export type ThrottleCause = "thermal" | 
+/**
+ * Held to a power or current limit.
+ */
+"power_limit" | 
+/**
+ * Clocks held low under load without the hardware saying why, or a
+ * GPU clock limit.
+ */
+"frequency_cap" | 
+/**
+ * Anything else the hardware reports, e.g. GPU sync boost.
+ */
+"other"
+export type ThrottleEvent = { 
+/**
+ * "CPU" or "GPU 0".
+ */
+device: string; cause: ThrottleCause; 
+/**
+ * Which cores, the limit, or the reasons the hardware gave.
+ */
+detail: string; 
+/**
+ * Unix milliseconds.
+ */
+started_at: number; 
+/**
+ * `None` while it's still going on.
+ */
+ended_at: number | null; duration_ms: number }
+export type ThrottleStatus = { 
+/**
+ * Periods still going on as of this sample.
+ */
+active: ThrottleEvent[]; 
+/**
+ * Finished periods, oldest first.
+ */
+events: ThrottleEvent[]; 
+/**
+ * `None` in recordings that predate it.
+ */
+policy_cap?: PolicyCap | null; provenance: Provenance }
+export type UsbDevice = { 
+/**
+ * Port path as the kernel names it, e.g. "1-2.3"; root hubs are "usb1".
+ * On Windows, the hub and port from the driver record.
+ */
+path: string; 
+/**
+ * Path of the hub this hangs off, `None` for root hubs.
+ */
+parent: string | null; bus: number | null; 
+/**
+ * Port on the parent hub.
+ */
+port: number | null; vendor_id: number; product_id: number; vendor: string | null; product: string | null; 
+/**
+ * Device class, or the first interface's when the device defers to them.
+ */
+class: string | null; 
+/**
+ * Negotiated speed in Mbit/s: 1.5, 12, 480, 5000, ...
+ */
+speed_mbps: number | null; 
+/**
+ * What the device asks the port for, in mA.
+ */
+max_power_ma: number | null; 
+/**
+ * Drivers bound to its interfaces.
+ */
+drivers: string[] }
+export type Vulnerability = { 
+/**
+ * "spectre_v2", "mds", ...
+ */
+name: string; status: MitigationStatus; 
+/**
+ * The kernel's own wording.
+ */
+detail: string }
+
+/** tauri-specta globals **/
+
+import {
+	invoke as TAURI_INVOKE,
+	Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
+import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
+
+type __EventObj__<T> = {
+	listen: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
+	once: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
+	emit: null extends T
+		? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
+		: (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
+};
+
+export type Result<T, E> =
+	| { status: "ok"; data: T }
+	| { status: "error"; error: E };
+
+function __makeEvents__<T extends Record<string, any>>(
+	mappings: Record<keyof T, string>,
+) {
+	return new Proxy(
+		{} as unknown as {
+			[K in keyof T]: __EventObj__<T[K]> & {
+				(handle: __WebviewWindow__): __EventObj__<T[K]>;
+			};
+		},
+		{
+			get: (_, event) => {
+				const name = mappings[event as keyof T];
+
+				return new Proxy((() => {}) as any, {
+					apply: (_, __, [window]: [__WebviewWindow__]) => ({
+						listen: (arg: any) => window.listen(name, arg),
+						once: (arg: any) => window.once(name, arg),
+						emit: (arg: any) => window.emit(name, arg),
+					}),
+					get: (_, command: keyof __EventObj__<any>) => {
+						switch (command) {
+							case "listen":
+								return (arg: any) => TAURI_API_EVENT.listen(name, arg);
+							case "once":
+								return (arg: any) => TAURI_API_EVENT.once(name, arg);
+							case "emit":
+								return (arg: any) => TAURI_API_EVENT.emit(name, arg);
+						}
+					},
+				});
+			},
+		},
+	);
+}
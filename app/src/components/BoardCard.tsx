@@ -0,0 +1,116 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { EyeIcon, EyeSlashIcon, Square3Stack3DIcon } from '@heroicons/react/24/outline';
+import { commands, type BoardInfo } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+export default function BoardCard() {
+    const [board, setBoard] = useState<BoardInfo | null>(null);
+    const [loading, setLoading] = useState(true);
+    const [showSerials, setShowSerials] = useState(false);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const fetchBoard = async () => {
+            try {
+                const data = await commands.getBoardInfo(showSerials);
+                setBoard(data);
+                setError(null);
+            } catch (err) {
+                setError(describeError(err));
+                setBoard(null);
+            }
+            setLoading(false);
+        };
+
+        fetchBoard();
+    }, [showSerials]);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !board) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <Square3Stack3DIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Motherboard</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to load board info'}
+                </p>
+            </div>
+        );
+    }
+
+    // withheld only if it was actually read
+    const serial = (value: string | null, field: string) => {
+        if (value !== null) return value;
+        return board.serials_redacted && board.provenance[field]?.status === 'available' ? 'Hidden' : 'N/A';
+    };
+
+    const rows: [string, string, string][] = [
+        ['System', `${board.system_vendor} ${board.system_product}`, 'system_product'],
+        ['Chassis', board.chassis_type, 'chassis_type'],
+        ['BIOS', `${board.bios_vendor} ${board.bios_version}`, 'bios_version'],
+        ['BIOS Date', board.bios_date, 'bios_date'],
+        ['Board Serial', serial(board.board_serial, 'board_serial'), 'board_serial'],
+        ['System Serial', serial(board.system_serial, 'system_serial'), 'system_serial'],
+    ];
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-amber-500 to-orange-600 rounded-lg flex items-center justify-center mr-3">
+                        <Square3Stack3DIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <div>
+                        <h2 className="text-xl font-bold text-gray-900 dark:text-white">Motherboard</h2>
+                        <p
+                            className="text-xs text-gray-500 dark:text-gray-400 max-w-xs truncate"
+                            title={fieldNote(board.provenance, 'board_model')}
+                        >
+                            {board.board_vendor} {board.board_model}
+                            {board.board_version !== 'Unknown' && ` (rev ${board.board_version})`}
+                        </p>
+                    </div>
+                </div>
+                <button
+                    onClick={() => setShowSerials(!showSerials)}
+                    title={showSerials ? 'Hide serial numbers' : 'Show serial numbers'}
+                    className="p-1 text-gray-500 hover:text-gray-700 dark:text-gray-400 dark:hover:text-gray-200 transition-colors duration-200"
+                >
+                    {showSerials ? <EyeSlashIcon className="w-5 h-5" /> : <EyeIcon className="w-5 h-5" />}
+                </button>
+            </div>
+
+            <div className="space-y-2">
+                {rows.map(([label, value, field]) => (
+                    <div key={label} className="flex justify-between text-sm">
+                        <span className="text-gray-500 dark:text-gray-400">{label}</span>
+                        <span
+                            className="text-gray-900 dark:text-white font-mono text-right truncate ml-4"
+                            title={fieldNote(board.provenance, field)}
+                        >
+                            {value}
+                        </span>
+                    </div>
+                ))}
+            </div>
+        </div>
+    );
+}
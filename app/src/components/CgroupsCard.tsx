@@ -0,0 +1,165 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { ChevronDownIcon, ChevronUpIcon, CubeIcon } from '@heroicons/react/24/outline';
+import { commands, type Cgroup, type CgroupStats } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+const TOP_CGROUPS = 12;
+// throttled in this share of periods or more is worth pointing out
+const THROTTLE_WARNING = 10;
+
+const formatBytes = (bytes: number) =>
+    bytes >= 1 << 30 ? `${(bytes / (1 << 30)).toFixed(1)} GiB` : `${(bytes / (1 << 20)).toFixed(0)} MiB`;
+
+const formatRate = (bytes: number) =>
+    bytes >= 1 << 20 ? `${(bytes / (1 << 20)).toFixed(1)} MiB/s` : `${(bytes / 1024).toFixed(0)} KiB/s`;
+
+const memory = (cgroup: Cgroup) => {
+    if (cgroup.memory_current === null) return '';
+    return cgroup.memory_max === null
+        ? formatBytes(cgroup.memory_current)
+        : `${formatBytes(cgroup.memory_current)} / ${formatBytes(cgroup.memory_max)}`;
+};
+
+export default function CgroupsCard() {
+    const [stats, setStats] = useState<CgroupStats | null>(null);
+    const [containersOnly, setContainersOnly] = useState(false);
+    const [showAll, setShowAll] = useState(false);
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const refresh = async () => {
+            try {
+                setStats(await commands.getCgroups());
+                setError(null);
+            } catch (err) {
+                setError(describeError(err));
+            }
+            setLoading(false);
+        };
+
+        refresh();
+        const timer = setInterval(refresh, 2000);
+
+        return () => clearInterval(timer);
+    }, []);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !stats) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <CubeIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Containers & cgroups</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to read cgroups'}
+                </p>
+            </div>
+        );
+    }
+
+    const containers = stats.cgroups.filter((cgroup) => cgroup.kind === 'container');
+    const listed = containersOnly ? containers : stats.cgroups;
+    const shown = showAll ? listed : listed.slice(0, TOP_CGROUPS);
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-sky-500 to-cyan-600 rounded-lg flex items-center justify-center mr-3">
+                        <CubeIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">Containers & cgroups</h2>
+                </div>
+                <label className="flex items-center text-sm text-gray-500 dark:text-gray-400" title={fieldNote(stats.provenance, 'names')}>
+                    <input
+                        type="checkbox"
+                        checked={containersOnly}
+                        onChange={(event) => setContainersOnly(event.target.checked)}
+                        className="mr-2"
+                    />
+                    {containers.length} container{containers.length === 1 ? '' : 's'} only
+                </label>
+            </div>
+
+            {listed.length > 0 ? (
+                <div className="overflow-x-auto">
+                    <table className="w-full text-xs font-mono">
+                        <thead>
+                            <tr className="text-gray-500 dark:text-gray-400">
+                                <th className="text-left font-medium py-1">Name</th>
+                                <th className="text-right font-medium py-1">CPU</th>
+                                <th className="text-right font-medium py-1">Memory</th>
+                                <th className="text-right font-medium py-1">Read</th>
+                                <th className="text-right font-medium py-1">Write</th>
+                                <th className="text-right font-medium py-1">PIDs</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {shown.map((cgroup) => {
+                                const throttled = (cgroup.throttled_percent ?? 0) >= THROTTLE_WARNING;
+                                return (
+                                    <tr key={cgroup.path} className="text-gray-900 dark:text-white">
+                                        <td
+                                            className="py-1 truncate max-w-[14rem]"
+                                            style={{ paddingLeft: containersOnly ? 0 : `${cgroup.depth}rem` }}
+                                            title={cgroup.container_id ? `${cgroup.runtime} ${cgroup.container_id}` : cgroup.path}
+                                        >
+                                            {cgroup.kind === 'container' && <CubeIcon className="w-3 h-3 inline mr-1 text-sky-500" />}
+                                            {cgroup.name}
+                                        </td>
+                                        <td
+                                            className={`py-1 text-right ${throttled ? 'text-amber-600 dark:text-amber-400 font-bold' : ''}`}
+                                            title={
+                                                cgroup.cpu_limit !== null
+                                                    ? `Limit ${cgroup.cpu_limit.toFixed(1)} CPUs, throttled ${(cgroup.throttled_percent ?? 0).toFixed(0)}% of periods`
+                                                    : undefined
+                                            }
+                                        >
+                                            {cgroup.cpu_percent.toFixed(1)}%
+                                        </td>
+                                        <td className="py-1 text-right">{memory(cgroup)}</td>
+                                        <td className="py-1 text-right">{cgroup.io_read !== null ? formatRate(cgroup.io_read) : ''}</td>
+                                        <td className="py-1 text-right">{cgroup.io_write !== null ? formatRate(cgroup.io_write) : ''}</td>
+                                        <td className="py-1 text-right text-gray-500 dark:text-gray-400">{cgroup.pids_current ?? ''}</td>
+                                    </tr>
+                                );
+                            })}
+                        </tbody>
+                    </table>
+                </div>
+            ) : (
+                <p className="text-sm text-gray-500 dark:text-gray-400">
+                    {containersOnly ? 'No containers running' : 'No cgroups found'}
+                </p>
+            )}
+
+            {listed.length > TOP_CGROUPS && (
+                <button
+                    onClick={() => setShowAll(!showAll)}
+                    className="w-full flex items-center justify-center py-2 mt-2 text-blue-600 hover:text-blue-700 dark:text-blue-400 dark:hover:text-blue-300 transition-colors duration-200"
+                >
+                    <span className="text-sm font-medium mr-1">{showAll ? 'Show Less' : `Show All ${listed.length}`}</span>
+                    {showAll ? <ChevronUpIcon className="w-4 h-4" /> : <ChevronDownIcon className="w-4 h-4" />}
+                </button>
+            )}
+        </div>
+    );
+}
@@ -1,41 +1,25 @@
 'use client';
 
 import { useState, useEffect } from 'react';
-import { invoke } from '@tauri-apps/api/core';
 import { ChevronDownIcon, ChevronUpIcon, CpuChipIcon } from '@heroicons/react/24/outline';
-
-interface CpuInfo {
-    brand: string;
-    frequency: number;
-    cores: number;
-    logical_cores: number;
-    usage: number;
-    per_core_usage: number[];
-    temperature: number;
-    temp_available: boolean;
-    vendor: string;
-    architecture: string;
-    max_frequency: number;
-    cache_l1: string;
-    cache_l2: string;
-    cache_l3: string;
-    socket: string;
-    process_node: string;
-    uptime: string;
-}
+import { commands, type CpuInfo, type IdleResidency, type Pressure } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
 
 export default function CpuCard() {
     const [processorInfo, setProcessorInfo] = useState<CpuInfo | null>(null);
     const [isLoadingCpuInfo, setIsLoadingCpuInfo] = useState(true);
+    const [cpuError, setCpuError] = useState<string | null>(null);
     const [showDetailedCoreInfo, setShowDetailedCoreInfo] = useState(false);
     const refreshRate = 1000;
 
     useEffect(() => {
         const getProcessorData = async () => {
             try {
-                const cpuData = await invoke<CpuInfo>('get_cpu_info');
+                const cpuData = await commands.getCpuInfo();
                 setProcessorInfo(cpuData);
+                setCpuError(null);
             } catch (err) {
+                setCpuError(describeError(err));
                 setProcessorInfo(null);
             }
             setIsLoadingCpuInfo(false);
@@ -72,6 +56,20 @@ export default function CpuCard() {
         return 'text-green-500';
     };
 
+    // shallow to deep
+    const formatRate = (perSecond: number) =>
+        perSecond >= 10000 ? `${(perSecond / 1000).toFixed(0)}k/s` : `${perSecond.toFixed(0)}/s`;
+
+    const formatPressure = (label: string, pressure: Pressure | null) =>
+        pressure && `${label} ${pressure.some.avg10.toFixed(1)}%` + (pressure.full ? ` (full ${pressure.full.avg10.toFixed(1)}%)` : '');
+
+    const idleStateColors = ['#fde68a', '#93c5fd', '#60a5fa', '#3b82f6', '#1d4ed8'];
+
+    const deepestState = (states: IdleResidency[]) => {
+        const deepest = [...states].reverse().find((state) => state.residency >= 1);
+        return deepest ? `${deepest.name} ${deepest.residency.toFixed(0)}%` : 'busy';
+    };
+
     if (isLoadingCpuInfo) {
         return (
             <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
@@ -95,7 +93,7 @@ export default function CpuCard() {
                     <CpuChipIcon className="w-8 h-8 text-red-500 mr-3" />
                     <h2 className="text-xl font-bold text-red-600">CPU Information</h2>
                 </div>
-                <p className="text-gray-600 dark:text-gray-400">Failed to load processor information</p>
+                <p className="text-gray-600 dark:text-gray-400">{cpuError || 'Failed to load processor information'}</p>
             </div>
         );
     }
@@ -150,7 +148,7 @@ export default function CpuCard() {
                             {processorInfo.temperature.toFixed(0)}°C
                         </p>
                     ) : (
-                        <p className="text-lg font-bold text-gray-500 dark:text-gray-400">N/A</p>
+                        <p className="text-lg font-bold text-gray-500 dark:text-gray-400" title={fieldNote(processorInfo.provenance, 'temperature')}>N/A</p>
                     )}
                 </div>
             </div>
@@ -224,6 +222,81 @@ export default function CpuCard() {
                         </div>
                     )}
 
+                    {processorInfo.frequency_policy && (
+                        <div title={fieldNote(processorInfo.provenance, 'frequency_policy')}>
+                            <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-1">Frequency Scaling</label>
+                            <p className="text-sm text-gray-900 dark:text-white font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded">
+                                {[
+                                    processorInfo.frequency_policy.driver,
+                                    processorInfo.frequency_policy.governor,
+                                    processorInfo.frequency_policy.epp && `EPP ${processorInfo.frequency_policy.epp}`,
+                                    processorInfo.frequency_policy.boost !== null && `boost ${processorInfo.frequency_policy.boost ? 'on' : 'off'}`,
+                                    !processorInfo.frequency_policy.uniform && 'varies by core',
+                                ].filter(Boolean).join(' · ')}
+                            </p>
+                        </div>
+                    )}
+
+                    {processorInfo.scheduler && (
+                        <div title={fieldNote(processorInfo.provenance, 'scheduler')}>
+                            <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-1">Load &amp; Scheduler</label>
+                            <div className="grid grid-cols-3 gap-2">
+                                {[
+                                    ['Load', processorInfo.scheduler.load
+                                        ? `${processorInfo.scheduler.load.one.toFixed(2)} ${processorInfo.scheduler.load.five.toFixed(2)} ${processorInfo.scheduler.load.fifteen.toFixed(2)}`
+                                        : 'N/A'],
+                                    ['Run queue', `${processorInfo.scheduler.run_queue}` + (processorInfo.scheduler.blocked ? ` · ${processorInfo.scheduler.blocked} on IO` : '')],
+                                    ['Context switches', formatRate(processorInfo.scheduler.context_switches)],
+                                    ['Interrupts', formatRate(processorInfo.scheduler.interrupts)],
+                                    ['Softirqs', formatRate(processorInfo.scheduler.softirqs)],
+                                    ['Forks', processorInfo.scheduler.forks !== null ? formatRate(processorInfo.scheduler.forks) : 'N/A'],
+                                ].map(([label, value]) => (
+                                    <div key={label} className="bg-gray-50 dark:bg-gray-700 p-2 rounded">
+                                        <p className="text-xs text-gray-500 dark:text-gray-400">{label}</p>
+                                        <p className="text-sm text-gray-900 dark:text-white font-mono">{value}</p>
+                                    </div>
+                                ))}
+                            </div>
+                            {(processorInfo.scheduler.cpu_pressure || processorInfo.scheduler.io_pressure) && (
+                                <p className="text-xs text-gray-500 dark:text-gray-400 mt-2 font-mono">
+                                    Pressure (10s): {[
+                                        formatPressure('CPU', processorInfo.scheduler.cpu_pressure),
+                                        formatPressure('IO', processorInfo.scheduler.io_pressure),
+                                    ].filter(Boolean).join(' · ')}
+                                </p>
+                            )}
+                        </div>
+                    )}
+
+                    {processorInfo.idle_states.length > 0 && (
+                        <div>
+                            <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-3">Idle State Residency</label>
+                            <div className="grid grid-cols-1 gap-2">
+                                {processorInfo.idle_states.map((core) => (
+                                    <div key={core.cpu} className="flex items-center space-x-3">
+                                        <span className="text-xs font-medium text-gray-600 dark:text-gray-300 w-14">Thread {core.cpu + 1}</span>
+                                        <div className="flex-1 flex bg-gray-200 dark:bg-gray-700 rounded-full h-2 overflow-hidden">
+                                            {core.states.map((state, index) => (
+                                                <div
+                                                    key={state.name}
+                                                    className="h-2 transition-all duration-500 ease-in-out"
+                                                    title={`${state.name}: ${state.residency.toFixed(1)}%`}
+                                                    style={{
+                                                        width: `${state.residency}%`,
+                                                        backgroundColor: idleStateColors[Math.min(index, idleStateColors.length - 1)]
+                                                    }}
+                                                />
+                                            ))}
+                                        </div>
+                                        <span className="text-xs font-medium w-20 text-right text-gray-600 dark:text-gray-300">
+                                            {deepestState(core.states)}
+                                        </span>
+                                    </div>
+                                ))}
+                            </div>
+                        </div>
+                    )}
+
                     <div>
                         <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-3">Individual Thread Usage ({processorInfo.logical_cores} threads)</label>
                         <div className="grid grid-cols-1 gap-2">
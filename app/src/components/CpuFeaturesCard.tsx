@@ -0,0 +1,137 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { MagnifyingGlassIcon, ShieldCheckIcon } from '@heroicons/react/24/outline';
+import { commands, type CpuFeatures, type MitigationStatus } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+const statusStyles: Record<MitigationStatus, { label: string; color: string }> = {
+    not_affected: { label: 'Not affected', color: 'text-gray-500 dark:text-gray-400' },
+    mitigated: { label: 'Mitigated', color: 'text-green-600 dark:text-green-400' },
+    partial: { label: 'Partial', color: 'text-amber-600 dark:text-amber-400' },
+    vulnerable: { label: 'Vulnerable', color: 'text-red-600 dark:text-red-400' },
+    unknown: { label: 'Unknown', color: 'text-gray-500 dark:text-gray-400' },
+};
+
+export default function CpuFeaturesCard() {
+    const [features, setFeatures] = useState<CpuFeatures | null>(null);
+    const [query, setQuery] = useState('');
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState<string | null>(null);
+
+    // none of this changes while the machine is up
+    useEffect(() => {
+        commands.getCpuFeatures()
+            .then((data) => {
+                setFeatures(data);
+                setError(null);
+            })
+            .catch((err) => setError(describeError(err)))
+            .finally(() => setLoading(false));
+    }, []);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !features) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <ShieldCheckIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">CPU Features</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to read CPU features'}
+                </p>
+            </div>
+        );
+    }
+
+    const search = query.trim().toLowerCase();
+    const matches = (...texts: string[]) => !search || texts.some((text) => text.toLowerCase().includes(search));
+    const grouped = new Set(features.groups.flatMap((group) => group.flags.map((flag) => flag.name)));
+    const groups = [
+        ...features.groups,
+        // everything else, so a search finds any flag the OS lists
+        { name: 'Other', flags: features.flags.filter((flag) => !grouped.has(flag.name)) },
+    ]
+        .map((group) => ({ ...group, flags: group.flags.filter((flag) => matches(flag.name, flag.label, group.name)) }))
+        .filter((group) => group.flags.length > 0 && (search || group.name !== 'Other'));
+    const vulnerabilities = features.vulnerabilities.filter((entry) => matches(entry.name, entry.detail, statusStyles[entry.status].label));
+    const exposed = features.vulnerabilities.filter((entry) => entry.status === 'vulnerable' || entry.status === 'partial').length;
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-emerald-500 to-green-600 rounded-lg flex items-center justify-center mr-3">
+                        <ShieldCheckIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">CPU Features</h2>
+                </div>
+                <span className="text-sm text-gray-500 dark:text-gray-400 font-mono" title={fieldNote(features.provenance, 'microcode')}>
+                    {features.architecture}{features.microcode ? ` · µcode ${features.microcode}` : ''}
+                </span>
+            </div>
+
+            <div className="relative mb-4">
+                <MagnifyingGlassIcon className="w-4 h-4 text-gray-400 absolute left-2 top-1/2 -translate-y-1/2" />
+                <input
+                    value={query}
+                    onChange={(e) => setQuery(e.target.value)}
+                    placeholder="Search flags and vulnerabilities"
+                    className="w-full text-sm bg-gray-50 dark:bg-gray-700 text-gray-900 dark:text-white py-2 pl-8 pr-2 rounded"
+                />
+            </div>
+
+            <div className="space-y-3" title={fieldNote(features.provenance, 'flags')}>
+                {groups.map((group) => (
+                    <div key={group.name}>
+                        <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-1">{group.name}</label>
+                        <div className="flex flex-wrap gap-1">
+                            {group.flags.map((flag) => (
+                                <span key={flag.name} title={flag.name} className="text-xs font-mono bg-gray-100 dark:bg-gray-700 text-gray-900 dark:text-white px-2 py-0.5 rounded">
+                                    {flag.label}
+                                </span>
+                            ))}
+                        </div>
+                    </div>
+                ))}
+                {groups.length === 0 && search && <p className="text-sm text-gray-500 dark:text-gray-400">No flags match</p>}
+            </div>
+
+            <div className="mt-4 pt-4 border-t border-gray-200 dark:border-gray-700" title={fieldNote(features.provenance, 'vulnerabilities')}>
+                <div className="flex items-center justify-between mb-2">
+                    <label className="text-sm font-medium text-gray-500 dark:text-gray-400">Vulnerabilities</label>
+                    {exposed > 0 && <span className="text-xs text-amber-600 dark:text-amber-400">{exposed} not fully mitigated</span>}
+                </div>
+                {features.vulnerabilities.length === 0 ? (
+                    <p className="text-sm text-gray-500 dark:text-gray-400">N/A</p>
+                ) : (
+                    <div className="space-y-1">
+                        {vulnerabilities.map((entry) => (
+                            <div key={entry.name} className="flex items-start justify-between text-xs" title={entry.detail}>
+                                <span className="font-mono text-gray-900 dark:text-white">{entry.name}</span>
+                                <span className={`ml-2 text-right truncate ${statusStyles[entry.status].color}`}>
+                                    {entry.status === 'not_affected' ? statusStyles[entry.status].label : entry.detail}
+                                </span>
+                            </div>
+                        ))}
+                    </div>
+                )}
+            </div>
+        </div>
+    );
+}
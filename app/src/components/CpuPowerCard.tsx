@@ -0,0 +1,114 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { BoltIcon } from '@heroicons/react/24/outline';
+import { commands, type CpuPower } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+const domainLabel = (name: string) => {
+    if (name.startsWith('package')) return `Package ${name.slice('package-'.length)}`;
+    return name.charAt(0).toUpperCase() + name.slice(1);
+};
+
+const formatEnergy = (wh: number) => (wh >= 1 ? `${wh.toFixed(2)} Wh` : `${(wh * 1000).toFixed(0)} mWh`);
+
+export default function CpuPowerCard() {
+    const [power, setPower] = useState<CpuPower | null>(null);
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState<string | null>(null);
+
+    const fetchPower = async () => {
+        try {
+            const data = await commands.getCpuPower();
+            setPower(data);
+            setError(null);
+        } catch (err) {
+            setError(describeError(err));
+            setPower(null);
+        }
+        setLoading(false);
+    };
+
+    useEffect(() => {
+        fetchPower();
+        const timer = setInterval(fetchPower, 2000);
+
+        return () => clearInterval(timer);
+    }, []);
+
+    const resetEnergy = async () => {
+        try {
+            await commands.resetEnergy();
+            await fetchPower();
+        } catch (err) {
+            setError(describeError(err));
+        }
+    };
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !power) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <BoltIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">CPU Power</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to read CPU energy counters'}
+                </p>
+            </div>
+        );
+    }
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-yellow-400 to-orange-500 rounded-lg flex items-center justify-center mr-3">
+                        <BoltIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">CPU Power</h2>
+                </div>
+                <span className="text-lg font-bold text-gray-900 dark:text-white" title={fieldNote(power.provenance, 'package_watts')}>
+                    {power.package_watts !== null ? `${power.package_watts.toFixed(1)} W` : 'N/A'}
+                </span>
+            </div>
+
+            <div className="space-y-2">
+                {power.domains.map((domain) => (
+                    <div key={`${domain.package}-${domain.name}`} className="flex justify-between text-sm">
+                        <span className="text-gray-600 dark:text-gray-400">{domainLabel(domain.name)}</span>
+                        <span className="text-gray-900 dark:text-white">
+                            {domain.watts !== null ? `${domain.watts.toFixed(1)} W` : 'N/A'}
+                            <span className="text-gray-500 dark:text-gray-400 ml-2">{formatEnergy(domain.energy_wh)}</span>
+                        </span>
+                    </div>
+                ))}
+            </div>
+
+            <div className="flex items-center justify-between mt-4 text-xs text-gray-500 dark:text-gray-400">
+                <span>Energy since {new Date(power.energy_since).toLocaleTimeString()}</span>
+                <button
+                    onClick={resetEnergy}
+                    className="px-2 py-1 rounded bg-gray-100 dark:bg-gray-700 hover:bg-gray-200 dark:hover:bg-gray-600 text-gray-700 dark:text-gray-300"
+                >
+                    Reset
+                </button>
+            </div>
+        </div>
+    );
+}
@@ -0,0 +1,223 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { CubeIcon, XMarkIcon } from '@heroicons/react/24/outline';
+import { commands, events, type DeviceInventory, type LinkEvent, type NvmeDevice, type PciDevice, type UsbDevice } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+const hex = (id: number) => id.toString(16).padStart(4, '0');
+
+const pciName = (device: PciDevice) =>
+    device.device ?? `${device.vendor ?? hex(device.vendor_id)} device ${hex(device.device_id)}`;
+
+const usbName = (device: UsbDevice) =>
+    [device.vendor, device.product].filter(Boolean).join(' ') || `${hex(device.vendor_id)}:${hex(device.product_id)}`;
+
+const formatLink = (device: PciDevice | NvmeDevice) =>
+    device.link?.speed_gts != null ? `${device.link.speed_gts} GT/s x${device.link.width ?? '?'}` : null;
+
+// link alerts kept until dismissed
+const MAX_ALERTS = 20;
+
+const formatSpeed = (mbps: number) => (mbps >= 1000 ? `${mbps / 1000} Gbps` : `${mbps} Mbps`);
+
+// depth in the tree, from the port path ("1-2.3" sits two hubs below the root)
+const usbDepth = (device: UsbDevice) => (device.parent === null ? 0 : device.path.split(/[-.]/).length - 1);
+
+export default function DevicesCard() {
+    const [inventory, setInventory] = useState<DeviceInventory | null>(null);
+    const [loading, setLoading] = useState(true);
+    const [tab, setTab] = useState<'pci' | 'usb' | 'nvme'>('pci');
+    const [error, setError] = useState<string | null>(null);
+    const [alerts, setAlerts] = useState<LinkEvent[]>([]);
+
+    useEffect(() => {
+        const fetchInventory = async () => {
+            try {
+                const data = await commands.getDeviceInventory();
+                setInventory(data);
+                setError(null);
+            } catch (err) {
+                setError(describeError(err));
+                setInventory(null);
+            }
+            setLoading(false);
+        };
+
+        fetchInventory();
+        const timer = setInterval(fetchInventory, 30000);
+        const unlisten = events.pcieLinkAlert.listen((event) => {
+            setAlerts((prev) => [...prev, event.payload].slice(-MAX_ALERTS));
+        });
+
+        return () => {
+            clearInterval(timer);
+            unlisten.then((stop) => stop());
+        };
+    }, []);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !inventory) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <CubeIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Devices</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to list devices'}
+                </p>
+            </div>
+        );
+    }
+
+    const tabClass = (active: boolean) =>
+        `px-3 py-1 text-sm rounded-md transition-colors duration-200 ${
+            active
+                ? 'bg-blue-600 text-white'
+                : 'text-gray-600 dark:text-gray-300 hover:bg-gray-100 dark:hover:bg-gray-700'
+        }`;
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-sky-500 to-blue-600 rounded-lg flex items-center justify-center mr-3">
+                        <CubeIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">Devices</h2>
+                </div>
+                <div className="flex space-x-1">
+                    <button className={tabClass(tab === 'pci')} onClick={() => setTab('pci')}>
+                        PCI ({inventory.pci.length})
+                    </button>
+                    <button className={tabClass(tab === 'usb')} onClick={() => setTab('usb')}>
+                        USB ({inventory.usb.length})
+                    </button>
+                    <button className={tabClass(tab === 'nvme')} onClick={() => setTab('nvme')}>
+                        NVMe ({inventory.nvme.length})
+                    </button>
+                </div>
+            </div>
+
+            {alerts.length > 0 && (
+                <div className="mb-4 p-3 rounded-lg bg-amber-50 dark:bg-amber-900/30 border border-amber-200 dark:border-amber-800">
+                    <div className="flex items-start justify-between">
+                        <div className="space-y-1">
+                            {alerts.slice(-3).map((alert, index) => (
+                                <p key={index} className="text-sm text-amber-600 dark:text-amber-400">
+                                    {alert.device ?? alert.address}: {alert.warning}
+                                </p>
+                            ))}
+                            {alerts.length > 3 && (
+                                <p className="text-xs text-gray-500 dark:text-gray-400">and {alerts.length - 3} more</p>
+                            )}
+                        </div>
+                        <button onClick={() => setAlerts([])} className="text-gray-400 hover:text-gray-600 dark:hover:text-gray-200" title="Dismiss">
+                            <XMarkIcon className="w-4 h-4" />
+                        </button>
+                    </div>
+                </div>
+            )}
+
+            <div className="space-y-1 max-h-96 overflow-y-auto">
+                {tab === 'pci' && inventory.pci.length === 0 && (
+                    <p className="text-sm text-gray-500 dark:text-gray-400">{fieldNote(inventory.provenance, 'pci') ?? 'No PCI devices'}</p>
+                )}
+                {tab === 'pci' && inventory.pci.map((device) => (
+                    <div key={device.address} className="text-xs bg-gray-50 dark:bg-gray-700 p-2 rounded">
+                        <div className="flex justify-between">
+                            <span className="font-medium text-gray-900 dark:text-white truncate" title={device.subsystem ?? undefined}>
+                                {pciName(device)}
+                            </span>
+                            <span className="font-mono text-gray-500 dark:text-gray-400 ml-2">{device.address}</span>
+                        </div>
+                        <div className="flex justify-between text-gray-500 dark:text-gray-400">
+                            <span className="truncate">
+                                {device.class}
+                                {device.interface && ` (${device.interface})`}
+                                {device.vendor && ` · ${device.vendor}`}
+                            </span>
+                            <span className="font-mono ml-2 whitespace-nowrap">
+                                {formatLink(device) && (
+                                    <span
+                                        className={device.link?.warning ? 'text-amber-600 dark:text-amber-400' : undefined}
+                                        title={device.link?.warning ?? device.link?.note ?? undefined}
+                                    >
+                                        {formatLink(device)}{device.link?.warning && ' ⚠'} ·{' '}
+                                    </span>
+                                )}
+                                {device.iommu_group !== null && `IOMMU ${device.iommu_group} · `}
+                                {device.driver ?? 'no driver'}
+                            </span>
+                        </div>
+                    </div>
+                ))}
+
+                {tab === 'usb' && inventory.usb.length === 0 && (
+                    <p className="text-sm text-gray-500 dark:text-gray-400">{fieldNote(inventory.provenance, 'usb') ?? 'No USB devices'}</p>
+                )}
+                {tab === 'usb' && inventory.usb.map((device) => (
+                    <div
+                        key={device.path}
+                        className="text-xs bg-gray-50 dark:bg-gray-700 p-2 rounded"
+                        style={{ marginLeft: `${usbDepth(device)}rem` }}
+                    >
+                        <div className="flex justify-between">
+                            <span className="font-medium text-gray-900 dark:text-white truncate">{usbName(device)}</span>
+                            <span className="font-mono text-gray-500 dark:text-gray-400 ml-2">{device.path}</span>
+                        </div>
+                        <div className="flex justify-between text-gray-500 dark:text-gray-400">
+                            <span className="truncate">{device.class ?? 'Unknown class'}</span>
+                            <span className="font-mono ml-2 whitespace-nowrap">
+                                {device.speed_mbps !== null && formatSpeed(device.speed_mbps)}
+                                {device.max_power_ma !== null && ` · ${device.max_power_ma} mA`}
+                                {device.drivers.length > 0 && ` · ${device.drivers.join(', ')}`}
+                            </span>
+                        </div>
+                    </div>
+                ))}
+
+                {tab === 'nvme' && inventory.nvme.length === 0 && (
+                    <p className="text-sm text-gray-500 dark:text-gray-400">{fieldNote(inventory.provenance, 'nvme') ?? 'No NVMe drives'}</p>
+                )}
+                {tab === 'nvme' && inventory.nvme.map((device) => (
+                    <div key={device.name} className="text-xs bg-gray-50 dark:bg-gray-700 p-2 rounded">
+                        <div className="flex justify-between">
+                            <span className="font-medium text-gray-900 dark:text-white truncate">{device.model ?? device.name}</span>
+                            <span className="font-mono text-gray-500 dark:text-gray-400 ml-2">{device.name}</span>
+                        </div>
+                        <div className="flex justify-between text-gray-500 dark:text-gray-400">
+                            <span className="truncate">
+                                {device.firmware && `Firmware ${device.firmware}`}
+                                {device.address && device.address !== device.name && ` · ${device.address}`}
+                            </span>
+                            <span className={`font-mono ml-2 whitespace-nowrap ${device.link?.warning ? 'text-amber-600 dark:text-amber-400' : ''}`}>
+                                {formatLink(device) ?? 'link unknown'}
+                                {device.link?.max_speed_gts != null && ` of ${device.link.max_speed_gts} GT/s x${device.link.max_width ?? '?'}`}
+                            </span>
+                        </div>
+                        {device.link?.warning && (
+                            <p className="text-amber-600 dark:text-amber-400 mt-1">{device.link.warning}</p>
+                        )}
+                        {device.link?.note && <p className="text-gray-500 dark:text-gray-400 mt-1">{device.link.note}</p>}
+                    </div>
+                ))}
+            </div>
+        </div>
+    );
+}
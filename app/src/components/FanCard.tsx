@@ -0,0 +1,187 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { ArrowPathIcon } from '@heroicons/react/24/outline';
+import { commands, type Fan, type FanCurve, type FanStatus, type TempSensor } from '@/bindings';
+import { describeError } from '@/lib/errors';
+
+const DEFAULT_POINTS = '40:25 60:45 75:70 85:100';
+
+// "40:25 60:45" <-> [{ celsius: 40, duty: 25 }, ...]
+const formatPoints = (curve: FanCurve) => curve.points.map((point) => `${point.celsius}:${point.duty}`).join(' ');
+const parsePoints = (text: string) =>
+    text.trim().split(/\s+/).filter(Boolean).map((pair) => {
+        const [celsius, duty] = pair.split(':').map(Number);
+        return { celsius, duty };
+    });
+
+const fanName = (fan: Fan) => fan.label ?? `${fan.chip} ${fan.id.split('/')[1]}`;
+const sensorName = (sensor: TempSensor) => `${sensor.chip} ${sensor.label ?? sensor.id.split('/')[1]}`;
+
+function CurveEditor({ fan, sensors, curve, busy, onApply, onRelease }: {
+    fan: Fan;
+    sensors: TempSensor[];
+    curve: FanCurve | undefined;
+    busy: boolean;
+    onApply: (curve: FanCurve) => void;
+    onRelease: () => void;
+}) {
+    const [sensor, setSensor] = useState(curve?.sensor ?? sensors[0]?.id ?? '');
+    const [points, setPoints] = useState(curve ? formatPoints(curve) : DEFAULT_POINTS);
+    const [hysteresis, setHysteresis] = useState(curve?.hysteresis ?? 3);
+    const [maxStep, setMaxStep] = useState(curve?.max_step ?? 5);
+    const input = 'text-sm font-mono bg-gray-50 dark:bg-gray-700 text-gray-900 dark:text-white p-1 rounded';
+    const button = 'px-3 py-1 text-sm font-medium rounded transition-colors duration-200 disabled:opacity-50';
+
+    return (
+        <div className="mt-2 space-y-2">
+            <div className="grid grid-cols-2 gap-2">
+                <select value={sensor} onChange={(e) => setSensor(e.target.value)} className={input}>
+                    {sensors.map((option) => (
+                        <option key={option.id} value={option.id}>{sensorName(option)} ({option.celsius.toFixed(0)}°C)</option>
+                    ))}
+                </select>
+                <input value={points} onChange={(e) => setPoints(e.target.value)} className={input} title="°C:duty% pairs" />
+            </div>
+            <div className="flex items-center gap-2 text-xs text-gray-500 dark:text-gray-400">
+                <label>Hysteresis °C</label>
+                <input type="number" min={0} max={20} value={hysteresis} onChange={(e) => setHysteresis(Number(e.target.value))} className={`${input} w-14`} />
+                <label>Step %/s</label>
+                <input type="number" min={1} max={100} value={maxStep} onChange={(e) => setMaxStep(Number(e.target.value))} className={`${input} w-14`} />
+                <div className="flex-1" />
+                <button
+                    onClick={() => onApply({ fan: fan.id, sensor, points: parsePoints(points), hysteresis, max_step: maxStep })}
+                    disabled={busy || !sensor}
+                    className={`${button} text-white bg-blue-600 hover:bg-blue-700`}
+                >
+                    Apply
+                </button>
+                <button onClick={onRelease} disabled={busy || !curve} className={`${button} text-white bg-gray-600 hover:bg-gray-700`}>
+                    Auto
+                </button>
+            </div>
+        </div>
+    );
+}
+
+export default function FanCard() {
+    const [status, setStatus] = useState<FanStatus | null>(null);
+    const [editing, setEditing] = useState<string | null>(null);
+    const [busy, setBusy] = useState(false);
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState<string | null>(null);
+
+    const refresh = async () => {
+        try {
+            setStatus(await commands.getFanStatus());
+            setError(null);
+        } catch (err) {
+            setError(describeError(err));
+        }
+        setLoading(false);
+    };
+
+    useEffect(() => {
+        refresh();
+        const timer = setInterval(refresh, 2000);
+
+        return () => clearInterval(timer);
+    }, []);
+
+    // the backend takes the whole set at once
+    const apply = async (curves: FanCurve[]) => {
+        setBusy(true);
+        try {
+            setStatus(await commands.setFanCurves(curves));
+            setError(null);
+        } catch (err) {
+            setError(describeError(err));
+        }
+        setBusy(false);
+    };
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (!status) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <ArrowPathIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Fans</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to read fans'}
+                </p>
+            </div>
+        );
+    }
+
+    const others = (fan: Fan) => status.curves.filter((curve) => curve.fan !== fan.id);
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-sky-500 to-blue-600 rounded-lg flex items-center justify-center mr-3">
+                        <ArrowPathIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">Fans</h2>
+                </div>
+                {status.curves.length > 0 && (
+                    <span className="text-sm text-gray-500 dark:text-gray-400">{status.curves.length} on a curve</span>
+                )}
+            </div>
+
+            {status.fans.length === 0 && (
+                <p className="text-sm text-gray-500 dark:text-gray-400">No PWM fans exposed by hwmon</p>
+            )}
+            <div className="space-y-3">
+                {status.fans.map((fan) => {
+                    const curve = status.curves.find((candidate) => candidate.fan === fan.id);
+                    return (
+                        <div key={fan.id} className="bg-gray-50 dark:bg-gray-700 rounded-lg p-3">
+                            <div className="flex items-center justify-between">
+                                <button
+                                    onClick={() => setEditing(editing === fan.id ? null : fan.id)}
+                                    className="text-sm font-medium text-gray-900 dark:text-white text-left"
+                                >
+                                    {fanName(fan)}
+                                </button>
+                                <span className="text-sm font-mono text-gray-600 dark:text-gray-300">
+                                    {fan.rpm != null ? `${fan.rpm} RPM` : '—'}
+                                    {fan.duty != null ? ` · ${fan.duty.toFixed(0)}%` : ''}
+                                    {' · '}{curve ? 'curve' : fan.mode === 'full_speed' ? 'full' : fan.mode ?? '?'}
+                                </span>
+                            </div>
+                            {editing === fan.id && (
+                                <CurveEditor
+                                    fan={fan}
+                                    sensors={status.sensors}
+                                    curve={curve}
+                                    busy={busy}
+                                    onApply={(edited) => apply([...others(fan), edited])}
+                                    onRelease={() => apply(others(fan))}
+                                />
+                            )}
+                        </div>
+                    );
+                })}
+            </div>
+
+            {error && <p className="text-sm text-red-600 dark:text-red-400 mt-3">{error}</p>}
+        </div>
+    );
+}
@@ -1,37 +1,15 @@
 'use client';
 
 import { useState, useEffect } from 'react';
-import { invoke } from '@tauri-apps/api/core';
 import { ChevronDownIcon, ChevronUpIcon, RectangleStackIcon } from '@heroicons/react/24/outline';
+import { commands, type GpuInfo, type PcieLink } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
 
-interface GpuInfo {
-    name: string;
-    driver_version: string;
-    memory_total: number;
-    memory_used: number;
-    memory_free: number;
-    temperature: number;
-    temp_available: boolean;
-    power_usage: number;
-    utilization: number;
-    fan_speed: number;
-    gpu_type: string;
-    vendor: string;
-    architecture: string;
-    device_id: string;
-    pci_slot: string;
-    memory_type: string;
-    memory_bus_width: number;
-    base_clock: number;
-    boost_clock: number;
-    memory_clock: number;
-    shader_units: number;
-    tmu_count: number;
-    rop_count: number;
-    directx_version: string;
-    opengl_version: string;
-    vulkan_support: boolean;
-}
+const formatLink = (speed: number | null, width: number | null) =>
+    `${speed ?? '?'} GT/s x${width ?? '?'}`;
+
+const linkSummary = (link: PcieLink) =>
+    `${formatLink(link.speed_gts, link.width)} (max ${formatLink(link.max_speed_gts, link.max_width)})`;
 
 export default function GpuCard() {
     const [gpu, setGpu] = useState<GpuInfo | null>(null);
@@ -42,11 +20,11 @@ export default function GpuCard() {
     useEffect(() => {
         const fetchGpu = async () => {
             try {
-                const data = await invoke<GpuInfo>('get_gpu_info');
+                const data = await commands.getGpuInfo();
                 setGpu(data);
                 setError(null);
             } catch (err) {
-                setError('Unable to get GPU info');
+                setError(describeError(err));
                 setGpu(null);
             }
             setLoading(false);
@@ -185,6 +163,13 @@ export default function GpuCard() {
                 )}
             </div>
 
+            {gpu.pcie_link?.warning && (
+                <div className="mb-4 p-3 bg-amber-50 dark:bg-amber-900/20 rounded-lg" title={linkSummary(gpu.pcie_link)}>
+                    <p className="text-sm text-amber-700 dark:text-amber-400 font-medium">PCIe link below capability</p>
+                    <p className="text-xs text-amber-600 dark:text-amber-300 mt-1">{gpu.pcie_link.warning}</p>
+                </div>
+            )}
+
             <button
                 onClick={() => setShowDetails(!showDetails)}
                 className="w-full flex items-center justify-center py-2 text-blue-600 hover:text-blue-700 dark:text-blue-400 dark:hover:text-blue-300 transition-colors duration-200"
@@ -208,7 +193,7 @@ export default function GpuCard() {
                         </div>
                         <div>
                             <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-1">Architecture</label>
-                            <p className="text-sm text-gray-900 dark:text-white font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded">{gpu.architecture}</p>
+                            <p className="text-sm text-gray-900 dark:text-white font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded" title={fieldNote(gpu.provenance, 'architecture')}>{gpu.architecture}</p>
                         </div>
                     </div>
 
@@ -228,7 +213,7 @@ export default function GpuCard() {
                             <div className="grid grid-cols-2 gap-4">
                                 <div>
                                     <label className="text-xs font-medium text-gray-400 dark:text-gray-500 block mb-1">Memory Type</label>
-                                    <p className="text-sm text-gray-900 dark:text-white font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded">{gpu.memory_type}</p>
+                                    <p className="text-sm text-gray-900 dark:text-white font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded" title={fieldNote(gpu.provenance, 'memory_type')}>{gpu.memory_type}</p>
                                 </div>
                                 {gpu.memory_bus_width > 0 && (
                                     <div>
@@ -351,6 +336,12 @@ export default function GpuCard() {
                                         <p className="text-xs text-gray-900 dark:text-white font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded">{gpu.pci_slot}</p>
                                     </div>
                                 )}
+                                {gpu.pcie_link && (
+                                    <div>
+                                        <label className="text-xs font-medium text-gray-400 dark:text-gray-500 block mb-1">PCIe Link</label>
+                                        <p className="text-xs text-gray-900 dark:text-white font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded" title={fieldNote(gpu.provenance, 'pcie_link')}>{linkSummary(gpu.pcie_link)}</p>
+                                    </div>
+                                )}
                             </div>
                         </div>
                     )}
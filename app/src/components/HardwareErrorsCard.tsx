@@ -0,0 +1,168 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { ExclamationTriangleIcon, XMarkIcon } from '@heroicons/react/24/outline';
+import { commands, events, type ErrorEvent, type ErrorSeverity, type ErrorSource, type HardwareErrors } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+const RECENT_EVENTS = 10;
+
+const SOURCES: { source: ErrorSource; title: string; field: string }[] = [
+    { source: 'machine_check', title: 'Machine Checks', field: 'machine_check_reports' },
+    { source: 'memory', title: 'Memory (EDAC)', field: 'memory' },
+    { source: 'pcie', title: 'PCIe AER', field: 'pcie' },
+];
+
+const SEVERITY_LABELS: Record<ErrorSeverity, string> = {
+    corrected: 'corrected',
+    uncorrected: 'uncorrected',
+    fatal: 'fatal',
+};
+
+const severityColor = (severity: ErrorSeverity) =>
+    severity === 'corrected' ? 'text-amber-600 dark:text-amber-400' : 'text-red-600 dark:text-red-400';
+
+const eventName = (event: ErrorEvent) => `${event.label ?? event.device}${event.label ? ` (${event.device})` : ''}`;
+
+export default function HardwareErrorsCard() {
+    const [errors, setErrors] = useState<HardwareErrors | null>(null);
+    const [alerts, setAlerts] = useState<ErrorEvent[]>([]);
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const refresh = async () => {
+            try {
+                setErrors(await commands.getHardwareErrors());
+                setError(null);
+            } catch (err) {
+                setError(describeError(err));
+            }
+            setLoading(false);
+        };
+
+        refresh();
+        const timer = setInterval(refresh, 30000);
+        const unlisten = events.hardwareErrorAlert.listen((event) => {
+            setAlerts((prev) => [...prev, event.payload]);
+            refresh();
+        });
+
+        return () => {
+            clearInterval(timer);
+            unlisten.then((stop) => stop());
+        };
+    }, []);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !errors) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <ExclamationTriangleIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Hardware Errors</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to read hardware error counters'}
+                </p>
+            </div>
+        );
+    }
+
+    const total = errors.counters.reduce((sum, counter) => sum + counter.count, 0);
+    const recent = errors.history.slice(-RECENT_EVENTS).reverse();
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-rose-500 to-orange-500 rounded-lg flex items-center justify-center mr-3">
+                        <ExclamationTriangleIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">Hardware Errors</h2>
+                </div>
+                <span className={`text-sm font-mono ${total > 0 ? 'text-amber-600 dark:text-amber-400' : 'text-gray-500 dark:text-gray-400'}`}>
+                    {total} since boot
+                </span>
+            </div>
+
+            {alerts.length > 0 && (
+                <div className="mb-4 p-3 rounded-lg bg-red-50 dark:bg-red-900/30 border border-red-200 dark:border-red-800">
+                    <div className="flex items-start justify-between">
+                        <div className="space-y-1">
+                            {alerts.slice(-3).map((alert, index) => (
+                                <p key={index} className={`text-sm ${severityColor(alert.severity)}`}>
+                                    {alert.increase} new {SEVERITY_LABELS[alert.severity]} error{alert.increase === 1 ? '' : 's'} on {eventName(alert)}
+                                </p>
+                            ))}
+                            {alerts.length > 3 && (
+                                <p className="text-xs text-gray-500 dark:text-gray-400">and {alerts.length - 3} more</p>
+                            )}
+                        </div>
+                        <button onClick={() => setAlerts([])} className="text-gray-400 hover:text-gray-600 dark:hover:text-gray-200" title="Dismiss">
+                            <XMarkIcon className="w-4 h-4" />
+                        </button>
+                    </div>
+                </div>
+            )}
+
+            <div className="space-y-4">
+                {SOURCES.map(({ source, title, field }) => {
+                    const counters = errors.counters.filter((counter) => counter.source === source && counter.count > 0);
+                    const watched = errors.counters.some((counter) => counter.source === source);
+                    return (
+                        <div key={source}>
+                            <h3 className="text-sm font-semibold text-gray-700 dark:text-gray-300 mb-1">{title}</h3>
+                            {counters.length > 0 ? (
+                                <div className="space-y-1">
+                                    {counters.map((counter) => (
+                                        <div key={`${counter.device}-${counter.severity}`} className="flex justify-between text-sm" title={counter.detail ?? undefined}>
+                                            <span className="text-gray-600 dark:text-gray-400 truncate mr-2">{counter.label ?? counter.device}</span>
+                                            <span className={`font-mono ${severityColor(counter.severity)}`}>
+                                                {counter.count} {SEVERITY_LABELS[counter.severity]}
+                                            </span>
+                                        </div>
+                                    ))}
+                                </div>
+                            ) : (
+                                <p className="text-sm text-gray-500 dark:text-gray-400" title={fieldNote(errors.provenance, field)}>
+                                    {watched ? 'No errors' : 'Not available'}
+                                </p>
+                            )}
+                        </div>
+                    );
+                })}
+            </div>
+
+            {recent.length > 0 && (
+                <div className="mt-4 pt-4 border-t border-gray-200 dark:border-gray-700">
+                    <h3 className="text-sm font-semibold text-gray-700 dark:text-gray-300 mb-1">History</h3>
+                    <div className="space-y-1">
+                        {recent.map((event, index) => (
+                            <div key={index} className="flex justify-between text-xs" title={event.detail ?? undefined}>
+                                <span className="text-gray-600 dark:text-gray-400 truncate mr-2">
+                                    {new Date(event.at).toLocaleTimeString()} · {eventName(event)}
+                                </span>
+                                <span className={`font-mono ${severityColor(event.severity)}`}>+{event.increase}</span>
+                            </div>
+                        ))}
+                    </div>
+                </div>
+            )}
+        </div>
+    );
+}
@@ -0,0 +1,156 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { ChevronDownIcon, ChevronUpIcon, SignalIcon } from '@heroicons/react/24/outline';
+import { commands, type InterruptSource, type InterruptStats } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+const TOP_SOURCES = 8;
+
+const formatRate = (perSecond: number) =>
+    perSecond >= 10000 ? `${(perSecond / 1000).toFixed(0)}k` : perSecond.toFixed(0);
+
+const sourceName = (source: InterruptSource) =>
+    source.kind === 'device' ? `${source.id} · ${source.name ?? source.chip ?? 'IRQ'}` : `${source.id}${source.name ? ` · ${source.name}` : ''}`;
+
+export default function InterruptsCard() {
+    const [stats, setStats] = useState<InterruptStats | null>(null);
+    const [showAll, setShowAll] = useState(false);
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const refresh = async () => {
+            try {
+                setStats(await commands.getInterrupts());
+                setError(null);
+            } catch (err) {
+                setError(describeError(err));
+            }
+            setLoading(false);
+        };
+
+        refresh();
+        const timer = setInterval(refresh, 2000);
+
+        return () => clearInterval(timer);
+    }, []);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !stats) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <SignalIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Interrupts</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to read interrupt counters'}
+                </p>
+            </div>
+        );
+    }
+
+    const totals = stats.cpus.map((_, column) => stats.cpu_interrupts[column] + stats.cpu_softirqs[column]);
+    const busiest = Math.max(...totals, 1);
+    const concentrated = stats.sources.filter((source) => source.concentrated_on !== null);
+    const sources = showAll ? stats.sources : stats.sources.slice(0, TOP_SOURCES);
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-indigo-500 to-violet-600 rounded-lg flex items-center justify-center mr-3">
+                        <SignalIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">Interrupts</h2>
+                </div>
+                <span className="text-sm text-gray-500 dark:text-gray-400 font-mono">{formatRate(totals.reduce((sum, rate) => sum + rate, 0))}/s</span>
+            </div>
+
+            {concentrated.length > 0 && (
+                <div className="mb-4 space-y-1">
+                    {concentrated.map((source) => (
+                        <p key={`${source.kind}-${source.id}`} className="text-sm text-amber-600 dark:text-amber-400">
+                            {sourceName(source)} lands on CPU {source.concentrated_on} ({formatRate(source.total)}/s)
+                            {source.affinity ? `, allowed on ${source.affinity}` : ''}
+                        </p>
+                    ))}
+                </div>
+            )}
+
+            <div className="grid grid-cols-1 gap-2 mb-4">
+                {stats.cpus.map((cpu, column) => (
+                    <div key={cpu} className="flex items-center space-x-3">
+                        <span className="text-xs font-medium text-gray-600 dark:text-gray-300 w-14">CPU {cpu}</span>
+                        <div className="flex-1 flex h-2 bg-gray-200 dark:bg-gray-700 rounded-full overflow-hidden">
+                            <div className="h-full bg-indigo-500" style={{ width: `${(stats.cpu_interrupts[column] / busiest) * 100}%` }} title="Interrupts" />
+                            <div className="h-full bg-violet-300" style={{ width: `${(stats.cpu_softirqs[column] / busiest) * 100}%` }} title="Softirqs" />
+                        </div>
+                        <span className="text-xs font-mono text-gray-600 dark:text-gray-300 w-16 text-right">{formatRate(totals[column])}/s</span>
+                    </div>
+                ))}
+            </div>
+
+            {stats.sources.length > 0 ? (
+                <div className="overflow-x-auto" title={fieldNote(stats.provenance, 'affinity')}>
+                    <table className="w-full text-xs font-mono">
+                        <thead>
+                            <tr className="text-gray-500 dark:text-gray-400">
+                                <th className="text-left font-medium py-1">Source</th>
+                                <th className="text-right font-medium py-1">Affinity</th>
+                                {stats.cpus.map((cpu) => (
+                                    <th key={cpu} className="text-right font-medium py-1">{cpu}</th>
+                                ))}
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {sources.map((source) => (
+                                <tr key={`${source.kind}-${source.id}`} className="text-gray-900 dark:text-white">
+                                    <td className="py-1 truncate max-w-[12rem]" title={source.chip ?? undefined}>{sourceName(source)}</td>
+                                    <td className="py-1 text-right text-gray-500 dark:text-gray-400">{source.effective_affinity ?? source.affinity ?? ''}</td>
+                                    {source.rates.map((rate, column) => (
+                                        <td
+                                            key={column}
+                                            className={`py-1 text-right ${stats.cpus[column] === source.concentrated_on ? 'text-amber-600 dark:text-amber-400 font-bold' : ''}`}
+                                        >
+                                            {rate > 0 ? formatRate(rate) : '·'}
+                                        </td>
+                                    ))}
+                                </tr>
+                            ))}
+                        </tbody>
+                    </table>
+                </div>
+            ) : (
+                <p className="text-sm text-gray-500 dark:text-gray-400" title={fieldNote(stats.provenance, 'sources')}>
+                    No per-source counters on this platform
+                </p>
+            )}
+
+            {stats.sources.length > TOP_SOURCES && (
+                <button
+                    onClick={() => setShowAll(!showAll)}
+                    className="w-full flex items-center justify-center py-2 mt-2 text-blue-600 hover:text-blue-700 dark:text-blue-400 dark:hover:text-blue-300 transition-colors duration-200"
+                >
+                    <span className="text-sm font-medium mr-1">{showAll ? 'Show Less' : `Show All ${stats.sources.length}`}</span>
+                    {showAll ? <ChevronUpIcon className="w-4 h-4" /> : <ChevronDownIcon className="w-4 h-4" />}
+                </button>
+            )}
+        </div>
+    );
+}
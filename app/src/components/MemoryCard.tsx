@@ -0,0 +1,156 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { ChevronDownIcon, ChevronUpIcon, RectangleStackIcon } from '@heroicons/react/24/outline';
+import { commands, type MemoryLayout, type MemoryModule } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+const formatSize = (bytes: number) => {
+    const gib = bytes / 1024 ** 3;
+    return gib >= 1 ? `${Number.isInteger(gib) ? gib : gib.toFixed(1)} GB` : `${Math.round(bytes / 1024 ** 2)} MB`;
+};
+
+const describeModule = (module: MemoryModule) => {
+    if (module.size === 0) return 'Empty';
+    const parts = [
+        module.size !== null ? formatSize(module.size) : null,
+        module.memory_type,
+        module.configured_speed !== null ? `${module.configured_speed} MT/s` : null,
+    ];
+    return parts.filter(Boolean).join(' ') || 'Installed';
+};
+
+export default function MemoryCard() {
+    const [layout, setLayout] = useState<MemoryLayout | null>(null);
+    const [loading, setLoading] = useState(true);
+    const [showDetails, setShowDetails] = useState(false);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const fetchLayout = async () => {
+            try {
+                const data = await commands.getMemoryLayout();
+                setLayout(data);
+                setError(null);
+            } catch (err) {
+                setError(describeError(err));
+                setLayout(null);
+            }
+            setLoading(false);
+        };
+
+        fetchLayout();
+    }, []);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !layout) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <RectangleStackIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Memory Modules</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to load memory modules'}
+                </p>
+            </div>
+        );
+    }
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center mb-4">
+                <div className="w-8 h-8 bg-gradient-to-br from-violet-500 to-purple-600 rounded-lg flex items-center justify-center mr-3">
+                    <RectangleStackIcon className="w-5 h-5 text-white" />
+                </div>
+                <div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">Memory Modules</h2>
+                    <p className="text-xs text-gray-500 dark:text-gray-400" title={fieldNote(layout.provenance, 'error_correction')}>
+                        {layout.error_correction ?? 'Unknown error correction'}
+                    </p>
+                </div>
+            </div>
+
+            <div className="grid grid-cols-3 gap-4 mb-4">
+                <div className="text-center p-3 bg-gray-50 dark:bg-gray-700 rounded-lg">
+                    <p className="text-sm text-gray-500 dark:text-gray-400">Installed</p>
+                    <p className="text-lg font-bold text-gray-900 dark:text-white">{formatSize(layout.installed)}</p>
+                </div>
+                <div className="text-center p-3 bg-gray-50 dark:bg-gray-700 rounded-lg">
+                    <p className="text-sm text-gray-500 dark:text-gray-400">Free Slots</p>
+                    <p className="text-lg font-bold text-gray-900 dark:text-white">
+                        {layout.free_slots} / {layout.total_slots}
+                    </p>
+                </div>
+                <div className="text-center p-3 bg-gray-50 dark:bg-gray-700 rounded-lg">
+                    <p className="text-sm text-gray-500 dark:text-gray-400">Max</p>
+                    <p className="text-lg font-bold text-gray-900 dark:text-white" title={fieldNote(layout.provenance, 'max_capacity')}>
+                        {layout.max_capacity !== null ? formatSize(layout.max_capacity) : 'N/A'}
+                    </p>
+                </div>
+            </div>
+
+            <div className="space-y-1">
+                {layout.modules.map((module) => (
+                    <div key={module.slot} className="flex justify-between text-xs font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded">
+                        <span className="text-gray-900 dark:text-white">{module.slot}</span>
+                        <span className={module.size === 0 ? 'text-gray-400 dark:text-gray-500' : 'text-gray-900 dark:text-white'}>
+                            {describeModule(module)}
+                        </span>
+                    </div>
+                ))}
+            </div>
+
+            <button
+                onClick={() => setShowDetails(!showDetails)}
+                className="w-full flex items-center justify-center py-2 mt-2 text-blue-600 hover:text-blue-700 dark:text-blue-400 dark:hover:text-blue-300 transition-colors duration-200"
+            >
+                <span className="text-sm font-medium mr-1">
+                    {showDetails ? 'Show Less' : 'Show Details'}
+                </span>
+                {showDetails ? (
+                    <ChevronUpIcon className="w-4 h-4" />
+                ) : (
+                    <ChevronDownIcon className="w-4 h-4" />
+                )}
+            </button>
+
+            {showDetails && (
+                <div className="mt-4 pt-4 border-t border-gray-200 dark:border-gray-700 space-y-3 animate-in slide-in-from-top duration-300">
+                    {layout.modules.filter((module) => module.size !== 0).map((module) => (
+                        <div key={module.slot} className="text-xs bg-gray-50 dark:bg-gray-700 p-2 rounded space-y-1">
+                            <p className="font-medium text-gray-900 dark:text-white">
+                                {module.slot} · {module.manufacturer ?? 'Unknown'} {module.part_number ?? ''}
+                            </p>
+                            <p className="text-gray-500 dark:text-gray-400 font-mono">
+                                {module.form_factor ?? 'Unknown form factor'}
+                                {module.rank !== null && `, ${module.rank}R`}
+                                {module.ecc !== null && (module.ecc ? ', ECC' : ', non-ECC')}
+                                {module.configured_voltage_mv !== null && `, ${(module.configured_voltage_mv / 1000).toFixed(2)} V`}
+                            </p>
+                            <p className={module.rated_speed !== null && module.configured_speed !== null && module.configured_speed < module.rated_speed
+                                ? 'text-amber-600 dark:text-amber-400'
+                                : 'text-gray-500 dark:text-gray-400'}>
+                                Running {module.configured_speed ?? '?'} of {module.rated_speed ?? '?'} MT/s rated
+                            </p>
+                        </div>
+                    ))}
+                </div>
+            )}
+        </div>
+    );
+}
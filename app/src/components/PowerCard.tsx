@@ -0,0 +1,165 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { Battery50Icon } from '@heroicons/react/24/outline';
+import { commands, type Battery, type BatteryState, type PowerInfo } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+const stateLabel: Record<BatteryState, string> = {
+    charging: 'Charging',
+    discharging: 'On battery',
+    full: 'Full',
+    not_charging: 'Not charging',
+    unknown: 'Unknown',
+};
+
+const formatDuration = (secs: number) => {
+    const hours = Math.floor(secs / 3600);
+    const minutes = Math.floor((secs % 3600) / 60);
+    return hours > 0 ? `${hours}h ${minutes}m` : `${minutes}m`;
+};
+
+const getChargeColor = (percent: number) => {
+    if (percent > 50) return '#10b981';
+    if (percent > 20) return '#f59e0b';
+    return '#ef4444';
+};
+
+function BatteryRow({ battery }: { battery: Battery }) {
+    const charge = battery.charge_percent ?? 0;
+    const eta = battery.time_to_empty_secs ?? battery.time_to_full_secs;
+
+    return (
+        <div className="space-y-2">
+            <div className="flex justify-between text-sm">
+                <span className="font-medium text-gray-900 dark:text-white truncate">
+                    {[battery.manufacturer, battery.model].filter(Boolean).join(' ') || battery.name}
+                </span>
+                <span className="text-gray-500 dark:text-gray-400 ml-2 whitespace-nowrap">
+                    {stateLabel[battery.state]}
+                    {battery.rate_watts !== null && ` · ${battery.rate_watts.toFixed(1)} W`}
+                </span>
+            </div>
+
+            <div className="w-full bg-gray-200 dark:bg-gray-700 rounded-full h-4 overflow-hidden">
+                <div
+                    className="h-full rounded-full transition-all duration-700 ease-out"
+                    style={{ width: `${Math.min(charge, 100)}%`, backgroundColor: getChargeColor(charge) }}
+                />
+            </div>
+
+            <div className="grid grid-cols-3 gap-2 text-center">
+                <div className="p-2 bg-gray-50 dark:bg-gray-700 rounded-lg">
+                    <p className="text-xs text-gray-500 dark:text-gray-400">Charge</p>
+                    <p className="text-sm font-bold text-gray-900 dark:text-white">
+                        {battery.charge_percent !== null ? `${battery.charge_percent.toFixed(0)}%` : 'N/A'}
+                    </p>
+                </div>
+                <div className="p-2 bg-gray-50 dark:bg-gray-700 rounded-lg">
+                    <p className="text-xs text-gray-500 dark:text-gray-400">
+                        {battery.time_to_full_secs !== null ? 'Until full' : 'Remaining'}
+                    </p>
+                    <p className="text-sm font-bold text-gray-900 dark:text-white">{eta !== null ? formatDuration(eta) : 'N/A'}</p>
+                </div>
+                <div
+                    className="p-2 bg-gray-50 dark:bg-gray-700 rounded-lg"
+                    title={battery.energy_full_wh !== null && battery.energy_full_design_wh !== null
+                        ? `${battery.energy_full_wh.toFixed(1)} of ${battery.energy_full_design_wh.toFixed(1)} Wh design capacity`
+                        : undefined}
+                >
+                    <p className="text-xs text-gray-500 dark:text-gray-400">Wear</p>
+                    <p className="text-sm font-bold text-gray-900 dark:text-white">
+                        {battery.wear_percent !== null ? `${battery.wear_percent.toFixed(0)}%` : 'N/A'}
+                    </p>
+                </div>
+            </div>
+
+            <p className="text-xs text-gray-500 dark:text-gray-400">
+                {[
+                    battery.chemistry,
+                    battery.cycle_count !== null && `${battery.cycle_count} cycles`,
+                    battery.energy_full_design_wh !== null && `${battery.energy_full_design_wh.toFixed(1)} Wh design`,
+                ].filter(Boolean).join(' · ')}
+            </p>
+        </div>
+    );
+}
+
+export default function PowerCard() {
+    const [power, setPower] = useState<PowerInfo | null>(null);
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const fetchPower = async () => {
+            try {
+                const data = await commands.getPowerInfo();
+                setPower(data);
+                setError(null);
+            } catch (err) {
+                setError(describeError(err));
+                setPower(null);
+            }
+            setLoading(false);
+        };
+
+        fetchPower();
+        const timer = setInterval(fetchPower, 5000);
+
+        return () => clearInterval(timer);
+    }, []);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !power) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <Battery50Icon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Power</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to read power supply'}
+                </p>
+            </div>
+        );
+    }
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-lime-500 to-green-600 rounded-lg flex items-center justify-center mr-3">
+                        <Battery50Icon className="w-5 h-5 text-white" />
+                    </div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">Power</h2>
+                </div>
+                <span className="text-sm text-gray-500 dark:text-gray-400" title={fieldNote(power.provenance, 'ac_online')}>
+                    {power.ac_online === null ? 'AC unknown' : power.ac_online ? 'On AC' : 'On battery'}
+                </span>
+            </div>
+
+            <div className="space-y-4">
+                {power.batteries.length === 0 && (
+                    <p className="text-sm text-gray-500 dark:text-gray-400">No battery</p>
+                )}
+                {power.batteries.map((battery) => (
+                    <BatteryRow key={battery.name} battery={battery} />
+                ))}
+            </div>
+        </div>
+    );
+}
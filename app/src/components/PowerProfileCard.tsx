@@ -0,0 +1,202 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { AdjustmentsHorizontalIcon } from '@heroicons/react/24/outline';
+import { commands, type AuditEntry, type PowerControls, type PowerSetting } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+const describeSetting = (setting: PowerSetting) => {
+    switch (setting.kind) {
+        case 'governor': return `governor ${setting.value}`;
+        case 'epp': return `EPP ${setting.value}`;
+        case 'boost': return `boost ${setting.value ? 'on' : 'off'}`;
+        case 'platform_profile': return `profile ${setting.value}`;
+    }
+};
+
+function Choice({ label, value, options, note, disabled, onChange }: {
+    label: string;
+    value: string | null;
+    options: string[];
+    note?: string;
+    disabled: boolean;
+    onChange: (value: string) => void;
+}) {
+    return (
+        <div title={note}>
+            <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-1">{label}</label>
+            <select
+                value={value ?? ''}
+                disabled={disabled || options.length === 0}
+                onChange={(e) => onChange(e.target.value)}
+                className="w-full text-sm font-mono bg-gray-50 dark:bg-gray-700 text-gray-900 dark:text-white p-2 rounded disabled:opacity-50"
+            >
+                {value === null && <option value="">N/A</option>}
+                {options.map((option) => (
+                    <option key={option} value={option}>{option}</option>
+                ))}
+            </select>
+        </div>
+    );
+}
+
+export default function PowerProfileCard() {
+    const [controls, setControls] = useState<PowerControls | null>(null);
+    const [audit, setAudit] = useState<AuditEntry[]>([]);
+    const [busy, setBusy] = useState(false);
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState<string | null>(null);
+
+    const refresh = async () => {
+        try {
+            setControls(await commands.getPowerControls());
+            setAudit(await commands.getPowerAudit());
+            setError(null);
+        } catch (err) {
+            setError(describeError(err));
+        }
+        setLoading(false);
+    };
+
+    useEffect(() => {
+        refresh();
+        const timer = setInterval(refresh, 5000);
+
+        return () => clearInterval(timer);
+    }, []);
+
+    const run = async (action: () => Promise<PowerControls>) => {
+        setBusy(true);
+        try {
+            setControls(await action());
+            setError(null);
+        } catch (err) {
+            setError(describeError(err));
+        }
+        setAudit(await commands.getPowerAudit().catch(() => audit));
+        setBusy(false);
+    };
+    const apply = (setting: PowerSetting) => run(() => commands.setPowerSetting(setting));
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (!controls) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <AdjustmentsHorizontalIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Power Profile</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to read power settings'}
+                </p>
+            </div>
+        );
+    }
+
+    const policy = controls.policy;
+    const button = 'px-3 py-1 text-sm font-medium rounded transition-colors duration-200 disabled:opacity-50';
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-teal-500 to-cyan-600 rounded-lg flex items-center justify-center mr-3">
+                        <AdjustmentsHorizontalIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">Power Profile</h2>
+                </div>
+                {policy?.driver && <span className="text-sm text-gray-500 dark:text-gray-400 font-mono">{policy.driver}</span>}
+            </div>
+
+            <div className="grid grid-cols-2 gap-4">
+                <Choice
+                    label="Platform profile"
+                    value={controls.platform_profile}
+                    options={controls.available_platform_profiles}
+                    note={fieldNote(controls.provenance, 'platform_profile')}
+                    disabled={busy}
+                    onChange={(value) => apply({ kind: 'platform_profile', value })}
+                />
+                <Choice
+                    label="Governor"
+                    value={policy?.governor ?? null}
+                    options={policy?.available_governors ?? []}
+                    note={fieldNote(controls.provenance, 'policy')}
+                    disabled={busy}
+                    onChange={(value) => apply({ kind: 'governor', value })}
+                />
+                <Choice
+                    label="Energy preference"
+                    value={policy?.epp ?? null}
+                    options={policy?.available_epp ?? []}
+                    disabled={busy}
+                    onChange={(value) => apply({ kind: 'epp', value })}
+                />
+                <div>
+                    <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-1">Turbo</label>
+                    <button
+                        onClick={() => apply({ kind: 'boost', value: !policy?.boost })}
+                        disabled={busy || policy?.boost == null}
+                        className={`${button} w-full p-2 ${policy?.boost ? 'text-white bg-green-600 hover:bg-green-700' : 'text-gray-700 dark:text-gray-300 bg-gray-100 dark:bg-gray-700 hover:bg-gray-200 dark:hover:bg-gray-600'}`}
+                    >
+                        {policy?.boost == null ? 'N/A' : policy.boost ? 'On' : 'Off'}
+                    </button>
+                </div>
+            </div>
+
+            {policy && !policy.uniform && (
+                <p className="text-xs text-amber-600 dark:text-amber-400 mt-2">Some cores use different settings; changes apply to all of them</p>
+            )}
+            {error && <p className="text-sm text-red-600 dark:text-red-400 mt-3">{error}</p>}
+
+            <div className="flex items-center justify-between mt-4">
+                <label className="flex items-center text-sm text-gray-600 dark:text-gray-400">
+                    <input
+                        type="checkbox"
+                        checked={controls.revert_on_exit}
+                        onChange={(e) => run(() => commands.setRevertOnExit(e.target.checked))}
+                        className="mr-2"
+                    />
+                    Revert on exit
+                </label>
+                <button
+                    onClick={() => run(commands.revertPowerSettings)}
+                    disabled={busy || controls.originals.length === 0}
+                    className={`${button} text-white bg-gray-600 hover:bg-gray-700`}
+                    title={controls.originals.map(describeSetting).join(', ') || undefined}
+                >
+                    Revert
+                </button>
+            </div>
+
+            {audit.length > 0 && (
+                <div className="mt-4 space-y-1">
+                    {audit.slice(-5).reverse().map((entry) => (
+                        <p
+                            key={`${entry.at}-${entry.setting.kind}-${entry.policies?.join(',') ?? 'all'}`}
+                            className="text-xs text-gray-500 dark:text-gray-400 truncate"
+                            title={entry.uid != null ? `uid ${entry.uid}` : undefined}
+                        >
+                            {new Date(entry.at).toLocaleTimeString()} · {entry.user} · {describeSetting(entry.setting)}
+                            {entry.error ? ` failed: ${describeError(entry.error)}` : ''}
+                        </p>
+                    ))}
+                </div>
+            )}
+        </div>
+    );
+}
@@ -0,0 +1,233 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { ChevronDownIcon, ChevronUpIcon, QueueListIcon, XMarkIcon } from '@heroicons/react/24/outline';
+import { commands, type OpenFile, type ProcessFiles, type ProcessInfo, type ProcessList } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+const TOP_PROCESSES = 15;
+const TOP_FILES = 40;
+// descriptors at this share of the limit or more get flagged
+const FILE_WARNING = 0.8;
+
+type SortKey = 'cpu' | 'memory' | 'disk' | 'network' | 'files';
+
+const SORTS: { key: SortKey; label: string; value: (process: ProcessInfo) => number }[] = [
+    { key: 'cpu', label: 'CPU', value: (process) => process.cpu_percent },
+    { key: 'memory', label: 'Memory', value: (process) => process.memory },
+    { key: 'disk', label: 'Disk', value: (process) => (process.disk_read ?? 0) + (process.disk_write ?? 0) },
+    { key: 'network', label: 'Network', value: (process) => (process.net_sent ?? 0) + (process.net_received ?? 0) },
+    { key: 'files', label: 'Files', value: (process) => process.open_files ?? 0 },
+];
+
+const formatBytes = (bytes: number) =>
+    bytes >= 1 << 30 ? `${(bytes / (1 << 30)).toFixed(1)} GiB` : `${(bytes / (1 << 20)).toFixed(0)} MiB`;
+
+const formatRate = (bytes: number | null) => {
+    if (bytes === null) return '';
+    if (bytes === 0) return '·';
+    return bytes >= 1 << 20 ? `${(bytes / (1 << 20)).toFixed(1)}M` : `${(bytes / 1024).toFixed(0)}K`;
+};
+
+const describeFile = (file: OpenFile) => {
+    const socket = file.socket;
+    if (!socket) return file.target;
+    const ends = [socket.local, socket.remote].filter((end) => end !== null).join(' → ');
+    return `${socket.protocol} ${ends || file.target}${socket.state ? ` (${socket.state})` : ''}`;
+};
+
+export default function ProcessesCard() {
+    const [list, setList] = useState<ProcessList | null>(null);
+    const [sort, setSort] = useState<SortKey>('cpu');
+    const [showAll, setShowAll] = useState(false);
+    const [selected, setSelected] = useState<number | null>(null);
+    const [files, setFiles] = useState<ProcessFiles | null>(null);
+    const [filesError, setFilesError] = useState<string | null>(null);
+    const [showAllFiles, setShowAllFiles] = useState(false);
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const refresh = async () => {
+            try {
+                setList(await commands.getProcesses());
+                setError(null);
+            } catch (err) {
+                setError(describeError(err));
+            }
+            setLoading(false);
+        };
+
+        refresh();
+        const timer = setInterval(refresh, 3000);
+
+        return () => clearInterval(timer);
+    }, []);
+
+    useEffect(() => {
+        setFiles(null);
+        setFilesError(null);
+        setShowAllFiles(false);
+        if (selected === null) return;
+        commands
+            .getProcessFiles(selected)
+            .then(setFiles)
+            .catch((err) => setFilesError(describeError(err)));
+    }, [selected]);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !list) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <QueueListIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Processes</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to list processes'}
+                </p>
+            </div>
+        );
+    }
+
+    const value = SORTS.find((option) => option.key === sort)!.value;
+    const sorted = [...list.processes].sort((a, b) => value(b) - value(a));
+    const shown = showAll ? sorted : sorted.slice(0, TOP_PROCESSES);
+    const selectedProcess = list.processes.find((process) => process.pid === selected);
+    const shownFiles = files ? (showAllFiles ? files.files : files.files.slice(0, TOP_FILES)) : [];
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-emerald-500 to-teal-600 rounded-lg flex items-center justify-center mr-3">
+                        <QueueListIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">Processes</h2>
+                </div>
+                <span className="text-sm text-gray-500 dark:text-gray-400">{list.processes.length}</span>
+            </div>
+
+            <div className="flex flex-wrap gap-2 mb-3">
+                {SORTS.map((option) => (
+                    <button
+                        key={option.key}
+                        onClick={() => setSort(option.key)}
+                        className={`px-2 py-1 rounded text-xs font-medium ${
+                            sort === option.key
+                                ? 'bg-emerald-100 text-emerald-700 dark:bg-emerald-900/30 dark:text-emerald-400'
+                                : 'bg-gray-100 text-gray-600 dark:bg-gray-700 dark:text-gray-400'
+                        }`}
+                    >
+                        {option.label}
+                    </button>
+                ))}
+            </div>
+
+            <div className="overflow-x-auto">
+                <table className="w-full text-xs font-mono">
+                    <thead>
+                        <tr className="text-gray-500 dark:text-gray-400">
+                            <th className="text-left font-medium py-1">Process</th>
+                            <th className="text-right font-medium py-1">CPU</th>
+                            <th className="text-right font-medium py-1">Mem</th>
+                            <th className="text-right font-medium py-1" title={fieldNote(list.provenance, 'disk')}>Disk R/W</th>
+                            <th className="text-right font-medium py-1" title={fieldNote(list.provenance, 'network')}>Net ↑/↓</th>
+                            <th className="text-right font-medium py-1" title={fieldNote(list.provenance, 'open_files')}>Files</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {shown.map((process) => {
+                            const nearLimit =
+                                process.open_files !== null && process.file_limit !== null && process.open_files >= process.file_limit * FILE_WARNING;
+                            return (
+                                <tr
+                                    key={process.pid}
+                                    onClick={() => setSelected(process.pid === selected ? null : process.pid)}
+                                    className={`cursor-pointer text-gray-900 dark:text-white hover:bg-gray-50 dark:hover:bg-gray-700/50 ${
+                                        process.pid === selected ? 'bg-emerald-50 dark:bg-emerald-900/20' : ''
+                                    }`}
+                                >
+                                    <td className="py-1 truncate max-w-[12rem]" title={`PID ${process.pid}`}>{process.name}</td>
+                                    <td className="py-1 text-right">{process.cpu_percent.toFixed(1)}%</td>
+                                    <td className="py-1 text-right">{formatBytes(process.memory)}</td>
+                                    <td className="py-1 text-right">
+                                        {process.disk_read === null ? '' : `${formatRate(process.disk_read)}/${formatRate(process.disk_write)}`}
+                                    </td>
+                                    <td className="py-1 text-right">
+                                        {process.net_sent === null ? '' : `${formatRate(process.net_sent)}/${formatRate(process.net_received)}`}
+                                    </td>
+                                    <td
+                                        className={`py-1 text-right ${nearLimit ? 'text-amber-600 dark:text-amber-400 font-bold' : ''}`}
+                                        title={process.file_limit !== null ? `Limit ${process.file_limit}` : undefined}
+                                    >
+                                        {process.open_files ?? ''}
+                                    </td>
+                                </tr>
+                            );
+                        })}
+                    </tbody>
+                </table>
+            </div>
+
+            {list.processes.length > TOP_PROCESSES && (
+                <button
+                    onClick={() => setShowAll(!showAll)}
+                    className="w-full flex items-center justify-center py-2 mt-2 text-blue-600 hover:text-blue-700 dark:text-blue-400 dark:hover:text-blue-300 transition-colors duration-200"
+                >
+                    <span className="text-sm font-medium mr-1">{showAll ? 'Show Less' : `Show All ${list.processes.length}`}</span>
+                    {showAll ? <ChevronUpIcon className="w-4 h-4" /> : <ChevronDownIcon className="w-4 h-4" />}
+                </button>
+            )}
+
+            {selected !== null && (
+                <div className="mt-4 pt-4 border-t border-gray-200 dark:border-gray-700">
+                    <div className="flex items-center justify-between mb-2">
+                        <h3 className="text-sm font-semibold text-gray-700 dark:text-gray-300">
+                            Open files · {selectedProcess?.name ?? 'process'} ({selected})
+                            {files && ` · ${files.files.length}${files.file_limit !== null ? ` of ${files.file_limit}` : ''}`}
+                        </h3>
+                        <button onClick={() => setSelected(null)} className="text-gray-400 hover:text-gray-600 dark:hover:text-gray-200" title="Close">
+                            <XMarkIcon className="w-4 h-4" />
+                        </button>
+                    </div>
+                    {filesError && <p className="text-sm text-gray-500 dark:text-gray-400">{filesError}</p>}
+                    {!files && !filesError && <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2 animate-pulse"></div>}
+                    {files && (
+                        <div className="space-y-1">
+                            {shownFiles.map((file) => (
+                                <div key={file.fd} className="flex text-xs font-mono">
+                                    <span className="w-10 text-right text-gray-500 dark:text-gray-400 mr-3">{file.fd}</span>
+                                    <span className="w-20 text-gray-500 dark:text-gray-400">{file.kind.replace('_', ' ')}</span>
+                                    <span className="flex-1 truncate text-gray-900 dark:text-white" title={file.target}>{describeFile(file)}</span>
+                                </div>
+                            ))}
+                            {files.files.length > TOP_FILES && (
+                                <button
+                                    onClick={() => setShowAllFiles(!showAllFiles)}
+                                    className="text-xs font-medium text-blue-600 hover:text-blue-700 dark:text-blue-400 dark:hover:text-blue-300"
+                                >
+                                    {showAllFiles ? 'Show Less' : `Show All ${files.files.length}`}
+                                </button>
+                            )}
+                        </div>
+                    )}
+                </div>
+            )}
+        </div>
+    );
+}
@@ -0,0 +1,150 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { ServerStackIcon, XMarkIcon } from '@heroicons/react/24/outline';
+import { commands, events, type RemoteHostStatus } from '@/bindings';
+import { describeError } from '@/lib/errors';
+
+export default function RemoteHosts() {
+    const [hosts, setHosts] = useState<Record<number, RemoteHostStatus>>({});
+    const [address, setAddress] = useState('');
+    const [token, setToken] = useState('');
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        commands.listRemoteHosts().then((list) => {
+            setHosts(Object.fromEntries(list.map((host) => [host.id, host])));
+        });
+
+        const unlisten = events.remoteHostUpdate.listen((event) => {
+            setHosts((prev) => (event.payload.id in prev ? { ...prev, [event.payload.id]: event.payload } : prev));
+        });
+
+        return () => {
+            unlisten.then((stop) => stop());
+        };
+    }, []);
+
+    const addHost = async () => {
+        try {
+            const host = await commands.addRemoteHost(address, token);
+            setHosts((prev) => ({ ...prev, [host.id]: host }));
+            setAddress('');
+            setToken('');
+            setError(null);
+        } catch (err) {
+            setError(describeError(err));
+        }
+    };
+
+    const removeHost = async (id: number) => {
+        await commands.removeRemoteHost(id).catch(() => {});
+        setHosts((prev) => {
+            const { [id]: _removed, ...rest } = prev;
+            return rest;
+        });
+    };
+
+    return (
+        <>
+            {Object.values(hosts).map((host) => (
+                <RemoteHostCard key={host.id} host={host} onRemove={() => removeHost(host.id)} />
+            ))}
+
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-dashed border-gray-300 dark:border-gray-600">
+                <div className="flex items-center mb-4">
+                    <ServerStackIcon className="w-6 h-6 text-gray-500 mr-2" />
+                    <h2 className="text-lg font-bold text-gray-900 dark:text-white">Add Remote Host</h2>
+                </div>
+                <div className="space-y-2">
+                    <input
+                        value={address}
+                        onChange={(e) => setAddress(e.target.value)}
+                        placeholder="buildbox-01:7878"
+                        className="w-full text-sm font-mono bg-gray-50 dark:bg-gray-700 text-gray-900 dark:text-white p-2 rounded"
+                    />
+                    <input
+                        value={token}
+                        onChange={(e) => setToken(e.target.value)}
+                        type="password"
+                        placeholder="Agent token"
+                        className="w-full text-sm font-mono bg-gray-50 dark:bg-gray-700 text-gray-900 dark:text-white p-2 rounded"
+                    />
+                    <button
+                        onClick={addHost}
+                        disabled={!address || !token}
+                        className="w-full py-2 text-sm font-medium text-white bg-blue-600 hover:bg-blue-700 disabled:opacity-50 rounded transition-colors duration-200"
+                    >
+                        Connect
+                    </button>
+                    {error && <p className="text-xs text-red-500">{error}</p>}
+                </div>
+            </div>
+        </>
+    );
+}
+
+function RemoteHostCard({ host, onRemove }: { host: RemoteHostStatus; onRemove: () => void }) {
+    const snapshot = host.snapshot;
+    const cpu = snapshot && 'ok' in snapshot.cpu ? snapshot.cpu.ok : null;
+    const gpu = snapshot && 'ok' in snapshot.gpu ? snapshot.gpu.ok : null;
+
+    const stateColor = host.state === 'connected'
+        ? 'bg-green-500'
+        : host.state === 'connecting' ? 'bg-yellow-500' : 'bg-red-500';
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-slate-500 to-slate-700 rounded-lg flex items-center justify-center mr-3">
+                        <ServerStackIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <div>
+                        <h2 className="text-xl font-bold text-gray-900 dark:text-white">{snapshot?.host ?? host.address}</h2>
+                        <p className="text-xs text-gray-500 dark:text-gray-400 flex items-center">
+                            <span className={`inline-block w-2 h-2 rounded-full mr-1 ${stateColor}`} />
+                            {host.address}
+                        </p>
+                    </div>
+                </div>
+                <button onClick={onRemove} className="text-gray-400 hover:text-red-500 transition-colors duration-200">
+                    <XMarkIcon className="w-5 h-5" />
+                </button>
+            </div>
+
+            {host.last_error && (
+                <p className="text-xs text-red-500 mb-3">{describeError(host.last_error)}</p>
+            )}
+
+            <div className="grid grid-cols-2 gap-4">
+                <div className="text-center p-3 bg-gray-50 dark:bg-gray-700 rounded-lg">
+                    <p className="text-sm text-gray-500 dark:text-gray-400">CPU</p>
+                    <p className="text-lg font-bold text-gray-900 dark:text-white">
+                        {cpu ? `${cpu.usage.toFixed(1)}%` : 'N/A'}
+                    </p>
+                    {cpu?.temp_available && (
+                        <p className="text-xs text-gray-500 dark:text-gray-400">{cpu.temperature.toFixed(0)}°C</p>
+                    )}
+                </div>
+                <div className="text-center p-3 bg-gray-50 dark:bg-gray-700 rounded-lg">
+                    <p className="text-sm text-gray-500 dark:text-gray-400">GPU</p>
+                    <p className="text-sm font-bold text-gray-900 dark:text-white truncate" title={gpu?.name}>
+                        {gpu ? gpu.name : 'N/A'}
+                    </p>
+                    {gpu && gpu.memory_total > 0 && (
+                        <p className="text-xs text-gray-500 dark:text-gray-400">
+                            {(gpu.memory_total / (1024 * 1024 * 1024)).toFixed(1)} GB
+                        </p>
+                    )}
+                </div>
+            </div>
+
+            {cpu && (
+                <p className="text-xs text-gray-500 dark:text-gray-400 mt-3 truncate" title={cpu.brand}>
+                    {cpu.brand} · {cpu.cores}C/{cpu.logical_cores}T
+                </p>
+            )}
+        </div>
+    );
+}
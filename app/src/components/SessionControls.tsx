@@ -0,0 +1,115 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { FilmIcon } from '@heroicons/react/24/outline';
+import { commands, type SessionStatus } from '@/bindings';
+import { describeError } from '@/lib/errors';
+
+const SPEEDS = [1, 10, 60];
+
+export default function SessionControls() {
+    const [status, setStatus] = useState<SessionStatus | null>(null);
+    const [path, setPath] = useState('');
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const refresh = () => commands.getSessionStatus().then(setStatus);
+        refresh();
+        const interval = setInterval(refresh, 1000);
+        return () => clearInterval(interval);
+    }, []);
+
+    const run = async (action: () => Promise<SessionStatus>) => {
+        try {
+            setStatus(await action());
+            setError(null);
+        } catch (err) {
+            setError(describeError(err));
+        }
+    };
+
+    if (!status) return null;
+
+    const button = 'px-3 py-1 text-sm font-medium rounded transition-colors duration-200 disabled:opacity-50';
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <FilmIcon className="w-6 h-6 text-gray-500 mr-2" />
+                    <h2 className="text-lg font-bold text-gray-900 dark:text-white">Session</h2>
+                </div>
+                <span className="text-xs uppercase tracking-wide text-gray-500 dark:text-gray-400">{status.mode}</span>
+            </div>
+
+            {status.mode === 'live' && (
+                <div className="space-y-2">
+                    <input
+                        value={path}
+                        onChange={(e) => setPath(e.target.value)}
+                        placeholder="/path/to/session.jsonl.gz"
+                        className="w-full text-sm font-mono bg-gray-50 dark:bg-gray-700 text-gray-900 dark:text-white p-2 rounded"
+                    />
+                    <div className="flex gap-2">
+                        <button onClick={() => run(() => commands.startRecording(path))} disabled={!path} className={`${button} text-white bg-red-600 hover:bg-red-700`}>
+                            Record
+                        </button>
+                        <button onClick={() => run(() => commands.openReplay(path))} disabled={!path} className={`${button} text-white bg-blue-600 hover:bg-blue-700`}>
+                            Replay
+                        </button>
+                    </div>
+                </div>
+            )}
+
+            {status.mode === 'recording' && (
+                <div className="space-y-2">
+                    <p className="text-sm text-gray-600 dark:text-gray-400 font-mono truncate">{status.path}</p>
+                    <p className="text-sm text-gray-900 dark:text-white">
+                        {Math.floor(status.position_ms / 1000)}s, {status.records} records
+                    </p>
+                    <button onClick={() => run(commands.stopRecording)} className={`${button} text-white bg-gray-600 hover:bg-gray-700`}>
+                        Stop
+                    </button>
+                </div>
+            )}
+
+            {status.mode === 'replaying' && (
+                <div className="space-y-2">
+                    <p className="text-sm text-gray-600 dark:text-gray-400 truncate">
+                        {status.header?.host} &middot; <span className="font-mono">{status.path}</span>
+                    </p>
+                    <input
+                        type="range"
+                        min={0}
+                        max={status.duration_ms}
+                        value={status.position_ms}
+                        onChange={(e) => run(() => commands.controlReplay(null, null, Number(e.target.value)))}
+                        className="w-full"
+                    />
+                    <p className="text-xs text-gray-500 dark:text-gray-400">
+                        {Math.floor(status.position_ms / 1000)}s / {Math.floor(status.duration_ms / 1000)}s
+                    </p>
+                    <div className="flex flex-wrap gap-2">
+                        <button onClick={() => run(() => commands.controlReplay(null, !status.paused, null))} className={`${button} text-white bg-blue-600 hover:bg-blue-700`}>
+                            {status.paused ? 'Play' : 'Pause'}
+                        </button>
+                        {SPEEDS.map((speed) => (
+                            <button
+                                key={speed}
+                                onClick={() => run(() => commands.controlReplay(speed, null, null))}
+                                className={`${button} ${status.speed === speed ? 'bg-blue-100 dark:bg-blue-900 text-blue-700 dark:text-blue-300' : 'bg-gray-100 dark:bg-gray-700 text-gray-700 dark:text-gray-300'}`}
+                            >
+                                {speed}x
+                            </button>
+                        ))}
+                        <button onClick={() => run(commands.closeReplay)} className={`${button} text-white bg-gray-600 hover:bg-gray-700`}>
+                            Close
+                        </button>
+                    </div>
+                </div>
+            )}
+
+            {error && <p className="mt-2 text-xs text-red-500">{error}</p>}
+        </div>
+    );
+}
@@ -0,0 +1,179 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { CircleStackIcon, XMarkIcon } from '@heroicons/react/24/outline';
+import { commands, events, type DriveHealth, type HealthEvent, type HealthStatus, type StorageHealth } from '@/bindings';
+import { describeError } from '@/lib/errors';
+
+const RECENT_EVENTS = 5;
+
+const statusStyle: Record<HealthStatus, string> = {
+    good: 'bg-green-100 text-green-700 dark:bg-green-900/30 dark:text-green-400',
+    warning: 'bg-amber-100 text-amber-700 dark:bg-amber-900/30 dark:text-amber-400',
+    failing: 'bg-red-100 text-red-700 dark:bg-red-900/30 dark:text-red-400',
+    unknown: 'bg-gray-100 text-gray-600 dark:bg-gray-700 dark:text-gray-400',
+};
+
+const formatBytes = (bytes: number) =>
+    bytes >= 1e12 ? `${(bytes / 1e12).toFixed(1)} TB` : `${(bytes / 1e9).toFixed(0)} GB`;
+
+// the few numbers that say the most, per kind of drive
+const summary = (drive: DriveHealth) => {
+    const parts: string[] = [];
+    if (drive.temperature !== null) parts.push(`${drive.temperature.toFixed(0)}°C`);
+    if (drive.power_on_hours !== null) parts.push(`${drive.power_on_hours.toLocaleString()} h`);
+    if (drive.nvme) {
+        parts.push(`${drive.nvme.percentage_used}% worn`);
+        parts.push(`spare ${drive.nvme.available_spare}%`);
+        parts.push(`${formatBytes(drive.nvme.bytes_written)} written`);
+        parts.push(`${drive.nvme.unsafe_shutdowns} unsafe shutdowns`);
+    }
+    return parts.join(' · ');
+};
+
+const describeEvent = (event: HealthEvent) =>
+    `${event.model ?? event.drive} ${event.from ? `went from ${event.from} to` : 'is'} ${event.to}${
+        event.problems.length > 0 ? `: ${event.problems[0]}` : ''
+    }`;
+
+export default function StorageCard() {
+    const [health, setHealth] = useState<StorageHealth | null>(null);
+    const [alerts, setAlerts] = useState<HealthEvent[]>([]);
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const fetchHealth = async () => {
+            try {
+                const data = await commands.getStorageHealth();
+                setHealth(data);
+                setError(null);
+            } catch (err) {
+                setError(describeError(err));
+                setHealth(null);
+            }
+            setLoading(false);
+        };
+
+        fetchHealth();
+        // SMART counters move slowly
+        const timer = setInterval(fetchHealth, 60000);
+        const unlisten = events.storageHealthAlert.listen((event) => {
+            setAlerts((prev) => [...prev, event.payload]);
+            fetchHealth();
+        });
+
+        return () => {
+            clearInterval(timer);
+            unlisten.then((stop) => stop());
+        };
+    }, []);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !health) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <CircleStackIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Storage Health</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to read drive health'}
+                </p>
+            </div>
+        );
+    }
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center mb-4">
+                <div className="w-8 h-8 bg-gradient-to-br from-emerald-500 to-teal-600 rounded-lg flex items-center justify-center mr-3">
+                    <CircleStackIcon className="w-5 h-5 text-white" />
+                </div>
+                <h2 className="text-xl font-bold text-gray-900 dark:text-white">Storage Health</h2>
+            </div>
+
+            {alerts.length > 0 && (
+                <div className="mb-4 p-3 rounded-lg bg-red-50 dark:bg-red-900/30 border border-red-200 dark:border-red-800">
+                    <div className="flex items-start justify-between">
+                        <div className="space-y-1">
+                            {alerts.slice(-3).map((alert, index) => (
+                                <p
+                                    key={index}
+                                    className={`text-sm ${alert.to === 'failing' ? 'text-red-600 dark:text-red-400' : 'text-amber-600 dark:text-amber-400'}`}
+                                >
+                                    {describeEvent(alert)}
+                                </p>
+                            ))}
+                            {alerts.length > 3 && (
+                                <p className="text-xs text-gray-500 dark:text-gray-400">and {alerts.length - 3} more</p>
+                            )}
+                        </div>
+                        <button onClick={() => setAlerts([])} className="text-gray-400 hover:text-gray-600 dark:hover:text-gray-200" title="Dismiss">
+                            <XMarkIcon className="w-4 h-4" />
+                        </button>
+                    </div>
+                </div>
+            )}
+
+            <div className="space-y-2">
+                {health.drives.length === 0 && (
+                    <p className="text-sm text-gray-500 dark:text-gray-400">No drives with SMART data</p>
+                )}
+                {health.drives.map((drive) => (
+                    <div key={drive.name} className="text-xs bg-gray-50 dark:bg-gray-700 p-2 rounded">
+                        <div className="flex justify-between items-center">
+                            <span className="font-medium text-gray-900 dark:text-white truncate">
+                                {drive.model ?? drive.name}
+                            </span>
+                            <span className={`ml-2 px-2 py-0.5 rounded-full font-medium capitalize ${statusStyle[drive.status]}`}>
+                                {drive.status}
+                            </span>
+                        </div>
+                        <div className="flex justify-between text-gray-500 dark:text-gray-400">
+                            <span className="truncate">{drive.error ? describeError(drive.error) : summary(drive)}</span>
+                            <span className="font-mono ml-2" title={`Read via ${drive.source}`}>{drive.name}</span>
+                        </div>
+                        {drive.problems.map((problem) => (
+                            <p key={problem} className={drive.status === 'failing' ? 'text-red-600 dark:text-red-400' : 'text-amber-600 dark:text-amber-400'}>
+                                {problem}
+                            </p>
+                        ))}
+                    </div>
+                ))}
+            </div>
+
+            {health.history != null && health.history.length > 0 && (
+                <>
+                    <h3 className="text-sm font-semibold text-gray-700 dark:text-gray-300 mt-4 mb-2">Recent</h3>
+                    <div className="space-y-1">
+                        {health.history.slice(-RECENT_EVENTS).reverse().map((event) => (
+                            <div key={`${event.drive}-${event.at}`} className="flex justify-between text-sm">
+                                <span className="truncate text-gray-600 dark:text-gray-400" title={event.problems.join('\n')}>
+                                    {describeEvent(event)}
+                                </span>
+                                <span className="text-gray-500 dark:text-gray-400 ml-2 whitespace-nowrap">
+                                    {new Date(event.at).toLocaleTimeString()}
+                                </span>
+                            </div>
+                        ))}
+                    </div>
+                </>
+            )}
+        </div>
+    );
+}
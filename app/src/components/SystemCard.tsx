@@ -0,0 +1,145 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { ChevronDownIcon, ChevronUpIcon, ComputerDesktopIcon } from '@heroicons/react/24/outline';
+import { commands, type SystemInfo } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+import { formatTimestamp, formatUptime } from '@/lib/format';
+
+export default function SystemCard() {
+    const [system, setSystem] = useState<SystemInfo | null>(null);
+    const [loading, setLoading] = useState(true);
+    const [showDetails, setShowDetails] = useState(false);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const fetchSystem = async () => {
+            try {
+                const data = await commands.getSystemInfo(null);
+                setSystem(data);
+                setError(null);
+            } catch (err) {
+                setError(describeError(err));
+                setSystem(null);
+            }
+            setLoading(false);
+        };
+
+        fetchSystem();
+        const timer = setInterval(fetchSystem, 60000);
+
+        return () => clearInterval(timer);
+    }, []);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !system) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <ComputerDesktopIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">System Information</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to load system info'}
+                </p>
+            </div>
+        );
+    }
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center mb-4">
+                <div className="w-8 h-8 bg-gradient-to-br from-emerald-500 to-teal-600 rounded-lg flex items-center justify-center mr-3">
+                    <ComputerDesktopIcon className="w-5 h-5 text-white" />
+                </div>
+                <div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">System</h2>
+                    <p className="text-xs text-gray-500 dark:text-gray-400 max-w-xs truncate" title={system.hostname}>
+                        {system.hostname}
+                    </p>
+                </div>
+            </div>
+
+            <div className="grid grid-cols-2 gap-4 mb-4">
+                <div className="text-center p-3 bg-gray-50 dark:bg-gray-700 rounded-lg">
+                    <p className="text-sm text-gray-500 dark:text-gray-400">Uptime</p>
+                    <p className="text-lg font-bold text-gray-900 dark:text-white">{formatUptime(system.uptime_secs)}</p>
+                </div>
+                <div className="text-center p-3 bg-gray-50 dark:bg-gray-700 rounded-lg">
+                    <p className="text-sm text-gray-500 dark:text-gray-400">Booted</p>
+                    <p className="text-sm font-bold text-gray-900 dark:text-white">{formatTimestamp(system.boot_time)}</p>
+                </div>
+            </div>
+
+            <button
+                onClick={() => setShowDetails(!showDetails)}
+                className="w-full flex items-center justify-center py-2 text-blue-600 hover:text-blue-700 dark:text-blue-400 dark:hover:text-blue-300 transition-colors duration-200"
+            >
+                <span className="text-sm font-medium mr-1">
+                    {showDetails ? 'Show Less' : 'Show Details'}
+                </span>
+                {showDetails ? (
+                    <ChevronUpIcon className="w-4 h-4" />
+                ) : (
+                    <ChevronDownIcon className="w-4 h-4" />
+                )}
+            </button>
+
+            {showDetails && (
+                <div className="mt-4 pt-4 border-t border-gray-200 dark:border-gray-700 space-y-4 animate-in slide-in-from-top duration-300">
+                    <div className="grid grid-cols-2 gap-4">
+                        <div>
+                            <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-1">OS</label>
+                            <p className="text-sm text-gray-900 dark:text-white font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded">{system.os_name} {system.os_version}</p>
+                        </div>
+                        <div>
+                            <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-1">Architecture</label>
+                            <p className="text-sm text-gray-900 dark:text-white font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded">{system.architecture}</p>
+                        </div>
+                    </div>
+
+                    <div>
+                        <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-1">Kernel</label>
+                        <p className="text-sm text-gray-900 dark:text-white font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded">{system.kernel_version}</p>
+                    </div>
+
+                    <div>
+                        <label className="text-sm font-medium text-gray-500 dark:text-gray-400 block mb-1">Boot History</label>
+                        {system.boot_history.length > 0 ? (
+                            <div className="space-y-1">
+                                {system.boot_history.map((boot) => (
+                                    <div key={boot.boot_id ?? boot.boot_time} className="flex justify-between text-xs font-mono bg-gray-50 dark:bg-gray-700 p-2 rounded">
+                                        <span className="text-gray-900 dark:text-white">{formatTimestamp(boot.boot_time)}</span>
+                                        {boot.last_seen !== null && (
+                                            <span className="text-gray-500 dark:text-gray-400">
+                                                up {formatUptime(boot.last_seen - boot.boot_time)}
+                                            </span>
+                                        )}
+                                    </div>
+                                ))}
+                            </div>
+                        ) : (
+                            <p className="text-sm text-gray-500 dark:text-gray-400">
+                                {fieldNote(system.provenance, 'boot_history') ?? 'No boot history'}
+                            </p>
+                        )}
+                    </div>
+                </div>
+            )}
+        </div>
+    );
+}
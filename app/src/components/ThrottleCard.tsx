@@ -0,0 +1,142 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { FireIcon } from '@heroicons/react/24/outline';
+import { commands, type ThrottleCause, type ThrottleEvent, type ThrottleStatus } from '@/bindings';
+import { describeError, fieldNote } from '@/lib/errors';
+
+const causeLabel: Record<ThrottleCause, string> = {
+    thermal: 'Thermal',
+    power_limit: 'Power limit',
+    frequency_cap: 'Frequency cap',
+    other: 'Other',
+};
+
+const causeColor: Record<ThrottleCause, string> = {
+    thermal: 'text-red-600 dark:text-red-400',
+    power_limit: 'text-amber-600 dark:text-amber-400',
+    frequency_cap: 'text-blue-600 dark:text-blue-400',
+    other: 'text-gray-600 dark:text-gray-400',
+};
+
+const formatDuration = (ms: number) => {
+    const secs = Math.round(ms / 1000);
+    if (secs < 60) return `${secs}s`;
+    const minutes = Math.floor(secs / 60);
+    return minutes < 60 ? `${minutes}m ${secs % 60}s` : `${Math.floor(minutes / 60)}h ${minutes % 60}m`;
+};
+
+function EventRow({ event }: { event: ThrottleEvent }) {
+    return (
+        <div className="flex justify-between text-sm">
+            <span className="truncate" title={event.detail}>
+                <span className={`font-medium ${causeColor[event.cause]}`}>{causeLabel[event.cause]}</span>
+                <span className="text-gray-600 dark:text-gray-400 ml-2">{event.device}</span>
+            </span>
+            <span className="text-gray-500 dark:text-gray-400 ml-2 whitespace-nowrap">
+                {new Date(event.started_at).toLocaleTimeString()} · {formatDuration(event.duration_ms)}
+            </span>
+        </div>
+    );
+}
+
+export default function ThrottleCard() {
+    const [status, setStatus] = useState<ThrottleStatus | null>(null);
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const fetchStatus = async () => {
+            try {
+                const data = await commands.getThrottleStatus();
+                setStatus(data);
+                setError(null);
+            } catch (err) {
+                setError(describeError(err));
+                setStatus(null);
+            }
+            setLoading(false);
+        };
+
+        fetchStatus();
+        const timer = setInterval(fetchStatus, 2000);
+
+        return () => clearInterval(timer);
+    }, []);
+
+    if (loading) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <div className="w-8 h-8 bg-gray-300 dark:bg-gray-600 rounded-lg animate-pulse mr-3"></div>
+                    <div className="h-6 bg-gray-300 dark:bg-gray-600 rounded w-32 animate-pulse"></div>
+                </div>
+                <div className="animate-pulse space-y-3">
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-3/4"></div>
+                    <div className="h-4 bg-gray-300 dark:bg-gray-600 rounded w-1/2"></div>
+                </div>
+            </div>
+        );
+    }
+
+    if (error || !status) {
+        return (
+            <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-red-200 dark:border-red-800 hover:shadow-xl transition-shadow duration-300">
+                <div className="flex items-center mb-4">
+                    <FireIcon className="w-8 h-8 text-red-500 mr-3" />
+                    <h2 className="text-xl font-bold text-red-600">Throttling</h2>
+                </div>
+                <p className="text-gray-600 dark:text-gray-400">
+                    {error || 'Unable to detect throttling'}
+                </p>
+            </div>
+        );
+    }
+
+    const recent = status.events.slice(-8).reverse();
+
+    return (
+        <div className="bg-white dark:bg-gray-800 rounded-xl shadow-lg p-6 border border-gray-200 dark:border-gray-700 hover:shadow-xl transition-shadow duration-300">
+            <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center">
+                    <div className="w-8 h-8 bg-gradient-to-br from-red-500 to-orange-600 rounded-lg flex items-center justify-center mr-3">
+                        <FireIcon className="w-5 h-5 text-white" />
+                    </div>
+                    <h2 className="text-xl font-bold text-gray-900 dark:text-white">Throttling</h2>
+                </div>
+                <span
+                    className={`text-sm font-medium ${status.active.length > 0 ? 'text-red-600 dark:text-red-400' : 'text-green-600 dark:text-green-400'}`}
+                    title={['cpu_counters', 'cpu_frequency', 'gpu'].map((field) => fieldNote(status.provenance, field)).filter(Boolean).join('\n') || undefined}
+                >
+                    {status.active.length > 0 ? 'Throttling now' : 'Not throttling'}
+                </span>
+            </div>
+
+            {status.policy_cap && (
+                <p className="text-sm text-gray-600 dark:text-gray-400 mb-2">
+                    Clocks limited by policy to {status.policy_cap.limit_khz / 1000} MHz on {status.policy_cap.cpus} of{' '}
+                    {status.policy_cap.total_cpus} CPUs
+                </p>
+            )}
+
+            <div className="space-y-2">
+                {status.active.map((event) => (
+                    <div key={`${event.device}-${event.cause}`} className="p-2 bg-red-50 dark:bg-red-900/20 rounded-lg">
+                        <EventRow event={event} />
+                        <p className="text-xs text-gray-500 dark:text-gray-400 mt-1">{event.detail}</p>
+                    </div>
+                ))}
+            </div>
+
+            <h3 className="text-sm font-semibold text-gray-700 dark:text-gray-300 mt-4 mb-2">Recent</h3>
+            <div className="space-y-1">
+                {recent.length === 0 && (
+                    <p className="text-sm text-gray-500 dark:text-gray-400">Nothing since monitoring started</p>
+                )}
+                {recent.map((event) => (
+                    <EventRow key={`${event.device}-${event.cause}-${event.started_at}`} event={event} />
+                ))}
+            </div>
+        </div>
+    );
+}
@@ -0,0 +1,32 @@
+import type { OverwatchError, Provenance } from '@/bindings';
+
+export const describeError = (err: unknown): string => {
+    if (typeof err !== 'object' || err === null || !('kind' in err)) {
+        return String(err);
+    }
+
+    const e = err as OverwatchError;
+    switch (e.kind) {
+        case 'unsupported':
+            return `${e.what} is not supported on this system`;
+        case 'permission_denied':
+            return `Permission denied: ${e.what}`;
+        case 'backend_failure':
+            return `${e.backend} failed: ${e.source}`;
+        case 'timeout':
+            return `${e.what} timed out`;
+        case 'not_found':
+            return `${e.what} not found`;
+    }
+};
+
+// Tooltip text for a field, explaining where it came from or why it's missing
+export const fieldNote = (provenance: Provenance, field: string): string | undefined => {
+    const status = provenance[field];
+    if (!status) return undefined;
+    if (status.status === 'missing') return describeError(status.reason);
+    if (status.status === 'estimated') {
+        return status.source === 'name_heuristic' ? 'Estimated from the model name' : `Approximated from ${status.source}`;
+    }
+    return `Read from ${status.source}`;
+};
@@ -0,0 +1,13 @@
+export const formatUptime = (secs: number): string => {
+    const days = Math.floor(secs / 86400);
+    const hours = Math.floor((secs % 86400) / 3600);
+    const mins = Math.floor((secs % 3600) / 60);
+
+    if (days > 0) return `${days}d ${hours}h ${mins}m`;
+    if (hours > 0) return `${hours}h ${mins}m`;
+    return `${mins}m`;
+};
+
+// Unix seconds to a local date/time string
+export const formatTimestamp = (secs: number): string =>
+    new Date(secs * 1000).toLocaleString();